@@ -0,0 +1,110 @@
+use crate::pdu::licensing::LicenseErrorMessage;
+use crate::pdu::rdp::{
+    BitmapCapability, CapabilitySet, GeneralCapability, InputCapability, OrderCapability,
+};
+use crate::pdu::x224::{NegotiationFailureCode, Protocol};
+
+/// Server acceptor configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcceptorConfig {
+    /// Security protocols the server accepts, in order of preference
+    pub protocols: Vec<Protocol>,
+    /// Desktop width advertised in Demand Active
+    pub desktop_width: u16,
+    /// Desktop height advertised in Demand Active
+    pub desktop_height: u16,
+    /// Share ID sent in Demand Active
+    pub share_id: u32,
+    /// Capability sets sent in Demand Active
+    pub capability_sets: Vec<CapabilitySet>,
+    /// License Error Message sent after the Client Info PDU
+    pub license_error: LicenseErrorMessage,
+}
+
+impl AcceptorConfig {
+    /// Create configuration accepting CredSSP or TLS at 1024x768
+    pub fn new() -> Self {
+        Self {
+            protocols: vec![Protocol::Hybrid, Protocol::Ssl],
+            desktop_width: 1024,
+            desktop_height: 768,
+            share_id: 0x0001_03EA,
+            capability_sets: Vec::new(),
+            license_error: LicenseErrorMessage::valid_client(),
+        }
+    }
+
+    /// Set accepted security protocols in order of preference
+    pub fn with_protocols(mut self, protocols: Vec<Protocol>) -> Self {
+        self.protocols = protocols;
+        self
+    }
+
+    /// Set desktop size
+    pub fn with_desktop_size(mut self, width: u16, height: u16) -> Self {
+        self.desktop_width = width;
+        self.desktop_height = height;
+        self
+    }
+
+    /// Set capability sets (default sets are used when empty)
+    pub fn with_capability_sets(mut self, capability_sets: Vec<CapabilitySet>) -> Self {
+        self.capability_sets = capability_sets;
+        self
+    }
+
+    /// Set the licensing reply (STATUS_VALID_CLIENT by default)
+    ///
+    /// Any state transition other than ST_NO_TRANSITION ends the connection
+    /// sequence after the message is sent.
+    pub fn with_license_error(mut self, license_error: LicenseErrorMessage) -> Self {
+        self.license_error = license_error;
+        self
+    }
+
+    /// Pick the first accepted protocol the client requested
+    ///
+    /// Standard RDP Security is always available to the client, so it
+    /// matches whenever it is accepted.
+    pub fn select_protocol(&self, requested_protocols: u32) -> Result<u32, NegotiationFailureCode> {
+        let selected = self.protocols.iter().find(|&&protocol| {
+            protocol == Protocol::RdpSecurity || requested_protocols & protocol as u32 != 0
+        });
+        if let Some(&protocol) = selected {
+            return Ok(protocol as u32);
+        }
+
+        let hybrid_only = !self.protocols.contains(&Protocol::Ssl)
+            && (self.protocols.contains(&Protocol::Hybrid)
+                || self.protocols.contains(&Protocol::HybridEx));
+        if hybrid_only {
+            Err(NegotiationFailureCode::HybridRequiredByServer)
+        } else {
+            Err(NegotiationFailureCode::SslRequiredByServer)
+        }
+    }
+
+    /// Capability sets for Demand Active
+    pub fn demand_capability_sets(&self) -> Vec<CapabilitySet> {
+        if !self.capability_sets.is_empty() {
+            return self.capability_sets.clone();
+        }
+
+        vec![
+            CapabilitySet::General(GeneralCapability::new()),
+            CapabilitySet::Bitmap(BitmapCapability::new(
+                self.desktop_width,
+                self.desktop_height,
+                32,
+            )),
+            CapabilitySet::Order(OrderCapability::new()),
+            CapabilitySet::Input(InputCapability::new()),
+        ]
+    }
+}
+
+impl Default for AcceptorConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,53 @@
+use crate::pdu::PduError;
+use crate::pdu::licensing::LicenseErrorMessage;
+use crate::pdu::x224::NegotiationFailureCode;
+use thiserror::Error;
+
+use super::server::ServerAcceptorState;
+
+/// Server acceptor result type
+pub type AcceptorResult<T> = std::result::Result<T, AcceptorError>;
+
+/// Server connection sequence errors
+#[derive(Error, Debug)]
+pub enum AcceptorError {
+    #[error(transparent)]
+    Pdu(#[from] PduError),
+
+    /// No acceptable protocol; `response` is the Connection Confirm to send
+    /// before closing the transport
+    #[error("Protocol negotiation failed: {code:?}")]
+    NegotiationFailure {
+        code: NegotiationFailureCode,
+        response: Vec<u8>,
+    },
+
+    /// Licensing refused by configuration; `response` is the License Error
+    /// Message to send before closing the transport
+    #[error("Licensing refused: {:?}", message.error_code)]
+    Licensing {
+        message: LicenseErrorMessage,
+        response: Vec<u8>,
+    },
+
+    #[error("Invalid client data: {0}")]
+    InvalidClientData(String),
+
+    #[error("Capability exchange failed: {0}")]
+    CapabilitiesExchange(String),
+
+    #[error("Connection finalization failed: {0}")]
+    Finalization(String),
+
+    #[error("Disconnected by client: {0}")]
+    Disconnected(String),
+
+    #[error("Unexpected {pdu} in state {state:?}")]
+    UnexpectedPdu {
+        state: ServerAcceptorState,
+        pdu: String,
+    },
+
+    #[error("Operation not valid in state {0:?}")]
+    InvalidState(ServerAcceptorState),
+}
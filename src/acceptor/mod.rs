@@ -0,0 +1,8 @@
+// Sans-IO server connection sequence
+pub mod config;
+pub mod error;
+pub mod server;
+
+pub use config::AcceptorConfig;
+pub use error::{AcceptorError, AcceptorResult};
+pub use server::{AcceptanceResult, ServerAcceptor, ServerAcceptorState};
@@ -0,0 +1,700 @@
+use crate::pdu::framing::{
+    X224Frame, decode_basic_security, decode_share_data, decode_tpkt, decode_x224_data,
+    disconnect_ultimatum, encode_license_error, encode_pdu, encode_share_data, encode_tpkt,
+    encode_x224_data, mcs_choice,
+};
+use crate::pdu::gcc::client_data::MAX_STATIC_CHANNELS;
+use crate::pdu::gcc::{
+    ClientData, ConferenceCreateResponse, ServerCoreData, ServerData, ServerNetworkData,
+    ServerSecurityData,
+};
+use crate::pdu::licensing::StateTransition;
+use crate::pdu::mcs::channel::MCS_CHANNEL_JOIN_REQUEST;
+use crate::pdu::mcs::domain::{MCS_ATTACH_USER_REQUEST, MCS_ERECT_DOMAIN_REQUEST};
+use crate::pdu::mcs::send_data::MCS_SEND_DATA_REQUEST;
+use crate::pdu::mcs::{
+    AttachUserConfirm, AttachUserRequest, ChannelJoinConfirm, ChannelJoinRequest, ConnectInitial,
+    ConnectResponse, ErectDomainRequest, McsResult, SendDataIndication, SendDataRequest,
+};
+use crate::pdu::rdp::activation::SERVER_CHANNEL_ID;
+use crate::pdu::rdp::{
+    CapabilitySet, ClientInfoPdu, ConfirmActivePdu, ControlAction, ControlPdu, DataPduType,
+    DemandActivePdu, FontListPdu, FontMapPdu, PduType, ShareControlHeader, SynchronizePdu,
+};
+use crate::pdu::security::SecurityFlags;
+use crate::pdu::x224::{ConnectionConfirm, ConnectionRequest, Protocol};
+use crate::pdu::{Pdu, PduWithHeader};
+use bitflags::bitflags;
+
+use super::config::AcceptorConfig;
+use super::error::{AcceptorError, AcceptorResult};
+
+/// MCS I/O channel ID assigned by the acceptor
+pub const IO_CHANNEL_ID: u16 = 1003;
+
+/// Server connection sequence state (MS-RDPBCGR 1.3.1.1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerAcceptorState {
+    /// Wait for X.224 Connection Request
+    ConnectionInitiationWaitRequest,
+    /// TLS (and CredSSP) handshake performed by the caller
+    EnhancedSecurityUpgrade,
+    /// Wait for MCS Connect Initial
+    BasicSettingsExchangeWaitInitial,
+    /// Wait for MCS Erect Domain Request
+    ChannelConnectionWaitErectDomain,
+    /// Wait for MCS Attach User Request
+    ChannelConnectionWaitAttachUser,
+    /// Wait for MCS Channel Join Requests, ended by the Client Info PDU
+    ChannelConnectionWaitChannelJoin,
+    /// Wait for Confirm Active
+    CapabilitiesExchange,
+    /// Wait for client Synchronize, Control and Font List
+    ConnectionFinalization,
+    /// Connection sequence complete
+    Accepted,
+}
+
+bitflags! {
+    /// Client finalization PDUs received
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct ClientFinalizationFlags: u8 {
+        const SYNCHRONIZE = 0x01;
+        const CONTROL_COOPERATE = 0x02;
+        const CONTROL_REQUEST = 0x04;
+        const FONT_LIST = 0x08;
+    }
+}
+
+/// Parameters negotiated during the connection sequence
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcceptanceResult {
+    /// Security protocol selected for the client
+    pub selected_protocol: u32,
+    /// Client data blocks from MCS Connect Initial
+    pub client_data: ClientData,
+    /// Client Info PDU
+    pub client_info: ClientInfoPdu,
+    /// MCS I/O channel ID
+    pub io_channel_id: u16,
+    /// MCS user channel ID assigned to the client
+    pub user_channel_id: u16,
+    /// Static virtual channel names with their MCS channel IDs
+    pub static_channels: Vec<(String, u16)>,
+    /// Share ID sent in Demand Active
+    pub share_id: u32,
+    /// Client capability sets from Confirm Active
+    pub client_capabilities: Vec<CapabilitySet>,
+}
+
+/// Sans-IO server connection sequence
+///
+/// Feed each complete inbound TPKT frame to `step` and write the returned
+/// frames to the transport.
+#[derive(Debug, Clone)]
+pub struct ServerAcceptor {
+    config: AcceptorConfig,
+    state: ServerAcceptorState,
+    requested_protocols: u32,
+    selected_protocol: u32,
+    client_data: Option<ClientData>,
+    static_channel_ids: Vec<u16>,
+    user_channel_id: u16,
+    joined_channels: Vec<u16>,
+    client_info: Option<ClientInfoPdu>,
+    client_capabilities: Vec<CapabilitySet>,
+    finalization: ClientFinalizationFlags,
+}
+
+impl ServerAcceptor {
+    /// Create new server acceptor
+    pub fn new(config: AcceptorConfig) -> Self {
+        Self {
+            config,
+            state: ServerAcceptorState::ConnectionInitiationWaitRequest,
+            requested_protocols: 0,
+            selected_protocol: Protocol::RdpSecurity as u32,
+            client_data: None,
+            static_channel_ids: Vec::new(),
+            user_channel_id: 0,
+            joined_channels: Vec::new(),
+            client_info: None,
+            client_capabilities: Vec::new(),
+            finalization: ClientFinalizationFlags::empty(),
+        }
+    }
+
+    /// Current state
+    pub fn state(&self) -> ServerAcceptorState {
+        self.state
+    }
+
+    /// Configuration
+    pub fn config(&self) -> &AcceptorConfig {
+        &self.config
+    }
+
+    /// Security protocol selected for the client
+    pub fn selected_protocol(&self) -> u32 {
+        self.selected_protocol
+    }
+
+    /// Client data blocks from MCS Connect Initial
+    pub fn client_data(&self) -> Option<&ClientData> {
+        self.client_data.as_ref()
+    }
+
+    /// Client Info PDU, once received
+    pub fn client_info(&self) -> Option<&ClientInfoPdu> {
+        self.client_info.as_ref()
+    }
+
+    /// Negotiated parameters, available once accepted
+    pub fn acceptance_result(&self) -> Option<AcceptanceResult> {
+        if self.state != ServerAcceptorState::Accepted {
+            return None;
+        }
+
+        let client_data = self.client_data.clone()?;
+        let static_channels = client_data
+            .network
+            .iter()
+            .flat_map(|network| &network.channels)
+            .zip(&self.static_channel_ids)
+            .map(|(channel, &id)| (channel.name.clone(), id))
+            .collect();
+
+        Some(AcceptanceResult {
+            selected_protocol: self.selected_protocol,
+            client_data,
+            client_info: self.client_info.clone()?,
+            io_channel_id: IO_CHANNEL_ID,
+            user_channel_id: self.user_channel_id,
+            static_channels,
+            share_id: self.config.share_id,
+            client_capabilities: self.client_capabilities.clone(),
+        })
+    }
+
+    /// Signal that the TLS (and CredSSP) handshake has completed
+    pub fn mark_security_upgrade_as_done(&mut self) -> AcceptorResult<()> {
+        if self.state != ServerAcceptorState::EnhancedSecurityUpgrade {
+            return Err(AcceptorError::InvalidState(self.state));
+        }
+        self.state = ServerAcceptorState::BasicSettingsExchangeWaitInitial;
+        Ok(())
+    }
+
+    /// Advance the connection sequence with one complete inbound TPKT frame
+    ///
+    /// Returns the frames to send, in order.
+    pub fn step(&mut self, input: &[u8]) -> AcceptorResult<Vec<Vec<u8>>> {
+        match self.state {
+            ServerAcceptorState::ConnectionInitiationWaitRequest => {
+                self.on_connection_request(input)
+            }
+            ServerAcceptorState::BasicSettingsExchangeWaitInitial => self.on_connect_initial(input),
+            ServerAcceptorState::ChannelConnectionWaitErectDomain => self.on_erect_domain(input),
+            ServerAcceptorState::ChannelConnectionWaitAttachUser => self.on_attach_user(input),
+            ServerAcceptorState::ChannelConnectionWaitChannelJoin => self.on_channel_join(input),
+            ServerAcceptorState::CapabilitiesExchange => self.on_confirm_active(input),
+            ServerAcceptorState::ConnectionFinalization => self.on_finalization(input),
+            ServerAcceptorState::EnhancedSecurityUpgrade | ServerAcceptorState::Accepted => {
+                Err(AcceptorError::InvalidState(self.state))
+            }
+        }
+    }
+
+    fn on_connection_request(&mut self, input: &[u8]) -> AcceptorResult<Vec<Vec<u8>>> {
+        let tpdu = decode_tpkt(input)?;
+        let request = ConnectionRequest::decode(&mut tpdu.as_slice())?;
+        let dst_ref = request.header().src_ref;
+
+        let negotiation = request.rdp_negotiation();
+        self.requested_protocols = negotiation.map(|n| n.selected_protocol).unwrap_or(0);
+
+        let selected_protocol = match self.config.select_protocol(self.requested_protocols) {
+            Ok(protocol) => protocol,
+            Err(code) => {
+                let confirm = ConnectionConfirm::new(dst_ref, 0).with_negotiation_failure(code);
+                return Err(AcceptorError::NegotiationFailure {
+                    code,
+                    response: encode_tpkt(&confirm)?,
+                });
+            }
+        };
+        self.selected_protocol = selected_protocol;
+
+        // Legacy clients without a negotiation request get a bare confirm
+        let confirm = match negotiation {
+            Some(_) => ConnectionConfirm::new(dst_ref, 0).with_negotiation(selected_protocol),
+            None => ConnectionConfirm::new(dst_ref, 0),
+        };
+
+        self.state = if selected_protocol == Protocol::RdpSecurity as u32 {
+            ServerAcceptorState::BasicSettingsExchangeWaitInitial
+        } else {
+            ServerAcceptorState::EnhancedSecurityUpgrade
+        };
+        Ok(vec![encode_tpkt(&confirm)?])
+    }
+
+    fn on_connect_initial(&mut self, input: &[u8]) -> AcceptorResult<Vec<Vec<u8>>> {
+        let mcs = self.read_mcs(input)?;
+        let connect_initial = ConnectInitial::decode(&mut mcs.as_slice())?;
+        let conference = connect_initial.conference_request()?;
+        let client_data = ClientData::decode(&mut conference.user_data.as_slice())?;
+
+        if let Some(protocol) = client_data.core.optional.server_selected_protocol
+            && protocol != self.selected_protocol
+        {
+            return Err(AcceptorError::InvalidClientData(format!(
+                "serverSelectedProtocol {:#x} does not match selected protocol {:#x}",
+                protocol, self.selected_protocol
+            )));
+        }
+
+        let channel_count = client_data
+            .network
+            .as_ref()
+            .map(|network| network.channels.len())
+            .unwrap_or(0);
+        if channel_count > MAX_STATIC_CHANNELS {
+            return Err(AcceptorError::InvalidClientData(format!(
+                "{} static channels requested",
+                channel_count
+            )));
+        }
+
+        // Static channels follow the I/O channel; the user channel comes next
+        self.static_channel_ids = (1..=channel_count as u16)
+            .map(|i| IO_CHANNEL_ID + i)
+            .collect();
+        self.user_channel_id = IO_CHANNEL_ID + channel_count as u16 + 1;
+
+        let server_data = ServerData::new(
+            ServerCoreData::new(self.requested_protocols),
+            ServerNetworkData::new(IO_CHANNEL_ID, self.static_channel_ids.clone()),
+            ServerSecurityData::none(),
+        );
+        let conference = ConferenceCreateResponse::new(encode_pdu(&server_data)?);
+        let response = ConnectResponse::success(&conference)?;
+
+        self.client_data = Some(client_data);
+        self.state = ServerAcceptorState::ChannelConnectionWaitErectDomain;
+        Ok(vec![encode_x224_data(&response)?])
+    }
+
+    fn on_erect_domain(&mut self, input: &[u8]) -> AcceptorResult<Vec<Vec<u8>>> {
+        let mcs = self.read_mcs(input)?;
+        if mcs_choice(&mcs) != Some(MCS_ERECT_DOMAIN_REQUEST) {
+            return Err(self.unexpected("MCS PDU instead of Erect Domain Request"));
+        }
+        ErectDomainRequest::decode(&mut mcs.as_slice())?;
+
+        self.state = ServerAcceptorState::ChannelConnectionWaitAttachUser;
+        Ok(Vec::new())
+    }
+
+    fn on_attach_user(&mut self, input: &[u8]) -> AcceptorResult<Vec<Vec<u8>>> {
+        let mcs = self.read_mcs(input)?;
+        if mcs_choice(&mcs) != Some(MCS_ATTACH_USER_REQUEST) {
+            return Err(self.unexpected("MCS PDU instead of Attach User Request"));
+        }
+        AttachUserRequest::decode(&mut mcs.as_slice())?;
+
+        self.state = ServerAcceptorState::ChannelConnectionWaitChannelJoin;
+        Ok(vec![encode_x224_data(&AttachUserConfirm::success(
+            self.user_channel_id,
+        ))?])
+    }
+
+    fn on_channel_join(&mut self, input: &[u8]) -> AcceptorResult<Vec<Vec<u8>>> {
+        let mcs = self.read_mcs(input)?;
+        match mcs_choice(&mcs) {
+            Some(MCS_CHANNEL_JOIN_REQUEST) => {
+                let request = ChannelJoinRequest::decode(&mut mcs.as_slice())?;
+                if request.user_id != self.user_channel_id {
+                    return Err(self.unexpected(&format!(
+                        "Channel Join Request from user {}",
+                        request.user_id
+                    )));
+                }
+
+                let known = request.channel_id == self.user_channel_id
+                    || request.channel_id == IO_CHANNEL_ID
+                    || self.static_channel_ids.contains(&request.channel_id);
+                let confirm = if known {
+                    self.joined_channels.push(request.channel_id);
+                    ChannelJoinConfirm::success(self.user_channel_id, request.channel_id)
+                } else {
+                    ChannelJoinConfirm::failure(
+                        McsResult::RtNoSuchChannel,
+                        self.user_channel_id,
+                        request.channel_id,
+                    )
+                };
+                Ok(vec![encode_x224_data(&confirm)?])
+            }
+            Some(MCS_SEND_DATA_REQUEST) => self.on_client_info(&mcs),
+            _ => Err(self.unexpected("MCS PDU during channel join")),
+        }
+    }
+
+    fn on_client_info(&mut self, mcs: &[u8]) -> AcceptorResult<Vec<Vec<u8>>> {
+        for channel_id in [self.user_channel_id, IO_CHANNEL_ID] {
+            if !self.joined_channels.contains(&channel_id) {
+                return Err(self.unexpected(&format!(
+                    "Client Info PDU before joining channel {}",
+                    channel_id
+                )));
+            }
+        }
+
+        let request = SendDataRequest::decode(&mut &mcs[..])?;
+        if request.channel_id != IO_CHANNEL_ID {
+            return Err(self.unexpected(&format!(
+                "Send Data Request on channel {}",
+                request.channel_id
+            )));
+        }
+
+        let (header, mut body) = decode_basic_security(&request.user_data)?;
+        if !header.flags.contains(SecurityFlags::INFO_PKT) {
+            return Err(self.unexpected(&format!(
+                "Send Data Request with security flags {:#06x}",
+                header.flags.bits()
+            )));
+        }
+        self.client_info = Some(ClientInfoPdu::decode(&mut body)?);
+
+        let license = SendDataIndication::new(
+            SERVER_CHANNEL_ID,
+            IO_CHANNEL_ID,
+            encode_license_error(&self.config.license_error)?,
+        );
+        if self.config.license_error.state_transition != StateTransition::NoTransition {
+            return Err(AcceptorError::Licensing {
+                message: self.config.license_error.clone(),
+                response: encode_x224_data(&license)?,
+            });
+        }
+
+        let demand_active =
+            DemandActivePdu::new(self.config.share_id, self.config.demand_capability_sets());
+        let demand_active = SendDataIndication::with_share_control(
+            SERVER_CHANNEL_ID,
+            IO_CHANNEL_ID,
+            PduType::DemandActive,
+            &encode_pdu(&demand_active)?,
+        )?;
+
+        self.state = ServerAcceptorState::CapabilitiesExchange;
+        Ok(vec![
+            encode_x224_data(&license)?,
+            encode_x224_data(&demand_active)?,
+        ])
+    }
+
+    fn on_confirm_active(&mut self, input: &[u8]) -> AcceptorResult<Vec<Vec<u8>>> {
+        let (header, payload) = self.read_share_control(input)?;
+        if header.pdu_type != PduType::ConfirmActive {
+            return Err(self.unexpected(&format!("{:?} PDU", header.pdu_type)));
+        }
+
+        let confirm_active = ConfirmActivePdu::decode(&mut payload.as_slice())?;
+        if confirm_active.share_id != self.config.share_id {
+            return Err(AcceptorError::CapabilitiesExchange(format!(
+                "Confirm Active share ID {:#x} does not match {:#x}",
+                confirm_active.share_id, self.config.share_id
+            )));
+        }
+        self.client_capabilities = confirm_active.capability_sets;
+
+        self.finalization = ClientFinalizationFlags::empty();
+        self.state = ServerAcceptorState::ConnectionFinalization;
+        Ok(Vec::new())
+    }
+
+    fn on_finalization(&mut self, input: &[u8]) -> AcceptorResult<Vec<Vec<u8>>> {
+        let (header, payload) = self.read_share_control(input)?;
+        if header.pdu_type != PduType::Data {
+            return Err(self.unexpected(&format!("{:?} PDU", header.pdu_type)));
+        }
+
+        let (share_data, mut body) = decode_share_data(&payload)?;
+        if share_data.share_id != self.config.share_id {
+            return Err(AcceptorError::Finalization(format!(
+                "Data PDU share ID {:#x} does not match {:#x}",
+                share_data.share_id, self.config.share_id
+            )));
+        }
+
+        let (flag, response) = match share_data.pdu_type2 {
+            DataPduType::Synchronize => {
+                SynchronizePdu::decode(&mut body)?;
+                let synchronize = SynchronizePdu::new(self.user_channel_id);
+                (
+                    ClientFinalizationFlags::SYNCHRONIZE,
+                    self.share_data_frame(DataPduType::Synchronize, &synchronize)?,
+                )
+            }
+            DataPduType::Control => {
+                let control = ControlPdu::decode(&mut body)?;
+                match control.action {
+                    ControlAction::Cooperate => (
+                        ClientFinalizationFlags::CONTROL_COOPERATE,
+                        self.share_data_frame(DataPduType::Control, &ControlPdu::cooperate())?,
+                    ),
+                    ControlAction::RequestControl => (
+                        ClientFinalizationFlags::CONTROL_REQUEST,
+                        self.share_data_frame(
+                            DataPduType::Control,
+                            &ControlPdu::granted_control(self.user_channel_id),
+                        )?,
+                    ),
+                    action => {
+                        return Err(AcceptorError::Finalization(format!(
+                            "Unexpected client control action: {:?}",
+                            action
+                        )));
+                    }
+                }
+            }
+            DataPduType::FontList => {
+                FontListPdu::decode(&mut body)?;
+                (
+                    ClientFinalizationFlags::FONT_LIST,
+                    self.share_data_frame(DataPduType::FontMap, &FontMapPdu::new())?,
+                )
+            }
+            // Persistent Key List PDUs may precede the Font List and need no answer
+            DataPduType::BitmapCachePersistentList => return Ok(Vec::new()),
+            pdu_type2 => return Err(self.unexpected(&format!("{:?} Data PDU", pdu_type2))),
+        };
+
+        self.finalization |= flag;
+        if self.finalization.is_all() {
+            self.state = ServerAcceptorState::Accepted;
+        }
+        Ok(vec![response])
+    }
+
+    /// Wrap a Share Data PDU for the I/O channel
+    fn share_data_frame(&self, pdu_type2: DataPduType, body: &impl Pdu) -> AcceptorResult<Vec<u8>> {
+        let payload = encode_share_data(self.config.share_id, pdu_type2, body)?;
+        let indication = SendDataIndication::with_share_control(
+            SERVER_CHANNEL_ID,
+            IO_CHANNEL_ID,
+            PduType::Data,
+            &payload,
+        )?;
+        Ok(encode_x224_data(&indication)?)
+    }
+
+    /// Unwrap TPKT and X.224 Data, failing on disconnect indications
+    fn read_mcs(&self, input: &[u8]) -> AcceptorResult<Vec<u8>> {
+        let mcs = match decode_x224_data(input)? {
+            X224Frame::Data(mcs) => mcs,
+            X224Frame::Disconnect(request) => {
+                return Err(AcceptorError::Disconnected(
+                    request.reason.description().to_string(),
+                ));
+            }
+        };
+
+        if let Some(ultimatum) = disconnect_ultimatum(&mcs)? {
+            return Err(AcceptorError::Disconnected(
+                ultimatum.reason.description().to_string(),
+            ));
+        }
+        Ok(mcs)
+    }
+
+    fn read_share_control(&self, input: &[u8]) -> AcceptorResult<(ShareControlHeader, Vec<u8>)> {
+        let mcs = self.read_mcs(input)?;
+        let request = SendDataRequest::decode(&mut mcs.as_slice())?;
+        Ok(request.share_control(IO_CHANNEL_ID)?)
+    }
+
+    fn unexpected(&self, pdu: &str) -> AcceptorError {
+        AcceptorError::UnexpectedPdu {
+            state: self.state,
+            pdu: pdu.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdu::framing::encode_basic_security;
+    use crate::pdu::licensing::{
+        LicenseErrorCode, LicenseErrorMessage, LicenseMessage, LicensePdu,
+    };
+    use crate::pdu::x224::NegotiationFailureCode;
+
+    fn connection_request(protocols: Option<u32>) -> Vec<u8> {
+        let request = ConnectionRequest::new(0x1234).with_cookie("user");
+        let request = match protocols {
+            Some(protocols) => request.with_negotiation(protocols),
+            None => request,
+        };
+        encode_tpkt(&request).unwrap()
+    }
+
+    #[test]
+    fn test_server_acceptor_selects_preferred_protocol() {
+        let mut acceptor = ServerAcceptor::new(AcceptorConfig::new());
+        let output = acceptor
+            .step(&connection_request(Some(
+                Protocol::Ssl as u32 | Protocol::Hybrid as u32,
+            )))
+            .unwrap();
+
+        let tpdu = decode_tpkt(&output[0]).unwrap();
+        let confirm = ConnectionConfirm::decode(&mut tpdu.as_slice()).unwrap();
+        assert_eq!(confirm.header().dst_ref, 0x1234);
+        assert_eq!(
+            confirm.rdp_negotiation().unwrap().selected_protocol,
+            Protocol::Hybrid as u32
+        );
+        assert_eq!(
+            acceptor.state(),
+            ServerAcceptorState::EnhancedSecurityUpgrade
+        );
+        assert!(matches!(
+            acceptor.step(&[]),
+            Err(AcceptorError::InvalidState(
+                ServerAcceptorState::EnhancedSecurityUpgrade
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_server_acceptor_negotiation_failure() {
+        let config = AcceptorConfig::new().with_protocols(vec![Protocol::Hybrid]);
+        let mut acceptor = ServerAcceptor::new(config);
+
+        let error = acceptor
+            .step(&connection_request(Some(Protocol::Ssl as u32)))
+            .unwrap_err();
+        let AcceptorError::NegotiationFailure { code, response } = error else {
+            panic!("expected negotiation failure");
+        };
+        assert_eq!(code, NegotiationFailureCode::HybridRequiredByServer);
+
+        let tpdu = decode_tpkt(&response).unwrap();
+        let confirm = ConnectionConfirm::decode(&mut tpdu.as_slice()).unwrap();
+        assert_eq!(
+            confirm.rdp_negotiation().unwrap().failure_code(),
+            Some(code)
+        );
+    }
+
+    #[test]
+    fn test_server_acceptor_legacy_client() {
+        let config =
+            AcceptorConfig::new().with_protocols(vec![Protocol::Ssl, Protocol::RdpSecurity]);
+        let mut acceptor = ServerAcceptor::new(config);
+
+        let output = acceptor.step(&connection_request(None)).unwrap();
+
+        let tpdu = decode_tpkt(&output[0]).unwrap();
+        let confirm = ConnectionConfirm::decode(&mut tpdu.as_slice()).unwrap();
+        assert!(confirm.rdp_negotiation().is_none());
+        assert_eq!(
+            acceptor.state(),
+            ServerAcceptorState::BasicSettingsExchangeWaitInitial
+        );
+    }
+
+    #[test]
+    fn test_server_acceptor_license_error() {
+        let client_info = |config: AcceptorConfig| {
+            let mut acceptor = ServerAcceptor::new(config);
+            acceptor.state = ServerAcceptorState::ChannelConnectionWaitChannelJoin;
+            acceptor.user_channel_id = 1004;
+            acceptor.joined_channels = vec![1004, IO_CHANNEL_ID];
+
+            let client_info = ClientInfoPdu::new("user".to_string(), "pass".to_string());
+            let request = SendDataRequest::new(
+                1004,
+                IO_CHANNEL_ID,
+                encode_basic_security(SecurityFlags::INFO_PKT, &encode_pdu(&client_info).unwrap())
+                    .unwrap(),
+            );
+            let result = acceptor.step(&encode_x224_data(&request).unwrap());
+            (acceptor.state(), result)
+        };
+
+        let license_pdu = |frame: &[u8]| {
+            let X224Frame::Data(mcs) = decode_x224_data(frame).unwrap() else {
+                panic!("expected X.224 Data");
+            };
+            let indication = SendDataIndication::decode(&mut mcs.as_slice()).unwrap();
+            let (header, body) = decode_basic_security(&indication.user_data).unwrap();
+            assert!(header.flags.contains(SecurityFlags::LICENSE_PKT));
+            LicensePdu::decode(&mut &body[..]).unwrap()
+        };
+
+        // Licensing denied without leaving the licensing phase
+        let denied =
+            LicenseErrorMessage::new(LicenseErrorCode::NoLicense, StateTransition::TotalAbort);
+        let (state, result) = client_info(AcceptorConfig::new().with_license_error(denied.clone()));
+        let Err(AcceptorError::Licensing { message, response }) = result else {
+            panic!("expected licensing error");
+        };
+        assert_eq!(message, denied);
+        assert_eq!(
+            license_pdu(&response).message,
+            LicenseMessage::ErrorAlert(denied)
+        );
+        assert_eq!(state, ServerAcceptorState::ChannelConnectionWaitChannelJoin);
+
+        // Any error code with ST_NO_TRANSITION lets the client continue
+        let no_server = LicenseErrorMessage::new(
+            LicenseErrorCode::NoLicenseServer,
+            StateTransition::NoTransition,
+        );
+        let (state, result) =
+            client_info(AcceptorConfig::new().with_license_error(no_server.clone()));
+        let output = result.unwrap();
+        assert_eq!(output.len(), 2);
+        assert_eq!(
+            license_pdu(&output[0]).message,
+            LicenseMessage::ErrorAlert(no_server)
+        );
+        assert_eq!(state, ServerAcceptorState::CapabilitiesExchange);
+    }
+
+    #[test]
+    fn test_server_acceptor_finalization_rejects_unexpected_data_pdu() {
+        let mut acceptor = ServerAcceptor::new(AcceptorConfig::new());
+        acceptor.state = ServerAcceptorState::ConnectionFinalization;
+        acceptor.user_channel_id = 1004;
+        let share_id = acceptor.config.share_id;
+
+        let client_data_pdu = |pdu_type2| {
+            let payload =
+                encode_share_data(share_id, pdu_type2, &SynchronizePdu::new(1004)).unwrap();
+            let request =
+                SendDataRequest::with_share_control(1004, IO_CHANNEL_ID, PduType::Data, &payload)
+                    .unwrap();
+            encode_x224_data(&request).unwrap()
+        };
+
+        let output = acceptor
+            .step(&client_data_pdu(DataPduType::Synchronize))
+            .unwrap();
+        assert_eq!(output.len(), 1);
+
+        assert!(matches!(
+            acceptor.step(&client_data_pdu(DataPduType::RefreshRect)),
+            Err(AcceptorError::UnexpectedPdu {
+                state: ServerAcceptorState::ConnectionFinalization,
+                ..
+            })
+        ));
+    }
+}
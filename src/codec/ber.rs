@@ -20,6 +20,9 @@ pub enum BerClass {
     Private = 0xC0,
 }
 
+/// Constructed encoding bit of the identifier octet
+pub const BER_CONSTRUCTED: u8 = 0x20;
+
 /// BER Reader - Utility for reading BER encoded data
 pub struct BerReader<'a> {
     buffer: &'a [u8],
@@ -45,6 +48,11 @@ impl<'a> BerReader<'a> {
         self.buffer.len().saturating_sub(self.position)
     }
 
+    /// Return next tag without consuming it
+    pub fn peek_tag(&self) -> Option<u8> {
+        self.buffer.get(self.position).copied()
+    }
+
     /// Read BER Tag (supports only 1-byte tag)
     pub fn read_tag(&mut self) -> Result<u8> {
         if self.remaining() < 1 {
@@ -119,14 +127,7 @@ impl<'a> BerReader<'a> {
             )));
         }
 
-        let length = self.read_length()?;
-        if length == 0 || length > 4 {
-            return Err(PduError::ParseError(format!(
-                "Invalid INTEGER length: {}",
-                length
-            )));
-        }
-
+        let mut length = self.read_length()?;
         if self.remaining() < length {
             return Err(PduError::InsufficientData {
                 needed: length,
@@ -134,6 +135,19 @@ impl<'a> BerReader<'a> {
             });
         }
 
+        // Leading zero octet keeps values >= 0x80000000 positive
+        if length == 5 && self.buffer[self.position] == 0 {
+            self.position += 1;
+            length -= 1;
+        }
+
+        if length == 0 || length > 4 {
+            return Err(PduError::ParseError(format!(
+                "Invalid INTEGER length: {}",
+                length
+            )));
+        }
+
         let mut value: u32 = 0;
         for _ in 0..length {
             value = (value << 8) | (self.buffer[self.position] as u32);
@@ -143,6 +157,41 @@ impl<'a> BerReader<'a> {
         Ok(value)
     }
 
+    /// Read INTEGER as a two's complement 32-bit value
+    pub fn read_signed_integer(&mut self) -> Result<i32> {
+        let tag = self.read_tag()?;
+        if tag != BerTag::Integer as u8 {
+            return Err(PduError::ParseError(format!(
+                "Expected INTEGER tag (0x02), got 0x{:02x}",
+                tag
+            )));
+        }
+
+        let length = self.read_length()?;
+        if length == 0 || length > 5 {
+            return Err(PduError::ParseError(format!(
+                "Invalid INTEGER length: {}",
+                length
+            )));
+        }
+
+        let mut bytes = self.read_bytes(length)?;
+        // Unsigned encoding of a value >= 0x80000000: drop the leading zero and cast
+        if length == 5 {
+            if bytes[0] != 0 || bytes[1] & 0x80 == 0 {
+                return Err(PduError::ParseError(format!(
+                    "Invalid INTEGER length: {}",
+                    length
+                )));
+            }
+            bytes.remove(0);
+        }
+        let sign = if bytes[0] & 0x80 != 0 { -1 } else { 0 };
+        Ok(bytes
+            .iter()
+            .fold(sign, |value: i32, &b| (value << 8) | b as i32))
+    }
+
     /// Read OCTET STRING
     pub fn read_octet_string(&mut self) -> Result<Vec<u8>> {
         let tag = self.read_tag()?;
@@ -260,6 +309,24 @@ impl<'a> BerReader<'a> {
 
         self.read_length()
     }
+
+    /// Read SEQUENCE tag, returning its length
+    pub fn read_sequence(&mut self) -> Result<usize> {
+        let tag = self.read_tag()?;
+        if tag != BerTag::Sequence as u8 {
+            return Err(PduError::ParseError(format!(
+                "Expected SEQUENCE tag (0x30), got 0x{:02x}",
+                tag
+            )));
+        }
+
+        self.read_length()
+    }
+
+    /// Read constructed Context-Specific tag ([n] EXPLICIT)
+    pub fn read_explicit_tag(&mut self, expected_tag: u8) -> Result<usize> {
+        self.read_context_tag(BER_CONSTRUCTED | expected_tag)
+    }
 }
 
 /// BER Writer - Utility for writing BER encoded data
@@ -340,6 +407,29 @@ impl BerWriter {
         self.buffer.extend_from_slice(&bytes);
     }
 
+    /// Write INTEGER as a two's complement 32-bit value
+    pub fn write_signed_integer(&mut self, value: i32) {
+        let bytes = value.to_be_bytes();
+
+        // Drop leading octets that only repeat the sign bit
+        let mut start = 0;
+        while start < 3 {
+            let redundant = match bytes[start] {
+                0x00 => bytes[start + 1] & 0x80 == 0,
+                0xFF => bytes[start + 1] & 0x80 != 0,
+                _ => false,
+            };
+            if !redundant {
+                break;
+            }
+            start += 1;
+        }
+
+        self.write_tag(BerTag::Integer as u8);
+        self.write_length(bytes.len() - start);
+        self.buffer.extend_from_slice(&bytes[start..]);
+    }
+
     /// Write OCTET STRING
     pub fn write_octet_string(&mut self, data: &[u8]) {
         self.write_tag(BerTag::OctetString as u8);
@@ -399,6 +489,14 @@ impl BerWriter {
         self.write_length(inner.buffer.len());
         self.buffer.extend_from_slice(&inner.buffer);
     }
+
+    /// Write with constructed Context-Specific tag ([n] EXPLICIT)
+    pub fn write_explicit_tag<F>(&mut self, tag: u8, f: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        self.write_context_tag(BER_CONSTRUCTED | tag, f);
+    }
 }
 
 impl Default for BerWriter {
@@ -453,6 +551,39 @@ mod tests {
             let mut reader = BerReader::new(writer.as_bytes());
             assert_eq!(reader.read_integer().unwrap(), value);
         }
+
+        // Values with the top bit set carry a leading zero octet
+        let mut writer = BerWriter::new();
+        writer.write_integer(u32::MAX);
+        assert_eq!(
+            writer.as_bytes(),
+            &[0x02, 0x05, 0x00, 0xFF, 0xFF, 0xFF, 0xFF]
+        );
+
+        let mut reader = BerReader::new(writer.as_bytes());
+        assert_eq!(reader.read_integer().unwrap(), u32::MAX);
+    }
+
+    #[test]
+    fn test_ber_signed_integer() {
+        let test_cases: [(i32, &[u8]); 6] = [
+            (0, &[0x00]),
+            (127, &[0x7F]),
+            (128, &[0x00, 0x80]),
+            (-1, &[0xFF]),
+            (-128, &[0x80]),
+            (0xC000_006Du32 as i32, &[0xC0, 0x00, 0x00, 0x6D]),
+        ];
+
+        for (value, contents) in test_cases {
+            let mut writer = BerWriter::new();
+            writer.write_signed_integer(value);
+            assert_eq!(&writer.as_bytes()[2..], contents);
+            assert_eq!(writer.as_bytes()[1] as usize, contents.len());
+
+            let mut reader = BerReader::new(writer.as_bytes());
+            assert_eq!(reader.read_signed_integer().unwrap(), value);
+        }
     }
 
     #[test]
@@ -492,6 +623,12 @@ mod tests {
 
         assert_eq!(reader.read_integer().unwrap(), 42);
         assert_eq!(reader.read_integer().unwrap(), 100);
+
+        let mut reader = BerReader::new(writer.as_bytes());
+        assert_eq!(reader.read_sequence().unwrap(), length);
+
+        assert_eq!(reader.read_integer().unwrap(), 42);
+        assert_eq!(reader.read_integer().unwrap(), 100);
     }
 
     #[test]
@@ -520,6 +657,21 @@ mod tests {
         assert!(length > 0);
 
         assert_eq!(reader.read_integer().unwrap(), 456);
+
+        let mut writer = BerWriter::new();
+        writer.write_explicit_tag(1, |w| {
+            w.write_octet_string(b"token");
+        });
+        assert_eq!(&writer.as_bytes()[..2], &[0xA1, 0x07]);
+
+        let mut reader = BerReader::new(writer.as_bytes());
+        assert_eq!(reader.peek_tag(), Some(0xA1));
+        assert!(reader.read_explicit_tag(2).is_err());
+
+        let mut reader = BerReader::new(writer.as_bytes());
+        assert_eq!(reader.read_explicit_tag(1).unwrap(), 7);
+        assert_eq!(reader.read_octet_string().unwrap(), b"token");
+        assert_eq!(reader.peek_tag(), None);
     }
 
     #[test]
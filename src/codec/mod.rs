@@ -1,3 +1,5 @@
 pub mod ber;
+pub mod per;
 
-pub use ber::{BerClass, BerReader, BerTag, BerWriter};
+pub use ber::{BER_CONSTRUCTED, BerClass, BerReader, BerTag, BerWriter};
+pub use per::{PerReader, PerWriter};
@@ -0,0 +1,546 @@
+use crate::pdu::{PduError, Result};
+use std::io::Write;
+
+/// Largest length that fits in a two-octet PER length determinant
+pub const PER_MAX_LENGTH: usize = 0x3FFF;
+
+/// PER Reader - Utility for reading ALIGNED PER encoded data
+pub struct PerReader<'a> {
+    buffer: &'a [u8],
+    position: usize,
+}
+
+impl<'a> PerReader<'a> {
+    /// Create new PER Reader
+    pub fn new(buffer: &'a [u8]) -> Self {
+        Self {
+            buffer,
+            position: 0,
+        }
+    }
+
+    /// Return current position
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Return remaining bytes
+    pub fn remaining(&self) -> usize {
+        self.buffer.len().saturating_sub(self.position)
+    }
+
+    /// Read a single octet
+    pub fn read_u8(&mut self) -> Result<u8> {
+        if self.remaining() < 1 {
+            return Err(PduError::InsufficientData {
+                needed: 1,
+                available: self.remaining(),
+            });
+        }
+
+        let value = self.buffer[self.position];
+        self.position += 1;
+        Ok(value)
+    }
+
+    /// Read a big-endian 16-bit value
+    pub fn read_u16(&mut self) -> Result<u16> {
+        let bytes = self.read_bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    /// Read specified number of bytes
+    pub fn read_bytes(&mut self, length: usize) -> Result<Vec<u8>> {
+        if self.remaining() < length {
+            return Err(PduError::InsufficientData {
+                needed: length,
+                available: self.remaining(),
+            });
+        }
+
+        let bytes = self.buffer[self.position..self.position + length].to_vec();
+        self.position += length;
+        Ok(bytes)
+    }
+
+    /// Read length determinant (1 octet below 0x80, otherwise 2 octets)
+    pub fn read_length(&mut self) -> Result<usize> {
+        let first_byte = self.read_u8()?;
+
+        if first_byte & 0x80 == 0 {
+            return Ok(first_byte as usize);
+        }
+
+        if first_byte & 0x40 != 0 {
+            // Fragmented lengths (16K blocks) are not used by RDP
+            return Err(PduError::ParseError(
+                "Fragmented PER length not supported".to_string(),
+            ));
+        }
+
+        let second_byte = self.read_u8()?;
+        Ok((((first_byte & 0x3F) as usize) << 8) | second_byte as usize)
+    }
+
+    /// Read CHOICE index
+    pub fn read_choice(&mut self) -> Result<u8> {
+        self.read_u8()
+    }
+
+    /// Read optional-field selection bitmap
+    pub fn read_selection(&mut self) -> Result<u8> {
+        self.read_u8()
+    }
+
+    /// Read number of SET OF elements
+    pub fn read_number_of_sets(&mut self) -> Result<u8> {
+        self.read_u8()
+    }
+
+    /// Read ENUMERATED and verify it is below `count`
+    pub fn read_enumerated(&mut self, count: u8) -> Result<u8> {
+        let value = self.read_u8()?;
+        if value >= count {
+            return Err(PduError::ParseError(format!(
+                "ENUMERATED value {} out of range (count {})",
+                value, count
+            )));
+        }
+
+        Ok(value)
+    }
+
+    /// Read INTEGER with lower bound 0 (length-prefixed, big-endian)
+    pub fn read_integer(&mut self) -> Result<u32> {
+        let length = self.read_length()?;
+        if length == 0 || length > 4 {
+            return Err(PduError::ParseError(format!(
+                "Invalid INTEGER length: {}",
+                length
+            )));
+        }
+
+        let bytes = self.read_bytes(length)?;
+        Ok(bytes
+            .iter()
+            .fold(0u32, |value, &byte| (value << 8) | byte as u32))
+    }
+
+    /// Read 16-bit constrained INTEGER with the given lower bound
+    pub fn read_integer16(&mut self, min: u16) -> Result<u16> {
+        let offset = self.read_u16()?;
+        min.checked_add(offset).ok_or_else(|| {
+            PduError::ParseError(format!(
+                "INTEGER16 overflow: {} + {} exceeds u16",
+                min, offset
+            ))
+        })
+    }
+
+    /// Skip padding octets
+    pub fn read_padding(&mut self, length: usize) -> Result<()> {
+        self.read_bytes(length)?;
+        Ok(())
+    }
+
+    /// Read OCTET STRING with the given minimum size constraint
+    pub fn read_octet_string(&mut self, min_length: usize) -> Result<Vec<u8>> {
+        let length = self.read_length()? + min_length;
+        self.read_bytes(length)
+    }
+
+    /// Read NumericString with the given minimum size constraint (4 bits per digit)
+    pub fn read_numeric_string(&mut self, min_length: usize) -> Result<String> {
+        let length = self.read_length()? + min_length;
+        let packed = self.read_bytes(length.div_ceil(2))?;
+
+        let mut digits = String::with_capacity(length);
+        for (i, byte) in packed.iter().enumerate() {
+            for (j, nibble) in [byte >> 4, byte & 0x0F].into_iter().enumerate() {
+                if i * 2 + j >= length {
+                    break;
+                }
+                if nibble > 9 {
+                    return Err(PduError::ParseError(format!(
+                        "Invalid NumericString digit: {:#x}",
+                        nibble
+                    )));
+                }
+                digits.push((b'0' + nibble) as char);
+            }
+        }
+
+        Ok(digits)
+    }
+
+    /// Read OBJECT IDENTIFIER and return its arcs
+    pub fn read_object_identifier(&mut self) -> Result<Vec<u32>> {
+        let length = self.read_length()?;
+        if length == 0 {
+            return Err(PduError::ParseError("Empty OBJECT IDENTIFIER".to_string()));
+        }
+
+        let bytes = self.read_bytes(length)?;
+
+        let mut arcs = vec![(bytes[0] / 40) as u32, (bytes[0] % 40) as u32];
+        let mut value: u32 = 0;
+        for &byte in &bytes[1..] {
+            value = (value << 7) | (byte & 0x7F) as u32;
+            if byte & 0x80 == 0 {
+                arcs.push(value);
+                value = 0;
+            }
+        }
+
+        Ok(arcs)
+    }
+}
+
+/// PER Writer - Utility for writing ALIGNED PER encoded data
+pub struct PerWriter {
+    buffer: Vec<u8>,
+}
+
+impl PerWriter {
+    /// Create new PER Writer
+    pub fn new() -> Self {
+        Self { buffer: Vec::new() }
+    }
+
+    /// Consume buffer and return Vec<u8>
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    /// Return reference to current buffer
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    /// Write a single octet
+    pub fn write_u8(&mut self, value: u8) {
+        self.buffer.push(value);
+    }
+
+    /// Write a big-endian 16-bit value
+    pub fn write_u16(&mut self, value: u16) {
+        self.buffer.extend_from_slice(&value.to_be_bytes());
+    }
+
+    /// Write raw bytes
+    pub fn write_bytes(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Write length determinant
+    ///
+    /// Fragmented lengths (above PER_MAX_LENGTH) are not supported.
+    pub fn write_length(&mut self, length: usize) -> Result<()> {
+        if length > PER_MAX_LENGTH {
+            return Err(PduError::InvalidLength {
+                expected: PER_MAX_LENGTH,
+                actual: length,
+            });
+        }
+
+        if length < 0x80 {
+            self.buffer.push(length as u8);
+        } else {
+            self.write_u16(0x8000 | length as u16);
+        }
+        Ok(())
+    }
+
+    /// Write CHOICE index
+    pub fn write_choice(&mut self, choice: u8) {
+        self.buffer.push(choice);
+    }
+
+    /// Write optional-field selection bitmap
+    pub fn write_selection(&mut self, selection: u8) {
+        self.buffer.push(selection);
+    }
+
+    /// Write number of SET OF elements
+    pub fn write_number_of_sets(&mut self, count: u8) {
+        self.buffer.push(count);
+    }
+
+    /// Write ENUMERATED
+    pub fn write_enumerated(&mut self, value: u8) {
+        self.buffer.push(value);
+    }
+
+    /// Write INTEGER with lower bound 0 (length-prefixed, big-endian)
+    pub fn write_integer(&mut self, value: u32) {
+        // Content lengths 1, 2 and 4 use the single-octet length form
+        if value <= 0xFF {
+            self.buffer.push(1);
+            self.buffer.push(value as u8);
+        } else if value <= 0xFFFF {
+            self.buffer.push(2);
+            self.write_u16(value as u16);
+        } else {
+            self.buffer.push(4);
+            self.buffer.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+
+    /// Write 16-bit constrained INTEGER with the given lower bound
+    pub fn write_integer16(&mut self, value: u16, min: u16) -> Result<()> {
+        let offset = value.checked_sub(min).ok_or_else(|| {
+            PduError::ParseError(format!(
+                "INTEGER16 value {} below lower bound {}",
+                value, min
+            ))
+        })?;
+
+        self.write_u16(offset);
+        Ok(())
+    }
+
+    /// Write padding octets
+    pub fn write_padding(&mut self, length: usize) {
+        self.buffer.resize(self.buffer.len() + length, 0);
+    }
+
+    /// Write OCTET STRING with the given minimum size constraint
+    pub fn write_octet_string(&mut self, data: &[u8], min_length: usize) -> Result<()> {
+        let length = data
+            .len()
+            .checked_sub(min_length)
+            .ok_or(PduError::InvalidLength {
+                expected: min_length,
+                actual: data.len(),
+            })?;
+
+        self.write_length(length)?;
+        self.buffer.extend_from_slice(data);
+        Ok(())
+    }
+
+    /// Write NumericString with the given minimum size constraint (4 bits per digit)
+    pub fn write_numeric_string(&mut self, digits: &str, min_length: usize) -> Result<()> {
+        if let Some(c) = digits.chars().find(|c| !c.is_ascii_digit()) {
+            return Err(PduError::ParseError(format!(
+                "Invalid NumericString character: {:?}",
+                c
+            )));
+        }
+
+        let length = digits
+            .len()
+            .checked_sub(min_length)
+            .ok_or(PduError::InvalidLength {
+                expected: min_length,
+                actual: digits.len(),
+            })?;
+        self.write_length(length)?;
+
+        for pair in digits.as_bytes().chunks(2) {
+            let high = pair[0] - b'0';
+            let low = pair.get(1).map(|c| c - b'0').unwrap_or(0);
+            self.buffer.push((high << 4) | low);
+        }
+
+        Ok(())
+    }
+
+    /// Write OBJECT IDENTIFIER from its arcs
+    pub fn write_object_identifier(&mut self, arcs: &[u32]) -> Result<()> {
+        if arcs.len() < 2 || arcs[0] > 2 || arcs[1] >= 40 {
+            return Err(PduError::ParseError(format!(
+                "Invalid OBJECT IDENTIFIER: {:?}",
+                arcs
+            )));
+        }
+
+        let mut content = vec![(arcs[0] * 40 + arcs[1]) as u8];
+        for &arc in &arcs[2..] {
+            let mut chunk = vec![(arc & 0x7F) as u8];
+            let mut rest = arc >> 7;
+            while rest > 0 {
+                chunk.push(0x80 | (rest & 0x7F) as u8);
+                rest >>= 7;
+            }
+            chunk.reverse();
+            content.extend_from_slice(&chunk);
+        }
+
+        self.write_length(content.len())?;
+        self.buffer.extend_from_slice(&content);
+        Ok(())
+    }
+}
+
+impl Default for PerWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Write for PerWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_per_length_short_form() {
+        let mut writer = PerWriter::new();
+        writer.write_length(0x7F).unwrap();
+        assert_eq!(writer.as_bytes(), &[0x7F]);
+
+        let mut reader = PerReader::new(writer.as_bytes());
+        assert_eq!(reader.read_length().unwrap(), 0x7F);
+    }
+
+    #[test]
+    fn test_per_length_long_form() {
+        let mut writer = PerWriter::new();
+        writer.write_length(0x12A).unwrap();
+        assert_eq!(writer.as_bytes(), &[0x81, 0x2A]);
+
+        let mut reader = PerReader::new(writer.as_bytes());
+        assert_eq!(reader.read_length().unwrap(), 0x12A);
+    }
+
+    #[test]
+    fn test_per_length_limit() {
+        let mut writer = PerWriter::new();
+        writer.write_length(PER_MAX_LENGTH).unwrap();
+        assert_eq!(writer.as_bytes(), &[0xBF, 0xFF]);
+
+        let mut reader = PerReader::new(writer.as_bytes());
+        assert_eq!(reader.read_length().unwrap(), PER_MAX_LENGTH);
+
+        let mut writer = PerWriter::new();
+        assert!(writer.write_length(PER_MAX_LENGTH + 1).is_err());
+        assert!(writer.write_octet_string(&[0; 0x4000], 0).is_err());
+        assert!(writer.as_bytes().is_empty());
+    }
+
+    #[test]
+    fn test_per_integer() {
+        let test_cases = vec![
+            (0, vec![0x01, 0x00]),
+            (255, vec![0x01, 0xFF]),
+            (256, vec![0x02, 0x01, 0x00]),
+            (0x10000, vec![0x04, 0x00, 0x01, 0x00, 0x00]),
+        ];
+
+        for (value, expected) in test_cases {
+            let mut writer = PerWriter::new();
+            writer.write_integer(value);
+            assert_eq!(writer.as_bytes(), &expected[..]);
+
+            let mut reader = PerReader::new(writer.as_bytes());
+            assert_eq!(reader.read_integer().unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_per_integer16_with_lower_bound() {
+        let mut writer = PerWriter::new();
+        writer.write_integer16(1007, 1001).unwrap();
+        assert_eq!(writer.as_bytes(), &[0x00, 0x06]);
+
+        let mut reader = PerReader::new(writer.as_bytes());
+        assert_eq!(reader.read_integer16(1001).unwrap(), 1007);
+
+        let mut writer = PerWriter::new();
+        assert!(writer.write_integer16(1000, 1001).is_err());
+    }
+
+    #[test]
+    fn test_per_enumerated() {
+        let mut writer = PerWriter::new();
+        writer.write_enumerated(3);
+
+        let mut reader = PerReader::new(writer.as_bytes());
+        assert_eq!(reader.read_enumerated(16).unwrap(), 3);
+
+        let mut reader = PerReader::new(&[16]);
+        assert!(reader.read_enumerated(16).is_err());
+    }
+
+    #[test]
+    fn test_per_octet_string() {
+        let mut writer = PerWriter::new();
+        writer.write_octet_string(b"Duca", 4).unwrap();
+        assert_eq!(writer.as_bytes(), &[0x00, b'D', b'u', b'c', b'a']);
+
+        let mut reader = PerReader::new(writer.as_bytes());
+        assert_eq!(reader.read_octet_string(4).unwrap(), b"Duca");
+
+        let mut writer = PerWriter::new();
+        assert!(writer.write_octet_string(b"abc", 4).is_err());
+    }
+
+    #[test]
+    fn test_per_numeric_string() {
+        let mut writer = PerWriter::new();
+        writer.write_numeric_string("1", 1).unwrap();
+        assert_eq!(writer.as_bytes(), &[0x00, 0x10]);
+
+        let mut reader = PerReader::new(writer.as_bytes());
+        assert_eq!(reader.read_numeric_string(1).unwrap(), "1");
+
+        let mut writer = PerWriter::new();
+        writer.write_numeric_string("12345", 1).unwrap();
+
+        let mut reader = PerReader::new(writer.as_bytes());
+        assert_eq!(reader.read_numeric_string(1).unwrap(), "12345");
+    }
+
+    #[test]
+    fn test_per_object_identifier() {
+        // t124Identifier {0 0 20 124 0 1}
+        let mut writer = PerWriter::new();
+        writer
+            .write_object_identifier(&[0, 0, 20, 124, 0, 1])
+            .unwrap();
+        assert_eq!(writer.as_bytes(), &[0x05, 0x00, 0x14, 0x7C, 0x00, 0x01]);
+
+        let mut reader = PerReader::new(writer.as_bytes());
+        assert_eq!(
+            reader.read_object_identifier().unwrap(),
+            vec![0, 0, 20, 124, 0, 1]
+        );
+    }
+
+    #[test]
+    fn test_per_object_identifier_multibyte_arc() {
+        let arcs = vec![1, 2, 840, 113549];
+
+        let mut writer = PerWriter::new();
+        writer.write_object_identifier(&arcs).unwrap();
+
+        let mut reader = PerReader::new(writer.as_bytes());
+        assert_eq!(reader.read_object_identifier().unwrap(), arcs);
+    }
+
+    #[test]
+    fn test_per_choice_and_padding() {
+        let mut writer = PerWriter::new();
+        writer.write_choice(0xC0);
+        writer.write_padding(2);
+        writer.write_number_of_sets(1);
+        assert_eq!(writer.as_bytes(), &[0xC0, 0x00, 0x00, 0x01]);
+
+        let mut reader = PerReader::new(writer.as_bytes());
+        assert_eq!(reader.read_choice().unwrap(), 0xC0);
+        reader.read_padding(2).unwrap();
+        assert_eq!(reader.read_number_of_sets().unwrap(), 1);
+        assert_eq!(reader.remaining(), 0);
+    }
+}
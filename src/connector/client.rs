@@ -0,0 +1,888 @@
+use crate::pdu::Pdu;
+use crate::pdu::framing::{
+    X224Frame, decode_basic_security, decode_share_data, decode_tpkt, decode_x224_data,
+    disconnect_request, disconnect_ultimatum, encode_basic_security, encode_pdu, encode_share_data,
+    encode_tpkt, encode_x224_data,
+};
+use crate::pdu::gcc::{ConferenceCreateRequest, ConferenceCreateResult, ServerData};
+use crate::pdu::licensing::{
+    LicenseClient, LicenseClientConfig, LicensePdu, LicenseStore, MemoryLicenseStore,
+};
+use crate::pdu::mcs::{
+    AttachUserConfirm, AttachUserRequest, ChannelJoinConfirm, ChannelJoinRequest, ConnectInitial,
+    ConnectResponse, ErectDomainRequest, McsResult, SendDataIndication, SendDataRequest,
+};
+use crate::pdu::rdp::{
+    CapabilitySet, ClientActivation, ClientActivationPdu, ControlPdu, DataPduType,
+    DeactivateAllPdu, DemandActivePdu, FontMapPdu, PduType, ShareControlHeader, SynchronizePdu,
+};
+use crate::pdu::security::SecurityFlags;
+use crate::pdu::x224::connection::RDP_NEG_FAILURE;
+use crate::pdu::x224::{ConnectionConfirm, ConnectionRequest, Protocol};
+use std::sync::Arc;
+
+use super::config::ConnectorConfig;
+use super::error::{ConnectorError, ConnectorResult};
+
+/// Client connection sequence state (MS-RDPBCGR 1.3.1.1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientConnectorState {
+    /// Send X.224 Connection Request
+    ConnectionInitiationSendRequest,
+    /// Wait for X.224 Connection Confirm
+    ConnectionInitiationWaitConfirm,
+    /// TLS (and CredSSP) handshake performed by the caller
+    EnhancedSecurityUpgrade,
+    /// Send MCS Connect Initial
+    BasicSettingsExchangeSendInitial,
+    /// Wait for MCS Connect Response
+    BasicSettingsExchangeWaitResponse,
+    /// Wait for MCS Attach User Confirm
+    ChannelConnectionWaitAttachUserConfirm,
+    /// Wait for MCS Channel Join Confirm
+    ChannelConnectionWaitChannelJoinConfirm,
+    /// Wait for licensing PDUs
+    Licensing,
+    /// Wait for Demand Active
+    CapabilitiesExchange,
+    /// Wait for server Synchronize, Control and Font Map
+    ConnectionFinalization,
+    /// Connection sequence complete
+    Connected,
+}
+
+impl ClientConnectorState {
+    /// Whether `step` expects an inbound frame in this state
+    pub fn expects_input(self) -> bool {
+        !matches!(
+            self,
+            ClientConnectorState::ConnectionInitiationSendRequest
+                | ClientConnectorState::EnhancedSecurityUpgrade
+                | ClientConnectorState::BasicSettingsExchangeSendInitial
+                | ClientConnectorState::Connected
+        )
+    }
+}
+
+/// Parameters negotiated during the connection sequence
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionResult {
+    /// Security protocol selected by the server
+    pub selected_protocol: u32,
+    /// MCS I/O channel ID
+    pub io_channel_id: u16,
+    /// MCS user channel ID
+    pub user_channel_id: u16,
+    /// MCS message channel ID
+    pub message_channel_id: Option<u16>,
+    /// Static virtual channel names with their MCS channel IDs
+    pub static_channels: Vec<(String, u16)>,
+    /// Share ID from Demand Active
+    pub share_id: u32,
+    /// Server capability sets from Demand Active
+    pub server_capabilities: Vec<CapabilitySet>,
+    /// Desktop width from the server Bitmap capability set
+    pub desktop_width: u16,
+    /// Desktop height from the server Bitmap capability set
+    pub desktop_height: u16,
+}
+
+/// Sans-IO client connection sequence
+///
+/// Feed each complete inbound TPKT frame to `step` and write the returned
+/// frames to the transport. States that only send (see
+/// `ClientConnectorState::expects_input`) are stepped with an empty input.
+#[derive(Debug, Clone)]
+pub struct ClientConnector {
+    config: ConnectorConfig,
+    state: ClientConnectorState,
+    selected_protocol: u32,
+    server_data: Option<ServerData>,
+    user_channel_id: u16,
+    join_channels: Vec<u16>,
+    joined_channels: usize,
+    activation: ClientActivation,
+    license_store: Arc<dyn LicenseStore>,
+    license: Option<LicenseClient>,
+}
+
+impl ClientConnector {
+    /// Create new client connector
+    pub fn new(config: ConnectorConfig) -> Self {
+        let activation = ClientActivation::new(config.confirm_capability_sets());
+        Self {
+            config,
+            state: ClientConnectorState::ConnectionInitiationSendRequest,
+            selected_protocol: Protocol::RdpSecurity as u32,
+            server_data: None,
+            user_channel_id: 0,
+            join_channels: Vec::new(),
+            joined_channels: 0,
+            activation,
+            license_store: Arc::new(MemoryLicenseStore::new()),
+            license: None,
+        }
+    }
+
+    /// Use a shared license store so that issued licenses are presented
+    /// again on later connections
+    pub fn with_license_store(mut self, store: Arc<dyn LicenseStore>) -> Self {
+        self.license_store = store;
+        self
+    }
+
+    /// Current state
+    pub fn state(&self) -> ClientConnectorState {
+        self.state
+    }
+
+    /// Configuration
+    pub fn config(&self) -> &ConnectorConfig {
+        &self.config
+    }
+
+    /// Security protocol selected by the server
+    pub fn selected_protocol(&self) -> u32 {
+        self.selected_protocol
+    }
+
+    /// Server data blocks from MCS Connect Response
+    pub fn server_data(&self) -> Option<&ServerData> {
+        self.server_data.as_ref()
+    }
+
+    /// Negotiated parameters, available once connected
+    pub fn connection_result(&self) -> Option<ConnectionResult> {
+        if self.state != ClientConnectorState::Connected {
+            return None;
+        }
+
+        let server_data = self.server_data.as_ref()?;
+        let static_channels = self
+            .config
+            .static_channels
+            .iter()
+            .zip(&server_data.network.channel_ids)
+            .map(|(channel, &id)| (channel.name.clone(), id))
+            .collect();
+
+        let (desktop_width, desktop_height) = self
+            .activation
+            .server_capabilities()
+            .iter()
+            .find_map(|c| match c {
+                CapabilitySet::Bitmap(bitmap) => {
+                    Some((bitmap.desktop_width, bitmap.desktop_height))
+                }
+                _ => None,
+            })
+            .unwrap_or((self.config.desktop_width, self.config.desktop_height));
+
+        Some(ConnectionResult {
+            selected_protocol: self.selected_protocol,
+            io_channel_id: server_data.network.io_channel,
+            user_channel_id: self.user_channel_id,
+            message_channel_id: server_data
+                .message_channel
+                .as_ref()
+                .map(|m| m.mcs_channel_id),
+            static_channels,
+            share_id: self.activation.share_id()?,
+            server_capabilities: self.activation.server_capabilities().to_vec(),
+            desktop_width,
+            desktop_height,
+        })
+    }
+
+    /// Signal that the TLS (and CredSSP) handshake has completed
+    pub fn mark_security_upgrade_as_done(&mut self) -> ConnectorResult<()> {
+        if self.state != ClientConnectorState::EnhancedSecurityUpgrade {
+            return Err(ConnectorError::InvalidState(self.state));
+        }
+        self.state = ClientConnectorState::BasicSettingsExchangeSendInitial;
+        Ok(())
+    }
+
+    /// Advance the connection sequence
+    ///
+    /// `input` is one complete inbound TPKT frame, or empty in states that
+    /// only send. Returns the frames to send, in order.
+    pub fn step(&mut self, input: &[u8]) -> ConnectorResult<Vec<Vec<u8>>> {
+        match self.state {
+            ClientConnectorState::ConnectionInitiationSendRequest => self.send_connection_request(),
+            ClientConnectorState::ConnectionInitiationWaitConfirm => {
+                self.on_connection_confirm(input)
+            }
+            ClientConnectorState::BasicSettingsExchangeSendInitial => self.send_connect_initial(),
+            ClientConnectorState::BasicSettingsExchangeWaitResponse => {
+                self.on_connect_response(input)
+            }
+            ClientConnectorState::ChannelConnectionWaitAttachUserConfirm => {
+                self.on_attach_user_confirm(input)
+            }
+            ClientConnectorState::ChannelConnectionWaitChannelJoinConfirm => {
+                self.on_channel_join_confirm(input)
+            }
+            ClientConnectorState::Licensing => self.on_licensing(input),
+            ClientConnectorState::CapabilitiesExchange => self.on_capabilities_exchange(input),
+            ClientConnectorState::ConnectionFinalization => self.on_finalization(input),
+            ClientConnectorState::EnhancedSecurityUpgrade | ClientConnectorState::Connected => {
+                Err(ConnectorError::InvalidState(self.state))
+            }
+        }
+    }
+
+    fn send_connection_request(&mut self) -> ConnectorResult<Vec<Vec<u8>>> {
+        let mut request = ConnectionRequest::new(0);
+        if !self.config.username.is_empty() {
+            request = request.with_cookie(&self.config.username);
+        }
+        let request = request.with_negotiation(self.config.requested_protocols);
+
+        self.state = ClientConnectorState::ConnectionInitiationWaitConfirm;
+        Ok(vec![encode_tpkt(&request)?])
+    }
+
+    fn on_connection_confirm(&mut self, input: &[u8]) -> ConnectorResult<Vec<Vec<u8>>> {
+        let tpdu = decode_tpkt(input)?;
+        if let Some(request) = disconnect_request(&tpdu)? {
+            return Err(ConnectorError::Disconnected(
+                request.reason.description().to_string(),
+            ));
+        }
+
+        let confirm = ConnectionConfirm::decode(&mut tpdu.as_slice())?;
+        let selected_protocol = match confirm.rdp_negotiation() {
+            Some(negotiation) if negotiation.neg_type == RDP_NEG_FAILURE => {
+                return Err(ConnectorError::NegotiationFailure(
+                    negotiation.selected_protocol,
+                ));
+            }
+            Some(negotiation) => negotiation.selected_protocol,
+            None => Protocol::RdpSecurity as u32,
+        };
+
+        let requested = selected_protocol == Protocol::RdpSecurity as u32
+            || selected_protocol & self.config.requested_protocols != 0;
+        self.state = match Protocol::from_u32(selected_protocol) {
+            Some(Protocol::RdpSecurity) => ClientConnectorState::BasicSettingsExchangeSendInitial,
+            Some(Protocol::Ssl | Protocol::Hybrid | Protocol::HybridEx) if requested => {
+                ClientConnectorState::EnhancedSecurityUpgrade
+            }
+            _ => return Err(ConnectorError::UnsupportedProtocol(selected_protocol)),
+        };
+        self.selected_protocol = selected_protocol;
+
+        Ok(Vec::new())
+    }
+
+    fn send_connect_initial(&mut self) -> ConnectorResult<Vec<Vec<u8>>> {
+        let client_data = self.config.client_data(self.selected_protocol);
+        let request = ConferenceCreateRequest::new(encode_pdu(&client_data)?);
+        let connect_initial = ConnectInitial::new(&request)?;
+
+        self.state = ClientConnectorState::BasicSettingsExchangeWaitResponse;
+        Ok(vec![encode_x224_data(&connect_initial)?])
+    }
+
+    fn on_connect_response(&mut self, input: &[u8]) -> ConnectorResult<Vec<Vec<u8>>> {
+        let mcs = self.read_mcs(input)?;
+        let response = ConnectResponse::decode(&mut mcs.as_slice())?;
+        if response.result != McsResult::RtSuccessful {
+            return Err(ConnectorError::McsConnect(response.result));
+        }
+
+        let conference = response.conference_response()?;
+        if conference.result != ConferenceCreateResult::Success {
+            return Err(ConnectorError::ConferenceCreate(conference.result));
+        }
+
+        let server_data = ServerData::decode(&mut conference.user_data.as_slice())?;
+        if self.selected_protocol == Protocol::RdpSecurity as u32
+            && server_data.security.is_encrypted()
+        {
+            return Err(ConnectorError::UnsupportedSecurity(
+                "Standard RDP Security encryption".to_string(),
+            ));
+        }
+        self.server_data = Some(server_data);
+
+        self.state = ClientConnectorState::ChannelConnectionWaitAttachUserConfirm;
+        Ok(vec![
+            encode_x224_data(&ErectDomainRequest::default_request())?,
+            encode_x224_data(&AttachUserRequest::new())?,
+        ])
+    }
+
+    fn on_attach_user_confirm(&mut self, input: &[u8]) -> ConnectorResult<Vec<Vec<u8>>> {
+        let mcs = self.read_mcs(input)?;
+        let confirm = AttachUserConfirm::decode(&mut mcs.as_slice())?;
+        let user_channel_id = match (confirm.result, confirm.user_id) {
+            (McsResult::RtSuccessful, Some(user_id)) => user_id,
+            (result, _) => return Err(ConnectorError::AttachUser(result)),
+        };
+        self.user_channel_id = user_channel_id;
+
+        let server_data = self
+            .server_data
+            .as_ref()
+            .ok_or(ConnectorError::InvalidState(self.state))?;
+        self.join_channels = std::iter::once(user_channel_id)
+            .chain(std::iter::once(server_data.network.io_channel))
+            .chain(
+                server_data
+                    .network
+                    .channel_ids
+                    .iter()
+                    .copied()
+                    .filter(|&id| id != 0),
+            )
+            .chain(
+                server_data
+                    .message_channel
+                    .as_ref()
+                    .map(|m| m.mcs_channel_id),
+            )
+            .collect();
+        self.joined_channels = 0;
+
+        self.state = ClientConnectorState::ChannelConnectionWaitChannelJoinConfirm;
+        Ok(vec![self.channel_join_request()?])
+    }
+
+    fn on_channel_join_confirm(&mut self, input: &[u8]) -> ConnectorResult<Vec<Vec<u8>>> {
+        let mcs = self.read_mcs(input)?;
+        let confirm = ChannelJoinConfirm::decode(&mut mcs.as_slice())?;
+
+        let channel_id = self.join_channels[self.joined_channels];
+        if confirm.result != McsResult::RtSuccessful {
+            return Err(ConnectorError::ChannelJoin {
+                channel_id,
+                result: confirm.result,
+            });
+        }
+        if confirm.requested_channel_id != channel_id {
+            return Err(self.unexpected(format!(
+                "Channel Join Confirm for channel {}",
+                confirm.requested_channel_id
+            )));
+        }
+
+        self.joined_channels += 1;
+        if self.joined_channels < self.join_channels.len() {
+            return Ok(vec![self.channel_join_request()?]);
+        }
+
+        let client_info = encode_pdu(&self.config.client_info())?;
+        let request = SendDataRequest::new(
+            self.user_channel_id,
+            self.io_channel_id(),
+            encode_basic_security(SecurityFlags::INFO_PKT, &client_info)?,
+        );
+
+        let mut license_config =
+            LicenseClientConfig::new(&self.config.username, &self.config.client_name);
+        // The certificate is only a fallback for License Requests without one
+        if let Some(Ok(Some(certificate))) = self
+            .server_data
+            .as_ref()
+            .map(|server_data| server_data.security.certificate())
+        {
+            license_config = license_config.with_server_certificate(certificate);
+        }
+        self.license = Some(LicenseClient::new(
+            license_config,
+            self.license_store.clone(),
+        ));
+
+        self.state = ClientConnectorState::Licensing;
+        Ok(vec![encode_x224_data(&request)?])
+    }
+
+    fn on_licensing(&mut self, input: &[u8]) -> ConnectorResult<Vec<Vec<u8>>> {
+        let indication = self.read_send_data(input)?;
+        let (header, body) = decode_basic_security(&indication.user_data)?;
+        if !header.flags.contains(SecurityFlags::LICENSE_PKT) {
+            return Err(ConnectorError::Licensing(format!(
+                "Expected licensing PDU, got security flags {:#06x}",
+                header.flags.bits()
+            )));
+        }
+
+        let pdu = LicensePdu::decode(&mut &body[..])?;
+        let license = self
+            .license
+            .as_mut()
+            .ok_or(ConnectorError::InvalidState(self.state))?;
+        let reply = license
+            .process(&pdu.message)
+            .map_err(|e| ConnectorError::Licensing(e.to_string()))?;
+        if license.is_completed() {
+            self.state = ClientConnectorState::CapabilitiesExchange;
+        }
+
+        let Some(message) = reply else {
+            return Ok(Vec::new());
+        };
+        let request = SendDataRequest::new(
+            self.user_channel_id,
+            self.io_channel_id(),
+            encode_basic_security(
+                SecurityFlags::LICENSE_PKT,
+                &encode_pdu(&LicensePdu::new(message))?,
+            )?,
+        );
+        Ok(vec![encode_x224_data(&request)?])
+    }
+
+    fn on_capabilities_exchange(&mut self, input: &[u8]) -> ConnectorResult<Vec<Vec<u8>>> {
+        let (header, payload) = self.read_share_control(input)?;
+        match header.pdu_type {
+            PduType::DemandActive => {
+                let demand_active = DemandActivePdu::decode(&mut payload.as_slice())?;
+                let pdus = self
+                    .activation
+                    .on_demand_active(&demand_active)
+                    .map_err(|e| ConnectorError::CapabilitiesExchange(e.to_string()))?;
+
+                self.state = ClientConnectorState::ConnectionFinalization;
+                pdus.iter()
+                    .map(|pdu| self.encode_activation_pdu(pdu))
+                    .collect()
+            }
+            // Data PDUs sent ahead of Demand Active (e.g. Set Error Info) are skipped
+            PduType::Data => Ok(Vec::new()),
+            other => Err(self.unexpected(format!("{:?} PDU", other))),
+        }
+    }
+
+    fn on_finalization(&mut self, input: &[u8]) -> ConnectorResult<Vec<Vec<u8>>> {
+        let (header, payload) = self.read_share_control(input)?;
+        match header.pdu_type {
+            PduType::Data => {
+                let (share_data, mut body) = decode_share_data(&payload)?;
+                let result = match share_data.pdu_type2 {
+                    DataPduType::Synchronize => self
+                        .activation
+                        .on_synchronize(&SynchronizePdu::decode(&mut body)?),
+                    DataPduType::Control => {
+                        self.activation.on_control(&ControlPdu::decode(&mut body)?)
+                    }
+                    DataPduType::FontMap => {
+                        self.activation.on_font_map(&FontMapPdu::decode(&mut body)?)
+                    }
+                    // Monitor Layout, Set Error Info and similar are left to the session
+                    _ => Ok(()),
+                };
+                result.map_err(|e| ConnectorError::Finalization(e.to_string()))?;
+
+                if self.activation.is_active() {
+                    self.state = ClientConnectorState::Connected;
+                }
+                Ok(Vec::new())
+            }
+            PduType::DeactivateAll => {
+                let deactivate_all = DeactivateAllPdu::decode(&mut payload.as_slice())?;
+                self.activation.on_deactivate_all(&deactivate_all);
+                self.state = ClientConnectorState::CapabilitiesExchange;
+                Ok(Vec::new())
+            }
+            other => Err(self.unexpected(format!("{:?} PDU", other))),
+        }
+    }
+
+    fn channel_join_request(&self) -> ConnectorResult<Vec<u8>> {
+        let request = ChannelJoinRequest::new(
+            self.user_channel_id,
+            self.join_channels[self.joined_channels],
+        );
+        Ok(encode_x224_data(&request)?)
+    }
+
+    fn encode_activation_pdu(&self, pdu: &ClientActivationPdu) -> ConnectorResult<Vec<u8>> {
+        let share_id = self.activation.share_id().unwrap_or(0);
+        let (pdu_type, payload) = match pdu {
+            ClientActivationPdu::ConfirmActive(confirm) => {
+                (PduType::ConfirmActive, encode_pdu(confirm)?)
+            }
+            ClientActivationPdu::Synchronize(synchronize) => (
+                PduType::Data,
+                encode_share_data(share_id, DataPduType::Synchronize, synchronize)?,
+            ),
+            ClientActivationPdu::Control(control) => (
+                PduType::Data,
+                encode_share_data(share_id, DataPduType::Control, control)?,
+            ),
+            ClientActivationPdu::FontList(font_list) => (
+                PduType::Data,
+                encode_share_data(share_id, DataPduType::FontList, font_list)?,
+            ),
+        };
+
+        let request = SendDataRequest::with_share_control(
+            self.user_channel_id,
+            self.io_channel_id(),
+            pdu_type,
+            &payload,
+        )?;
+        Ok(encode_x224_data(&request)?)
+    }
+
+    fn io_channel_id(&self) -> u16 {
+        self.server_data
+            .as_ref()
+            .map(|server_data| server_data.network.io_channel)
+            .unwrap_or(0)
+    }
+
+    /// Unwrap TPKT and X.224 Data, failing on disconnect indications
+    fn read_mcs(&self, input: &[u8]) -> ConnectorResult<Vec<u8>> {
+        let mcs = match decode_x224_data(input)? {
+            X224Frame::Data(mcs) => mcs,
+            X224Frame::Disconnect(request) => {
+                return Err(ConnectorError::Disconnected(
+                    request.reason.description().to_string(),
+                ));
+            }
+        };
+
+        if let Some(ultimatum) = disconnect_ultimatum(&mcs)? {
+            return Err(ConnectorError::Disconnected(
+                ultimatum.reason.description().to_string(),
+            ));
+        }
+        Ok(mcs)
+    }
+
+    fn read_send_data(&self, input: &[u8]) -> ConnectorResult<SendDataIndication> {
+        let mcs = self.read_mcs(input)?;
+        Ok(SendDataIndication::decode(&mut mcs.as_slice())?)
+    }
+
+    fn read_share_control(&self, input: &[u8]) -> ConnectorResult<(ShareControlHeader, Vec<u8>)> {
+        let indication = self.read_send_data(input)?;
+        Ok(indication.share_control(self.io_channel_id())?)
+    }
+
+    fn unexpected(&self, pdu: String) -> ConnectorError {
+        ConnectorError::UnexpectedPdu {
+            state: self.state,
+            pdu,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdu::framing::encode_license_error;
+    use crate::pdu::gcc::{
+        ChannelDef, ChannelOptions, ConferenceCreateResponse, ServerCoreData, ServerNetworkData,
+        ServerSecurityData,
+    };
+    use crate::pdu::licensing::{
+        LicenseErrorMessage, LicenseMessage, ProductInfo, ScopeList, ServerLicenseRequest,
+    };
+    use crate::pdu::mcs::{DisconnectProviderUltimatum, DisconnectReason};
+    use crate::pdu::rdp::{BitmapCapability, ConfirmActivePdu, GeneralCapability};
+    use crate::pdu::security::{ProprietaryCertificate, RsaPublicKey, ServerCertificate};
+
+    const USER_CHANNEL_ID: u16 = 1007;
+    const IO_CHANNEL_ID: u16 = 1003;
+    const SHARE_ID: u32 = 0x0001_03EA;
+
+    /// MCS payload of an X.224 Data frame
+    fn data_payload(frame: &[u8]) -> Vec<u8> {
+        match decode_x224_data(frame).unwrap() {
+            X224Frame::Data(mcs) => mcs,
+            X224Frame::Disconnect(_) => panic!("unexpected disconnect"),
+        }
+    }
+
+    fn server_send(pdu_type: PduType, payload: &[u8]) -> Vec<u8> {
+        let indication =
+            SendDataIndication::with_share_control(1002, IO_CHANNEL_ID, pdu_type, payload).unwrap();
+        encode_x224_data(&indication).unwrap()
+    }
+
+    fn license_valid_client() -> Vec<u8> {
+        encode_license_error(&LicenseErrorMessage::valid_client()).unwrap()
+    }
+
+    fn server_share_data(pdu_type2: DataPduType, body: &impl Pdu) -> Vec<u8> {
+        server_send(
+            PduType::Data,
+            &encode_share_data(SHARE_ID, pdu_type2, body).unwrap(),
+        )
+    }
+
+    fn connect_response(protocol: u32) -> Vec<u8> {
+        let server_data = ServerData::new(
+            ServerCoreData::new(protocol),
+            ServerNetworkData::new(IO_CHANNEL_ID, vec![1004]),
+            ServerSecurityData::none(),
+        );
+        let conference = ConferenceCreateResponse::new(encode_pdu(&server_data).unwrap());
+        encode_x224_data(&ConnectResponse::success(&conference).unwrap()).unwrap()
+    }
+
+    /// Drive the connector up to the licensing exchange
+    fn connect_until_licensing(connector: &mut ClientConnector) {
+        let output = connector.step(&[]).unwrap();
+        let request =
+            ConnectionRequest::decode(&mut decode_tpkt(&output[0]).unwrap().as_slice()).unwrap();
+        assert_eq!(request.cookie(), Some("Cookie: mstshash=user\r\n"));
+
+        let confirm = ConnectionConfirm::new(0, 0x1234).with_negotiation(Protocol::Ssl as u32);
+        assert!(
+            connector
+                .step(&encode_tpkt(&confirm).unwrap())
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            connector.state(),
+            ClientConnectorState::EnhancedSecurityUpgrade
+        );
+        connector.mark_security_upgrade_as_done().unwrap();
+
+        let output = connector.step(&[]).unwrap();
+        assert_eq!(output.len(), 1);
+        assert_eq!(
+            connector.state(),
+            ClientConnectorState::BasicSettingsExchangeWaitResponse
+        );
+
+        let output = connector
+            .step(&connect_response(Protocol::Ssl as u32))
+            .unwrap();
+        assert_eq!(output.len(), 2); // Erect Domain + Attach User
+
+        let confirm = encode_x224_data(&AttachUserConfirm::success(USER_CHANNEL_ID)).unwrap();
+        let mut output = connector.step(&confirm).unwrap();
+
+        for channel_id in [USER_CHANNEL_ID, IO_CHANNEL_ID, 1004] {
+            let mcs = data_payload(&output[0]);
+            let request = ChannelJoinRequest::decode(&mut mcs.as_slice()).unwrap();
+            assert_eq!(request.channel_id, channel_id);
+
+            let confirm = ChannelJoinConfirm::success(USER_CHANNEL_ID, channel_id);
+            output = connector
+                .step(&encode_x224_data(&confirm).unwrap())
+                .unwrap();
+        }
+
+        // Client Info PDU
+        assert_eq!(output.len(), 1);
+        assert_eq!(connector.state(), ClientConnectorState::Licensing);
+    }
+
+    #[test]
+    fn test_client_connector_full_sequence() {
+        let config = ConnectorConfig::new("user", "secret")
+            .with_static_channels(vec![ChannelDef::new("rdpdr", ChannelOptions::INITIALIZED)]);
+        let mut connector = ClientConnector::new(config);
+        connect_until_licensing(&mut connector);
+
+        let license = SendDataIndication::new(1002, IO_CHANNEL_ID, license_valid_client());
+        assert!(
+            connector
+                .step(&encode_x224_data(&license).unwrap())
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            connector.state(),
+            ClientConnectorState::CapabilitiesExchange
+        );
+
+        let demand_active = DemandActivePdu::new(
+            SHARE_ID,
+            vec![
+                CapabilitySet::General(GeneralCapability::new()),
+                CapabilitySet::Bitmap(BitmapCapability::new(1280, 800, 32)),
+            ],
+        );
+        let output = connector
+            .step(&server_send(
+                PduType::DemandActive,
+                &encode_pdu(&demand_active).unwrap(),
+            ))
+            .unwrap();
+        assert_eq!(output.len(), 5);
+
+        let mcs = data_payload(&output[0]);
+        let request = SendDataRequest::decode(&mut mcs.as_slice()).unwrap();
+        let (header, payload) = request.share_control(IO_CHANNEL_ID).unwrap();
+        assert_eq!(header.pdu_type, PduType::ConfirmActive);
+        assert_eq!(header.pdu_source, USER_CHANNEL_ID);
+        let confirm = ConfirmActivePdu::decode(&mut payload.as_slice()).unwrap();
+        assert_eq!(confirm.share_id, SHARE_ID);
+
+        connector
+            .step(&server_share_data(
+                DataPduType::Synchronize,
+                &SynchronizePdu::new(USER_CHANNEL_ID),
+            ))
+            .unwrap();
+        connector
+            .step(&server_share_data(
+                DataPduType::Control,
+                &ControlPdu::cooperate(),
+            ))
+            .unwrap();
+        connector
+            .step(&server_share_data(
+                DataPduType::Control,
+                &ControlPdu::granted_control(USER_CHANNEL_ID),
+            ))
+            .unwrap();
+        assert_eq!(
+            connector.state(),
+            ClientConnectorState::ConnectionFinalization
+        );
+        connector
+            .step(&server_share_data(DataPduType::FontMap, &FontMapPdu::new()))
+            .unwrap();
+        assert_eq!(connector.state(), ClientConnectorState::Connected);
+
+        let result = connector.connection_result().unwrap();
+        assert_eq!(result.selected_protocol, Protocol::Ssl as u32);
+        assert_eq!(result.user_channel_id, USER_CHANNEL_ID);
+        assert_eq!(result.io_channel_id, IO_CHANNEL_ID);
+        assert_eq!(result.static_channels, vec![("rdpdr".to_string(), 1004)]);
+        assert_eq!(result.share_id, SHARE_ID);
+        assert_eq!((result.desktop_width, result.desktop_height), (1280, 800));
+
+        assert!(matches!(
+            connector.step(&[]),
+            Err(ConnectorError::InvalidState(
+                ClientConnectorState::Connected
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_client_connector_negotiation_failure() {
+        let mut connector = ClientConnector::new(ConnectorConfig::new("user", ""));
+        connector.step(&[]).unwrap();
+
+        // RDP_NEG_FAILURE with SSL_REQUIRED_BY_SERVER
+        let frame = [
+            0x03, 0x00, 0x00, 0x13, 0x0E, 0xD0, 0x00, 0x00, 0x12, 0x34, 0x00, 0x03, 0x00, 0x08,
+            0x00, 0x01, 0x00, 0x00, 0x00,
+        ];
+        assert!(matches!(
+            connector.step(&frame),
+            Err(ConnectorError::NegotiationFailure(0x01))
+        ));
+    }
+
+    #[test]
+    fn test_client_connector_rejects_unrequested_protocol() {
+        let config =
+            ConnectorConfig::new("user", "").with_requested_protocols(Protocol::Ssl as u32);
+        let mut connector = ClientConnector::new(config);
+        connector.step(&[]).unwrap();
+
+        let confirm = ConnectionConfirm::new(0, 0).with_negotiation(Protocol::Hybrid as u32);
+        assert!(matches!(
+            connector.step(&encode_tpkt(&confirm).unwrap()),
+            Err(ConnectorError::UnsupportedProtocol(0x02))
+        ));
+    }
+
+    #[test]
+    fn test_client_connector_disconnect_and_license_error() {
+        let mut connector = ClientConnector::new(ConnectorConfig::new("user", ""));
+        connect_until_licensing(&mut connector);
+
+        let mut license = license_valid_client();
+        license[8] = 0x08; // ERR_INVALID_CLIENT
+        license[12] = 0x01; // ST_TOTAL_ABORT
+        let indication = SendDataIndication::new(1002, IO_CHANNEL_ID, license);
+        assert!(matches!(
+            connector.step(&encode_x224_data(&indication).unwrap()),
+            Err(ConnectorError::Licensing(_))
+        ));
+
+        let mut connector = ClientConnector::new(ConnectorConfig::new("user", ""));
+        connect_until_licensing(&mut connector);
+
+        let ultimatum = DisconnectProviderUltimatum::new(DisconnectReason::RnProviderInitiated);
+        assert!(matches!(
+            connector.step(&encode_x224_data(&ultimatum).unwrap()),
+            Err(ConnectorError::Disconnected(_))
+        ));
+    }
+
+    #[test]
+    fn test_client_connector_license_request() {
+        let store = Arc::new(MemoryLicenseStore::new());
+        let mut connector =
+            ClientConnector::new(ConnectorConfig::new("user", "")).with_license_store(store);
+        connect_until_licensing(&mut connector);
+
+        // 512-bit key from the X.509 test certificate
+        let modulus = hex::decode(concat!(
+            "d04665436f4ef741502eedb6a7558c1397dab40818d33f288cc590761788399c",
+            "bc78594c60207abf3e8bc68c40f0998b3dbd6b073c8de7b263bfcf2677846931"
+        ))
+        .unwrap();
+        let certificate = ServerCertificate::Proprietary(ProprietaryCertificate::new(
+            RsaPublicKey::new(65537, &modulus).unwrap(),
+        ));
+        let request = LicensePdu::new(LicenseMessage::LicenseRequest(ServerLicenseRequest::new(
+            [0x33; 32],
+            ProductInfo::new(0x0006_0000, "Microsoft Corporation", "A02"),
+            encode_pdu(&certificate).unwrap(),
+            ScopeList::new(vec!["microsoft.com".to_string()]),
+        )));
+        let indication = SendDataIndication::new(
+            1002,
+            IO_CHANNEL_ID,
+            encode_basic_security(SecurityFlags::LICENSE_PKT, &encode_pdu(&request).unwrap())
+                .unwrap(),
+        );
+        let output = connector
+            .step(&encode_x224_data(&indication).unwrap())
+            .unwrap();
+        assert_eq!(output.len(), 1);
+        assert_eq!(connector.state(), ClientConnectorState::Licensing);
+
+        let mcs = data_payload(&output[0]);
+        let request = SendDataRequest::decode(&mut mcs.as_slice()).unwrap();
+        assert_eq!(request.channel_id, IO_CHANNEL_ID);
+        let (header, body) = decode_basic_security(&request.user_data).unwrap();
+        assert_eq!(header.flags, SecurityFlags::LICENSE_PKT);
+        let reply = LicensePdu::decode(&mut &body[..]).unwrap();
+        let LicenseMessage::NewLicenseRequest(new_license_request) = reply.message else {
+            panic!("expected new license request");
+        };
+        assert_eq!(new_license_request.user_name, "user");
+
+        // Server skips the platform challenge
+        let license = SendDataIndication::new(1002, IO_CHANNEL_ID, license_valid_client());
+        assert!(
+            connector
+                .step(&encode_x224_data(&license).unwrap())
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            connector.state(),
+            ClientConnectorState::CapabilitiesExchange
+        );
+    }
+
+    #[test]
+    fn test_client_connector_mark_security_upgrade_in_wrong_state() {
+        let mut connector = ClientConnector::new(ConnectorConfig::new("user", ""));
+        assert!(matches!(
+            connector.mark_security_upgrade_as_done(),
+            Err(ConnectorError::InvalidState(
+                ClientConnectorState::ConnectionInitiationSendRequest
+            ))
+        ));
+    }
+}
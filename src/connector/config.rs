@@ -0,0 +1,122 @@
+use crate::pdu::gcc::{ChannelDef, ClientCoreData, ClientData, ClientNetworkData};
+use crate::pdu::rdp::{
+    BitmapCapability, CapabilitySet, ClientInfoFlags, ClientInfoPdu, GeneralCapability,
+    InputCapability, OrderCapability,
+};
+use crate::pdu::x224::Protocol;
+
+/// Client connector configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectorConfig {
+    /// User name (also used for the X.224 routing cookie)
+    pub username: String,
+    /// Password
+    pub password: String,
+    /// Domain
+    pub domain: String,
+    /// Client computer name
+    pub client_name: String,
+    /// Requested desktop width
+    pub desktop_width: u16,
+    /// Requested desktop height
+    pub desktop_height: u16,
+    /// Requested security protocols (Protocol flags OR'd together)
+    pub requested_protocols: u32,
+    /// Static virtual channels to join
+    pub static_channels: Vec<ChannelDef>,
+    /// Capability sets sent in Confirm Active
+    pub capability_sets: Vec<CapabilitySet>,
+}
+
+impl ConnectorConfig {
+    /// Create configuration requesting TLS and CredSSP at 1024x768
+    pub fn new(username: &str, password: &str) -> Self {
+        Self {
+            username: username.to_string(),
+            password: password.to_string(),
+            domain: String::new(),
+            client_name: "pentardp".to_string(),
+            desktop_width: 1024,
+            desktop_height: 768,
+            requested_protocols: Protocol::Ssl as u32 | Protocol::Hybrid as u32,
+            static_channels: Vec::new(),
+            capability_sets: Vec::new(),
+        }
+    }
+
+    /// Set domain
+    pub fn with_domain(mut self, domain: &str) -> Self {
+        self.domain = domain.to_string();
+        self
+    }
+
+    /// Set client computer name
+    pub fn with_client_name(mut self, client_name: &str) -> Self {
+        self.client_name = client_name.to_string();
+        self
+    }
+
+    /// Set desktop size
+    pub fn with_desktop_size(mut self, width: u16, height: u16) -> Self {
+        self.desktop_width = width;
+        self.desktop_height = height;
+        self
+    }
+
+    /// Set requested security protocols
+    pub fn with_requested_protocols(mut self, protocols: u32) -> Self {
+        self.requested_protocols = protocols;
+        self
+    }
+
+    /// Set static virtual channels
+    pub fn with_static_channels(mut self, channels: Vec<ChannelDef>) -> Self {
+        self.static_channels = channels;
+        self
+    }
+
+    /// Set capability sets (default sets are used when empty)
+    pub fn with_capability_sets(mut self, capability_sets: Vec<CapabilitySet>) -> Self {
+        self.capability_sets = capability_sets;
+        self
+    }
+
+    /// GCC client data blocks for the given server-selected protocol
+    pub fn client_data(&self, selected_protocol: u32) -> ClientData {
+        let core = ClientCoreData::new(self.desktop_width, self.desktop_height)
+            .with_client_name(&self.client_name)
+            .with_server_selected_protocol(selected_protocol);
+
+        let mut client_data = ClientData::new(core);
+        client_data.network = Some(ClientNetworkData::new(self.static_channels.clone()));
+        client_data
+    }
+
+    /// Client Info PDU
+    pub fn client_info(&self) -> ClientInfoPdu {
+        let mut client_info = ClientInfoPdu::new(self.username.clone(), self.password.clone())
+            .with_domain(self.domain.clone());
+        if !self.password.is_empty() {
+            client_info.flags |= ClientInfoFlags::AUTOLOGON;
+        }
+        client_info
+    }
+
+    /// Capability sets for Confirm Active
+    pub fn confirm_capability_sets(&self) -> Vec<CapabilitySet> {
+        if !self.capability_sets.is_empty() {
+            return self.capability_sets.clone();
+        }
+
+        vec![
+            CapabilitySet::General(GeneralCapability::new()),
+            CapabilitySet::Bitmap(BitmapCapability::new(
+                self.desktop_width,
+                self.desktop_height,
+                32,
+            )),
+            CapabilitySet::Order(OrderCapability::new()),
+            CapabilitySet::Input(InputCapability::new()),
+        ]
+    }
+}
@@ -0,0 +1,58 @@
+use crate::pdu::PduError;
+use crate::pdu::gcc::ConferenceCreateResult;
+use crate::pdu::mcs::McsResult;
+use thiserror::Error;
+
+use super::client::ClientConnectorState;
+
+/// Client connector result type
+pub type ConnectorResult<T> = std::result::Result<T, ConnectorError>;
+
+/// Client connection sequence errors
+#[derive(Error, Debug)]
+pub enum ConnectorError {
+    #[error(transparent)]
+    Pdu(#[from] PduError),
+
+    #[error("Protocol negotiation failed: failure code {0:#x}")]
+    NegotiationFailure(u32),
+
+    #[error("Unsupported security protocol selected by server: {0:#x}")]
+    UnsupportedProtocol(u32),
+
+    #[error("Unsupported security: {0}")]
+    UnsupportedSecurity(String),
+
+    #[error("MCS Connect failed: {0:?}")]
+    McsConnect(McsResult),
+
+    #[error("GCC Conference Create failed: {0:?}")]
+    ConferenceCreate(ConferenceCreateResult),
+
+    #[error("MCS Attach User failed: {0:?}")]
+    AttachUser(McsResult),
+
+    #[error("MCS Channel Join failed for channel {channel_id}: {result:?}")]
+    ChannelJoin { channel_id: u16, result: McsResult },
+
+    #[error("Licensing failed: {0}")]
+    Licensing(String),
+
+    #[error("Capability exchange failed: {0}")]
+    CapabilitiesExchange(String),
+
+    #[error("Connection finalization failed: {0}")]
+    Finalization(String),
+
+    #[error("Disconnected by server: {0}")]
+    Disconnected(String),
+
+    #[error("Unexpected {pdu} in state {state:?}")]
+    UnexpectedPdu {
+        state: ClientConnectorState,
+        pdu: String,
+    },
+
+    #[error("Operation not valid in state {0:?}")]
+    InvalidState(ClientConnectorState),
+}
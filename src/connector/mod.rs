@@ -0,0 +1,8 @@
+// Sans-IO client connection sequence
+pub mod client;
+pub mod config;
+pub mod error;
+
+pub use client::{ClientConnector, ClientConnectorState, ConnectionResult};
+pub use config::ConnectorConfig;
+pub use error::{ConnectorError, ConnectorResult};
@@ -1,4 +1,6 @@
+pub mod acceptor;
 pub mod codec;
+pub mod connector;
 pub mod pdu;
 
 pub use pdu::{Pdu, PduError, PduWithHeader, Result};
@@ -0,0 +1,313 @@
+use super::credentials::TsCredentials;
+use super::pub_key_auth::{HASH_BINDING_VERSION, client_pub_key_auth, server_pub_key_auth};
+use super::ts_request::{CLIENT_NONCE_LENGTH, TS_REQUEST_VERSION, TsRequest};
+use crate::pdu::{Pdu, PduError, Result};
+use rand::RngCore;
+use std::fmt::Debug;
+
+/// Authentication package carried in negoTokens (NTLM, Kerberos, SPNEGO)
+///
+/// The package owns the user's credentials and the security context;
+/// CredSSP only moves its tokens and uses it to seal pubKeyAuth and
+/// authInfo.
+pub trait AuthPackage: Debug + Send {
+    /// Produce the next token from the server's token
+    ///
+    /// `input` is None on the first call. Returns None when the package
+    /// has nothing to send.
+    fn step(&mut self, input: Option<&[u8]>) -> Result<Option<Vec<u8>>>;
+
+    /// Whether the security context is established
+    fn is_complete(&self) -> bool;
+
+    /// Encrypt a message with the established context (GSS_WrapEx)
+    fn encrypt(&mut self, data: &[u8]) -> Result<Vec<u8>>;
+
+    /// Decrypt a message from the server (GSS_UnwrapEx)
+    fn decrypt(&mut self, data: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// Client CredSSP state (MS-CSSP 3.1.5)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CredsspClientState {
+    /// First negoToken not sent yet
+    Initial,
+    /// Exchanging authentication package tokens
+    NegoTokens,
+    /// pubKeyAuth sent, waiting for the server's
+    PubKeyAuth,
+    /// TSCredentials sent
+    Completed,
+}
+
+/// Sans-IO client side of CredSSP
+///
+/// Call `start` for the first TSRequest, then feed each server TSRequest to
+/// `process` and send the returned one until `is_completed`.
+#[derive(Debug)]
+pub struct CredsspClient {
+    package: Box<dyn AuthPackage>,
+    credentials: TsCredentials,
+    public_key: Vec<u8>,
+    client_nonce: [u8; CLIENT_NONCE_LENGTH],
+    version: u32,
+    state: CredsspClientState,
+}
+
+impl CredsspClient {
+    /// Create client
+    ///
+    /// `public_key` is the SubjectPublicKey of the server's TLS certificate
+    /// (see `X509Certificate::subject_public_key`).
+    pub fn new(
+        package: Box<dyn AuthPackage>,
+        credentials: TsCredentials,
+        public_key: Vec<u8>,
+    ) -> Self {
+        let mut client_nonce = [0u8; CLIENT_NONCE_LENGTH];
+        rand::thread_rng().fill_bytes(&mut client_nonce);
+
+        Self {
+            package,
+            credentials,
+            public_key,
+            client_nonce,
+            version: TS_REQUEST_VERSION,
+            state: CredsspClientState::Initial,
+        }
+    }
+
+    /// Use a fixed client nonce
+    pub fn with_client_nonce(mut self, client_nonce: [u8; CLIENT_NONCE_LENGTH]) -> Self {
+        self.client_nonce = client_nonce;
+        self
+    }
+
+    /// Current state
+    pub fn state(&self) -> CredsspClientState {
+        self.state
+    }
+
+    /// Whether the credentials have been sent
+    pub fn is_completed(&self) -> bool {
+        self.state == CredsspClientState::Completed
+    }
+
+    /// Version used for the pubKeyAuth binding (lower of both sides)
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// First TSRequest carrying the package's initial token
+    pub fn start(&mut self) -> Result<TsRequest> {
+        if self.state != CredsspClientState::Initial {
+            return Err(self.unexpected());
+        }
+
+        let token = self.package.step(None)?.ok_or_else(|| {
+            PduError::ParseError("Authentication package produced no initial token".to_string())
+        })?;
+
+        let mut request = TsRequest::new(TS_REQUEST_VERSION);
+        request.nego_tokens.push(token);
+        self.state = CredsspClientState::NegoTokens;
+        Ok(request)
+    }
+
+    /// Handle a server TSRequest, returning the client reply
+    pub fn process(&mut self, response: &TsRequest) -> Result<TsRequest> {
+        if let Some(error_code) = response.error_code {
+            return Err(PduError::ParseError(format!(
+                "CredSSP error {:#010x}",
+                error_code
+            )));
+        }
+
+        match self.state {
+            CredsspClientState::NegoTokens => self.on_nego_token(response),
+            CredsspClientState::PubKeyAuth => self.on_pub_key_auth(response),
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn on_nego_token(&mut self, response: &TsRequest) -> Result<TsRequest> {
+        self.version = self.version.min(response.version);
+
+        let input = response
+            .nego_token()
+            .ok_or_else(|| PduError::ParseError("TSRequest without negoTokens".to_string()))?;
+        let token = self.package.step(Some(input))?;
+
+        let mut request = TsRequest::new(TS_REQUEST_VERSION);
+        request.nego_tokens.extend(token);
+        if !self.package.is_complete() {
+            if request.nego_tokens.is_empty() {
+                return Err(PduError::ParseError(
+                    "Authentication package stalled before completion".to_string(),
+                ));
+            }
+            return Ok(request);
+        }
+
+        // The last package token travels with pubKeyAuth
+        let pub_key_auth = client_pub_key_auth(self.version, &self.client_nonce, &self.public_key);
+        request.pub_key_auth = Some(self.package.encrypt(&pub_key_auth)?);
+        if self.version >= HASH_BINDING_VERSION {
+            request.client_nonce = Some(self.client_nonce);
+        }
+
+        self.state = CredsspClientState::PubKeyAuth;
+        Ok(request)
+    }
+
+    fn on_pub_key_auth(&mut self, response: &TsRequest) -> Result<TsRequest> {
+        // SPNEGO may send a final token (mechListMIC) alongside pubKeyAuth
+        if let Some(token) = response.nego_token() {
+            self.package.step(Some(token))?;
+        }
+
+        let encrypted = response
+            .pub_key_auth
+            .as_ref()
+            .ok_or_else(|| PduError::ParseError("TSRequest without pubKeyAuth".to_string()))?;
+        let expected = server_pub_key_auth(self.version, &self.client_nonce, &self.public_key);
+        if self.package.decrypt(encrypted)? != expected {
+            return Err(PduError::ParseError(
+                "Server pubKeyAuth does not match the TLS public key".to_string(),
+            ));
+        }
+
+        let mut credentials = Vec::with_capacity(self.credentials.size());
+        self.credentials.encode(&mut credentials)?;
+
+        let mut request = TsRequest::new(TS_REQUEST_VERSION);
+        request.auth_info = Some(self.package.encrypt(&credentials)?);
+        self.state = CredsspClientState::Completed;
+        Ok(request)
+    }
+
+    fn unexpected(&self) -> PduError {
+        PduError::ParseError(format!("Unexpected TSRequest in state {:?}", self.state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdu::credssp::TsPasswordCreds;
+
+    /// Two-leg package that "encrypts" by XOR
+    #[derive(Debug, Default)]
+    struct TestPackage {
+        complete: bool,
+    }
+
+    impl AuthPackage for TestPackage {
+        fn step(&mut self, input: Option<&[u8]>) -> Result<Option<Vec<u8>>> {
+            match input {
+                None => Ok(Some(b"NEGOTIATE".to_vec())),
+                Some(b"CHALLENGE") => {
+                    self.complete = true;
+                    Ok(Some(b"AUTHENTICATE".to_vec()))
+                }
+                Some(_) => Err(PduError::ParseError("bad token".to_string())),
+            }
+        }
+
+        fn is_complete(&self) -> bool {
+            self.complete
+        }
+
+        fn encrypt(&mut self, data: &[u8]) -> Result<Vec<u8>> {
+            Ok(data.iter().map(|b| b ^ 0x5A).collect())
+        }
+
+        fn decrypt(&mut self, data: &[u8]) -> Result<Vec<u8>> {
+            self.encrypt(data)
+        }
+    }
+
+    fn client() -> CredsspClient {
+        CredsspClient::new(
+            Box::new(TestPackage::default()),
+            TsCredentials::password(&TsPasswordCreds::new("D", "user", "secret")),
+            vec![0x30, 0x48, 0x02, 0x41],
+        )
+        .with_client_nonce([0x33; CLIENT_NONCE_LENGTH])
+    }
+
+    fn challenge(version: u32) -> TsRequest {
+        let mut challenge = TsRequest::new(version);
+        challenge.nego_tokens.push(b"CHALLENGE".to_vec());
+        challenge
+    }
+
+    fn xor(data: &[u8]) -> Vec<u8> {
+        data.iter().map(|b| b ^ 0x5A).collect()
+    }
+
+    #[test]
+    fn test_credssp_client_sequence() {
+        for server_version in [6, 4] {
+            let mut client = client();
+            let request = client.start().unwrap();
+            assert_eq!(request.version, TS_REQUEST_VERSION);
+            assert_eq!(request.nego_token(), Some(&b"NEGOTIATE"[..]));
+
+            let request = client.process(&challenge(server_version)).unwrap();
+            assert_eq!(client.state(), CredsspClientState::PubKeyAuth);
+            assert_eq!(client.version(), server_version);
+            assert_eq!(request.nego_token(), Some(&b"AUTHENTICATE"[..]));
+
+            let nonce = [0x33; CLIENT_NONCE_LENGTH];
+            let public_key = [0x30, 0x48, 0x02, 0x41];
+            assert_eq!(
+                xor(request.pub_key_auth.as_ref().unwrap()),
+                client_pub_key_auth(server_version, &nonce, &public_key)
+            );
+            assert_eq!(request.client_nonce.is_some(), server_version >= 5);
+
+            let mut response = TsRequest::new(server_version);
+            response.pub_key_auth = Some(xor(&server_pub_key_auth(
+                server_version,
+                &nonce,
+                &public_key,
+            )));
+            let request = client.process(&response).unwrap();
+            assert!(client.is_completed());
+
+            let credentials =
+                TsCredentials::decode(&mut xor(request.auth_info.as_ref().unwrap()).as_slice())
+                    .unwrap();
+            assert_eq!(
+                credentials.password_creds().unwrap(),
+                TsPasswordCreds::new("D", "user", "secret")
+            );
+            assert!(client.process(&response).is_err());
+        }
+    }
+
+    #[test]
+    fn test_credssp_client_errors() {
+        let mut client = client();
+        assert!(client.process(&challenge(6)).is_err());
+        client.start().unwrap();
+        assert!(client.start().is_err());
+
+        let mut error = TsRequest::new(6);
+        error.error_code = Some(0xC000_006D); // STATUS_LOGON_FAILURE
+        assert!(client.process(&error).is_err());
+
+        client.process(&challenge(6)).unwrap();
+
+        // pubKeyAuth for a different public key
+        let mut response = TsRequest::new(6);
+        response.pub_key_auth = Some(xor(&server_pub_key_auth(
+            6,
+            &[0x33; CLIENT_NONCE_LENGTH],
+            &[0x30, 0x00],
+        )));
+        assert!(client.process(&response).is_err());
+        assert_eq!(client.state(), CredsspClientState::PubKeyAuth);
+    }
+}
@@ -0,0 +1,314 @@
+use super::ts_request::read_optional_tag;
+use crate::codec::{BerReader, BerWriter};
+use crate::pdu::{Pdu, PduError, Result};
+use std::io::{Read, Write};
+
+/// Credential type (credType, MS-CSSP 2.2.1.2)
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CredentialType {
+    /// TSPasswordCreds
+    Password = 1,
+    /// TSSmartCardCreds
+    SmartCard = 2,
+    /// TSRemoteGuardCreds
+    RemoteGuard = 6,
+}
+
+impl CredentialType {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            1 => Some(CredentialType::Password),
+            2 => Some(CredentialType::SmartCard),
+            6 => Some(CredentialType::RemoteGuard),
+            _ => None,
+        }
+    }
+
+    pub fn as_u32(self) -> u32 {
+        self as u32
+    }
+}
+
+/// User credentials delegated to the server (TSCredentials, MS-CSSP 2.2.1.2)
+///
+/// TSCredentials ::= SEQUENCE {
+///     credType    [0] INTEGER,
+///     credentials [1] OCTET STRING
+/// }
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TsCredentials {
+    /// Type of the encoded credentials
+    pub cred_type: CredentialType,
+    /// DER encoded credentials structure
+    pub credentials: Vec<u8>,
+}
+
+impl TsCredentials {
+    /// Wrap password credentials
+    pub fn password(credentials: &TsPasswordCreds) -> Self {
+        let mut writer = BerWriter::new();
+        credentials.write(&mut writer);
+        Self {
+            cred_type: CredentialType::Password,
+            credentials: writer.into_bytes(),
+        }
+    }
+
+    /// Parse password credentials
+    pub fn password_creds(&self) -> Result<TsPasswordCreds> {
+        if self.cred_type != CredentialType::Password {
+            return Err(PduError::ParseError(format!(
+                "Expected password credentials, got {:?}",
+                self.cred_type
+            )));
+        }
+        TsPasswordCreds::read(&mut BerReader::new(&self.credentials))
+    }
+
+    /// DER Encode
+    pub fn write(&self, writer: &mut BerWriter) {
+        writer.write_sequence(|w| {
+            w.write_explicit_tag(0, |w| w.write_integer(self.cred_type.as_u32()));
+            w.write_explicit_tag(1, |w| w.write_octet_string(&self.credentials));
+        });
+    }
+
+    /// DER Decode
+    pub fn read(reader: &mut BerReader) -> Result<Self> {
+        let length = reader.read_sequence()?;
+        let body = reader.read_bytes(length)?;
+        let mut reader = BerReader::new(&body);
+
+        reader.read_explicit_tag(0)?;
+        let value = reader.read_integer()?;
+        let cred_type = CredentialType::from_u32(value).ok_or_else(|| {
+            PduError::ParseError(format!("Unsupported credential type: {}", value))
+        })?;
+        reader.read_explicit_tag(1)?;
+        let credentials = reader.read_octet_string()?;
+        reject_trailing_fields(&reader, "TSCredentials")?;
+
+        Ok(Self {
+            cred_type,
+            credentials,
+        })
+    }
+}
+
+impl Pdu for TsCredentials {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        let mut writer = BerWriter::new();
+        self.write(&mut writer);
+        buffer.write_all(writer.as_bytes())?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let mut data = Vec::new();
+        buffer.read_to_end(&mut data)?;
+        Self::read(&mut BerReader::new(&data))
+    }
+
+    fn size(&self) -> usize {
+        let mut writer = BerWriter::new();
+        self.write(&mut writer);
+        writer.as_bytes().len()
+    }
+}
+
+/// Password credentials (TSPasswordCreds, MS-CSSP 2.2.1.2.1)
+///
+/// TSPasswordCreds ::= SEQUENCE {
+///     domainName  [0] OCTET STRING,
+///     userName    [1] OCTET STRING,
+///     password    [2] OCTET STRING
+/// }
+///
+/// Strings are UTF-16LE without a terminator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TsPasswordCreds {
+    /// Domain name
+    pub domain_name: String,
+    /// User name
+    pub user_name: String,
+    /// Password
+    pub password: String,
+}
+
+impl TsPasswordCreds {
+    /// Create password credentials
+    pub fn new(domain_name: &str, user_name: &str, password: &str) -> Self {
+        Self {
+            domain_name: domain_name.to_string(),
+            user_name: user_name.to_string(),
+            password: password.to_string(),
+        }
+    }
+
+    /// DER Encode
+    pub fn write(&self, writer: &mut BerWriter) {
+        writer.write_sequence(|w| {
+            for (tag, value) in [
+                (0, &self.domain_name),
+                (1, &self.user_name),
+                (2, &self.password),
+            ] {
+                w.write_explicit_tag(tag, |w| w.write_octet_string(&utf16_bytes(value)));
+            }
+        });
+    }
+
+    /// DER Decode
+    pub fn read(reader: &mut BerReader) -> Result<Self> {
+        let length = reader.read_sequence()?;
+        let body = reader.read_bytes(length)?;
+        let mut reader = BerReader::new(&body);
+
+        let mut fields = [String::new(), String::new(), String::new()];
+        for (tag, field) in (0..).zip(fields.iter_mut()) {
+            // Empty strings may be omitted by some encoders
+            if read_optional_tag(&mut reader, tag)? {
+                *field = from_utf16_bytes(&reader.read_octet_string()?)?;
+            }
+        }
+        reject_trailing_fields(&reader, "TSPasswordCreds")?;
+        let [domain_name, user_name, password] = fields;
+
+        Ok(Self {
+            domain_name,
+            user_name,
+            password,
+        })
+    }
+}
+
+impl Pdu for TsPasswordCreds {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        let mut writer = BerWriter::new();
+        self.write(&mut writer);
+        buffer.write_all(writer.as_bytes())?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let mut data = Vec::new();
+        buffer.read_to_end(&mut data)?;
+        Self::read(&mut BerReader::new(&data))
+    }
+
+    fn size(&self) -> usize {
+        let mut writer = BerWriter::new();
+        self.write(&mut writer);
+        writer.as_bytes().len()
+    }
+}
+
+fn reject_trailing_fields(reader: &BerReader, name: &str) -> Result<()> {
+    match reader.peek_tag() {
+        Some(tag) => Err(PduError::ParseError(format!(
+            "Unexpected {} field tag 0x{:02x}",
+            name, tag
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn utf16_bytes(s: &str) -> Vec<u8> {
+    s.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+fn from_utf16_bytes(bytes: &[u8]) -> Result<String> {
+    if !bytes.len().is_multiple_of(2) {
+        return Err(PduError::ParseError(format!(
+            "Odd UTF-16 string length: {}",
+            bytes.len()
+        )));
+    }
+
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect();
+    String::from_utf16(&units).map_err(|e| PduError::ParseError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_credentials() {
+        let password = TsPasswordCreds::new("D", "u", "p");
+        let mut buffer = Vec::new();
+        password.encode(&mut buffer).unwrap();
+        assert_eq!(
+            buffer,
+            vec![
+                0x30, 0x12, 0xA0, 0x04, 0x04, 0x02, 0x44, 0x00, 0xA1, 0x04, 0x04, 0x02, 0x75, 0x00,
+                0xA2, 0x04, 0x04, 0x02, 0x70, 0x00
+            ]
+        );
+        assert_eq!(
+            TsPasswordCreds::decode(&mut buffer.as_slice()).unwrap(),
+            password
+        );
+
+        let credentials = TsCredentials::password(&password);
+        let mut encoded = Vec::new();
+        credentials.encode(&mut encoded).unwrap();
+        assert_eq!(encoded.len(), credentials.size());
+        assert_eq!(
+            &encoded[..9],
+            &[0x30, 0x1D, 0xA0, 0x03, 0x02, 0x01, 0x01, 0xA1, 0x16]
+        );
+        assert_eq!(&encoded[11..], &buffer[..]);
+
+        let decoded = TsCredentials::decode(&mut encoded.as_slice()).unwrap();
+        assert_eq!(decoded.password_creds().unwrap(), password);
+
+        encoded[6] = 0x02;
+        let smart_card = TsCredentials::decode(&mut encoded.as_slice()).unwrap();
+        assert_eq!(smart_card.cred_type, CredentialType::SmartCard);
+        assert!(smart_card.password_creds().is_err());
+
+        encoded[6] = 0x03;
+        assert!(TsCredentials::decode(&mut encoded.as_slice()).is_err());
+
+        // Missing domain and odd-length user name
+        let buffer = [0x30, 0x05, 0xA1, 0x03, 0x04, 0x01, 0x75];
+        assert!(TsPasswordCreds::decode(&mut &buffer[..]).is_err());
+        let buffer = [0x30, 0x06, 0xA1, 0x04, 0x04, 0x02, 0x75, 0x00];
+        let decoded = TsPasswordCreds::decode(&mut &buffer[..]).unwrap();
+        assert_eq!(decoded, TsPasswordCreds::new("", "u", ""));
+    }
+
+    #[test]
+    fn test_credentials_sequence_bounds() {
+        // Fields past the SEQUENCE length are not part of it
+        let buffer = [
+            0x30, 0x04, 0xA0, 0x02, 0x04, 0x00, 0xA1, 0x04, 0x04, 0x02, 0x75, 0x00,
+        ];
+        assert_eq!(
+            TsPasswordCreds::read(&mut BerReader::new(&buffer)).unwrap(),
+            TsPasswordCreds::new("", "", "")
+        );
+
+        // Unknown field inside the SEQUENCE
+        let buffer = [0x30, 0x06, 0xA0, 0x02, 0x04, 0x00, 0xA3, 0x00];
+        assert!(TsPasswordCreds::decode(&mut &buffer[..]).is_err());
+
+        // SEQUENCE length shorter than the credentials field
+        let mut encoded = Vec::new();
+        TsCredentials::password(&TsPasswordCreds::new("D", "u", "p"))
+            .encode(&mut encoded)
+            .unwrap();
+        encoded[1] -= 1;
+        assert!(TsCredentials::decode(&mut encoded.as_slice()).is_err());
+
+        // Trailing field after credentials
+        encoded[1] += 3;
+        encoded.extend_from_slice(&[0xA2, 0x00]);
+        assert!(TsCredentials::decode(&mut encoded.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,13 @@
+// CredSSP (MS-CSSP)
+pub mod client;
+pub mod credentials;
+pub mod pub_key_auth;
+pub mod ts_request;
+
+pub use client::{AuthPackage, CredsspClient, CredsspClientState};
+pub use credentials::{CredentialType, TsCredentials, TsPasswordCreds};
+pub use pub_key_auth::{
+    CLIENT_SERVER_HASH_MAGIC, HASH_BINDING_VERSION, SERVER_CLIENT_HASH_MAGIC, client_pub_key_auth,
+    server_pub_key_auth,
+};
+pub use ts_request::{CLIENT_NONCE_LENGTH, TS_REQUEST_VERSION, TsRequest};
@@ -0,0 +1,81 @@
+use super::ts_request::CLIENT_NONCE_LENGTH;
+use sha2::{Digest, Sha256};
+
+/// ClientServerHashMagic (MS-CSSP 3.1.5)
+pub const CLIENT_SERVER_HASH_MAGIC: &[u8] = b"CredSSP Client-To-Server Binding Hash\0";
+
+/// ServerClientHashMagic (MS-CSSP 3.1.5)
+pub const SERVER_CLIENT_HASH_MAGIC: &[u8] = b"CredSSP Server-To-Client Binding Hash\0";
+
+/// First version that binds pubKeyAuth with a SHA-256 hash and client nonce
+pub const HASH_BINDING_VERSION: u32 = 5;
+
+/// Client pubKeyAuth before encryption
+///
+/// `public_key` is the server's SubjectPublicKey from the TLS certificate.
+/// Versions 5 and 6 send SHA256(ClientServerHashMagic, ClientNonce,
+/// SubjectPublicKey); earlier versions send the public key itself.
+pub fn client_pub_key_auth(
+    version: u32,
+    client_nonce: &[u8; CLIENT_NONCE_LENGTH],
+    public_key: &[u8],
+) -> Vec<u8> {
+    if version >= HASH_BINDING_VERSION {
+        binding_hash(CLIENT_SERVER_HASH_MAGIC, client_nonce, public_key)
+    } else {
+        public_key.to_vec()
+    }
+}
+
+/// Server pubKeyAuth expected after decryption
+///
+/// Versions before 5 increment the first byte of the public key.
+pub fn server_pub_key_auth(
+    version: u32,
+    client_nonce: &[u8; CLIENT_NONCE_LENGTH],
+    public_key: &[u8],
+) -> Vec<u8> {
+    if version >= HASH_BINDING_VERSION {
+        return binding_hash(SERVER_CLIENT_HASH_MAGIC, client_nonce, public_key);
+    }
+
+    let mut echo = public_key.to_vec();
+    if let Some(first) = echo.first_mut() {
+        *first = first.wrapping_add(1);
+    }
+    echo
+}
+
+fn binding_hash(magic: &[u8], client_nonce: &[u8], public_key: &[u8]) -> Vec<u8> {
+    Sha256::new()
+        .chain_update(magic)
+        .chain_update(client_nonce)
+        .chain_update(public_key)
+        .finalize()
+        .to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pub_key_auth_binding() {
+        let public_key: Vec<u8> = (0..16).collect();
+        let nonce = [0x33; CLIENT_NONCE_LENGTH];
+
+        assert_eq!(
+            hex::encode(client_pub_key_auth(6, &nonce, &public_key)),
+            "857500cbafeb73b4de1b78a1dd015054b8eeded19dde23f393040d806ca52570"
+        );
+        assert_eq!(
+            hex::encode(server_pub_key_auth(5, &nonce, &public_key)),
+            "3a8e1578c2b2e574c17cb59001bd98119a3b978a191b28236c1c1a22ab551fd4"
+        );
+
+        assert_eq!(client_pub_key_auth(4, &nonce, &public_key), public_key);
+        let echo = server_pub_key_auth(2, &nonce, &public_key);
+        assert_eq!(echo[0], 0x01);
+        assert_eq!(echo[1..], public_key[1..]);
+    }
+}
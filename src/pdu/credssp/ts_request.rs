@@ -0,0 +1,250 @@
+use crate::codec::{BER_CONSTRUCTED, BerClass, BerReader, BerWriter};
+use crate::pdu::{Pdu, PduError, Result};
+use std::io::{Read, Write};
+
+/// Highest TSRequest version implemented
+pub const TS_REQUEST_VERSION: u32 = 6;
+
+/// Client nonce length (bytes)
+pub const CLIENT_NONCE_LENGTH: usize = 32;
+
+/// CredSSP message (TSRequest, MS-CSSP 2.2.1)
+///
+/// TSRequest ::= SEQUENCE {
+///     version     [0] INTEGER,
+///     negoTokens  [1] NegoData OPTIONAL,
+///     authInfo    [2] OCTET STRING OPTIONAL,
+///     pubKeyAuth  [3] OCTET STRING OPTIONAL,
+///     errorCode   [4] INTEGER OPTIONAL,
+///     clientNonce [5] OCTET STRING OPTIONAL
+/// }
+///
+/// NegoData ::= SEQUENCE OF SEQUENCE {
+///     negoToken   [0] OCTET STRING
+/// }
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TsRequest {
+    /// CredSSP protocol version
+    pub version: u32,
+    /// Authentication package tokens (NegoData)
+    pub nego_tokens: Vec<Vec<u8>>,
+    /// Encrypted TSCredentials
+    pub auth_info: Option<Vec<u8>>,
+    /// Encrypted public key binding
+    pub pub_key_auth: Option<Vec<u8>>,
+    /// NTSTATUS failure code (version 3 and later)
+    pub error_code: Option<u32>,
+    /// Nonce bound into pubKeyAuth (version 5 and later)
+    pub client_nonce: Option<[u8; CLIENT_NONCE_LENGTH]>,
+}
+
+impl TsRequest {
+    /// Create empty request
+    pub fn new(version: u32) -> Self {
+        Self {
+            version,
+            nego_tokens: Vec::new(),
+            auth_info: None,
+            pub_key_auth: None,
+            error_code: None,
+            client_nonce: None,
+        }
+    }
+
+    /// First authentication package token
+    pub fn nego_token(&self) -> Option<&[u8]> {
+        self.nego_tokens.first().map(Vec::as_slice)
+    }
+
+    /// DER Encode
+    pub fn write(&self, writer: &mut BerWriter) {
+        writer.write_sequence(|w| {
+            w.write_explicit_tag(0, |w| w.write_integer(self.version));
+            if !self.nego_tokens.is_empty() {
+                w.write_explicit_tag(1, |w| {
+                    w.write_sequence(|w| {
+                        for token in &self.nego_tokens {
+                            w.write_sequence(|w| {
+                                w.write_explicit_tag(0, |w| w.write_octet_string(token));
+                            });
+                        }
+                    });
+                });
+            }
+            if let Some(auth_info) = &self.auth_info {
+                w.write_explicit_tag(2, |w| w.write_octet_string(auth_info));
+            }
+            if let Some(pub_key_auth) = &self.pub_key_auth {
+                w.write_explicit_tag(3, |w| w.write_octet_string(pub_key_auth));
+            }
+            if let Some(error_code) = self.error_code {
+                // NTSTATUS, encoded as a negative INTEGER like Windows does
+                w.write_explicit_tag(4, |w| w.write_signed_integer(error_code as i32));
+            }
+            if let Some(client_nonce) = &self.client_nonce {
+                w.write_explicit_tag(5, |w| w.write_octet_string(client_nonce));
+            }
+        });
+    }
+
+    /// DER Decode
+    pub fn read(reader: &mut BerReader) -> Result<Self> {
+        let length = reader.read_sequence()?;
+        let body = reader.read_bytes(length)?;
+        let mut reader = BerReader::new(&body);
+
+        reader.read_explicit_tag(0)?;
+        let mut request = Self::new(reader.read_integer()?);
+
+        if read_optional_tag(&mut reader, 1)? {
+            let length = reader.read_sequence()?;
+            let nego_data = reader.read_bytes(length)?;
+            let mut nego_reader = BerReader::new(&nego_data);
+            while nego_reader.remaining() > 0 {
+                nego_reader.read_sequence()?;
+                nego_reader.read_explicit_tag(0)?;
+                request.nego_tokens.push(nego_reader.read_octet_string()?);
+            }
+        }
+        if read_optional_tag(&mut reader, 2)? {
+            request.auth_info = Some(reader.read_octet_string()?);
+        }
+        if read_optional_tag(&mut reader, 3)? {
+            request.pub_key_auth = Some(reader.read_octet_string()?);
+        }
+        if read_optional_tag(&mut reader, 4)? {
+            request.error_code = Some(reader.read_signed_integer()? as u32);
+        }
+        if read_optional_tag(&mut reader, 5)? {
+            let nonce = reader.read_octet_string()?;
+            if nonce.len() != CLIENT_NONCE_LENGTH {
+                return Err(PduError::InvalidLength {
+                    expected: CLIENT_NONCE_LENGTH,
+                    actual: nonce.len(),
+                });
+            }
+            let mut client_nonce = [0u8; CLIENT_NONCE_LENGTH];
+            client_nonce.copy_from_slice(&nonce);
+            request.client_nonce = Some(client_nonce);
+        }
+
+        if let Some(tag) = reader.peek_tag() {
+            return Err(PduError::ParseError(format!(
+                "Unexpected TSRequest field tag 0x{:02x}",
+                tag
+            )));
+        }
+        Ok(request)
+    }
+}
+
+impl Pdu for TsRequest {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        let mut writer = BerWriter::new();
+        self.write(&mut writer);
+        buffer.write_all(writer.as_bytes())?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let mut data = Vec::new();
+        buffer.read_to_end(&mut data)?;
+        Self::read(&mut BerReader::new(&data))
+    }
+
+    fn size(&self) -> usize {
+        let mut writer = BerWriter::new();
+        self.write(&mut writer);
+        writer.as_bytes().len()
+    }
+}
+
+/// Consume `[n] EXPLICIT` if it is the next tag
+pub(crate) fn read_optional_tag(reader: &mut BerReader, tag: u8) -> Result<bool> {
+    if reader.peek_tag() != Some(BerClass::ContextSpecific as u8 | BER_CONSTRUCTED | tag) {
+        return Ok(false);
+    }
+    reader.read_explicit_tag(tag)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ts_request_nego_token() {
+        let mut request = TsRequest::new(TS_REQUEST_VERSION);
+        request.nego_tokens.push(b"abc".to_vec());
+
+        let mut buffer = Vec::new();
+        request.encode(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), request.size());
+        assert_eq!(
+            buffer,
+            vec![
+                0x30, 0x12, 0xA0, 0x03, 0x02, 0x01, 0x06, 0xA1, 0x0B, 0x30, 0x09, 0x30, 0x07, 0xA0,
+                0x05, 0x04, 0x03, 0x61, 0x62, 0x63
+            ]
+        );
+
+        let decoded = TsRequest::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded, request);
+        assert_eq!(decoded.nego_token(), Some(&b"abc"[..]));
+
+        // Fields must appear in tag order
+        buffer[7] = 0xA6;
+        assert!(TsRequest::decode(&mut buffer.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_ts_request_optional_fields() {
+        let request = TsRequest {
+            version: 6,
+            nego_tokens: vec![b"one".to_vec(), b"two".to_vec()],
+            auth_info: Some(vec![0x11; 200]),
+            pub_key_auth: Some(vec![0x22; 16]),
+            error_code: Some(0xC000_006D),
+            client_nonce: Some([0x33; CLIENT_NONCE_LENGTH]),
+        };
+
+        let mut buffer = Vec::new();
+        request.encode(&mut buffer).unwrap();
+        assert_eq!(&buffer[..2], &[0x30, 0x82]);
+        assert_eq!(TsRequest::decode(&mut buffer.as_slice()).unwrap(), request);
+
+        // errorCode is a negative INTEGER, as Windows encodes it
+        let mut error = TsRequest::new(6);
+        error.error_code = Some(0x0000_0001);
+        let mut buffer = Vec::new();
+        error.encode(&mut buffer).unwrap();
+        assert_eq!(&buffer[7..], &[0xA4, 0x03, 0x02, 0x01, 0x01]);
+
+        error.error_code = Some(0xC000_006D);
+        let mut buffer = Vec::new();
+        error.encode(&mut buffer).unwrap();
+        assert_eq!(
+            &buffer[7..],
+            &[0xA4, 0x06, 0x02, 0x04, 0xC0, 0x00, 0x00, 0x6D]
+        );
+        let decoded = TsRequest::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded.error_code, Some(0xC000_006D));
+
+        // Minimal encoding of a small negative value
+        buffer.splice(7.., [0xA4, 0x03, 0x02, 0x01, 0x80].iter().copied());
+        buffer[1] = buffer.len() as u8 - 2;
+        let decoded = TsRequest::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded.error_code, Some(0xFFFF_FF80));
+
+        // Unsigned encoding with a leading zero octet
+        buffer.splice(
+            7..,
+            [0xA4, 0x07, 0x02, 0x05, 0x00, 0xC0, 0x00, 0x00, 0x6D]
+                .iter()
+                .copied(),
+        );
+        buffer[1] = buffer.len() as u8 - 2;
+        let decoded = TsRequest::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded.error_code, Some(0xC000_006D));
+    }
+}
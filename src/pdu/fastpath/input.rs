@@ -0,0 +1,628 @@
+use crate::pdu::frame::{FASTPATH_ACTION_FASTPATH, FastPathFrame};
+use crate::pdu::rdp::input::{
+    ExtendedMouseEvent, InputEvent, InputEventPdu, KeyboardEvent, KeyboardFlags, MouseEvent,
+    SyncEvent, UnicodeKeyboardEvent, UnicodeKeyboardFlags,
+};
+use crate::pdu::{Pdu, PduError, Result};
+use bitflags::bitflags;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Read, Write};
+
+/// Maximum number of events in a fast-path input PDU
+pub const FASTPATH_INPUT_MAX_EVENTS: usize = u8::MAX as usize;
+
+/// Largest event count that fits in the fpInputHeader numEvents field
+const HEADER_MAX_EVENTS: usize = 0x0F;
+
+/// Largest value of the 5-bit eventFlags field
+const EVENT_FLAGS_MASK: u8 = 0x1F;
+
+bitflags! {
+    /// Fast-path input header flags (MS-RDPBCGR 2.2.8.1.2)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct FastPathInputFlags: u8 {
+        /// FASTPATH_INPUT_SECURE_CHECKSUM - MAC was salted with the encryption count
+        const SECURE_CHECKSUM = 0x01;
+        /// FASTPATH_INPUT_ENCRYPTED - Events are encrypted
+        const ENCRYPTED = 0x02;
+    }
+}
+
+bitflags! {
+    /// Fast-path keyboard event flags (MS-RDPBCGR 2.2.8.1.2.2.1)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct FastPathKeyboardFlags: u8 {
+        /// FASTPATH_INPUT_KBDFLAGS_RELEASE - Key released
+        const RELEASE = 0x01;
+        /// FASTPATH_INPUT_KBDFLAGS_EXTENDED - Extended scancode
+        const EXTENDED = 0x02;
+        /// FASTPATH_INPUT_KBDFLAGS_EXTENDED1 - Extended1 scancode (Pause)
+        const EXTENDED1 = 0x04;
+    }
+}
+
+/// Fast-path input event codes (MS-RDPBCGR 2.2.8.1.2.2)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum FastPathInputEventCode {
+    /// FASTPATH_INPUT_EVENT_SCANCODE
+    Scancode = 0x0,
+    /// FASTPATH_INPUT_EVENT_MOUSE
+    Mouse = 0x1,
+    /// FASTPATH_INPUT_EVENT_MOUSEX
+    MouseX = 0x2,
+    /// FASTPATH_INPUT_EVENT_SYNC
+    Sync = 0x3,
+    /// FASTPATH_INPUT_EVENT_UNICODE
+    Unicode = 0x4,
+    /// FASTPATH_INPUT_EVENT_RELMOUSE
+    RelativeMouse = 0x5,
+    /// FASTPATH_INPUT_EVENT_QOE_TIMESTAMP
+    QoeTimestamp = 0x6,
+}
+
+impl FastPathInputEventCode {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0x0 => Some(FastPathInputEventCode::Scancode),
+            0x1 => Some(FastPathInputEventCode::Mouse),
+            0x2 => Some(FastPathInputEventCode::MouseX),
+            0x3 => Some(FastPathInputEventCode::Sync),
+            0x4 => Some(FastPathInputEventCode::Unicode),
+            0x5 => Some(FastPathInputEventCode::RelativeMouse),
+            0x6 => Some(FastPathInputEventCode::QoeTimestamp),
+            _ => None,
+        }
+    }
+
+    pub fn as_u8(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Fast-path input event (MS-RDPBCGR 2.2.8.1.2.2)
+///
+/// Each event starts with a one-byte eventHeader holding the event code
+/// (high 3 bits) and event flags (low 5 bits).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FastPathInputEvent {
+    /// Keyboard scancode (TS_FP_KEYBOARD_EVENT)
+    Scancode {
+        flags: FastPathKeyboardFlags,
+        key_code: u8,
+    },
+    /// Mouse event (TS_FP_POINTER_EVENT)
+    Mouse(MouseEvent),
+    /// Extended mouse event (TS_FP_POINTERX_EVENT)
+    MouseX(ExtendedMouseEvent),
+    /// Toggle key synchronization (TS_FP_SYNC_EVENT), same bits as `SyncEvent`
+    Sync { flags: u8 },
+    /// Unicode keyboard event (TS_FP_UNICODE_KEYBOARD_EVENT)
+    Unicode {
+        flags: FastPathKeyboardFlags,
+        unicode_code: u16,
+    },
+    /// Relative mouse movement (TS_FP_RELPOINTER_EVENT)
+    RelativeMouse {
+        pointer_flags: u16,
+        x_delta: i16,
+        y_delta: i16,
+    },
+    /// Quality of experience timestamp (TS_FP_QOETIMESTAMP_EVENT)
+    QoeTimestamp { timestamp: u32 },
+}
+
+impl FastPathInputEvent {
+    /// Get event code
+    pub fn event_code(&self) -> FastPathInputEventCode {
+        match self {
+            FastPathInputEvent::Scancode { .. } => FastPathInputEventCode::Scancode,
+            FastPathInputEvent::Mouse(_) => FastPathInputEventCode::Mouse,
+            FastPathInputEvent::MouseX(_) => FastPathInputEventCode::MouseX,
+            FastPathInputEvent::Sync { .. } => FastPathInputEventCode::Sync,
+            FastPathInputEvent::Unicode { .. } => FastPathInputEventCode::Unicode,
+            FastPathInputEvent::RelativeMouse { .. } => FastPathInputEventCode::RelativeMouse,
+            FastPathInputEvent::QoeTimestamp { .. } => FastPathInputEventCode::QoeTimestamp,
+        }
+    }
+
+    /// Low 5 bits of the eventHeader
+    fn event_flags(&self) -> u8 {
+        match self {
+            FastPathInputEvent::Scancode { flags, .. }
+            | FastPathInputEvent::Unicode { flags, .. } => flags.bits(),
+            FastPathInputEvent::Sync { flags } => *flags,
+            _ => 0,
+        }
+    }
+
+    /// Encode input event
+    pub fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        let event_flags = self.event_flags();
+        if event_flags & !EVENT_FLAGS_MASK != 0 {
+            return Err(PduError::ParseError(format!(
+                "Event flags {:#x} do not fit in 5 bits",
+                event_flags
+            )));
+        }
+        buffer.write_u8((self.event_code().as_u8() << 5) | event_flags)?;
+
+        match self {
+            FastPathInputEvent::Scancode { key_code, .. } => buffer.write_u8(*key_code)?,
+            FastPathInputEvent::Mouse(event) => event.encode(buffer)?,
+            FastPathInputEvent::MouseX(event) => event.encode(buffer)?,
+            FastPathInputEvent::Sync { .. } => {}
+            FastPathInputEvent::Unicode { unicode_code, .. } => {
+                buffer.write_u16::<LittleEndian>(*unicode_code)?
+            }
+            FastPathInputEvent::RelativeMouse {
+                pointer_flags,
+                x_delta,
+                y_delta,
+            } => {
+                buffer.write_u16::<LittleEndian>(*pointer_flags)?;
+                buffer.write_i16::<LittleEndian>(*x_delta)?;
+                buffer.write_i16::<LittleEndian>(*y_delta)?;
+            }
+            FastPathInputEvent::QoeTimestamp { timestamp } => {
+                buffer.write_u32::<LittleEndian>(*timestamp)?
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decode input event
+    pub fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let event_header = buffer.read_u8()?;
+        let event_flags = event_header & EVENT_FLAGS_MASK;
+        let code = event_header >> 5;
+
+        let event_code = FastPathInputEventCode::from_u8(code).ok_or_else(|| {
+            PduError::ParseError(format!("Invalid fast-path input event code: {:#x}", code))
+        })?;
+
+        let event = match event_code {
+            FastPathInputEventCode::Scancode => FastPathInputEvent::Scancode {
+                flags: FastPathKeyboardFlags::from_bits_retain(event_flags),
+                key_code: buffer.read_u8()?,
+            },
+            FastPathInputEventCode::Mouse => FastPathInputEvent::Mouse(MouseEvent::decode(buffer)?),
+            FastPathInputEventCode::MouseX => {
+                FastPathInputEvent::MouseX(ExtendedMouseEvent::decode(buffer)?)
+            }
+            FastPathInputEventCode::Sync => FastPathInputEvent::Sync { flags: event_flags },
+            FastPathInputEventCode::Unicode => FastPathInputEvent::Unicode {
+                flags: FastPathKeyboardFlags::from_bits_retain(event_flags),
+                unicode_code: buffer.read_u16::<LittleEndian>()?,
+            },
+            FastPathInputEventCode::RelativeMouse => FastPathInputEvent::RelativeMouse {
+                pointer_flags: buffer.read_u16::<LittleEndian>()?,
+                x_delta: buffer.read_i16::<LittleEndian>()?,
+                y_delta: buffer.read_i16::<LittleEndian>()?,
+            },
+            FastPathInputEventCode::QoeTimestamp => FastPathInputEvent::QoeTimestamp {
+                timestamp: buffer.read_u32::<LittleEndian>()?,
+            },
+        };
+
+        Ok(event)
+    }
+
+    /// Return size (eventHeader + event data)
+    pub fn size(&self) -> usize {
+        1 + match self {
+            FastPathInputEvent::Scancode { .. } => 1,
+            FastPathInputEvent::Mouse(event) => event.size(),
+            FastPathInputEvent::MouseX(event) => event.size(),
+            FastPathInputEvent::Sync { .. } => 0,
+            FastPathInputEvent::Unicode { .. } => 2,
+            FastPathInputEvent::RelativeMouse { .. } => 6,
+            FastPathInputEvent::QoeTimestamp { .. } => 4,
+        }
+    }
+}
+
+/// Map slow-path keyboard flags to fast-path flags, if representable
+fn keyboard_flags_to_fastpath(flags: u16) -> Option<FastPathKeyboardFlags> {
+    let known = KeyboardFlags::RELEASE | KeyboardFlags::EXTENDED | KeyboardFlags::EXTENDED1;
+    if flags & !known != 0 {
+        return None;
+    }
+
+    let mut fastpath = FastPathKeyboardFlags::empty();
+    fastpath.set(
+        FastPathKeyboardFlags::RELEASE,
+        flags & KeyboardFlags::RELEASE != 0,
+    );
+    fastpath.set(
+        FastPathKeyboardFlags::EXTENDED,
+        flags & KeyboardFlags::EXTENDED != 0,
+    );
+    fastpath.set(
+        FastPathKeyboardFlags::EXTENDED1,
+        flags & KeyboardFlags::EXTENDED1 != 0,
+    );
+    Some(fastpath)
+}
+
+/// Map fast-path keyboard flags to slow-path flags, if representable
+fn keyboard_flags_from_fastpath(flags: FastPathKeyboardFlags) -> Option<u16> {
+    if flags.bits() & !FastPathKeyboardFlags::all().bits() != 0 {
+        return None;
+    }
+
+    let mut slow = 0;
+    if flags.contains(FastPathKeyboardFlags::RELEASE) {
+        slow |= KeyboardFlags::RELEASE;
+    }
+    if flags.contains(FastPathKeyboardFlags::EXTENDED) {
+        slow |= KeyboardFlags::EXTENDED;
+    }
+    if flags.contains(FastPathKeyboardFlags::EXTENDED1) {
+        slow |= KeyboardFlags::EXTENDED1;
+    }
+    Some(slow)
+}
+
+fn not_representable(event: &dyn std::fmt::Debug, transport: &str) -> PduError {
+    PduError::ParseError(format!(
+        "{:?} cannot be represented as {}",
+        event, transport
+    ))
+}
+
+/// Lossless conversion from a slow-path event
+///
+/// Fails when a field does not fit the fast-path encoding (scancodes above
+/// 0xFF, flag bits without a fast-path equivalent or non-zero padding).
+impl TryFrom<InputEvent> for FastPathInputEvent {
+    type Error = PduError;
+
+    fn try_from(event: InputEvent) -> Result<Self> {
+        let converted = match &event {
+            InputEvent::Keyboard(keyboard) => u8::try_from(keyboard.key_code)
+                .ok()
+                .zip(keyboard_flags_to_fastpath(keyboard.flags.as_u16()))
+                .filter(|_| keyboard.pad == 0)
+                .map(|(key_code, flags)| FastPathInputEvent::Scancode { flags, key_code }),
+            InputEvent::Unicode(unicode) => {
+                (unicode.flags.as_u16() & !UnicodeKeyboardFlags::RELEASE == 0 && unicode.pad == 0)
+                    .then(|| FastPathInputEvent::Unicode {
+                        flags: if unicode.flags.is_release() {
+                            FastPathKeyboardFlags::RELEASE
+                        } else {
+                            FastPathKeyboardFlags::empty()
+                        },
+                        unicode_code: unicode.unicode_code,
+                    })
+            }
+            InputEvent::Mouse(mouse) => Some(FastPathInputEvent::Mouse(mouse.clone())),
+            InputEvent::ExtendedMouse(mouse) => Some(FastPathInputEvent::MouseX(mouse.clone())),
+            InputEvent::Sync(sync) => u8::try_from(sync.flags)
+                .ok()
+                .filter(|flags| flags & !EVENT_FLAGS_MASK == 0 && sync.pad == 0)
+                .map(|flags| FastPathInputEvent::Sync { flags }),
+        };
+
+        converted.ok_or_else(|| not_representable(&event, "a fast-path event"))
+    }
+}
+
+/// Lossless conversion to a slow-path event
+///
+/// Relative mouse and QoE timestamp events have no slow-path equivalent.
+impl TryFrom<FastPathInputEvent> for InputEvent {
+    type Error = PduError;
+
+    fn try_from(event: FastPathInputEvent) -> Result<Self> {
+        let converted = match &event {
+            FastPathInputEvent::Scancode { flags, key_code } => {
+                keyboard_flags_from_fastpath(*flags).map(|flags| {
+                    InputEvent::Keyboard(KeyboardEvent::new(
+                        KeyboardFlags::new(flags),
+                        *key_code as u16,
+                    ))
+                })
+            }
+            FastPathInputEvent::Unicode {
+                flags,
+                unicode_code,
+            } => (*flags & !FastPathKeyboardFlags::RELEASE)
+                .is_empty()
+                .then(|| {
+                    let unicode_flags = if flags.contains(FastPathKeyboardFlags::RELEASE) {
+                        UnicodeKeyboardFlags::key_up()
+                    } else {
+                        UnicodeKeyboardFlags::key_down()
+                    };
+                    InputEvent::Unicode(UnicodeKeyboardEvent::new(unicode_flags, *unicode_code))
+                }),
+            FastPathInputEvent::Mouse(mouse) => Some(InputEvent::Mouse(mouse.clone())),
+            FastPathInputEvent::MouseX(mouse) => Some(InputEvent::ExtendedMouse(mouse.clone())),
+            FastPathInputEvent::Sync { flags } => {
+                Some(InputEvent::Sync(SyncEvent::new(*flags as u16)))
+            }
+            FastPathInputEvent::RelativeMouse { .. } | FastPathInputEvent::QoeTimestamp { .. } => {
+                None
+            }
+        };
+
+        converted.ok_or_else(|| not_representable(&event, "a slow-path event"))
+    }
+}
+
+/// Fast-path Input Event PDU (MS-RDPBCGR 2.2.8.1.2)
+///
+/// ```text
+/// +---------------+-----------+---------------+-----------+--------------+
+/// | fpInputHeader | length    | dataSignature | numEvents | fpInputEvents|
+/// | 1 byte        | 1-2 bytes | 8 (optional)  | 1 (opt.)  | variable     |
+/// +---------------+-----------+---------------+-----------+--------------+
+/// ```
+///
+/// Up to 15 events are counted in the header; larger batches carry an
+/// explicit numEvents byte. When `ENCRYPTED` is set the events are
+/// expected to be already decrypted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FastPathInputPdu {
+    /// Header flags
+    pub flags: FastPathInputFlags,
+    /// MAC signature (present when encrypted)
+    pub data_signature: Option<[u8; 8]>,
+    /// Input events
+    pub events: Vec<FastPathInputEvent>,
+}
+
+impl FastPathInputPdu {
+    /// Create unencrypted fast-path input PDU
+    pub fn new(events: Vec<FastPathInputEvent>) -> Self {
+        Self {
+            flags: FastPathInputFlags::empty(),
+            data_signature: None,
+            events,
+        }
+    }
+
+    /// Size of the data following the length field
+    fn data_size(&self) -> usize {
+        self.data_signature.map_or(0, |sig| sig.len())
+            + usize::from(self.events.len() > HEADER_MAX_EVENTS || self.events.is_empty())
+            + self.events.iter().map(|e| e.size()).sum::<usize>()
+    }
+
+    /// Build the raw fast-path frame
+    pub fn to_frame(&self) -> Result<FastPathFrame> {
+        if self.events.len() > FASTPATH_INPUT_MAX_EVENTS {
+            return Err(PduError::InvalidLength {
+                expected: FASTPATH_INPUT_MAX_EVENTS,
+                actual: self.events.len(),
+            });
+        }
+        if self.flags.contains(FastPathInputFlags::ENCRYPTED) != self.data_signature.is_some() {
+            return Err(PduError::InvalidHeader(
+                "Data signature must be present exactly when encrypted".to_string(),
+            ));
+        }
+
+        let header_events = if self.events.len() > HEADER_MAX_EVENTS {
+            0
+        } else {
+            self.events.len() as u8
+        };
+        let header = FASTPATH_ACTION_FASTPATH | (header_events << 2) | (self.flags.bits() << 6);
+
+        let mut data = Vec::with_capacity(self.data_size());
+        if let Some(signature) = &self.data_signature {
+            data.write_all(signature)?;
+        }
+        if header_events == 0 {
+            data.write_u8(self.events.len() as u8)?;
+        }
+        for event in &self.events {
+            event.encode(&mut data)?;
+        }
+
+        Ok(FastPathFrame::new(header, data))
+    }
+
+    /// Parse a fast-path frame read from the transport
+    pub fn from_frame(frame: &FastPathFrame) -> Result<Self> {
+        let flags = FastPathInputFlags::from_bits_retain(frame.flags());
+        let mut data = frame.data.as_slice();
+
+        let data_signature = if flags.contains(FastPathInputFlags::ENCRYPTED) {
+            let mut signature = [0u8; 8];
+            data.read_exact(&mut signature)?;
+            Some(signature)
+        } else {
+            None
+        };
+
+        let num_events = match frame.num_events() {
+            0 => data.read_u8()? as usize,
+            count => count as usize,
+        };
+
+        let mut events = Vec::with_capacity(num_events);
+        for _ in 0..num_events {
+            events.push(FastPathInputEvent::decode(&mut data)?);
+        }
+
+        if !data.is_empty() {
+            return Err(PduError::InvalidLength {
+                expected: frame.data.len() - data.len(),
+                actual: frame.data.len(),
+            });
+        }
+
+        Ok(Self {
+            flags,
+            data_signature,
+            events,
+        })
+    }
+}
+
+impl Pdu for FastPathInputPdu {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        self.to_frame()?.encode(buffer)
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        Self::from_frame(&FastPathFrame::decode(buffer)?)
+    }
+
+    fn size(&self) -> usize {
+        FastPathFrame::encoded_size(self.data_size())
+    }
+}
+
+/// Lossless conversion of a slow-path Input Event PDU
+impl TryFrom<InputEventPdu> for FastPathInputPdu {
+    type Error = PduError;
+
+    fn try_from(pdu: InputEventPdu) -> Result<Self> {
+        let events = pdu
+            .events
+            .into_iter()
+            .map(FastPathInputEvent::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::new(events))
+    }
+}
+
+/// Lossless conversion to a slow-path Input Event PDU
+impl TryFrom<FastPathInputPdu> for InputEventPdu {
+    type Error = PduError;
+
+    fn try_from(pdu: FastPathInputPdu) -> Result<Self> {
+        let events = pdu
+            .events
+            .into_iter()
+            .map(InputEvent::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::new(events))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fastpath_input_encode() {
+        let pdu = FastPathInputPdu::new(vec![
+            FastPathInputEvent::Scancode {
+                flags: FastPathKeyboardFlags::RELEASE,
+                key_code: 0x1E,
+            },
+            FastPathInputEvent::Mouse(MouseEvent::move_to(0x0100, 0x0080)),
+        ]);
+
+        let mut buffer = Vec::new();
+        pdu.encode(&mut buffer).unwrap();
+
+        assert_eq!(
+            buffer,
+            vec![
+                0x08, 0x0B, // header (2 events), length
+                0x01, 0x1E, // scancode release 'A'
+                0x20, 0x00, 0x08, 0x00, 0x01, 0x80, 0x00, // mouse move
+            ]
+        );
+        assert_eq!(buffer.len(), pdu.size());
+
+        let decoded = FastPathInputPdu::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded, pdu);
+    }
+
+    #[test]
+    fn test_fastpath_input_all_events_roundtrip() {
+        let events = vec![
+            FastPathInputEvent::Sync {
+                flags: (SyncEvent::NUM_LOCK | SyncEvent::CAPS_LOCK) as u8,
+            },
+            FastPathInputEvent::Unicode {
+                flags: FastPathKeyboardFlags::empty(),
+                unicode_code: 0x4E2D,
+            },
+            FastPathInputEvent::MouseX(ExtendedMouseEvent::move_to(10, 20)),
+            FastPathInputEvent::RelativeMouse {
+                pointer_flags: 0x0800,
+                x_delta: -5,
+                y_delta: 7,
+            },
+            FastPathInputEvent::QoeTimestamp {
+                timestamp: 0x1234_5678,
+            },
+        ];
+        let mut pdu = FastPathInputPdu::new(events);
+        pdu.flags = FastPathInputFlags::ENCRYPTED;
+        pdu.data_signature = Some([0xAA; 8]);
+
+        let mut buffer = Vec::new();
+        pdu.encode(&mut buffer).unwrap();
+        assert_eq!(buffer[0], 0x80 | (5 << 2));
+        assert_eq!(&buffer[2..10], &[0xAA; 8]);
+
+        let decoded = FastPathInputPdu::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded, pdu);
+    }
+
+    #[test]
+    fn test_fastpath_input_explicit_event_count() {
+        let events = (0..20u8)
+            .map(|key_code| FastPathInputEvent::Scancode {
+                flags: FastPathKeyboardFlags::empty(),
+                key_code,
+            })
+            .collect();
+        let pdu = FastPathInputPdu::new(events);
+
+        let frame = pdu.to_frame().unwrap();
+        assert_eq!(frame.num_events(), 0);
+        assert_eq!(frame.data[0], 20);
+
+        assert_eq!(FastPathInputPdu::from_frame(&frame).unwrap(), pdu);
+    }
+
+    #[test]
+    fn test_fastpath_input_from_slow_path() {
+        let slow = InputEventPdu::new(vec![
+            InputEvent::Keyboard(KeyboardEvent::extended_key_up(0x48)),
+            InputEvent::Unicode(UnicodeKeyboardEvent::key_up(0x0041)),
+            InputEvent::Mouse(MouseEvent::left_down(100, 200)),
+            InputEvent::ExtendedMouse(ExtendedMouseEvent::move_to(1, 2)),
+            InputEvent::Sync(SyncEvent::new(SyncEvent::SCROLL_LOCK)),
+        ]);
+
+        let fast = FastPathInputPdu::try_from(slow.clone()).unwrap();
+        assert_eq!(
+            fast.events[0],
+            FastPathInputEvent::Scancode {
+                flags: FastPathKeyboardFlags::RELEASE | FastPathKeyboardFlags::EXTENDED,
+                key_code: 0x48,
+            }
+        );
+
+        let back = InputEventPdu::try_from(fast).unwrap();
+        assert_eq!(back, slow);
+    }
+
+    #[test]
+    fn test_fastpath_input_conversion_rejects_lossy() {
+        let wide = InputEvent::Keyboard(KeyboardEvent::key_down(0x0100));
+        assert!(FastPathInputEvent::try_from(wide).is_err());
+
+        let unknown_flag = InputEvent::Keyboard(KeyboardEvent::new(KeyboardFlags::new(0x4000), 1));
+        assert!(FastPathInputEvent::try_from(unknown_flag).is_err());
+
+        let relative = FastPathInputEvent::RelativeMouse {
+            pointer_flags: 0,
+            x_delta: 1,
+            y_delta: 1,
+        };
+        assert!(InputEvent::try_from(relative).is_err());
+    }
+}
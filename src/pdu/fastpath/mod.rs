@@ -0,0 +1,12 @@
+// Fast-path PDUs
+pub mod input;
+pub mod output;
+
+pub use input::{
+    FastPathInputEvent, FastPathInputEventCode, FastPathInputFlags, FastPathInputPdu,
+    FastPathKeyboardFlags,
+};
+pub use output::{
+    FastPathOutputFlags, FastPathOutputPdu, FastPathReassembler, FastPathUpdate,
+    FastPathUpdateCode, Fragmentation,
+};
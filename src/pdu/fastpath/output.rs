@@ -0,0 +1,571 @@
+use crate::pdu::frame::{FASTPATH_ACTION_FASTPATH, FastPathFrame};
+use crate::pdu::{Pdu, PduError, Result};
+use bitflags::bitflags;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Read, Write};
+
+/// Bulk compression flag: payload is compressed (MS-RDPBCGR 3.1.8.2.1)
+pub const PACKET_COMPRESSED: u8 = 0x20;
+
+/// Default size cap for reassembled updates
+pub const DEFAULT_MAX_REASSEMBLY_SIZE: usize = 8 * 1024 * 1024;
+
+/// FASTPATH_OUTPUT_COMPRESSION_USED in the updateHeader compression bits
+const COMPRESSION_USED: u8 = 0x2;
+
+bitflags! {
+    /// Fast-path output header flags (MS-RDPBCGR 2.2.9.1.2)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct FastPathOutputFlags: u8 {
+        /// FASTPATH_OUTPUT_SECURE_CHECKSUM - MAC was salted with the encryption count
+        const SECURE_CHECKSUM = 0x01;
+        /// FASTPATH_OUTPUT_ENCRYPTED - Updates are encrypted
+        const ENCRYPTED = 0x02;
+    }
+}
+
+/// Fast-path update codes (MS-RDPBCGR 2.2.9.1.2.1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum FastPathUpdateCode {
+    /// FASTPATH_UPDATETYPE_ORDERS
+    Orders = 0x0,
+    /// FASTPATH_UPDATETYPE_BITMAP
+    Bitmap = 0x1,
+    /// FASTPATH_UPDATETYPE_PALETTE
+    Palette = 0x2,
+    /// FASTPATH_UPDATETYPE_SYNCHRONIZE
+    Synchronize = 0x3,
+    /// FASTPATH_UPDATETYPE_SURFCMDS
+    SurfaceCommands = 0x4,
+    /// FASTPATH_UPDATETYPE_PTR_NULL
+    PointerHidden = 0x5,
+    /// FASTPATH_UPDATETYPE_PTR_DEFAULT
+    PointerDefault = 0x6,
+    /// FASTPATH_UPDATETYPE_PTR_POSITION
+    PointerPosition = 0x8,
+    /// FASTPATH_UPDATETYPE_COLOR
+    ColorPointer = 0x9,
+    /// FASTPATH_UPDATETYPE_CACHED
+    CachedPointer = 0xA,
+    /// FASTPATH_UPDATETYPE_POINTER
+    NewPointer = 0xB,
+    /// FASTPATH_UPDATETYPE_LARGE_POINTER
+    LargePointer = 0xC,
+}
+
+impl FastPathUpdateCode {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0x0 => Some(FastPathUpdateCode::Orders),
+            0x1 => Some(FastPathUpdateCode::Bitmap),
+            0x2 => Some(FastPathUpdateCode::Palette),
+            0x3 => Some(FastPathUpdateCode::Synchronize),
+            0x4 => Some(FastPathUpdateCode::SurfaceCommands),
+            0x5 => Some(FastPathUpdateCode::PointerHidden),
+            0x6 => Some(FastPathUpdateCode::PointerDefault),
+            0x8 => Some(FastPathUpdateCode::PointerPosition),
+            0x9 => Some(FastPathUpdateCode::ColorPointer),
+            0xA => Some(FastPathUpdateCode::CachedPointer),
+            0xB => Some(FastPathUpdateCode::NewPointer),
+            0xC => Some(FastPathUpdateCode::LargePointer),
+            _ => None,
+        }
+    }
+
+    pub fn as_u8(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Fast-path update fragmentation (MS-RDPBCGR 2.2.9.1.2.1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Fragmentation {
+    /// FASTPATH_FRAGMENT_SINGLE - Update is not fragmented
+    Single = 0x0,
+    /// FASTPATH_FRAGMENT_LAST - Last fragment
+    Last = 0x1,
+    /// FASTPATH_FRAGMENT_FIRST - First fragment
+    First = 0x2,
+    /// FASTPATH_FRAGMENT_NEXT - Intermediate fragment
+    Next = 0x3,
+}
+
+impl Fragmentation {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0x0 => Some(Fragmentation::Single),
+            0x1 => Some(Fragmentation::Last),
+            0x2 => Some(Fragmentation::First),
+            0x3 => Some(Fragmentation::Next),
+            _ => None,
+        }
+    }
+
+    pub fn as_u8(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Fast-path update (TS_FP_UPDATE, MS-RDPBCGR 2.2.9.1.2.1)
+///
+/// ```text
+/// +--------------+-------------------+--------+------------+
+/// | updateHeader | compressionFlags  | size   | updateData |
+/// | 1 byte       | 1 byte (optional) | 2 (LE) | size bytes |
+/// +--------------+-------------------+--------+------------+
+/// ```
+///
+/// `data` holds the raw updateData, still compressed when
+/// `compression_flags` has `PACKET_COMPRESSED` set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FastPathUpdate {
+    /// Update code
+    pub update_code: FastPathUpdateCode,
+    /// Fragmentation state
+    pub fragmentation: Fragmentation,
+    /// Bulk compression flags (present when compression is used)
+    pub compression_flags: Option<u8>,
+    /// Update data
+    pub data: Vec<u8>,
+}
+
+impl FastPathUpdate {
+    /// Create unfragmented, uncompressed update
+    pub fn new(update_code: FastPathUpdateCode, data: Vec<u8>) -> Self {
+        Self {
+            update_code,
+            fragmentation: Fragmentation::Single,
+            compression_flags: None,
+            data,
+        }
+    }
+
+    /// Check whether the data is bulk compressed
+    pub fn is_compressed(&self) -> bool {
+        self.compression_flags
+            .is_some_and(|flags| flags & PACKET_COMPRESSED != 0)
+    }
+
+    /// Split into fragments carrying at most `max_fragment_size` bytes each
+    ///
+    /// Updates that already fit are returned unchanged. Bulk compression
+    /// runs per fragment, so updates carrying compression flags are
+    /// rejected; fragment first, then compress each fragment.
+    pub fn fragment(self, max_fragment_size: usize) -> Result<Vec<FastPathUpdate>> {
+        if self.fragmentation != Fragmentation::Single {
+            return Err(PduError::ParseError(
+                "Update is already a fragment".to_string(),
+            ));
+        }
+        if max_fragment_size == 0 {
+            return Err(PduError::InvalidLength {
+                expected: 1,
+                actual: 0,
+            });
+        }
+        if self.data.len() <= max_fragment_size {
+            return Ok(vec![self]);
+        }
+        if let Some(flags) = self.compression_flags {
+            return Err(PduError::ParseError(format!(
+                "Cannot fragment update with compression flags {:#04x}",
+                flags
+            )));
+        }
+
+        let chunks: Vec<&[u8]> = self.data.chunks(max_fragment_size).collect();
+        let last = chunks.len() - 1;
+        Ok(chunks
+            .into_iter()
+            .enumerate()
+            .map(|(index, chunk)| FastPathUpdate {
+                update_code: self.update_code,
+                fragmentation: match index {
+                    0 => Fragmentation::First,
+                    i if i == last => Fragmentation::Last,
+                    _ => Fragmentation::Next,
+                },
+                compression_flags: None,
+                data: chunk.to_vec(),
+            })
+            .collect())
+    }
+
+    /// Encode update
+    pub fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        if self.data.len() > u16::MAX as usize {
+            return Err(PduError::FrameTooLarge {
+                size: self.data.len(),
+                max: u16::MAX as usize,
+            });
+        }
+
+        let compression = if self.compression_flags.is_some() {
+            COMPRESSION_USED
+        } else {
+            0
+        };
+        buffer.write_u8(
+            self.update_code.as_u8() | (self.fragmentation.as_u8() << 4) | (compression << 6),
+        )?;
+        if let Some(flags) = self.compression_flags {
+            buffer.write_u8(flags)?;
+        }
+        buffer.write_u16::<LittleEndian>(self.data.len() as u16)?;
+        buffer.write_all(&self.data)?;
+        Ok(())
+    }
+
+    /// Decode update
+    pub fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let update_header = buffer.read_u8()?;
+        let code = update_header & 0x0F;
+        let update_code = FastPathUpdateCode::from_u8(code).ok_or_else(|| {
+            PduError::ParseError(format!("Invalid fast-path update code: {:#x}", code))
+        })?;
+        // Two-bit field, always valid
+        let fragmentation =
+            Fragmentation::from_u8((update_header >> 4) & 0x03).unwrap_or(Fragmentation::Single);
+
+        let compression_flags = if (update_header >> 6) & COMPRESSION_USED != 0 {
+            Some(buffer.read_u8()?)
+        } else {
+            None
+        };
+
+        let size = buffer.read_u16::<LittleEndian>()? as usize;
+        let mut data = vec![0u8; size];
+        buffer.read_exact(&mut data)?;
+
+        Ok(Self {
+            update_code,
+            fragmentation,
+            compression_flags,
+            data,
+        })
+    }
+
+    /// Return size (updateHeader + compressionFlags + size + data)
+    pub fn size(&self) -> usize {
+        1 + usize::from(self.compression_flags.is_some()) + 2 + self.data.len()
+    }
+}
+
+/// Fast-path Update PDU (TS_FP_UPDATE_PDU, MS-RDPBCGR 2.2.9.1.2)
+///
+/// ```text
+/// +----------------+-----------+---------------+-----------------+
+/// | fpOutputHeader | length    | dataSignature | fpOutputUpdates |
+/// | 1 byte         | 1-2 bytes | 8 (optional)  | variable        |
+/// +----------------+-----------+---------------+-----------------+
+/// ```
+///
+/// When `ENCRYPTED` is set the updates are expected to be already
+/// decrypted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FastPathOutputPdu {
+    /// Header flags
+    pub flags: FastPathOutputFlags,
+    /// MAC signature (present when encrypted)
+    pub data_signature: Option<[u8; 8]>,
+    /// Updates
+    pub updates: Vec<FastPathUpdate>,
+}
+
+impl FastPathOutputPdu {
+    /// Create unencrypted fast-path update PDU
+    pub fn new(updates: Vec<FastPathUpdate>) -> Self {
+        Self {
+            flags: FastPathOutputFlags::empty(),
+            data_signature: None,
+            updates,
+        }
+    }
+
+    /// Size of the data following the length field
+    fn data_size(&self) -> usize {
+        self.data_signature.map_or(0, |sig| sig.len())
+            + self.updates.iter().map(|u| u.size()).sum::<usize>()
+    }
+
+    /// Build the raw fast-path frame
+    pub fn to_frame(&self) -> Result<FastPathFrame> {
+        if self.flags.contains(FastPathOutputFlags::ENCRYPTED) != self.data_signature.is_some() {
+            return Err(PduError::InvalidHeader(
+                "Data signature must be present exactly when encrypted".to_string(),
+            ));
+        }
+
+        let header = FASTPATH_ACTION_FASTPATH | (self.flags.bits() << 6);
+
+        let mut data = Vec::with_capacity(self.data_size());
+        if let Some(signature) = &self.data_signature {
+            data.write_all(signature)?;
+        }
+        for update in &self.updates {
+            update.encode(&mut data)?;
+        }
+
+        Ok(FastPathFrame::new(header, data))
+    }
+
+    /// Parse a fast-path frame read from the transport
+    pub fn from_frame(frame: &FastPathFrame) -> Result<Self> {
+        let flags = FastPathOutputFlags::from_bits_retain(frame.flags());
+        let mut data = frame.data.as_slice();
+
+        let data_signature = if flags.contains(FastPathOutputFlags::ENCRYPTED) {
+            let mut signature = [0u8; 8];
+            data.read_exact(&mut signature)?;
+            Some(signature)
+        } else {
+            None
+        };
+
+        let mut updates = Vec::new();
+        while !data.is_empty() {
+            updates.push(FastPathUpdate::decode(&mut data)?);
+        }
+
+        Ok(Self {
+            flags,
+            data_signature,
+            updates,
+        })
+    }
+}
+
+impl Pdu for FastPathOutputPdu {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        self.to_frame()?.encode(buffer)
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        Self::from_frame(&FastPathFrame::decode(buffer)?)
+    }
+
+    fn size(&self) -> usize {
+        FastPathFrame::encoded_size(self.data_size())
+    }
+}
+
+/// Reassembles fragmented fast-path updates
+///
+/// Fragments must arrive uncompressed (decompress each fragment first,
+/// since bulk compression state advances per fragment). The combined size
+/// is capped to bound memory use from a misbehaving peer.
+#[derive(Debug, Clone)]
+pub struct FastPathReassembler {
+    max_size: usize,
+    pending: Option<(FastPathUpdateCode, Vec<u8>)>,
+}
+
+impl FastPathReassembler {
+    /// Create reassembler with the given size cap
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            max_size,
+            pending: None,
+        }
+    }
+
+    /// Size cap for reassembled updates
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Check whether a fragmented update is in progress
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Drop any partially reassembled update
+    pub fn reset(&mut self) {
+        self.pending = None;
+    }
+
+    /// Feed an update, returning the complete update once available
+    pub fn push(&mut self, update: FastPathUpdate) -> Result<Option<FastPathUpdate>> {
+        if update.is_compressed() {
+            return Err(PduError::ParseError(
+                "Compressed fragments must be decompressed before reassembly".to_string(),
+            ));
+        }
+
+        match update.fragmentation {
+            Fragmentation::Single => {
+                if self.pending.is_some() {
+                    self.reset();
+                    return Err(PduError::ParseError(
+                        "Unfragmented update while reassembly in progress".to_string(),
+                    ));
+                }
+                self.check_size(update.data.len())?;
+                Ok(Some(update))
+            }
+            Fragmentation::First => {
+                if self.pending.is_some() {
+                    self.reset();
+                    return Err(PduError::ParseError(
+                        "First fragment while reassembly in progress".to_string(),
+                    ));
+                }
+                self.check_size(update.data.len())?;
+                self.pending = Some((update.update_code, update.data));
+                Ok(None)
+            }
+            Fragmentation::Next | Fragmentation::Last => {
+                let Some((update_code, mut data)) = self.pending.take() else {
+                    return Err(PduError::ParseError(format!(
+                        "{:?} fragment without first fragment",
+                        update.fragmentation
+                    )));
+                };
+                if update_code != update.update_code {
+                    return Err(PduError::ParseError(format!(
+                        "Fragment update code {:?} does not match {:?}",
+                        update.update_code, update_code
+                    )));
+                }
+                self.check_size(data.len() + update.data.len())?;
+                data.extend_from_slice(&update.data);
+
+                if update.fragmentation == Fragmentation::Last {
+                    Ok(Some(FastPathUpdate::new(update_code, data)))
+                } else {
+                    self.pending = Some((update_code, data));
+                    Ok(None)
+                }
+            }
+        }
+    }
+
+    fn check_size(&self, size: usize) -> Result<()> {
+        if size > self.max_size {
+            return Err(PduError::FrameTooLarge {
+                size,
+                max: self.max_size,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for FastPathReassembler {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_REASSEMBLY_SIZE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fastpath_update_encode() {
+        let pdu = FastPathOutputPdu::new(vec![
+            FastPathUpdate::new(FastPathUpdateCode::Synchronize, vec![]),
+            FastPathUpdate::new(
+                FastPathUpdateCode::PointerPosition,
+                vec![0x10, 0x00, 0x20, 0x00],
+            ),
+        ]);
+
+        let mut buffer = Vec::new();
+        pdu.encode(&mut buffer).unwrap();
+
+        assert_eq!(
+            buffer,
+            vec![
+                0x00, 0x0C, // header, length
+                0x03, 0x00, 0x00, // synchronize
+                0x08, 0x04, 0x00, 0x10, 0x00, 0x20, 0x00, // pointer position
+            ]
+        );
+        assert_eq!(buffer.len(), pdu.size());
+
+        let decoded = FastPathOutputPdu::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded, pdu);
+    }
+
+    #[test]
+    fn test_fastpath_update_compressed_encrypted_roundtrip() {
+        let mut update = FastPathUpdate::new(FastPathUpdateCode::Bitmap, vec![0x55; 200]);
+        update.compression_flags = Some(PACKET_COMPRESSED | 0x01);
+        let mut pdu = FastPathOutputPdu::new(vec![update]);
+        pdu.flags = FastPathOutputFlags::ENCRYPTED;
+        pdu.data_signature = Some([0x11; 8]);
+
+        let frame = pdu.to_frame().unwrap();
+        assert_eq!(frame.flags(), 0x02);
+        assert_eq!(frame.data[8], 0x81); // bitmap, compression used
+        assert_eq!(frame.data[9], 0x21);
+
+        let decoded = FastPathOutputPdu::from_frame(&frame).unwrap();
+        assert_eq!(decoded, pdu);
+        assert!(decoded.updates[0].is_compressed());
+    }
+
+    #[test]
+    fn test_fastpath_fragment_and_reassemble() {
+        let data: Vec<u8> = (0..=255).collect();
+        let update = FastPathUpdate::new(FastPathUpdateCode::Orders, data.clone());
+
+        let fragments = update.clone().fragment(100).unwrap();
+        let kinds: Vec<_> = fragments.iter().map(|f| f.fragmentation).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Fragmentation::First,
+                Fragmentation::Next,
+                Fragmentation::Last
+            ]
+        );
+
+        let mut compressed = update.clone();
+        compressed.compression_flags = Some(PACKET_COMPRESSED);
+        assert_eq!(
+            compressed.clone().fragment(256).unwrap(),
+            vec![compressed.clone()]
+        );
+        assert!(compressed.fragment(100).is_err());
+
+        let mut reassembler = FastPathReassembler::default();
+        assert!(reassembler.push(fragments[0].clone()).unwrap().is_none());
+        assert!(reassembler.push(fragments[1].clone()).unwrap().is_none());
+        assert!(reassembler.is_pending());
+        assert_eq!(
+            reassembler.push(fragments[2].clone()).unwrap(),
+            Some(update)
+        );
+        assert!(!reassembler.is_pending());
+    }
+
+    #[test]
+    fn test_fastpath_reassembler_errors() {
+        let fragments = FastPathUpdate::new(FastPathUpdateCode::Bitmap, vec![0; 300])
+            .fragment(100)
+            .unwrap();
+
+        let mut reassembler = FastPathReassembler::new(250);
+        reassembler.push(fragments[0].clone()).unwrap();
+        reassembler.push(fragments[1].clone()).unwrap();
+        assert!(matches!(
+            reassembler.push(fragments[2].clone()),
+            Err(PduError::FrameTooLarge {
+                size: 300,
+                max: 250
+            })
+        ));
+
+        let mut reassembler = FastPathReassembler::default();
+        assert!(reassembler.push(fragments[2].clone()).is_err());
+
+        reassembler.push(fragments[0].clone()).unwrap();
+        let mut mismatched = fragments[2].clone();
+        mismatched.update_code = FastPathUpdateCode::Orders;
+        assert!(reassembler.push(mismatched).is_err());
+    }
+}
@@ -0,0 +1,418 @@
+use crate::pdu::tpkt::{TPKT_HEADER_SIZE, TpktHeader, TpktPacket};
+use crate::pdu::{Pdu, PduError, Result};
+use byteorder::{ReadBytesExt, WriteBytesExt};
+use bytes::{Buf, BytesMut};
+use std::io::{Read, Write};
+
+/// Fast-path action code (low 2 bits of the first byte)
+pub const FASTPATH_ACTION_FASTPATH: u8 = 0x00;
+
+/// Slow-path action code (TPKT version byte)
+pub const FASTPATH_ACTION_X224: u8 = 0x03;
+
+/// Largest length representable by the fast-path length field
+pub const FASTPATH_MAX_LENGTH: usize = 0x7FFF;
+
+/// Default maximum frame size accepted by `FrameReader`
+pub const DEFAULT_MAX_FRAME_SIZE: usize = u16::MAX as usize;
+
+/// Raw fast-path frame
+///
+/// Holds the header byte (fpInputHeader / fpOutputHeader) and everything
+/// following the length field.
+///
+/// ```text
+/// +--------+-----------+------------------+
+/// | header | length    | data             |
+/// | 1 byte | 1-2 bytes | length - header  |
+/// +--------+-----------+------------------+
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FastPathFrame {
+    /// Header byte (action, numEvents/reserved, flags)
+    pub header: u8,
+    /// Frame data after the length field
+    pub data: Vec<u8>,
+}
+
+impl FastPathFrame {
+    /// Create a fast-path frame
+    pub fn new(header: u8, data: Vec<u8>) -> Self {
+        Self { header, data }
+    }
+
+    /// Action code (bits 0-1)
+    pub fn action(&self) -> u8 {
+        self.header & 0x03
+    }
+
+    /// numEvents (input) or reserved (output) field (bits 2-5)
+    pub fn num_events(&self) -> u8 {
+        (self.header >> 2) & 0x0F
+    }
+
+    /// Encryption flags (bits 6-7)
+    pub fn flags(&self) -> u8 {
+        self.header >> 6
+    }
+
+    /// Encoded size of a frame carrying `data_len` bytes of data
+    pub fn encoded_size(data_len: usize) -> usize {
+        if data_len + 2 <= 0x7F {
+            data_len + 2
+        } else {
+            data_len + 3
+        }
+    }
+}
+
+impl Pdu for FastPathFrame {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        let length = self.size();
+        if length > FASTPATH_MAX_LENGTH {
+            return Err(PduError::FrameTooLarge {
+                size: length,
+                max: FASTPATH_MAX_LENGTH,
+            });
+        }
+
+        buffer.write_u8(self.header)?;
+        if length == self.data.len() + 2 {
+            buffer.write_u8(length as u8)?;
+        } else {
+            buffer.write_u8(0x80 | (length >> 8) as u8)?;
+            buffer.write_u8(length as u8)?;
+        }
+        buffer.write_all(&self.data)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let header = buffer.read_u8()?;
+        if header & 0x03 != FASTPATH_ACTION_FASTPATH {
+            return Err(PduError::InvalidHeader(format!(
+                "Not a fast-path action: {:#x}",
+                header & 0x03
+            )));
+        }
+
+        let first = buffer.read_u8()?;
+        let (length, header_size) = if first & 0x80 != 0 {
+            let second = buffer.read_u8()?;
+            ((((first & 0x7F) as usize) << 8) | second as usize, 3)
+        } else {
+            (first as usize, 2)
+        };
+        if length < header_size {
+            return Err(PduError::InvalidLength {
+                expected: header_size,
+                actual: length,
+            });
+        }
+
+        let mut data = vec![0u8; length - header_size];
+        buffer.read_exact(&mut data)?;
+
+        Ok(Self { header, data })
+    }
+
+    fn size(&self) -> usize {
+        Self::encoded_size(self.data.len())
+    }
+}
+
+/// Frame read from an RDP transport
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Frame {
+    /// TPKT-framed X.224 traffic
+    SlowPath(TpktPacket),
+    /// Fast-path input or output
+    FastPath(FastPathFrame),
+}
+
+impl Frame {
+    /// Total encoded size of the frame
+    pub fn size(&self) -> usize {
+        match self {
+            Frame::SlowPath(packet) => packet.size(),
+            Frame::FastPath(frame) => frame.size(),
+        }
+    }
+}
+
+/// Result of inspecting the start of a buffer
+enum FrameLength {
+    /// Full frame length is known
+    Known(usize),
+    /// At least this many bytes are needed to learn the length
+    Partial(usize),
+}
+
+/// Determine the length of the frame at the start of `buffer`
+fn frame_length(buffer: &[u8]) -> Result<FrameLength> {
+    let Some(&first) = buffer.first() else {
+        return Ok(FrameLength::Partial(1));
+    };
+
+    match first & 0x03 {
+        FASTPATH_ACTION_X224 => {
+            if buffer.len() < TPKT_HEADER_SIZE {
+                return Ok(FrameLength::Partial(TPKT_HEADER_SIZE));
+            }
+            let header = TpktHeader::decode(&mut &buffer[..TPKT_HEADER_SIZE])?;
+            Ok(FrameLength::Known(header.length as usize))
+        }
+        FASTPATH_ACTION_FASTPATH => {
+            let Some(&length1) = buffer.get(1) else {
+                return Ok(FrameLength::Partial(2));
+            };
+            let (length, header_size) = if length1 & 0x80 != 0 {
+                let Some(&length2) = buffer.get(2) else {
+                    return Ok(FrameLength::Partial(3));
+                };
+                ((((length1 & 0x7F) as usize) << 8) | length2 as usize, 3)
+            } else {
+                (length1 as usize, 2)
+            };
+            if length < header_size {
+                return Err(PduError::InvalidLength {
+                    expected: header_size,
+                    actual: length,
+                });
+            }
+            Ok(FrameLength::Known(length))
+        }
+        action => Err(PduError::InvalidHeader(format!(
+            "Unknown frame action: {action:#x} (first byte {first:#04x})"
+        ))),
+    }
+}
+
+/// Incremental frame reader
+///
+/// Accumulates bytes from a transport and splits them into slow-path
+/// (TPKT) and fast-path frames, which share a stream and are told apart
+/// by the action bits of the first byte. Frames larger than the
+/// configured maximum are rejected as soon as their length is known.
+#[derive(Debug, Clone)]
+pub struct FrameReader {
+    buffer: BytesMut,
+    max_frame_size: usize,
+}
+
+impl FrameReader {
+    /// Create a reader with the default maximum frame size
+    pub fn new() -> Self {
+        Self {
+            buffer: BytesMut::new(),
+            max_frame_size: DEFAULT_MAX_FRAME_SIZE,
+        }
+    }
+
+    /// Set the maximum accepted frame size
+    pub fn with_max_frame_size(mut self, max_frame_size: usize) -> Self {
+        self.max_frame_size = max_frame_size;
+        self
+    }
+
+    /// Maximum accepted frame size
+    pub fn max_frame_size(&self) -> usize {
+        self.max_frame_size
+    }
+
+    /// Number of buffered bytes not yet returned as a frame
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Append bytes received from the transport
+    pub fn extend(&mut self, data: &[u8]) {
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Number of additional bytes required before the next frame is
+    /// available (0 when a complete frame is buffered)
+    ///
+    /// While the frame header is incomplete this is the amount needed to
+    /// read the length field, so callers may have to ask again.
+    pub fn bytes_needed(&self) -> Result<usize> {
+        let needed = match self.checked_frame_length()? {
+            FrameLength::Known(length) | FrameLength::Partial(length) => length,
+        };
+        Ok(needed.saturating_sub(self.buffer.len()))
+    }
+
+    /// Take the next complete frame from the buffer, if any
+    pub fn next_frame(&mut self) -> Result<Option<Frame>> {
+        let FrameLength::Known(length) = self.checked_frame_length()? else {
+            return Ok(None);
+        };
+        if self.buffer.len() < length {
+            return Ok(None);
+        }
+
+        let bytes = self.buffer.split_to(length).freeze();
+        let frame = if bytes[0] & 0x03 == FASTPATH_ACTION_X224 {
+            Frame::SlowPath(TpktPacket::decode(&mut bytes.reader())?)
+        } else {
+            Frame::FastPath(FastPathFrame::decode(&mut bytes.reader())?)
+        };
+        Ok(Some(frame))
+    }
+
+    /// Read from a blocking stream until a complete frame is available
+    ///
+    /// Only the bytes belonging to the frame are read, so the stream is
+    /// left positioned at the start of the next frame.
+    pub fn read_frame(&mut self, stream: &mut dyn Read) -> Result<Frame> {
+        loop {
+            if let Some(frame) = self.next_frame()? {
+                return Ok(frame);
+            }
+
+            let needed = self.bytes_needed()?;
+            let start = self.buffer.len();
+            self.buffer.resize(start + needed, 0);
+            if let Err(err) = stream.read_exact(&mut self.buffer[start..]) {
+                self.buffer.truncate(start);
+                return Err(err.into());
+            }
+        }
+    }
+
+    /// Frame length at the start of the buffer, checked against the limit
+    fn checked_frame_length(&self) -> Result<FrameLength> {
+        let length = frame_length(&self.buffer)?;
+        if let FrameLength::Known(size) = length
+            && size > self.max_frame_size
+        {
+            return Err(PduError::FrameTooLarge {
+                size,
+                max: self.max_frame_size,
+            });
+        }
+        Ok(length)
+    }
+}
+
+impl Default for FrameReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tpkt_bytes(payload: &[u8]) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        TpktPacket::new(payload.to_vec())
+            .encode(&mut buffer)
+            .unwrap();
+        buffer
+    }
+
+    #[test]
+    fn test_fastpath_frame_length_forms() {
+        let short = FastPathFrame::new(0x04, vec![0xAA; 3]);
+        let mut buffer = Vec::new();
+        short.encode(&mut buffer).unwrap();
+        assert_eq!(buffer, vec![0x04, 0x05, 0xAA, 0xAA, 0xAA]);
+        assert_eq!(short.num_events(), 1);
+
+        let long = FastPathFrame::new(0x00, vec![0x55; 0x200]);
+        let mut buffer = Vec::new();
+        long.encode(&mut buffer).unwrap();
+        assert_eq!(&buffer[..3], &[0x00, 0x82, 0x03]);
+        assert_eq!(buffer.len(), long.size());
+
+        let decoded = FastPathFrame::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded, long);
+    }
+
+    #[test]
+    fn test_frame_reader_partial_input() {
+        let slow = tpkt_bytes(&[0x02, 0xF0, 0x80, 0x01, 0x02]);
+        let fast = [0x00, 0x83, 0x04];
+        let stream = [slow.clone(), fast.to_vec(), vec![0; 0x301]].concat();
+
+        let mut reader = FrameReader::new();
+        assert_eq!(reader.bytes_needed().unwrap(), 1);
+
+        reader.extend(&stream[..2]);
+        assert_eq!(reader.bytes_needed().unwrap(), 2);
+        assert!(reader.next_frame().unwrap().is_none());
+
+        reader.extend(&stream[2..6]);
+        assert_eq!(reader.bytes_needed().unwrap(), slow.len() - 6);
+
+        reader.extend(&stream[6..slow.len() + 2]);
+        let Some(Frame::SlowPath(packet)) = reader.next_frame().unwrap() else {
+            panic!("expected slow-path frame");
+        };
+        assert_eq!(packet.payload(), &slow[4..]);
+
+        // Two-byte fast-path length needs the third byte
+        assert_eq!(reader.bytes_needed().unwrap(), 1);
+        reader.extend(&stream[slow.len() + 2..]);
+        assert_eq!(reader.bytes_needed().unwrap(), 0);
+        let Some(Frame::FastPath(frame)) = reader.next_frame().unwrap() else {
+            panic!("expected fast-path frame");
+        };
+        assert_eq!(frame.data.len(), 0x301);
+        assert_eq!(reader.buffered(), 0);
+    }
+
+    #[test]
+    fn test_frame_reader_max_frame_size() {
+        let mut reader = FrameReader::new().with_max_frame_size(64);
+        reader.extend(&[0x03, 0x00, 0x01, 0x00]);
+
+        assert!(matches!(
+            reader.next_frame(),
+            Err(PduError::FrameTooLarge {
+                size: 0x100,
+                max: 64
+            })
+        ));
+    }
+
+    #[test]
+    fn test_frame_reader_invalid_action() {
+        let mut reader = FrameReader::new();
+        reader.extend(&[0x01, 0x02]);
+
+        assert!(matches!(
+            reader.next_frame(),
+            Err(PduError::InvalidHeader(_))
+        ));
+    }
+
+    #[test]
+    fn test_frame_reader_read_frame() {
+        let stream = [
+            vec![0x00, 0x04, 0xAB, 0xCD],
+            tpkt_bytes(&[0x02, 0xF0, 0x80]),
+        ]
+        .concat();
+        let mut cursor = stream.as_slice();
+        let mut reader = FrameReader::new();
+
+        let first = reader.read_frame(&mut cursor).unwrap();
+        assert_eq!(
+            first,
+            Frame::FastPath(FastPathFrame::new(0x00, vec![0xAB, 0xCD]))
+        );
+        assert_eq!(cursor.len(), 7);
+
+        let second = reader.read_frame(&mut cursor).unwrap();
+        assert_eq!(second.size(), 7);
+        assert!(cursor.is_empty());
+
+        assert!(matches!(
+            reader.read_frame(&mut cursor),
+            Err(PduError::IoError(_))
+        ));
+    }
+}
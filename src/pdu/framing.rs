@@ -0,0 +1,155 @@
+//! Slow-path framing helpers shared by the client connector and the server acceptor
+
+use crate::pdu::licensing::{LicenseErrorMessage, LicenseMessage, LicensePdu};
+use crate::pdu::mcs::DisconnectProviderUltimatum;
+use crate::pdu::mcs::domain::MCS_DISCONNECT_PROVIDER_ULTIMATUM;
+use crate::pdu::rdp::{DataPduType, ShareDataHeader};
+use crate::pdu::security::{BasicSecurityHeader, SecurityFlags};
+use crate::pdu::tpkt::TpktPacket;
+use crate::pdu::x224::disconnect::X224_DR_TYPE;
+use crate::pdu::x224::{DataPdu, DisconnectRequest};
+use crate::pdu::{Pdu, PduError, Result};
+use byteorder::{LittleEndian, WriteBytesExt};
+use std::io::Write;
+
+/// X.224 TPDU carried in a TPKT frame
+pub(crate) enum X224Frame {
+    /// Data TPDU payload
+    Data(Vec<u8>),
+    /// Disconnect Request TPDU
+    Disconnect(DisconnectRequest),
+}
+
+/// Encode a PDU into a byte vector
+pub(crate) fn encode_pdu(pdu: &impl Pdu) -> Result<Vec<u8>> {
+    let mut buffer = Vec::with_capacity(pdu.size());
+    pdu.encode(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// Wrap an X.224 TPDU in a TPKT frame
+pub(crate) fn encode_tpkt(x224: &impl Pdu) -> Result<Vec<u8>> {
+    encode_pdu(&TpktPacket::new(encode_pdu(x224)?))
+}
+
+/// Wrap an MCS PDU in X.224 Data and TPKT
+pub(crate) fn encode_x224_data(mcs: &impl Pdu) -> Result<Vec<u8>> {
+    encode_tpkt(&DataPdu::new(encode_pdu(mcs)?))
+}
+
+/// Unwrap the X.224 TPDU of a TPKT frame
+pub(crate) fn decode_tpkt(frame: &[u8]) -> Result<Vec<u8>> {
+    Ok(TpktPacket::decode(&mut &frame[..])?.into_payload())
+}
+
+/// Unwrap a TPKT frame holding X.224 Data or Disconnect Request
+pub(crate) fn decode_x224_data(frame: &[u8]) -> Result<X224Frame> {
+    let tpdu = decode_tpkt(frame)?;
+
+    if let Some(request) = disconnect_request(&tpdu)? {
+        return Ok(X224Frame::Disconnect(request));
+    }
+
+    Ok(X224Frame::Data(
+        DataPdu::decode(&mut tpdu.as_slice())?.into_payload(),
+    ))
+}
+
+/// Decode an X.224 Disconnect Request if the TPDU is one
+pub(crate) fn disconnect_request(tpdu: &[u8]) -> Result<Option<DisconnectRequest>> {
+    match tpdu.get(1) {
+        Some(&code) if code == X224_DR_TYPE => DisconnectRequest::decode(&mut &tpdu[..]).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// DomainMCSPDU choice of an MCS PDU
+pub(crate) fn mcs_choice(mcs: &[u8]) -> Option<u8> {
+    mcs.first().map(|b| b >> 2)
+}
+
+/// Decode a Disconnect Provider Ultimatum if the MCS PDU is one
+pub(crate) fn disconnect_ultimatum(mcs: &[u8]) -> Result<Option<DisconnectProviderUltimatum>> {
+    if mcs_choice(mcs) != Some(MCS_DISCONNECT_PROVIDER_ULTIMATUM) {
+        return Ok(None);
+    }
+    DisconnectProviderUltimatum::decode(&mut &mcs[..]).map(Some)
+}
+
+/// Encode Share Data Header + body
+pub(crate) fn encode_share_data(
+    share_id: u32,
+    pdu_type2: DataPduType,
+    body: &impl Pdu,
+) -> Result<Vec<u8>> {
+    let mut buffer = Vec::with_capacity(ShareDataHeader::SIZE + body.size());
+    encode_share_data_header(
+        &mut buffer,
+        share_id,
+        ShareDataHeader::STREAM_LOW,
+        pdu_type2.as_u8(),
+        body.size(),
+    )?;
+    body.encode(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// Encode an uncompressed Share Data Header for a body of `body_size` bytes
+///
+/// pduType2 is written raw so unknown types survive.
+pub(crate) fn encode_share_data_header(
+    buffer: &mut dyn Write,
+    share_id: u32,
+    stream_id: u8,
+    pdu_type2: u8,
+    body_size: usize,
+) -> Result<()> {
+    // uncompressedLength counts from pduType2 to the end of the body
+    let uncompressed_length = 4 + body_size;
+    if uncompressed_length > u16::MAX as usize {
+        return Err(PduError::InvalidLength {
+            expected: u16::MAX as usize,
+            actual: uncompressed_length,
+        });
+    }
+
+    buffer.write_u32::<LittleEndian>(share_id)?;
+    buffer.write_u8(0)?; // pad1
+    buffer.write_u8(stream_id)?;
+    buffer.write_u16::<LittleEndian>(uncompressed_length as u16)?;
+    buffer.write_u8(pdu_type2)?;
+    buffer.write_u8(0)?; // compressedType
+    buffer.write_u16::<LittleEndian>(0)?; // compressedLength
+    Ok(())
+}
+
+/// Split Share Data Header and body
+pub(crate) fn decode_share_data(payload: &[u8]) -> Result<(ShareDataHeader, &[u8])> {
+    let mut cursor = payload;
+    let header = ShareDataHeader::decode(&mut cursor)?;
+    Ok((header, cursor))
+}
+
+/// Prefix a body with a basic security header (TS_SECURITY_HEADER)
+pub(crate) fn encode_basic_security(flags: SecurityFlags, body: &[u8]) -> Result<Vec<u8>> {
+    let mut buffer = Vec::with_capacity(BasicSecurityHeader::SIZE + body.len());
+    BasicSecurityHeader::new(flags).encode(&mut buffer)?;
+    buffer.extend_from_slice(body);
+    Ok(buffer)
+}
+
+/// Split basic security header and body
+pub(crate) fn decode_basic_security(data: &[u8]) -> Result<(BasicSecurityHeader, &[u8])> {
+    let mut cursor = data;
+    let header = BasicSecurityHeader::decode(&mut cursor)?;
+    Ok((header, cursor))
+}
+
+/// Encode License Error Message (MS-RDPBCGR 2.2.1.12.1.3)
+///
+/// Servers send STATUS_VALID_CLIENT instead of a full licensing exchange
+/// when no license is required.
+pub(crate) fn encode_license_error(message: &LicenseErrorMessage) -> Result<Vec<u8>> {
+    let pdu = LicensePdu::new(LicenseMessage::ErrorAlert(message.clone()));
+    encode_basic_security(SecurityFlags::LICENSE_PKT, &encode_pdu(&pdu)?)
+}
@@ -0,0 +1,1175 @@
+use crate::pdu::{Pdu, PduError, Result};
+use bitflags::bitflags;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Read, Write};
+
+use super::header::{
+    DataBlockType, OptionalFieldReader, OptionalFieldWriter, RawDataBlock, UserDataHeader,
+    optional_field_count, peek_block_type, read_block, write_block_header,
+};
+use super::monitor::{ClientMonitorData, ClientMonitorExData, MonitorDef};
+
+/// RDP 4.0 clients
+pub const RDP_VERSION_4: u32 = 0x0008_0001;
+/// RDP 5.0 - 8.1 clients
+pub const RDP_VERSION_5_PLUS: u32 = 0x0008_0004;
+/// RDP 10.0 clients
+pub const RDP_VERSION_10_0: u32 = 0x0008_0005;
+/// RDP 10.12 clients
+pub const RDP_VERSION_10_12: u32 = 0x0008_0011;
+
+/// RNS_UD_COLOR_4BPP
+pub const RNS_UD_COLOR_4BPP: u16 = 0xCA00;
+/// RNS_UD_COLOR_8BPP
+pub const RNS_UD_COLOR_8BPP: u16 = 0xCA01;
+/// RNS_UD_COLOR_16BPP_555
+pub const RNS_UD_COLOR_16BPP_555: u16 = 0xCA02;
+/// RNS_UD_COLOR_16BPP_565
+pub const RNS_UD_COLOR_16BPP_565: u16 = 0xCA03;
+/// RNS_UD_COLOR_24BPP
+pub const RNS_UD_COLOR_24BPP: u16 = 0xCA04;
+
+/// RNS_UD_SAS_DEL - Secure Access Sequence (Ctrl+Alt+Del)
+pub const RNS_UD_SAS_DEL: u16 = 0xAA03;
+
+/// Maximum number of static virtual channels in Client Network Data
+pub const MAX_STATIC_CHANNELS: usize = 31;
+
+/// Maximum channel name length (excluding null terminator)
+pub const CHANNEL_NAME_MAX_LENGTH: usize = 7;
+
+bitflags! {
+    /// Supported Color Depths (TS_UD_CS_CORE supportedColorDepths)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct SupportedColorDepths: u16 {
+        /// RNS_UD_24BPP_SUPPORT
+        const BPP_24 = 0x0001;
+        /// RNS_UD_16BPP_SUPPORT
+        const BPP_16 = 0x0002;
+        /// RNS_UD_15BPP_SUPPORT
+        const BPP_15 = 0x0004;
+        /// RNS_UD_32BPP_SUPPORT
+        const BPP_32 = 0x0008;
+    }
+}
+
+bitflags! {
+    /// Early Capability Flags (TS_UD_CS_CORE earlyCapabilityFlags)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct EarlyCapabilityFlags: u16 {
+        /// RNS_UD_CS_SUPPORT_ERRINFO_PDU
+        const SUPPORT_ERRINFO_PDU = 0x0001;
+        /// RNS_UD_CS_WANT_32BPP_SESSION
+        const WANT_32BPP_SESSION = 0x0002;
+        /// RNS_UD_CS_SUPPORT_STATUSINFO_PDU
+        const SUPPORT_STATUSINFO_PDU = 0x0004;
+        /// RNS_UD_CS_STRONG_ASYMMETRIC_KEYS
+        const STRONG_ASYMMETRIC_KEYS = 0x0008;
+        /// RNS_UD_CS_RELATIVE_MOUSE_INPUT
+        const RELATIVE_MOUSE_INPUT = 0x0010;
+        /// RNS_UD_CS_VALID_CONNECTION_TYPE
+        const VALID_CONNECTION_TYPE = 0x0020;
+        /// RNS_UD_CS_SUPPORT_MONITOR_LAYOUT_PDU
+        const SUPPORT_MONITOR_LAYOUT_PDU = 0x0040;
+        /// RNS_UD_CS_SUPPORT_NETCHAR_AUTODETECT
+        const SUPPORT_NETCHAR_AUTODETECT = 0x0080;
+        /// RNS_UD_CS_SUPPORT_DYNVC_GFX_PROTOCOL
+        const SUPPORT_DYNVC_GFX_PROTOCOL = 0x0100;
+        /// RNS_UD_CS_SUPPORT_DYNAMIC_TIME_ZONE
+        const SUPPORT_DYNAMIC_TIME_ZONE = 0x0200;
+        /// RNS_UD_CS_SUPPORT_HEARTBEAT_PDU
+        const SUPPORT_HEARTBEAT_PDU = 0x0400;
+        /// RNS_UD_CS_SUPPORT_SKIP_CHANNELJOIN
+        const SUPPORT_SKIP_CHANNELJOIN = 0x0800;
+    }
+}
+
+bitflags! {
+    /// Encryption Methods (TS_UD_CS_SEC / TS_UD_SC_SEC1)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct EncryptionMethods: u32 {
+        /// ENCRYPTION_METHOD_40BIT
+        const BIT_40 = 0x0000_0001;
+        /// ENCRYPTION_METHOD_128BIT
+        const BIT_128 = 0x0000_0002;
+        /// ENCRYPTION_METHOD_56BIT
+        const BIT_56 = 0x0000_0008;
+        /// ENCRYPTION_METHOD_FIPS
+        const FIPS = 0x0000_0010;
+    }
+}
+
+bitflags! {
+    /// Channel Definition Options (CHANNEL_DEF options)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ChannelOptions: u32 {
+        /// CHANNEL_OPTION_INITIALIZED
+        const INITIALIZED = 0x8000_0000;
+        /// CHANNEL_OPTION_ENCRYPT_RDP
+        const ENCRYPT_RDP = 0x4000_0000;
+        /// CHANNEL_OPTION_ENCRYPT_SC
+        const ENCRYPT_SC = 0x2000_0000;
+        /// CHANNEL_OPTION_ENCRYPT_CS
+        const ENCRYPT_CS = 0x1000_0000;
+        /// CHANNEL_OPTION_PRI_HIGH
+        const PRI_HIGH = 0x0800_0000;
+        /// CHANNEL_OPTION_PRI_MED
+        const PRI_MED = 0x0400_0000;
+        /// CHANNEL_OPTION_PRI_LOW
+        const PRI_LOW = 0x0200_0000;
+        /// CHANNEL_OPTION_COMPRESS_RDP
+        const COMPRESS_RDP = 0x0080_0000;
+        /// CHANNEL_OPTION_COMPRESS
+        const COMPRESS = 0x0040_0000;
+        /// CHANNEL_OPTION_SHOW_PROTOCOL
+        const SHOW_PROTOCOL = 0x0020_0000;
+        /// REMOTE_CONTROL_PERSISTENT
+        const REMOTE_CONTROL_PERSISTENT = 0x0010_0000;
+    }
+}
+
+bitflags! {
+    /// Client Cluster Data flags (TS_UD_CS_CLUSTER)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ClusterFlags: u32 {
+        /// REDIRECTION_SUPPORTED
+        const REDIRECTION_SUPPORTED = 0x0000_0001;
+        /// REDIRECTED_SESSIONID_FIELD_VALID
+        const REDIRECTED_SESSIONID_FIELD_VALID = 0x0000_0002;
+        /// ServerSessionRedirectionVersionMask
+        const REDIRECTION_VERSION_MASK = 0x0000_003C;
+        /// REDIRECTED_SMARTCARD
+        const REDIRECTED_SMARTCARD = 0x0000_0040;
+    }
+}
+
+/// Client Core Data optional trailing fields
+///
+/// Each field may be absent when a peer sends a shorter block. Fields are
+/// encoded in order up to the last present one; missing fields before it are
+/// written as zero.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientCoreOptionalData {
+    pub post_beta2_color_depth: Option<u16>,
+    pub client_product_id: Option<u16>,
+    pub serial_number: Option<u32>,
+    /// Requested color depth (4, 8, 15, 16, 24)
+    pub high_color_depth: Option<u16>,
+    pub supported_color_depths: Option<SupportedColorDepths>,
+    pub early_capability_flags: Option<EarlyCapabilityFlags>,
+    /// Client digital product ID, raw null-terminated UTF-16LE field
+    ///
+    /// Kept as received so bytes after the terminator survive re-encoding;
+    /// see `dig_product_id` and `set_dig_product_id`.
+    pub client_dig_product_id: Option<[u8; 64]>,
+    /// Connection type hint (valid with VALID_CONNECTION_TYPE)
+    pub connection_type: Option<u8>,
+    pub pad1_octet: Option<u8>,
+    /// Protocol selected by the server in the X.224 Connection Confirm
+    pub server_selected_protocol: Option<u32>,
+    /// Physical width in millimeters
+    pub desktop_physical_width: Option<u32>,
+    /// Physical height in millimeters
+    pub desktop_physical_height: Option<u32>,
+    /// Orientation (0, 90, 180, 270)
+    pub desktop_orientation: Option<u16>,
+    /// Desktop scale factor (100 - 500 percent)
+    pub desktop_scale_factor: Option<u32>,
+    /// Device scale factor (100, 140, 180 percent)
+    pub device_scale_factor: Option<u32>,
+}
+
+impl ClientCoreOptionalData {
+    /// Size of each optional field in wire order
+    const FIELD_SIZES: [usize; 15] = [2, 2, 4, 2, 2, 2, 64, 1, 1, 4, 4, 4, 2, 4, 4];
+
+    fn present(&self) -> [bool; 15] {
+        [
+            self.post_beta2_color_depth.is_some(),
+            self.client_product_id.is_some(),
+            self.serial_number.is_some(),
+            self.high_color_depth.is_some(),
+            self.supported_color_depths.is_some(),
+            self.early_capability_flags.is_some(),
+            self.client_dig_product_id.is_some(),
+            self.connection_type.is_some(),
+            self.pad1_octet.is_some(),
+            self.server_selected_protocol.is_some(),
+            self.desktop_physical_width.is_some(),
+            self.desktop_physical_height.is_some(),
+            self.desktop_orientation.is_some(),
+            self.desktop_scale_factor.is_some(),
+            self.device_scale_factor.is_some(),
+        ]
+    }
+
+    /// Client digital product ID as text (up to the first null)
+    pub fn dig_product_id(&self) -> Option<String> {
+        self.client_dig_product_id
+            .as_ref()
+            .map(|field| decode_fixed_unicode(field))
+    }
+
+    /// Set client digital product ID (up to 31 characters)
+    pub fn set_dig_product_id(&mut self, dig_product_id: &str) -> Result<()> {
+        let mut field = [0u8; 64];
+        field.copy_from_slice(&encode_fixed_unicode(dig_product_id, 64)?);
+        self.client_dig_product_id = Some(field);
+        Ok(())
+    }
+
+    /// Encoded size of the optional fields
+    pub fn size(&self) -> usize {
+        let count = optional_field_count(&self.present());
+        Self::FIELD_SIZES[..count].iter().sum()
+    }
+
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        let mut writer = OptionalFieldWriter::new(buffer, &self.present());
+        writer.write_u16(self.post_beta2_color_depth)?;
+        writer.write_u16(self.client_product_id)?;
+        writer.write_u32(self.serial_number)?;
+        writer.write_u16(self.high_color_depth)?;
+        writer.write_u16(self.supported_color_depths.map(|f| f.bits()))?;
+        writer.write_u16(self.early_capability_flags.map(|f| f.bits()))?;
+        writer.write_bytes(self.client_dig_product_id.as_ref().map(|f| &f[..]), 64)?;
+        writer.write_u8(self.connection_type)?;
+        writer.write_u8(self.pad1_octet)?;
+        writer.write_u32(self.server_selected_protocol)?;
+        writer.write_u32(self.desktop_physical_width)?;
+        writer.write_u32(self.desktop_physical_height)?;
+        writer.write_u16(self.desktop_orientation)?;
+        writer.write_u32(self.desktop_scale_factor)?;
+        writer.write_u32(self.device_scale_factor)?;
+        Ok(())
+    }
+
+    fn decode(data: &[u8]) -> Result<Self> {
+        let mut reader = OptionalFieldReader::new(data);
+
+        let optional = Self {
+            post_beta2_color_depth: reader.read_u16(),
+            client_product_id: reader.read_u16(),
+            serial_number: reader.read_u32(),
+            high_color_depth: reader.read_u16(),
+            supported_color_depths: reader
+                .read_u16()
+                .map(SupportedColorDepths::from_bits_retain),
+            early_capability_flags: reader
+                .read_u16()
+                .map(EarlyCapabilityFlags::from_bits_retain),
+            client_dig_product_id: reader
+                .read_bytes(64)
+                .and_then(|field| field.try_into().ok()),
+            connection_type: reader.read_u8(),
+            pad1_octet: reader.read_u8(),
+            server_selected_protocol: reader.read_u32(),
+            desktop_physical_width: reader.read_u32(),
+            desktop_physical_height: reader.read_u32(),
+            desktop_orientation: reader.read_u16(),
+            desktop_scale_factor: reader.read_u32(),
+            device_scale_factor: reader.read_u32(),
+        };
+
+        reader.finish()?;
+        Ok(optional)
+    }
+}
+
+/// Client Core Data (TS_UD_CS_CORE, MS-RDPBCGR 2.2.1.3.2)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientCoreData {
+    /// RDP version (RDP_VERSION_*)
+    pub version: u32,
+    /// Requested desktop width in pixels
+    pub desktop_width: u16,
+    /// Requested desktop height in pixels
+    pub desktop_height: u16,
+    /// Color depth (RNS_UD_COLOR_*, superseded by optional fields)
+    pub color_depth: u16,
+    /// Secure access sequence (RNS_UD_SAS_DEL)
+    pub sas_sequence: u16,
+    /// Keyboard layout (active input locale identifier)
+    pub keyboard_layout: u32,
+    /// Client build number
+    pub client_build: u32,
+    /// Client computer name (up to 15 characters)
+    pub client_name: String,
+    /// Keyboard type (4 = IBM enhanced 101/102-key)
+    pub keyboard_type: u32,
+    /// Keyboard subtype (OEM dependent)
+    pub keyboard_sub_type: u32,
+    /// Number of function keys
+    pub keyboard_function_key: u32,
+    /// Input Method Editor file name (up to 31 characters)
+    pub ime_file_name: String,
+    /// Optional trailing fields
+    pub optional: ClientCoreOptionalData,
+}
+
+impl ClientCoreData {
+    /// Fixed data size (excluding header and optional fields)
+    pub const FIXED_DATA_SIZE: usize = 128;
+
+    /// Create Client Core Data with typical client defaults
+    pub fn new(desktop_width: u16, desktop_height: u16) -> Self {
+        Self {
+            version: RDP_VERSION_5_PLUS,
+            desktop_width,
+            desktop_height,
+            color_depth: RNS_UD_COLOR_8BPP,
+            sas_sequence: RNS_UD_SAS_DEL,
+            keyboard_layout: 0x0000_0409, // en-US
+            client_build: 2600,
+            client_name: "pentardp".to_string(),
+            keyboard_type: 4,
+            keyboard_sub_type: 0,
+            keyboard_function_key: 12,
+            ime_file_name: String::new(),
+            optional: ClientCoreOptionalData {
+                post_beta2_color_depth: Some(RNS_UD_COLOR_8BPP),
+                client_product_id: Some(1),
+                serial_number: Some(0),
+                high_color_depth: Some(24),
+                supported_color_depths: Some(
+                    SupportedColorDepths::BPP_15
+                        | SupportedColorDepths::BPP_16
+                        | SupportedColorDepths::BPP_24
+                        | SupportedColorDepths::BPP_32,
+                ),
+                early_capability_flags: Some(EarlyCapabilityFlags::SUPPORT_ERRINFO_PDU),
+                client_dig_product_id: Some([0; 64]),
+                connection_type: Some(0),
+                pad1_octet: Some(0),
+                server_selected_protocol: Some(0),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Set client name
+    pub fn with_client_name(mut self, client_name: &str) -> Self {
+        self.client_name = client_name.to_string();
+        self
+    }
+
+    /// Set protocol selected by the server
+    pub fn with_server_selected_protocol(mut self, protocol: u32) -> Self {
+        self.optional.server_selected_protocol = Some(protocol);
+        self
+    }
+
+    fn data_size(&self) -> usize {
+        Self::FIXED_DATA_SIZE + self.optional.size()
+    }
+}
+
+impl Pdu for ClientCoreData {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        let client_name = encode_fixed_unicode(&self.client_name, 32)?;
+        let ime_file_name = encode_fixed_unicode(&self.ime_file_name, 64)?;
+
+        write_block_header(buffer, DataBlockType::ClientCore, self.data_size())?;
+
+        buffer.write_u32::<LittleEndian>(self.version)?;
+        buffer.write_u16::<LittleEndian>(self.desktop_width)?;
+        buffer.write_u16::<LittleEndian>(self.desktop_height)?;
+        buffer.write_u16::<LittleEndian>(self.color_depth)?;
+        buffer.write_u16::<LittleEndian>(self.sas_sequence)?;
+        buffer.write_u32::<LittleEndian>(self.keyboard_layout)?;
+        buffer.write_u32::<LittleEndian>(self.client_build)?;
+        buffer.write_all(&client_name)?;
+        buffer.write_u32::<LittleEndian>(self.keyboard_type)?;
+        buffer.write_u32::<LittleEndian>(self.keyboard_sub_type)?;
+        buffer.write_u32::<LittleEndian>(self.keyboard_function_key)?;
+        buffer.write_all(&ime_file_name)?;
+
+        self.optional.encode(buffer)
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let data = read_block(buffer, DataBlockType::ClientCore)?;
+        if data.len() < Self::FIXED_DATA_SIZE {
+            return Err(PduError::InvalidLength {
+                expected: Self::FIXED_DATA_SIZE,
+                actual: data.len(),
+            });
+        }
+
+        let mut cursor = &data[..];
+        let version = cursor.read_u32::<LittleEndian>()?;
+        let desktop_width = cursor.read_u16::<LittleEndian>()?;
+        let desktop_height = cursor.read_u16::<LittleEndian>()?;
+        let color_depth = cursor.read_u16::<LittleEndian>()?;
+        let sas_sequence = cursor.read_u16::<LittleEndian>()?;
+        let keyboard_layout = cursor.read_u32::<LittleEndian>()?;
+        let client_build = cursor.read_u32::<LittleEndian>()?;
+
+        let mut client_name_buf = [0u8; 32];
+        cursor.read_exact(&mut client_name_buf)?;
+
+        let keyboard_type = cursor.read_u32::<LittleEndian>()?;
+        let keyboard_sub_type = cursor.read_u32::<LittleEndian>()?;
+        let keyboard_function_key = cursor.read_u32::<LittleEndian>()?;
+
+        let mut ime_file_name_buf = [0u8; 64];
+        cursor.read_exact(&mut ime_file_name_buf)?;
+
+        let optional = ClientCoreOptionalData::decode(cursor)?;
+
+        Ok(Self {
+            version,
+            desktop_width,
+            desktop_height,
+            color_depth,
+            sas_sequence,
+            keyboard_layout,
+            client_build,
+            client_name: decode_fixed_unicode(&client_name_buf),
+            keyboard_type,
+            keyboard_sub_type,
+            keyboard_function_key,
+            ime_file_name: decode_fixed_unicode(&ime_file_name_buf),
+            optional,
+        })
+    }
+
+    fn size(&self) -> usize {
+        UserDataHeader::SIZE + self.data_size()
+    }
+}
+
+/// Client Security Data (TS_UD_CS_SEC, MS-RDPBCGR 2.2.1.3.3)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientSecurityData {
+    /// Supported encryption methods
+    pub encryption_methods: EncryptionMethods,
+    /// Encryption methods for French locale clients (only FIPS/40-bit)
+    pub ext_encryption_methods: EncryptionMethods,
+}
+
+impl ClientSecurityData {
+    /// Data size (excluding header)
+    pub const DATA_SIZE: usize = 8;
+
+    /// Create Client Security Data
+    pub fn new(encryption_methods: EncryptionMethods) -> Self {
+        Self {
+            encryption_methods,
+            ext_encryption_methods: EncryptionMethods::empty(),
+        }
+    }
+
+    /// No Standard RDP Security (TLS/CredSSP connections)
+    pub fn none() -> Self {
+        Self::new(EncryptionMethods::empty())
+    }
+}
+
+impl Pdu for ClientSecurityData {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        write_block_header(buffer, DataBlockType::ClientSecurity, Self::DATA_SIZE)?;
+        buffer.write_u32::<LittleEndian>(self.encryption_methods.bits())?;
+        buffer.write_u32::<LittleEndian>(self.ext_encryption_methods.bits())?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let data = read_block(buffer, DataBlockType::ClientSecurity)?;
+        if data.len() != Self::DATA_SIZE {
+            return Err(PduError::InvalidLength {
+                expected: Self::DATA_SIZE,
+                actual: data.len(),
+            });
+        }
+
+        let mut cursor = &data[..];
+        Ok(Self {
+            encryption_methods: EncryptionMethods::from_bits_retain(
+                cursor.read_u32::<LittleEndian>()?,
+            ),
+            ext_encryption_methods: EncryptionMethods::from_bits_retain(
+                cursor.read_u32::<LittleEndian>()?,
+            ),
+        })
+    }
+
+    fn size(&self) -> usize {
+        UserDataHeader::SIZE + Self::DATA_SIZE
+    }
+}
+
+/// Channel Definition (CHANNEL_DEF, MS-RDPBCGR 2.2.1.3.4.1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelDef {
+    /// Channel name (up to 7 ANSI characters)
+    pub name: String,
+    /// Channel options
+    pub options: ChannelOptions,
+}
+
+impl ChannelDef {
+    /// Encoded size
+    pub const SIZE: usize = 12;
+
+    /// Create new Channel Definition
+    pub fn new(name: &str, options: ChannelOptions) -> Self {
+        Self {
+            name: name.to_string(),
+            options,
+        }
+    }
+
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        if !self.name.is_ascii() || self.name.len() > CHANNEL_NAME_MAX_LENGTH {
+            return Err(PduError::ParseError(format!(
+                "Invalid channel name: {:?}",
+                self.name
+            )));
+        }
+
+        let mut name = [0u8; 8];
+        name[..self.name.len()].copy_from_slice(self.name.as_bytes());
+        buffer.write_all(&name)?;
+        buffer.write_u32::<LittleEndian>(self.options.bits())?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let mut name = [0u8; 8];
+        buffer.read_exact(&mut name)?;
+        let name_len = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+
+        let options = ChannelOptions::from_bits_retain(buffer.read_u32::<LittleEndian>()?);
+
+        Ok(Self {
+            name: String::from_utf8_lossy(&name[..name_len]).into_owned(),
+            options,
+        })
+    }
+}
+
+/// Client Network Data (TS_UD_CS_NET, MS-RDPBCGR 2.2.1.3.4)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientNetworkData {
+    /// Requested static virtual channels
+    pub channels: Vec<ChannelDef>,
+}
+
+impl ClientNetworkData {
+    /// Create Client Network Data
+    pub fn new(channels: Vec<ChannelDef>) -> Self {
+        Self { channels }
+    }
+
+    fn data_size(&self) -> usize {
+        4 + self.channels.len() * ChannelDef::SIZE
+    }
+}
+
+impl Pdu for ClientNetworkData {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        if self.channels.len() > MAX_STATIC_CHANNELS {
+            return Err(PduError::InvalidLength {
+                expected: MAX_STATIC_CHANNELS,
+                actual: self.channels.len(),
+            });
+        }
+
+        write_block_header(buffer, DataBlockType::ClientNetwork, self.data_size())?;
+        buffer.write_u32::<LittleEndian>(self.channels.len() as u32)?;
+        for channel in &self.channels {
+            channel.encode(buffer)?;
+        }
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let data = read_block(buffer, DataBlockType::ClientNetwork)?;
+
+        let mut cursor = &data[..];
+        let channel_count = cursor.read_u32::<LittleEndian>()? as usize;
+        if channel_count > MAX_STATIC_CHANNELS {
+            return Err(PduError::InvalidLength {
+                expected: MAX_STATIC_CHANNELS,
+                actual: channel_count,
+            });
+        }
+
+        let expected = 4 + channel_count * ChannelDef::SIZE;
+        if data.len() != expected {
+            return Err(PduError::InvalidLength {
+                expected,
+                actual: data.len(),
+            });
+        }
+
+        let channels = (0..channel_count)
+            .map(|_| ChannelDef::decode(&mut cursor))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { channels })
+    }
+
+    fn size(&self) -> usize {
+        UserDataHeader::SIZE + self.data_size()
+    }
+}
+
+/// Client Cluster Data (TS_UD_CS_CLUSTER, MS-RDPBCGR 2.2.1.3.5)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientClusterData {
+    /// Cluster flags (including redirection version)
+    pub flags: ClusterFlags,
+    /// Session ID to reconnect to (valid with REDIRECTED_SESSIONID_FIELD_VALID)
+    pub redirected_session_id: u32,
+}
+
+impl ClientClusterData {
+    /// Data size (excluding header)
+    pub const DATA_SIZE: usize = 8;
+
+    /// Create Client Cluster Data supporting redirection with the given version (1 - 6)
+    pub fn new(redirection_version: u8) -> Self {
+        let version_bits = ((redirection_version.saturating_sub(1) as u32) << 2)
+            & ClusterFlags::REDIRECTION_VERSION_MASK.bits();
+
+        Self {
+            flags: ClusterFlags::REDIRECTION_SUPPORTED
+                | ClusterFlags::from_bits_retain(version_bits),
+            redirected_session_id: 0,
+        }
+    }
+
+    /// Request reconnection to the given session
+    pub fn with_redirected_session(mut self, session_id: u32) -> Self {
+        self.flags |= ClusterFlags::REDIRECTED_SESSIONID_FIELD_VALID;
+        self.redirected_session_id = session_id;
+        self
+    }
+
+    /// Redirection version (1 - 16)
+    pub fn redirection_version(&self) -> u8 {
+        (((self.flags.bits() & ClusterFlags::REDIRECTION_VERSION_MASK.bits()) >> 2) + 1) as u8
+    }
+}
+
+impl Pdu for ClientClusterData {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        write_block_header(buffer, DataBlockType::ClientCluster, Self::DATA_SIZE)?;
+        buffer.write_u32::<LittleEndian>(self.flags.bits())?;
+        buffer.write_u32::<LittleEndian>(self.redirected_session_id)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let data = read_block(buffer, DataBlockType::ClientCluster)?;
+        if data.len() != Self::DATA_SIZE {
+            return Err(PduError::InvalidLength {
+                expected: Self::DATA_SIZE,
+                actual: data.len(),
+            });
+        }
+
+        let mut cursor = &data[..];
+        Ok(Self {
+            flags: ClusterFlags::from_bits_retain(cursor.read_u32::<LittleEndian>()?),
+            redirected_session_id: cursor.read_u32::<LittleEndian>()?,
+        })
+    }
+
+    fn size(&self) -> usize {
+        UserDataHeader::SIZE + Self::DATA_SIZE
+    }
+}
+
+/// Client data blocks carried in the GCC Conference Create Request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientData {
+    pub core: ClientCoreData,
+    pub security: Option<ClientSecurityData>,
+    pub network: Option<ClientNetworkData>,
+    pub cluster: Option<ClientClusterData>,
+    pub monitor: Option<ClientMonitorData>,
+    pub monitor_ex: Option<ClientMonitorExData>,
+    /// Blocks without a typed representation, kept in received order
+    pub other: Vec<RawDataBlock>,
+    /// Block types in received order (empty for locally built data)
+    ///
+    /// Encoding follows this order, then writes any remaining blocks in
+    /// mstsc order.
+    pub block_order: Vec<u16>,
+}
+
+impl ClientData {
+    /// Create client data with core, security and network blocks
+    pub fn new(core: ClientCoreData) -> Self {
+        Self {
+            core,
+            security: Some(ClientSecurityData::none()),
+            network: Some(ClientNetworkData::default()),
+            cluster: None,
+            monitor: None,
+            monitor_ex: None,
+            other: Vec::new(),
+            block_order: Vec::new(),
+        }
+    }
+
+    /// Attach a monitor layout (see MonitorLayoutBuilder) and advertise
+    /// support for the Monitor Layout PDU
+    pub fn with_monitors(mut self, monitors: Vec<MonitorDef>) -> Self {
+        let flags = self
+            .core
+            .optional
+            .early_capability_flags
+            .unwrap_or(EarlyCapabilityFlags::empty());
+        self.core.optional.early_capability_flags =
+            Some(flags | EarlyCapabilityFlags::SUPPORT_MONITOR_LAYOUT_PDU);
+        self.monitor = Some(ClientMonitorData::new(monitors));
+        self
+    }
+
+    /// Encode the typed block of the given type, if present
+    fn encode_typed(&self, block_type: DataBlockType, buffer: &mut dyn Write) -> Result<()> {
+        match block_type {
+            DataBlockType::ClientCore => self.core.encode(buffer),
+            DataBlockType::ClientCluster => encode_block(&self.cluster, buffer),
+            DataBlockType::ClientSecurity => encode_block(&self.security, buffer),
+            DataBlockType::ClientNetwork => encode_block(&self.network, buffer),
+            DataBlockType::ClientMonitor => encode_block(&self.monitor, buffer),
+            DataBlockType::ClientMonitorEx => encode_block(&self.monitor_ex, buffer),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Typed client blocks in the order used by mstsc
+const MSTSC_BLOCK_ORDER: [DataBlockType; 6] = [
+    DataBlockType::ClientCore,
+    DataBlockType::ClientCluster,
+    DataBlockType::ClientSecurity,
+    DataBlockType::ClientNetwork,
+    DataBlockType::ClientMonitor,
+    DataBlockType::ClientMonitorEx,
+];
+
+fn encode_block(block: &Option<impl Pdu>, buffer: &mut dyn Write) -> Result<()> {
+    match block {
+        Some(block) => block.encode(buffer),
+        None => Ok(()),
+    }
+}
+
+impl Pdu for ClientData {
+    /// Blocks are written in `block_order`, then typed blocks in the order
+    /// used by mstsc (core, cluster, security, network, monitor, monitor
+    /// extended), then the remaining untyped blocks
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        let mut written = Vec::with_capacity(MSTSC_BLOCK_ORDER.len());
+        let mut other = self.other.iter();
+
+        for &block_type in &self.block_order {
+            match DataBlockType::from_u16(block_type) {
+                Some(typed) if MSTSC_BLOCK_ORDER.contains(&typed) => {
+                    if !written.contains(&typed) {
+                        self.encode_typed(typed, buffer)?;
+                        written.push(typed);
+                    }
+                }
+                _ => {
+                    if let Some(block) = other.next() {
+                        block.encode(buffer)?;
+                    }
+                }
+            }
+        }
+
+        for typed in MSTSC_BLOCK_ORDER {
+            if !written.contains(&typed) {
+                self.encode_typed(typed, buffer)?;
+            }
+        }
+        for block in other {
+            block.encode(buffer)?;
+        }
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let mut data = Vec::new();
+        buffer.read_to_end(&mut data)?;
+
+        let mut core = None;
+        let mut security = None;
+        let mut network = None;
+        let mut cluster = None;
+        let mut monitor = None;
+        let mut monitor_ex = None;
+        let mut other = Vec::new();
+        let mut block_order = Vec::new();
+
+        let mut cursor = data.as_slice();
+        while !cursor.is_empty() {
+            let block_type = peek_block_type(cursor)?;
+            block_order.push(block_type);
+            match DataBlockType::from_u16(block_type) {
+                Some(DataBlockType::ClientCore) => {
+                    core = Some(ClientCoreData::decode(&mut cursor)?)
+                }
+                Some(DataBlockType::ClientSecurity) => {
+                    security = Some(ClientSecurityData::decode(&mut cursor)?)
+                }
+                Some(DataBlockType::ClientNetwork) => {
+                    network = Some(ClientNetworkData::decode(&mut cursor)?)
+                }
+                Some(DataBlockType::ClientCluster) => {
+                    cluster = Some(ClientClusterData::decode(&mut cursor)?)
+                }
+                Some(DataBlockType::ClientMonitor) => {
+                    monitor = Some(ClientMonitorData::decode(&mut cursor)?)
+                }
+                Some(DataBlockType::ClientMonitorEx) => {
+                    monitor_ex = Some(ClientMonitorExData::decode(&mut cursor)?)
+                }
+                _ => other.push(RawDataBlock::decode(&mut cursor)?),
+            }
+        }
+
+        let core = core.ok_or_else(|| {
+            PduError::ParseError("Client data is missing CS_CORE block".to_string())
+        })?;
+
+        Ok(Self {
+            core,
+            security,
+            network,
+            cluster,
+            monitor,
+            monitor_ex,
+            other,
+            block_order,
+        })
+    }
+
+    fn size(&self) -> usize {
+        self.core.size()
+            + self.security.as_ref().map_or(0, |b| b.size())
+            + self.network.as_ref().map_or(0, |b| b.size())
+            + self.cluster.as_ref().map_or(0, |b| b.size())
+            + self.monitor.as_ref().map_or(0, |b| b.size())
+            + self.monitor_ex.as_ref().map_or(0, |b| b.size())
+            + self.other.iter().map(|b| b.size()).sum::<usize>()
+    }
+}
+
+/// Encode string as null-terminated UTF-16LE in a fixed-size field
+fn encode_fixed_unicode(s: &str, size: usize) -> Result<Vec<u8>> {
+    let chars: Vec<u16> = s.encode_utf16().collect();
+    if (chars.len() + 1) * 2 > size {
+        return Err(PduError::InvalidLength {
+            expected: size / 2 - 1,
+            actual: chars.len(),
+        });
+    }
+
+    let mut field = vec![0u8; size];
+    for (i, ch) in chars.iter().enumerate() {
+        field[i * 2..i * 2 + 2].copy_from_slice(&ch.to_le_bytes());
+    }
+    Ok(field)
+}
+
+/// Decode null-terminated UTF-16LE from a fixed-size field
+fn decode_fixed_unicode(field: &[u8]) -> String {
+    let chars: Vec<u16> = field
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .take_while(|&ch| ch != 0)
+        .collect();
+
+    String::from_utf16_lossy(&chars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdu::gcc::MonitorLayoutBuilder;
+    use std::io::Cursor;
+
+    fn encode<T: Pdu>(pdu: &T) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        pdu.encode(&mut buffer).unwrap();
+        assert_eq!(pdu.size(), buffer.len());
+        buffer
+    }
+
+    #[test]
+    fn test_client_core_data_roundtrip() {
+        let core = ClientCoreData::new(1024, 768).with_client_name("WORKSTATION");
+
+        let buffer = encode(&core);
+        // header + fixed part + fields up to serverSelectedProtocol
+        assert_eq!(buffer.len(), 4 + 128 + 84);
+        assert_eq!(&buffer[..4], &[0x01, 0xC0, 0xD8, 0x00]);
+        assert_eq!(&buffer[8..12], &[0x00, 0x04, 0x00, 0x03]);
+
+        let mut cursor = Cursor::new(buffer);
+        let decoded = ClientCoreData::decode(&mut cursor).unwrap();
+        assert_eq!(decoded, core);
+        assert_eq!(decoded.client_name, "WORKSTATION");
+    }
+
+    #[test]
+    fn test_client_core_data_truncated_roundtrip() {
+        let full = encode(&ClientCoreData::new(800, 600));
+
+        // Cut the block after each optional field boundary
+        for data_len in [128, 130, 132, 136, 138, 140, 142, 206, 207, 208, 212] {
+            let mut truncated = full[..4 + data_len].to_vec();
+            truncated[2..4].copy_from_slice(&((4 + data_len) as u16).to_le_bytes());
+
+            let mut cursor = Cursor::new(truncated.clone());
+            let decoded = ClientCoreData::decode(&mut cursor).unwrap();
+            assert_eq!(encode(&decoded), truncated, "data length {}", data_len);
+        }
+    }
+
+    #[test]
+    fn test_client_core_data_captured_roundtrip() {
+        // MS-RDPBCGR 4.1.3 Client Core Data (client "ELTONS-DEV2")
+        let mut block = hex::decode(concat!(
+            "01c0d800040008000005000401ca03aa09040000ce0e000045004c0054004f00",
+            "4e0053002d004400450056003200000000000000000000000400000000000000",
+            "0c00000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "0000000001ca010000000000180007000100360039003700310032002d003700",
+            "380033002d0030003300350037003900370034002d0034003200370031003400",
+            "000000000000000000000000000000000000000000000000",
+        ))
+        .unwrap();
+        assert_eq!(block.len(), 216);
+
+        let mut cursor = Cursor::new(block.clone());
+        let decoded = ClientCoreData::decode(&mut cursor).unwrap();
+        assert_eq!(decoded.client_name, "ELTONS-DEV2");
+        assert_eq!(decoded.optional.high_color_depth, Some(24));
+        assert_eq!(
+            decoded.optional.dig_product_id().as_deref(),
+            Some("69712-783-0357974-42714")
+        );
+        assert_eq!(encode(&decoded), block);
+
+        // Bytes after the product ID terminator survive re-encoding
+        block[132 + 14 + 60] = 0xAA;
+        let mut cursor = Cursor::new(block.clone());
+        let decoded = ClientCoreData::decode(&mut cursor).unwrap();
+        assert_eq!(encode(&decoded), block);
+    }
+
+    #[test]
+    fn test_client_core_data_dig_product_id() {
+        let mut core = ClientCoreData::new(800, 600);
+        core.optional.set_dig_product_id("12345-678").unwrap();
+        assert_eq!(core.optional.dig_product_id().as_deref(), Some("12345-678"));
+        assert!(core.optional.set_dig_product_id(&"9".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn test_client_core_data_all_optional_fields() {
+        let mut core = ClientCoreData::new(3840, 2160);
+        core.version = RDP_VERSION_10_12;
+        core.optional.desktop_physical_width = Some(600);
+        core.optional.desktop_physical_height = Some(340);
+        core.optional.desktop_orientation = Some(0);
+        core.optional.desktop_scale_factor = Some(200);
+        core.optional.device_scale_factor = Some(180);
+
+        let buffer = encode(&core);
+        assert_eq!(buffer.len(), 4 + 128 + 102);
+
+        let mut cursor = Cursor::new(buffer);
+        assert_eq!(ClientCoreData::decode(&mut cursor).unwrap(), core);
+    }
+
+    #[test]
+    fn test_client_core_data_gap_filled_with_zero() {
+        let mut core = ClientCoreData::new(800, 600);
+        core.optional = ClientCoreOptionalData {
+            high_color_depth: Some(16),
+            ..Default::default()
+        };
+
+        let buffer = encode(&core);
+        assert_eq!(
+            &buffer[132..],
+            &[0x00, 0x00, 0x00, 0x00, 0, 0, 0, 0, 0x10, 0x00]
+        );
+    }
+
+    #[test]
+    fn test_client_core_data_partial_field() {
+        let full = encode(&ClientCoreData::new(800, 600));
+        let mut truncated = full[..4 + 129].to_vec();
+        truncated[2..4].copy_from_slice(&(4u16 + 129).to_le_bytes());
+
+        let mut cursor = Cursor::new(truncated);
+        assert!(ClientCoreData::decode(&mut cursor).is_err());
+    }
+
+    #[test]
+    fn test_client_core_data_name_too_long() {
+        let core = ClientCoreData::new(800, 600).with_client_name("ABCDEFGHIJKLMNOP");
+        let mut buffer = Vec::new();
+        assert!(core.encode(&mut buffer).is_err());
+    }
+
+    #[test]
+    fn test_client_security_data() {
+        let security =
+            ClientSecurityData::new(EncryptionMethods::BIT_128 | EncryptionMethods::FIPS);
+
+        let buffer = encode(&security);
+        assert_eq!(
+            buffer,
+            vec![
+                0x02, 0xC0, 0x0C, 0x00, 0x12, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+            ]
+        );
+
+        let mut cursor = Cursor::new(buffer);
+        assert_eq!(ClientSecurityData::decode(&mut cursor).unwrap(), security);
+    }
+
+    #[test]
+    fn test_client_network_data() {
+        let network = ClientNetworkData::new(vec![
+            ChannelDef::new(
+                "rdpdr",
+                ChannelOptions::INITIALIZED | ChannelOptions::COMPRESS_RDP,
+            ),
+            ChannelDef::new(
+                "cliprdr",
+                ChannelOptions::INITIALIZED
+                    | ChannelOptions::ENCRYPT_RDP
+                    | ChannelOptions::COMPRESS_RDP
+                    | ChannelOptions::SHOW_PROTOCOL,
+            ),
+        ]);
+
+        let buffer = encode(&network);
+        assert_eq!(
+            &buffer[..8],
+            &[0x03, 0xC0, 0x20, 0x00, 0x02, 0x00, 0x00, 0x00]
+        );
+        assert_eq!(&buffer[8..20], b"rdpdr\0\0\0\x00\x00\x80\x80");
+
+        let mut cursor = Cursor::new(buffer);
+        assert_eq!(ClientNetworkData::decode(&mut cursor).unwrap(), network);
+    }
+
+    #[test]
+    fn test_client_network_data_invalid_channel_name() {
+        let network = ClientNetworkData::new(vec![ChannelDef::new(
+            "toolongname",
+            ChannelOptions::INITIALIZED,
+        )]);
+
+        let mut buffer = Vec::new();
+        assert!(network.encode(&mut buffer).is_err());
+    }
+
+    #[test]
+    fn test_client_cluster_data() {
+        let cluster = ClientClusterData::new(4).with_redirected_session(7);
+        assert_eq!(cluster.redirection_version(), 4);
+
+        let buffer = encode(&cluster);
+        assert_eq!(
+            buffer,
+            vec![
+                0x04, 0xC0, 0x0C, 0x00, 0x0F, 0x00, 0x00, 0x00, 0x07, 0x00, 0x00, 0x00
+            ]
+        );
+
+        let mut cursor = Cursor::new(buffer);
+        assert_eq!(ClientClusterData::decode(&mut cursor).unwrap(), cluster);
+    }
+
+    #[test]
+    fn test_client_data_roundtrip() {
+        let mut client_data = ClientData::new(ClientCoreData::new(1280, 1024));
+        client_data.cluster = Some(ClientClusterData::new(4));
+        client_data.monitor_ex = Some(ClientMonitorExData::new(vec![Default::default()]));
+        client_data.other.push(RawDataBlock {
+            block_type: DataBlockType::ClientMessageChannel.as_u16(),
+            data: vec![0x00, 0x00, 0x00, 0x00],
+        });
+
+        let buffer = encode(&client_data);
+        let mut cursor = Cursor::new(buffer.clone());
+        let mut decoded = ClientData::decode(&mut cursor).unwrap();
+        assert_eq!(encode(&decoded), buffer);
+        assert_eq!(decoded.block_order.len(), 6);
+
+        decoded.block_order.clear();
+        assert_eq!(decoded, client_data);
+    }
+
+    #[test]
+    fn test_client_data_received_order() {
+        // Security and network before core, an untyped block in between
+        let mut buffer = encode(&ClientSecurityData::none());
+        buffer.extend(encode(&RawDataBlock {
+            block_type: DataBlockType::ClientMessageChannel.as_u16(),
+            data: vec![0x00, 0x00, 0x00, 0x00],
+        }));
+        buffer.extend(encode(&ClientNetworkData::default()));
+        buffer.extend(encode(&ClientCoreData::new(800, 600)));
+
+        let mut cursor = Cursor::new(buffer.clone());
+        let decoded = ClientData::decode(&mut cursor).unwrap();
+        assert_eq!(encode(&decoded), buffer);
+
+        // Blocks added after decoding follow the received ones
+        let mut extended = decoded.clone();
+        extended.cluster = Some(ClientClusterData::new(4));
+        let encoded = encode(&extended);
+        assert_eq!(&encoded[..buffer.len()], &buffer[..]);
+        assert_eq!(
+            &encoded[buffer.len()..],
+            &encode(&ClientClusterData::new(4))[..]
+        );
+    }
+
+    #[test]
+    fn test_client_data_missing_core() {
+        let buffer = encode(&ClientSecurityData::none());
+        let mut cursor = Cursor::new(buffer);
+        assert!(ClientData::decode(&mut cursor).is_err());
+    }
+
+    #[test]
+    fn test_client_data_with_monitors() {
+        let monitors = MonitorLayoutBuilder::new()
+            .primary(1920, 1080)
+            .secondary(1920, 0, 1920, 1080)
+            .build()
+            .unwrap();
+        let client_data = ClientData::new(ClientCoreData::new(3840, 1080)).with_monitors(monitors);
+
+        assert!(
+            client_data
+                .core
+                .optional
+                .early_capability_flags
+                .unwrap()
+                .contains(EarlyCapabilityFlags::SUPPORT_MONITOR_LAYOUT_PDU)
+        );
+
+        let buffer = encode(&client_data);
+        let mut cursor = Cursor::new(buffer);
+        let decoded = ClientData::decode(&mut cursor).unwrap();
+        assert_eq!(decoded.monitor.unwrap().monitors.len(), 2);
+    }
+}
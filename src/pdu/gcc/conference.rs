@@ -0,0 +1,427 @@
+use crate::codec::{PerReader, PerWriter, per::PER_MAX_LENGTH};
+use crate::pdu::{Pdu, PduError, Result};
+use std::io::{Read, Write};
+
+/// T.124 identifier OID (itu-t recommendation t 124 version(0) 1)
+pub const T124_02_98_OID: [u32; 6] = [0, 0, 20, 124, 0, 1];
+
+/// H.221 non-standard key used by clients ("Duca")
+pub const H221_CS_KEY: &[u8; 4] = b"Duca";
+
+/// H.221 non-standard key used by servers ("McDn")
+pub const H221_SC_KEY: &[u8; 4] = b"McDn";
+
+/// Lower bound of GCC UserID (integer16 constraint)
+pub const GCC_NODE_ID_MIN: u16 = 1001;
+
+/// Node ID sent by Microsoft RDP servers
+pub const GCC_DEFAULT_NODE_ID: u16 = 0x79F3;
+
+/// ConnectGCCPDU CHOICE: conferenceCreateRequest
+const GCC_CONFERENCE_CREATE_REQUEST: u8 = 0x00;
+
+/// ConnectGCCPDU CHOICE: conferenceCreateResponse (with extension bit)
+const GCC_CONFERENCE_CREATE_RESPONSE: u8 = 0x14;
+
+/// ConferenceCreateRequest optional field selection (userData present)
+const GCC_REQUEST_SELECTION_USER_DATA: u8 = 0x08;
+
+/// UserData key CHOICE: h221NonStandard
+const GCC_H221_NON_STANDARD: u8 = 0xC0;
+
+/// ConferenceCreateResponse result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ConferenceCreateResult {
+    Success = 0,
+    UserRejected = 1,
+    ResourcesNotAvailable = 2,
+    RejectedForSymmetryBreaking = 3,
+    LockedConferenceNotSupported = 4,
+}
+
+impl ConferenceCreateResult {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(ConferenceCreateResult::Success),
+            1 => Some(ConferenceCreateResult::UserRejected),
+            2 => Some(ConferenceCreateResult::ResourcesNotAvailable),
+            3 => Some(ConferenceCreateResult::RejectedForSymmetryBreaking),
+            4 => Some(ConferenceCreateResult::LockedConferenceNotSupported),
+            _ => None,
+        }
+    }
+}
+
+/// Size of a PER length determinant for the given length
+fn length_size(length: usize) -> usize {
+    if length < 0x80 { 1 } else { 2 }
+}
+
+/// Reject lengths that do not fit in a non-fragmented PER length determinant
+fn check_length(length: usize) -> Result<()> {
+    if length > PER_MAX_LENGTH {
+        return Err(PduError::InvalidLength {
+            expected: PER_MAX_LENGTH,
+            actual: length,
+        });
+    }
+    Ok(())
+}
+
+/// Write ConnectData header (t124Identifier + connectPDU length)
+fn write_connect_data(writer: &mut PerWriter, connect_pdu: &[u8]) -> Result<()> {
+    check_length(connect_pdu.len())?;
+
+    writer.write_choice(0); // Key: object
+    writer.write_object_identifier(&T124_02_98_OID)?;
+    writer.write_length(connect_pdu.len())?;
+    writer.write_bytes(connect_pdu);
+    Ok(())
+}
+
+/// Read ConnectData header and return connectPDU length
+fn read_connect_data(reader: &mut PerReader) -> Result<usize> {
+    let key = reader.read_choice()?;
+    if key != 0 {
+        return Err(PduError::ParseError(format!(
+            "Unsupported T.124 key choice: {}",
+            key
+        )));
+    }
+
+    let oid = reader.read_object_identifier()?;
+    if oid != T124_02_98_OID {
+        return Err(PduError::ParseError(format!(
+            "Invalid T.124 identifier: {:?}",
+            oid
+        )));
+    }
+
+    reader.read_length()
+}
+
+/// Write single h221NonStandard UserData set
+fn write_user_data(writer: &mut PerWriter, key: &[u8; 4], user_data: &[u8]) -> Result<()> {
+    check_length(user_data.len())?;
+
+    writer.write_number_of_sets(1);
+    writer.write_choice(GCC_H221_NON_STANDARD);
+    writer.write_octet_string(key, key.len())?;
+    writer.write_octet_string(user_data, 0)
+}
+
+/// Read single h221NonStandard UserData set and verify its key
+fn read_user_data(reader: &mut PerReader, key: &[u8; 4]) -> Result<Vec<u8>> {
+    let sets = reader.read_number_of_sets()?;
+    if sets != 1 {
+        return Err(PduError::ParseError(format!(
+            "Expected 1 GCC user data set, got {}",
+            sets
+        )));
+    }
+
+    let choice = reader.read_choice()?;
+    if choice != GCC_H221_NON_STANDARD {
+        return Err(PduError::ParseError(format!(
+            "Expected h221NonStandard user data, got choice {:#x}",
+            choice
+        )));
+    }
+
+    let actual_key = reader.read_octet_string(key.len())?;
+    if actual_key != key {
+        return Err(PduError::InvalidHeader(format!(
+            "Invalid H.221 key: {:?}",
+            String::from_utf8_lossy(&actual_key)
+        )));
+    }
+
+    reader.read_octet_string(0)
+}
+
+/// GCC Conference Create Request (carried in MCS Connect-Initial userData)
+///
+/// ConnectData ::= SEQUENCE {
+///     t124Identifier  Key,
+///     connectPDU      OCTET STRING   -- ConnectGCCPDU
+/// }
+///
+/// ConferenceCreateRequest ::= SEQUENCE {
+///     conferenceName  ConferenceName,
+///     ...
+///     userData        UserData OPTIONAL   -- h221NonStandard "Duca"
+/// }
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConferenceCreateRequest {
+    /// Conference name (NumericString)
+    pub conference_name: String,
+    /// Client data blocks
+    pub user_data: Vec<u8>,
+}
+
+impl ConferenceCreateRequest {
+    /// Create new Conference Create Request with conference name "1"
+    pub fn new(user_data: Vec<u8>) -> Self {
+        Self {
+            conference_name: "1".to_string(),
+            user_data,
+        }
+    }
+
+    fn connect_pdu_size(&self) -> usize {
+        let name_len = self.conference_name.len();
+        // choice + selection + conferenceName + padding
+        let header = 1 + 1 + length_size(name_len.saturating_sub(1)) + name_len.div_ceil(2) + 1;
+        // number of sets + choice + h221 key
+        let key = 1 + 1 + 1 + H221_CS_KEY.len();
+        header + key + length_size(self.user_data.len()) + self.user_data.len()
+    }
+}
+
+impl Pdu for ConferenceCreateRequest {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        let mut connect_pdu = PerWriter::new();
+        connect_pdu.write_choice(GCC_CONFERENCE_CREATE_REQUEST);
+        connect_pdu.write_selection(GCC_REQUEST_SELECTION_USER_DATA);
+        connect_pdu.write_numeric_string(&self.conference_name, 1)?;
+        connect_pdu.write_padding(1);
+        write_user_data(&mut connect_pdu, H221_CS_KEY, &self.user_data)?;
+
+        let mut writer = PerWriter::new();
+        write_connect_data(&mut writer, connect_pdu.as_bytes())?;
+
+        buffer.write_all(writer.as_bytes())?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let mut data = Vec::new();
+        buffer.read_to_end(&mut data)?;
+
+        let mut reader = PerReader::new(&data);
+        // connectPDU length is not trusted; some implementations miscount it
+        read_connect_data(&mut reader)?;
+
+        let choice = reader.read_choice()?;
+        if choice != GCC_CONFERENCE_CREATE_REQUEST {
+            return Err(PduError::InvalidPduType(choice));
+        }
+
+        let selection = reader.read_selection()?;
+        if selection & GCC_REQUEST_SELECTION_USER_DATA == 0 {
+            return Err(PduError::ParseError(
+                "Conference Create Request without user data".to_string(),
+            ));
+        }
+
+        let conference_name = reader.read_numeric_string(1)?;
+        reader.read_padding(1)?;
+
+        let user_data = read_user_data(&mut reader, H221_CS_KEY)?;
+
+        Ok(Self {
+            conference_name,
+            user_data,
+        })
+    }
+
+    fn size(&self) -> usize {
+        let connect_pdu_size = self.connect_pdu_size();
+        // choice + OID (length + 5 octets) + connectPDU length
+        1 + 1 + 5 + length_size(connect_pdu_size) + connect_pdu_size
+    }
+}
+
+/// GCC Conference Create Response (carried in MCS Connect-Response userData)
+///
+/// ConferenceCreateResponse ::= SEQUENCE {
+///     nodeID    UserID,             -- INTEGER (1001..65535)
+///     tag       INTEGER,
+///     result    ENUMERATED,
+///     userData  UserData OPTIONAL   -- h221NonStandard "McDn"
+/// }
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConferenceCreateResponse {
+    /// Node ID
+    pub node_id: u16,
+    /// Conference tag
+    pub tag: u32,
+    /// Result
+    pub result: ConferenceCreateResult,
+    /// Server data blocks
+    pub user_data: Vec<u8>,
+}
+
+impl ConferenceCreateResponse {
+    /// Create successful Conference Create Response
+    pub fn new(user_data: Vec<u8>) -> Self {
+        Self {
+            node_id: GCC_DEFAULT_NODE_ID,
+            tag: 1,
+            result: ConferenceCreateResult::Success,
+            user_data,
+        }
+    }
+
+    fn connect_pdu_size(&self) -> usize {
+        let tag_size = match self.tag {
+            0..=0xFF => 1,
+            0x100..=0xFFFF => 2,
+            _ => 4,
+        };
+        // choice + nodeID + tag (length + value) + result
+        let header = 1 + 2 + 1 + tag_size + 1;
+        // number of sets + choice + h221 key
+        let key = 1 + 1 + 1 + H221_SC_KEY.len();
+        header + key + length_size(self.user_data.len()) + self.user_data.len()
+    }
+}
+
+impl Pdu for ConferenceCreateResponse {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        let mut connect_pdu = PerWriter::new();
+        connect_pdu.write_choice(GCC_CONFERENCE_CREATE_RESPONSE);
+        connect_pdu.write_integer16(self.node_id, GCC_NODE_ID_MIN)?;
+        connect_pdu.write_integer(self.tag);
+        connect_pdu.write_enumerated(self.result as u8);
+        write_user_data(&mut connect_pdu, H221_SC_KEY, &self.user_data)?;
+
+        let mut writer = PerWriter::new();
+        write_connect_data(&mut writer, connect_pdu.as_bytes())?;
+
+        buffer.write_all(writer.as_bytes())?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let mut data = Vec::new();
+        buffer.read_to_end(&mut data)?;
+
+        let mut reader = PerReader::new(&data);
+        // connectPDU length is not trusted; some implementations miscount it
+        read_connect_data(&mut reader)?;
+
+        let choice = reader.read_choice()?;
+        if choice != GCC_CONFERENCE_CREATE_RESPONSE {
+            return Err(PduError::InvalidPduType(choice));
+        }
+
+        let node_id = reader.read_integer16(GCC_NODE_ID_MIN)?;
+        let tag = reader.read_integer()?;
+
+        let result_code = reader.read_enumerated(5)?;
+        let result = ConferenceCreateResult::from_u8(result_code).ok_or_else(|| {
+            PduError::ParseError(format!("Invalid Conference Create result: {}", result_code))
+        })?;
+
+        let user_data = read_user_data(&mut reader, H221_SC_KEY)?;
+
+        Ok(Self {
+            node_id,
+            tag,
+            result,
+            user_data,
+        })
+    }
+
+    fn size(&self) -> usize {
+        let connect_pdu_size = self.connect_pdu_size();
+        // choice + OID (length + 5 octets) + connectPDU length
+        1 + 1 + 5 + length_size(connect_pdu_size) + connect_pdu_size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_conference_create_request_bytes() {
+        let request = ConferenceCreateRequest::new(vec![0xAA, 0xBB]);
+
+        let mut buffer = Vec::new();
+        request.encode(&mut buffer).unwrap();
+
+        let expected = vec![
+            0x00, 0x05, 0x00, 0x14, 0x7C, 0x00, 0x01, // t124Identifier
+            0x0F, // connectPDU length
+            0x00, 0x08, 0x00, 0x10, 0x00, // choice, selection, conferenceName, padding
+            0x01, 0xC0, 0x00, b'D', b'u', b'c', b'a', // h221NonStandard "Duca"
+            0x02, 0xAA, 0xBB, // userData
+        ];
+        assert_eq!(buffer, expected);
+        assert_eq!(request.size(), buffer.len());
+
+        let mut cursor = Cursor::new(buffer);
+        let decoded = ConferenceCreateRequest::decode(&mut cursor).unwrap();
+        assert_eq!(request, decoded);
+    }
+
+    #[test]
+    fn test_conference_create_request_long_user_data() {
+        let request = ConferenceCreateRequest::new(vec![0x5A; 300]);
+
+        let mut buffer = Vec::new();
+        request.encode(&mut buffer).unwrap();
+
+        // connectPDU length: 14 header octets + 300 user data octets
+        assert_eq!(&buffer[7..9], &[0x81, 0x3A]);
+        assert_eq!(&buffer[21..23], &[0x81, 0x2C]);
+        assert_eq!(request.size(), buffer.len());
+
+        let mut cursor = Cursor::new(buffer);
+        let decoded = ConferenceCreateRequest::decode(&mut cursor).unwrap();
+        assert_eq!(request, decoded);
+    }
+
+    #[test]
+    fn test_conference_create_response_bytes() {
+        let response = ConferenceCreateResponse::new(vec![0x01, 0x0C]);
+
+        let mut buffer = Vec::new();
+        response.encode(&mut buffer).unwrap();
+
+        let expected = vec![
+            0x00, 0x05, 0x00, 0x14, 0x7C, 0x00, 0x01, // t124Identifier
+            0x10, // connectPDU length
+            0x14, 0x76, 0x0A, 0x01, 0x01, 0x00, // choice, nodeID, tag, result
+            0x01, 0xC0, 0x00, b'M', b'c', b'D', b'n', // h221NonStandard "McDn"
+            0x02, 0x01, 0x0C, // userData
+        ];
+        assert_eq!(buffer, expected);
+        assert_eq!(response.size(), buffer.len());
+
+        let mut cursor = Cursor::new(buffer);
+        let decoded = ConferenceCreateResponse::decode(&mut cursor).unwrap();
+        assert_eq!(response, decoded);
+    }
+
+    #[test]
+    fn test_conference_create_wrong_key() {
+        let request = ConferenceCreateRequest::new(vec![0x01]);
+
+        let mut buffer = Vec::new();
+        request.encode(&mut buffer).unwrap();
+        buffer[16..20].copy_from_slice(H221_SC_KEY);
+
+        let mut cursor = Cursor::new(buffer);
+        assert!(matches!(
+            ConferenceCreateRequest::decode(&mut cursor),
+            Err(PduError::InvalidHeader(_))
+        ));
+    }
+
+    #[test]
+    fn test_conference_create_invalid_oid() {
+        let mut buffer = Vec::new();
+        ConferenceCreateResponse::new(vec![])
+            .encode(&mut buffer)
+            .unwrap();
+        buffer[4] = 0x7D;
+
+        let mut cursor = Cursor::new(buffer);
+        assert!(ConferenceCreateResponse::decode(&mut cursor).is_err());
+    }
+}
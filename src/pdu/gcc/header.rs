@@ -0,0 +1,382 @@
+use crate::pdu::{Pdu, PduError, Result};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Read, Write};
+
+/// User Data Block Type (MS-RDPBCGR 2.2.1.3.1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u16)]
+pub enum DataBlockType {
+    /// CS_CORE - Client Core Data
+    ClientCore = 0xC001,
+    /// CS_SECURITY - Client Security Data
+    ClientSecurity = 0xC002,
+    /// CS_NET - Client Network Data
+    ClientNetwork = 0xC003,
+    /// CS_CLUSTER - Client Cluster Data
+    ClientCluster = 0xC004,
+    /// CS_MONITOR - Client Monitor Data
+    ClientMonitor = 0xC005,
+    /// CS_MCS_MSGCHANNEL - Client Message Channel Data
+    ClientMessageChannel = 0xC006,
+    /// CS_MONITOR_EX - Client Monitor Extended Data
+    ClientMonitorEx = 0xC008,
+    /// CS_MULTITRANSPORT - Client Multitransport Channel Data
+    ClientMultitransport = 0xC00A,
+    /// SC_CORE - Server Core Data
+    ServerCore = 0x0C01,
+    /// SC_SECURITY - Server Security Data
+    ServerSecurity = 0x0C02,
+    /// SC_NET - Server Network Data
+    ServerNetwork = 0x0C03,
+    /// SC_MCS_MSGCHANNEL - Server Message Channel Data
+    ServerMessageChannel = 0x0C04,
+    /// SC_MULTITRANSPORT - Server Multitransport Channel Data
+    ServerMultitransport = 0x0C08,
+}
+
+impl DataBlockType {
+    pub fn from_u16(value: u16) -> Option<Self> {
+        match value {
+            0xC001 => Some(DataBlockType::ClientCore),
+            0xC002 => Some(DataBlockType::ClientSecurity),
+            0xC003 => Some(DataBlockType::ClientNetwork),
+            0xC004 => Some(DataBlockType::ClientCluster),
+            0xC005 => Some(DataBlockType::ClientMonitor),
+            0xC006 => Some(DataBlockType::ClientMessageChannel),
+            0xC008 => Some(DataBlockType::ClientMonitorEx),
+            0xC00A => Some(DataBlockType::ClientMultitransport),
+            0x0C01 => Some(DataBlockType::ServerCore),
+            0x0C02 => Some(DataBlockType::ServerSecurity),
+            0x0C03 => Some(DataBlockType::ServerNetwork),
+            0x0C04 => Some(DataBlockType::ServerMessageChannel),
+            0x0C08 => Some(DataBlockType::ServerMultitransport),
+            _ => None,
+        }
+    }
+
+    pub fn as_u16(self) -> u16 {
+        self as u16
+    }
+}
+
+/// User Data Header (TS_UD_HEADER)
+///
+/// ```text
+/// +--------+--------+--------+--------+
+/// |      type       |     length      |
+/// +--------+--------+--------+--------+
+/// ```
+///
+/// `length` includes the header itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UserDataHeader {
+    /// Block type (raw value, see [`DataBlockType`])
+    pub block_type: u16,
+    /// Block length including header
+    pub length: u16,
+}
+
+impl UserDataHeader {
+    /// Header size
+    pub const SIZE: usize = 4;
+
+    /// Create new User Data Header
+    pub fn new(block_type: DataBlockType, length: u16) -> Self {
+        Self {
+            block_type: block_type.as_u16(),
+            length,
+        }
+    }
+
+    /// Known block type, if any
+    pub fn data_block_type(&self) -> Option<DataBlockType> {
+        DataBlockType::from_u16(self.block_type)
+    }
+
+    /// Data length (excluding header)
+    pub fn data_length(&self) -> usize {
+        (self.length as usize).saturating_sub(Self::SIZE)
+    }
+}
+
+impl Pdu for UserDataHeader {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_u16::<LittleEndian>(self.block_type)?;
+        buffer.write_u16::<LittleEndian>(self.length)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let block_type = buffer.read_u16::<LittleEndian>()?;
+        let length = buffer.read_u16::<LittleEndian>()?;
+
+        if (length as usize) < Self::SIZE {
+            return Err(PduError::InvalidLength {
+                expected: Self::SIZE,
+                actual: length as usize,
+            });
+        }
+
+        Ok(Self { block_type, length })
+    }
+
+    fn size(&self) -> usize {
+        Self::SIZE
+    }
+}
+
+/// Write header for a block whose data part is `data_length` bytes
+pub(crate) fn write_block_header(
+    buffer: &mut dyn Write,
+    block_type: DataBlockType,
+    data_length: usize,
+) -> Result<()> {
+    let length = UserDataHeader::SIZE + data_length;
+    if length > u16::MAX as usize {
+        return Err(PduError::InvalidLength {
+            expected: u16::MAX as usize,
+            actual: length,
+        });
+    }
+
+    UserDataHeader::new(block_type, length as u16).encode(buffer)
+}
+
+/// Read header of the expected block type and return its data part
+pub(crate) fn read_block(buffer: &mut dyn Read, expected: DataBlockType) -> Result<Vec<u8>> {
+    let header = UserDataHeader::decode(buffer)?;
+    if header.block_type != expected.as_u16() {
+        return Err(PduError::InvalidHeader(format!(
+            "Expected data block {:?}, got {:#06x}",
+            expected, header.block_type
+        )));
+    }
+
+    let mut data = vec![0u8; header.data_length()];
+    buffer.read_exact(&mut data)?;
+    Ok(data)
+}
+
+/// User data block kept as raw bytes (unknown or unparsed block type)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawDataBlock {
+    /// Block type
+    pub block_type: u16,
+    /// Data (excluding header)
+    pub data: Vec<u8>,
+}
+
+impl Pdu for RawDataBlock {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        let length = self.size();
+        if length > u16::MAX as usize {
+            return Err(PduError::InvalidLength {
+                expected: u16::MAX as usize,
+                actual: length,
+            });
+        }
+
+        UserDataHeader {
+            block_type: self.block_type,
+            length: length as u16,
+        }
+        .encode(buffer)?;
+        buffer.write_all(&self.data)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let header = UserDataHeader::decode(buffer)?;
+
+        let mut data = vec![0u8; header.data_length()];
+        buffer.read_exact(&mut data)?;
+
+        Ok(Self {
+            block_type: header.block_type,
+            data,
+        })
+    }
+
+    fn size(&self) -> usize {
+        UserDataHeader::SIZE + self.data.len()
+    }
+}
+
+/// Return block type of the next block without consuming it
+pub(crate) fn peek_block_type(data: &[u8]) -> Result<u16> {
+    if data.len() < UserDataHeader::SIZE {
+        return Err(PduError::InsufficientData {
+            needed: UserDataHeader::SIZE,
+            available: data.len(),
+        });
+    }
+
+    Ok(u16::from_le_bytes([data[0], data[1]]))
+}
+
+/// Reader for trailing optional fields of a data block
+///
+/// Fields are read in order until the data is exhausted; once one field is
+/// missing, all following fields are treated as missing too.
+pub(crate) struct OptionalFieldReader<'a> {
+    data: &'a [u8],
+    truncated: bool,
+}
+
+impl<'a> OptionalFieldReader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            truncated: false,
+        }
+    }
+
+    fn take(&mut self, size: usize) -> Option<&'a [u8]> {
+        if self.truncated || self.data.len() < size {
+            self.truncated = true;
+            return None;
+        }
+
+        let (field, rest) = self.data.split_at(size);
+        self.data = rest;
+        Some(field)
+    }
+
+    pub(crate) fn read_u8(&mut self) -> Option<u8> {
+        self.take(1).map(|b| b[0])
+    }
+
+    pub(crate) fn read_u16(&mut self) -> Option<u16> {
+        self.take(2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    pub(crate) fn read_u32(&mut self) -> Option<u32> {
+        self.take(4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    pub(crate) fn read_bytes(&mut self, size: usize) -> Option<&'a [u8]> {
+        self.take(size)
+    }
+
+    /// Fail if a field was cut in the middle or unknown bytes follow
+    pub(crate) fn finish(self) -> Result<()> {
+        if !self.data.is_empty() {
+            return Err(PduError::ParseError(format!(
+                "{} unexpected trailing bytes in data block",
+                self.data.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Writer for trailing optional fields of a data block
+///
+/// Writes the first `count` fields; a missing field that precedes a present
+/// one is written as zero so that later fields keep their offsets.
+pub(crate) struct OptionalFieldWriter<'a> {
+    buffer: &'a mut dyn Write,
+    remaining: usize,
+}
+
+impl<'a> OptionalFieldWriter<'a> {
+    pub(crate) fn new(buffer: &'a mut dyn Write, present: &[bool]) -> Self {
+        Self {
+            buffer,
+            remaining: optional_field_count(present),
+        }
+    }
+
+    fn next(&mut self) -> bool {
+        if self.remaining == 0 {
+            return false;
+        }
+        self.remaining -= 1;
+        true
+    }
+
+    pub(crate) fn write_u8(&mut self, value: Option<u8>) -> Result<()> {
+        if self.next() {
+            self.buffer.write_u8(value.unwrap_or_default())?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn write_u16(&mut self, value: Option<u16>) -> Result<()> {
+        if self.next() {
+            self.buffer
+                .write_u16::<LittleEndian>(value.unwrap_or_default())?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn write_u32(&mut self, value: Option<u32>) -> Result<()> {
+        if self.next() {
+            self.buffer
+                .write_u32::<LittleEndian>(value.unwrap_or_default())?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn write_bytes(&mut self, value: Option<&[u8]>, size: usize) -> Result<()> {
+        if self.next() {
+            let mut field = vec![0u8; size];
+            if let Some(value) = value {
+                let len = value.len().min(size);
+                field[..len].copy_from_slice(&value[..len]);
+            }
+            self.buffer.write_all(&field)?;
+        }
+        Ok(())
+    }
+}
+
+/// Number of optional fields to emit (up to and including the last present one)
+pub(crate) fn optional_field_count(present: &[bool]) -> usize {
+    present.iter().rposition(|&p| p).map_or(0, |i| i + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_user_data_header() {
+        let header = UserDataHeader::new(DataBlockType::ClientCore, 0x00D8);
+
+        let mut buffer = Vec::new();
+        header.encode(&mut buffer).unwrap();
+        assert_eq!(buffer, vec![0x01, 0xC0, 0xD8, 0x00]);
+
+        let mut cursor = Cursor::new(buffer);
+        let decoded = UserDataHeader::decode(&mut cursor).unwrap();
+        assert_eq!(decoded.data_block_type(), Some(DataBlockType::ClientCore));
+        assert_eq!(decoded.data_length(), 0xD4);
+    }
+
+    #[test]
+    fn test_user_data_header_invalid_length() {
+        let mut cursor = Cursor::new(vec![0x01, 0xC0, 0x03, 0x00]);
+        assert!(UserDataHeader::decode(&mut cursor).is_err());
+    }
+
+    #[test]
+    fn test_raw_data_block() {
+        let data = vec![
+            0x34, 0x12, 0x05, 0x00, 0xFF, // unknown block
+            0x04, 0xC0, // next block
+        ];
+
+        assert_eq!(peek_block_type(&data).unwrap(), 0x1234);
+
+        let mut cursor = data.as_slice();
+        let block = RawDataBlock::decode(&mut cursor).unwrap();
+        assert_eq!(block.block_type, 0x1234);
+        assert_eq!(block.data, vec![0xFF]);
+        assert_eq!(block.size(), 5);
+
+        assert!(peek_block_type(cursor).is_err());
+    }
+}
@@ -0,0 +1,25 @@
+pub mod client_data;
+pub mod conference;
+pub mod header;
+pub mod monitor;
+pub mod server_data;
+
+pub use client_data::{
+    ChannelDef, ChannelOptions, ClientClusterData, ClientCoreData, ClientCoreOptionalData,
+    ClientData, ClientNetworkData, ClientSecurityData, ClusterFlags, EarlyCapabilityFlags,
+    EncryptionMethods, SupportedColorDepths,
+};
+pub use conference::{
+    ConferenceCreateRequest, ConferenceCreateResponse, ConferenceCreateResult, H221_CS_KEY,
+    H221_SC_KEY, T124_02_98_OID,
+};
+pub use header::{DataBlockType, RawDataBlock, UserDataHeader};
+pub use monitor::{
+    ClientMonitorData, ClientMonitorExData, MAX_MONITOR_COUNT, MonitorAttributes, MonitorDef,
+    MonitorFlags, MonitorLayoutBuilder,
+};
+pub use server_data::{
+    EncryptionLevel, MultitransportFlags, ServerCoreData, ServerData, ServerEarlyCapabilityFlags,
+    ServerMessageChannelData, ServerMultitransportChannelData, ServerNetworkData,
+    ServerSecurityData,
+};
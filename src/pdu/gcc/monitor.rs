@@ -0,0 +1,483 @@
+use crate::pdu::{Pdu, PduError, Result};
+use bitflags::bitflags;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Read, Write};
+
+use super::header::{DataBlockType, UserDataHeader, read_block, write_block_header};
+
+/// Maximum number of monitors in Client Monitor Data and Monitor Layout PDU
+pub const MAX_MONITOR_COUNT: usize = 16;
+
+bitflags! {
+    /// Monitor Definition flags (TS_MONITOR_DEF flags)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct MonitorFlags: u32 {
+        /// TS_MONITOR_PRIMARY
+        const PRIMARY = 0x0000_0001;
+    }
+}
+
+/// Monitor Definition (TS_MONITOR_DEF, MS-RDPBCGR 2.2.1.3.6.1)
+///
+/// Coordinates are inclusive and relative to the virtual desktop; the
+/// primary monitor's top-left corner is (0, 0).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MonitorDef {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+    pub flags: MonitorFlags,
+}
+
+impl MonitorDef {
+    /// Encoded size
+    pub const SIZE: usize = 20;
+
+    /// Create monitor from origin and size
+    pub fn new(left: i32, top: i32, width: u32, height: u32, primary: bool) -> Self {
+        Self {
+            left,
+            top,
+            right: left.saturating_add(width as i32).saturating_sub(1),
+            bottom: top.saturating_add(height as i32).saturating_sub(1),
+            flags: if primary {
+                MonitorFlags::PRIMARY
+            } else {
+                MonitorFlags::empty()
+            },
+        }
+    }
+
+    /// Whether this is the primary monitor
+    pub fn is_primary(&self) -> bool {
+        self.flags.contains(MonitorFlags::PRIMARY)
+    }
+
+    /// Width in pixels
+    pub fn width(&self) -> u32 {
+        (self.right as i64 - self.left as i64 + 1) as u32
+    }
+
+    /// Height in pixels
+    pub fn height(&self) -> u32 {
+        (self.bottom as i64 - self.top as i64 + 1) as u32
+    }
+
+    /// Whether two monitors share at least one pixel
+    pub fn overlaps(&self, other: &MonitorDef) -> bool {
+        self.left <= other.right
+            && other.left <= self.right
+            && self.top <= other.bottom
+            && other.top <= self.bottom
+    }
+
+    pub(crate) fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_i32::<LittleEndian>(self.left)?;
+        buffer.write_i32::<LittleEndian>(self.top)?;
+        buffer.write_i32::<LittleEndian>(self.right)?;
+        buffer.write_i32::<LittleEndian>(self.bottom)?;
+        buffer.write_u32::<LittleEndian>(self.flags.bits())?;
+        Ok(())
+    }
+
+    pub(crate) fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        Ok(Self {
+            left: buffer.read_i32::<LittleEndian>()?,
+            top: buffer.read_i32::<LittleEndian>()?,
+            right: buffer.read_i32::<LittleEndian>()?,
+            bottom: buffer.read_i32::<LittleEndian>()?,
+            flags: MonitorFlags::from_bits_retain(buffer.read_u32::<LittleEndian>()?),
+        })
+    }
+}
+
+/// Check monitor count against MAX_MONITOR_COUNT
+pub(crate) fn check_monitor_count(count: usize) -> Result<()> {
+    if count > MAX_MONITOR_COUNT {
+        return Err(PduError::InvalidLength {
+            expected: MAX_MONITOR_COUNT,
+            actual: count,
+        });
+    }
+    Ok(())
+}
+
+/// Validated monitor layout builder
+///
+/// Enforces the MS-RDPBCGR rules for a monitor layout:
+/// - between 1 and 16 monitors
+/// - exactly one primary monitor, with its top-left corner at (0, 0)
+/// - non-empty, non-overlapping rectangles
+#[derive(Debug, Clone, Default)]
+pub struct MonitorLayoutBuilder {
+    monitors: Vec<MonitorDef>,
+}
+
+impl MonitorLayoutBuilder {
+    /// Create empty builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add primary monitor at (0, 0)
+    pub fn primary(self, width: u32, height: u32) -> Self {
+        self.monitor(MonitorDef::new(0, 0, width, height, true))
+    }
+
+    /// Add secondary monitor at the given virtual desktop position
+    pub fn secondary(self, left: i32, top: i32, width: u32, height: u32) -> Self {
+        self.monitor(MonitorDef::new(left, top, width, height, false))
+    }
+
+    /// Add monitor definition as-is
+    pub fn monitor(mut self, monitor: MonitorDef) -> Self {
+        self.monitors.push(monitor);
+        self
+    }
+
+    /// Validate and return the monitor definitions
+    pub fn build(self) -> Result<Vec<MonitorDef>> {
+        if self.monitors.is_empty() {
+            return Err(PduError::ParseError(
+                "Monitor layout requires at least one monitor".to_string(),
+            ));
+        }
+        check_monitor_count(self.monitors.len())?;
+
+        for monitor in &self.monitors {
+            if monitor.right < monitor.left || monitor.bottom < monitor.top {
+                return Err(PduError::ParseError(format!(
+                    "Empty monitor rectangle: {:?}",
+                    monitor
+                )));
+            }
+        }
+
+        let mut primaries = self.monitors.iter().filter(|m| m.is_primary());
+        let primary = primaries.next().ok_or_else(|| {
+            PduError::ParseError("Monitor layout has no primary monitor".to_string())
+        })?;
+        if primaries.next().is_some() {
+            return Err(PduError::ParseError(
+                "Monitor layout has more than one primary monitor".to_string(),
+            ));
+        }
+        if primary.left != 0 || primary.top != 0 {
+            return Err(PduError::ParseError(format!(
+                "Primary monitor must start at (0, 0), got ({}, {})",
+                primary.left, primary.top
+            )));
+        }
+
+        for (i, a) in self.monitors.iter().enumerate() {
+            if let Some(b) = self.monitors[i + 1..].iter().find(|b| a.overlaps(b)) {
+                return Err(PduError::ParseError(format!(
+                    "Overlapping monitors: {:?} and {:?}",
+                    a, b
+                )));
+            }
+        }
+
+        Ok(self.monitors)
+    }
+}
+
+/// Client Monitor Data (TS_UD_CS_MONITOR, MS-RDPBCGR 2.2.1.3.6)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientMonitorData {
+    /// Flags (unused, must be zero)
+    pub flags: u32,
+    /// Monitor definitions
+    pub monitors: Vec<MonitorDef>,
+}
+
+impl ClientMonitorData {
+    /// Create Client Monitor Data (use MonitorLayoutBuilder to validate the layout)
+    pub fn new(monitors: Vec<MonitorDef>) -> Self {
+        Self { flags: 0, monitors }
+    }
+
+    fn data_size(&self) -> usize {
+        8 + self.monitors.len() * MonitorDef::SIZE
+    }
+}
+
+impl Pdu for ClientMonitorData {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        check_monitor_count(self.monitors.len())?;
+
+        write_block_header(buffer, DataBlockType::ClientMonitor, self.data_size())?;
+        buffer.write_u32::<LittleEndian>(self.flags)?;
+        buffer.write_u32::<LittleEndian>(self.monitors.len() as u32)?;
+        for monitor in &self.monitors {
+            monitor.encode(buffer)?;
+        }
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let data = read_block(buffer, DataBlockType::ClientMonitor)?;
+
+        let mut cursor = &data[..];
+        let flags = cursor.read_u32::<LittleEndian>()?;
+        let monitor_count = cursor.read_u32::<LittleEndian>()? as usize;
+        check_monitor_count(monitor_count)?;
+
+        let monitors = (0..monitor_count)
+            .map(|_| MonitorDef::decode(&mut cursor))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { flags, monitors })
+    }
+
+    fn size(&self) -> usize {
+        UserDataHeader::SIZE + self.data_size()
+    }
+}
+
+/// Monitor Attributes (TS_MONITOR_ATTRIBUTES, MS-RDPBCGR 2.2.1.3.9.1)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MonitorAttributes {
+    /// Physical width in millimeters
+    pub physical_width: u32,
+    /// Physical height in millimeters
+    pub physical_height: u32,
+    /// Orientation (0, 90, 180, 270)
+    pub orientation: u32,
+    /// Desktop scale factor (100 - 500 percent)
+    pub desktop_scale_factor: u32,
+    /// Device scale factor (100, 140, 180 percent)
+    pub device_scale_factor: u32,
+}
+
+impl MonitorAttributes {
+    /// Encoded size
+    pub const SIZE: usize = 20;
+}
+
+/// Client Monitor Extended Data (TS_UD_CS_MONITOR_EX, MS-RDPBCGR 2.2.1.3.9)
+///
+/// Attributes are listed in the same order as the monitors in Client Monitor Data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientMonitorExData {
+    /// Flags (unused, must be zero)
+    pub flags: u32,
+    /// Monitor attributes
+    pub attributes: Vec<MonitorAttributes>,
+}
+
+impl ClientMonitorExData {
+    /// Create Client Monitor Extended Data
+    pub fn new(attributes: Vec<MonitorAttributes>) -> Self {
+        Self {
+            flags: 0,
+            attributes,
+        }
+    }
+
+    fn data_size(&self) -> usize {
+        12 + self.attributes.len() * MonitorAttributes::SIZE
+    }
+}
+
+impl Pdu for ClientMonitorExData {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        check_monitor_count(self.attributes.len())?;
+
+        write_block_header(buffer, DataBlockType::ClientMonitorEx, self.data_size())?;
+        buffer.write_u32::<LittleEndian>(self.flags)?;
+        buffer.write_u32::<LittleEndian>(MonitorAttributes::SIZE as u32)?;
+        buffer.write_u32::<LittleEndian>(self.attributes.len() as u32)?;
+        for attributes in &self.attributes {
+            buffer.write_u32::<LittleEndian>(attributes.physical_width)?;
+            buffer.write_u32::<LittleEndian>(attributes.physical_height)?;
+            buffer.write_u32::<LittleEndian>(attributes.orientation)?;
+            buffer.write_u32::<LittleEndian>(attributes.desktop_scale_factor)?;
+            buffer.write_u32::<LittleEndian>(attributes.device_scale_factor)?;
+        }
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let data = read_block(buffer, DataBlockType::ClientMonitorEx)?;
+
+        let mut cursor = &data[..];
+        let flags = cursor.read_u32::<LittleEndian>()?;
+
+        let attribute_size = cursor.read_u32::<LittleEndian>()? as usize;
+        if attribute_size != MonitorAttributes::SIZE {
+            return Err(PduError::InvalidLength {
+                expected: MonitorAttributes::SIZE,
+                actual: attribute_size,
+            });
+        }
+
+        let monitor_count = cursor.read_u32::<LittleEndian>()? as usize;
+        check_monitor_count(monitor_count)?;
+
+        let attributes = (0..monitor_count)
+            .map(|_| {
+                Ok(MonitorAttributes {
+                    physical_width: cursor.read_u32::<LittleEndian>()?,
+                    physical_height: cursor.read_u32::<LittleEndian>()?,
+                    orientation: cursor.read_u32::<LittleEndian>()?,
+                    desktop_scale_factor: cursor.read_u32::<LittleEndian>()?,
+                    device_scale_factor: cursor.read_u32::<LittleEndian>()?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { flags, attributes })
+    }
+
+    fn size(&self) -> usize {
+        UserDataHeader::SIZE + self.data_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_monitor_layout_builder() {
+        let monitors = MonitorLayoutBuilder::new()
+            .primary(1920, 1080)
+            .secondary(1920, 0, 1280, 1024)
+            .secondary(-1024, 0, 1024, 768)
+            .build()
+            .unwrap();
+
+        assert_eq!(monitors.len(), 3);
+        assert_eq!(monitors[0].right, 1919);
+        assert_eq!(monitors[0].bottom, 1079);
+        assert_eq!(monitors[1].width(), 1280);
+        assert_eq!(monitors[2].left, -1024);
+        assert_eq!(monitors[2].right, -1);
+    }
+
+    #[test]
+    fn test_monitor_layout_builder_rules() {
+        // no monitors
+        assert!(MonitorLayoutBuilder::new().build().is_err());
+
+        // no primary
+        assert!(
+            MonitorLayoutBuilder::new()
+                .secondary(0, 0, 800, 600)
+                .build()
+                .is_err()
+        );
+
+        // two primaries
+        assert!(
+            MonitorLayoutBuilder::new()
+                .primary(800, 600)
+                .monitor(MonitorDef::new(800, 0, 800, 600, true))
+                .build()
+                .is_err()
+        );
+
+        // primary not at origin
+        assert!(
+            MonitorLayoutBuilder::new()
+                .monitor(MonitorDef::new(10, 0, 800, 600, true))
+                .build()
+                .is_err()
+        );
+
+        // overlapping rectangles
+        assert!(
+            MonitorLayoutBuilder::new()
+                .primary(800, 600)
+                .secondary(799, 0, 800, 600)
+                .build()
+                .is_err()
+        );
+
+        // too many monitors
+        let mut builder = MonitorLayoutBuilder::new().primary(100, 100);
+        for i in 1..=MAX_MONITOR_COUNT as i32 {
+            builder = builder.secondary(i * 100, 0, 100, 100);
+        }
+        assert!(builder.build().is_err());
+    }
+
+    #[test]
+    fn test_client_monitor_data() {
+        let monitors = MonitorLayoutBuilder::new()
+            .primary(1024, 768)
+            .secondary(1024, 0, 1024, 768)
+            .build()
+            .unwrap();
+        let monitor_data = ClientMonitorData::new(monitors);
+
+        let mut buffer = Vec::new();
+        monitor_data.encode(&mut buffer).unwrap();
+
+        assert_eq!(buffer.len(), monitor_data.size());
+        assert_eq!(
+            &buffer[..12],
+            &[
+                0x05, 0xC0, 0x34, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00
+            ]
+        );
+        assert_eq!(
+            &buffer[12..32],
+            &[
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0x03, 0x00, 0x00, 0xFF, 0x02,
+                0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+            ]
+        );
+
+        let mut cursor = Cursor::new(buffer);
+        assert_eq!(
+            ClientMonitorData::decode(&mut cursor).unwrap(),
+            monitor_data
+        );
+    }
+
+    #[test]
+    fn test_client_monitor_ex_data() {
+        let monitor_ex = ClientMonitorExData::new(vec![
+            MonitorAttributes {
+                physical_width: 520,
+                physical_height: 290,
+                orientation: 0,
+                desktop_scale_factor: 100,
+                device_scale_factor: 100,
+            },
+            MonitorAttributes {
+                physical_width: 340,
+                physical_height: 270,
+                orientation: 90,
+                desktop_scale_factor: 150,
+                device_scale_factor: 140,
+            },
+        ]);
+
+        let mut buffer = Vec::new();
+        monitor_ex.encode(&mut buffer).unwrap();
+
+        assert_eq!(buffer.len(), monitor_ex.size());
+        assert_eq!(&buffer[..4], &[0x08, 0xC0, 0x38, 0x00]);
+        assert_eq!(&buffer[8..12], &[0x14, 0x00, 0x00, 0x00]);
+
+        let mut cursor = Cursor::new(buffer);
+        assert_eq!(
+            ClientMonitorExData::decode(&mut cursor).unwrap(),
+            monitor_ex
+        );
+    }
+
+    #[test]
+    fn test_client_monitor_data_count_limit() {
+        let buffer = vec![
+            0x05, 0xC0, 0x0C, 0x00, 0x00, 0x00, 0x00, 0x00, 0x11, 0x00, 0x00, 0x00,
+        ];
+        let mut cursor = Cursor::new(buffer);
+        assert!(ClientMonitorData::decode(&mut cursor).is_err());
+    }
+}
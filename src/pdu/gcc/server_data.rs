@@ -0,0 +1,713 @@
+use crate::pdu::security::ServerCertificate;
+use crate::pdu::{Pdu, PduError, Result};
+use bitflags::bitflags;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Read, Write};
+
+use super::client_data::{EncryptionMethods, RDP_VERSION_5_PLUS};
+use super::header::{
+    DataBlockType, OptionalFieldReader, OptionalFieldWriter, RawDataBlock, UserDataHeader,
+    optional_field_count, peek_block_type, read_block, write_block_header,
+};
+
+/// Server random length (always 32 bytes)
+pub const SERVER_RANDOM_LENGTH: usize = 32;
+
+bitflags! {
+    /// Server Early Capability Flags (TS_UD_SC_CORE earlyCapabilityFlags)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ServerEarlyCapabilityFlags: u32 {
+        /// RNS_UD_SC_EDGE_ACTIONS_SUPPORTED_V1
+        const EDGE_ACTIONS_SUPPORTED_V1 = 0x0000_0001;
+        /// RNS_UD_SC_DYNAMIC_DST_SUPPORTED
+        const DYNAMIC_DST_SUPPORTED = 0x0000_0002;
+        /// RNS_UD_SC_EDGE_ACTIONS_SUPPORTED_V2
+        const EDGE_ACTIONS_SUPPORTED_V2 = 0x0000_0004;
+        /// RNS_UD_SC_SKIP_CHANNELJOIN_SUPPORTED
+        const SKIP_CHANNELJOIN_SUPPORTED = 0x0000_0008;
+    }
+}
+
+bitflags! {
+    /// Multitransport Flags (TS_UD_SC_MULTITRANSPORT / TS_UD_CS_MULTITRANSPORT)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct MultitransportFlags: u32 {
+        /// TRANSPORTTYPE_UDPFECR - RDP-UDP reliable
+        const UDPFECR = 0x0000_0001;
+        /// TRANSPORTTYPE_UDPFECL - RDP-UDP lossy
+        const UDPFECL = 0x0000_0004;
+        /// TRANSPORTTYPE_UDP_PREFERRED
+        const UDP_PREFERRED = 0x0000_0100;
+        /// SOFTSYNC_TCP_TO_UDP
+        const SOFTSYNC_TCP_TO_UDP = 0x0000_0200;
+    }
+}
+
+/// Encryption Level (TS_UD_SC_SEC1 encryptionLevel)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum EncryptionLevel {
+    /// ENCRYPTION_LEVEL_NONE
+    None = 0,
+    /// ENCRYPTION_LEVEL_LOW
+    Low = 1,
+    /// ENCRYPTION_LEVEL_CLIENT_COMPATIBLE
+    ClientCompatible = 2,
+    /// ENCRYPTION_LEVEL_HIGH
+    High = 3,
+    /// ENCRYPTION_LEVEL_FIPS
+    Fips = 4,
+}
+
+impl EncryptionLevel {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(EncryptionLevel::None),
+            1 => Some(EncryptionLevel::Low),
+            2 => Some(EncryptionLevel::ClientCompatible),
+            3 => Some(EncryptionLevel::High),
+            4 => Some(EncryptionLevel::Fips),
+            _ => None,
+        }
+    }
+
+    pub fn as_u32(self) -> u32 {
+        self as u32
+    }
+}
+
+/// Server Core Data (TS_UD_SC_CORE, MS-RDPBCGR 2.2.1.4.2)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerCoreData {
+    /// RDP version (RDP_VERSION_*)
+    pub version: u32,
+    /// Protocols requested by the client in the X.224 Connection Request (optional)
+    pub client_requested_protocols: Option<u32>,
+    /// Early capability flags (optional)
+    pub early_capability_flags: Option<ServerEarlyCapabilityFlags>,
+}
+
+impl ServerCoreData {
+    /// Create Server Core Data echoing the client's requested protocols
+    pub fn new(client_requested_protocols: u32) -> Self {
+        Self {
+            version: RDP_VERSION_5_PLUS,
+            client_requested_protocols: Some(client_requested_protocols),
+            early_capability_flags: Some(ServerEarlyCapabilityFlags::empty()),
+        }
+    }
+
+    fn present(&self) -> [bool; 2] {
+        [
+            self.client_requested_protocols.is_some(),
+            self.early_capability_flags.is_some(),
+        ]
+    }
+
+    fn data_size(&self) -> usize {
+        4 + optional_field_count(&self.present()) * 4
+    }
+}
+
+impl Pdu for ServerCoreData {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        write_block_header(buffer, DataBlockType::ServerCore, self.data_size())?;
+        buffer.write_u32::<LittleEndian>(self.version)?;
+
+        let mut writer = OptionalFieldWriter::new(buffer, &self.present());
+        writer.write_u32(self.client_requested_protocols)?;
+        writer.write_u32(self.early_capability_flags.map(|f| f.bits()))?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let data = read_block(buffer, DataBlockType::ServerCore)?;
+
+        let mut cursor = &data[..];
+        let version = cursor.read_u32::<LittleEndian>()?;
+
+        let mut reader = OptionalFieldReader::new(cursor);
+        let client_requested_protocols = reader.read_u32();
+        let early_capability_flags = reader
+            .read_u32()
+            .map(ServerEarlyCapabilityFlags::from_bits_retain);
+        reader.finish()?;
+
+        Ok(Self {
+            version,
+            client_requested_protocols,
+            early_capability_flags,
+        })
+    }
+
+    fn size(&self) -> usize {
+        UserDataHeader::SIZE + self.data_size()
+    }
+}
+
+/// Server Security Data (TS_UD_SC_SEC1, MS-RDPBCGR 2.2.1.4.3)
+///
+/// Server random and certificate are only present when Standard RDP Security
+/// is in use (method or level non-zero).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerSecurityData {
+    /// Selected encryption method (single ENCRYPTION_METHOD_* value or empty)
+    pub encryption_method: EncryptionMethods,
+    /// Encryption level
+    pub encryption_level: EncryptionLevel,
+    /// Server random (32 bytes)
+    pub server_random: Vec<u8>,
+    /// Server certificate (proprietary or X.509 chain)
+    pub server_certificate: Vec<u8>,
+}
+
+impl ServerSecurityData {
+    /// No Standard RDP Security (TLS/CredSSP connections)
+    pub fn none() -> Self {
+        Self {
+            encryption_method: EncryptionMethods::empty(),
+            encryption_level: EncryptionLevel::None,
+            server_random: Vec::new(),
+            server_certificate: Vec::new(),
+        }
+    }
+
+    /// Standard RDP Security with the given method, level, random and certificate
+    pub fn new(
+        encryption_method: EncryptionMethods,
+        encryption_level: EncryptionLevel,
+        server_random: Vec<u8>,
+        server_certificate: Vec<u8>,
+    ) -> Self {
+        Self {
+            encryption_method,
+            encryption_level,
+            server_random,
+            server_certificate,
+        }
+    }
+
+    /// Whether Standard RDP Security is in use
+    pub fn is_encrypted(&self) -> bool {
+        !self.encryption_method.is_empty() || self.encryption_level != EncryptionLevel::None
+    }
+
+    /// Parse the server certificate (None when no certificate was sent)
+    pub fn certificate(&self) -> Result<Option<ServerCertificate>> {
+        if self.server_certificate.is_empty() {
+            return Ok(None);
+        }
+        ServerCertificate::decode(&mut self.server_certificate.as_slice()).map(Some)
+    }
+
+    fn data_size(&self) -> usize {
+        if self.is_encrypted() {
+            16 + self.server_random.len() + self.server_certificate.len()
+        } else {
+            8
+        }
+    }
+}
+
+impl Pdu for ServerSecurityData {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        if self.is_encrypted() && self.server_random.len() != SERVER_RANDOM_LENGTH {
+            return Err(PduError::InvalidLength {
+                expected: SERVER_RANDOM_LENGTH,
+                actual: self.server_random.len(),
+            });
+        }
+
+        write_block_header(buffer, DataBlockType::ServerSecurity, self.data_size())?;
+        buffer.write_u32::<LittleEndian>(self.encryption_method.bits())?;
+        buffer.write_u32::<LittleEndian>(self.encryption_level.as_u32())?;
+
+        if self.is_encrypted() {
+            buffer.write_u32::<LittleEndian>(self.server_random.len() as u32)?;
+            buffer.write_u32::<LittleEndian>(self.server_certificate.len() as u32)?;
+            buffer.write_all(&self.server_random)?;
+            buffer.write_all(&self.server_certificate)?;
+        }
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let data = read_block(buffer, DataBlockType::ServerSecurity)?;
+
+        let mut cursor = &data[..];
+        let encryption_method =
+            EncryptionMethods::from_bits_retain(cursor.read_u32::<LittleEndian>()?);
+        let level = cursor.read_u32::<LittleEndian>()?;
+        let encryption_level = EncryptionLevel::from_u32(level)
+            .ok_or_else(|| PduError::ParseError(format!("Invalid encryption level: {}", level)))?;
+
+        let mut security = Self {
+            encryption_method,
+            encryption_level,
+            server_random: Vec::new(),
+            server_certificate: Vec::new(),
+        };
+
+        if !security.is_encrypted() {
+            return Ok(security);
+        }
+
+        let random_len = cursor.read_u32::<LittleEndian>()? as usize;
+        let certificate_len = cursor.read_u32::<LittleEndian>()? as usize;
+        if random_len != SERVER_RANDOM_LENGTH {
+            return Err(PduError::InvalidLength {
+                expected: SERVER_RANDOM_LENGTH,
+                actual: random_len,
+            });
+        }
+        if cursor.len() < random_len + certificate_len {
+            return Err(PduError::InsufficientData {
+                needed: random_len + certificate_len,
+                available: cursor.len(),
+            });
+        }
+
+        security.server_random = cursor[..random_len].to_vec();
+        security.server_certificate = cursor[random_len..random_len + certificate_len].to_vec();
+        Ok(security)
+    }
+
+    fn size(&self) -> usize {
+        UserDataHeader::SIZE + self.data_size()
+    }
+}
+
+/// Server Network Data (TS_UD_SC_NET, MS-RDPBCGR 2.2.1.4.4)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerNetworkData {
+    /// MCS I/O channel ID
+    pub io_channel: u16,
+    /// Channel IDs assigned to the client's static virtual channels, in request order
+    pub channel_ids: Vec<u16>,
+}
+
+impl ServerNetworkData {
+    /// Create Server Network Data
+    pub fn new(io_channel: u16, channel_ids: Vec<u16>) -> Self {
+        Self {
+            io_channel,
+            channel_ids,
+        }
+    }
+
+    fn data_size(&self) -> usize {
+        // channel ID array is padded to a multiple of 4 bytes
+        4 + self.channel_ids.len() * 2 + (self.channel_ids.len() % 2) * 2
+    }
+}
+
+impl Pdu for ServerNetworkData {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        if self.channel_ids.len() > u16::MAX as usize {
+            return Err(PduError::InvalidLength {
+                expected: u16::MAX as usize,
+                actual: self.channel_ids.len(),
+            });
+        }
+
+        write_block_header(buffer, DataBlockType::ServerNetwork, self.data_size())?;
+        buffer.write_u16::<LittleEndian>(self.io_channel)?;
+        buffer.write_u16::<LittleEndian>(self.channel_ids.len() as u16)?;
+        for &channel_id in &self.channel_ids {
+            buffer.write_u16::<LittleEndian>(channel_id)?;
+        }
+        if self.channel_ids.len() % 2 == 1 {
+            buffer.write_u16::<LittleEndian>(0)?; // padding
+        }
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let data = read_block(buffer, DataBlockType::ServerNetwork)?;
+
+        let mut cursor = &data[..];
+        let io_channel = cursor.read_u16::<LittleEndian>()?;
+        let channel_count = cursor.read_u16::<LittleEndian>()? as usize;
+
+        // Padding is optional in practice; some servers omit it
+        if cursor.len() < channel_count * 2 {
+            return Err(PduError::InsufficientData {
+                needed: channel_count * 2,
+                available: cursor.len(),
+            });
+        }
+
+        let channel_ids = (0..channel_count)
+            .map(|_| cursor.read_u16::<LittleEndian>())
+            .collect::<std::io::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            io_channel,
+            channel_ids,
+        })
+    }
+
+    fn size(&self) -> usize {
+        UserDataHeader::SIZE + self.data_size()
+    }
+}
+
+/// Server Message Channel Data (TS_UD_SC_MCS_MSGCHANNEL, MS-RDPBCGR 2.2.1.4.5)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServerMessageChannelData {
+    /// MCS message channel ID
+    pub mcs_channel_id: u16,
+}
+
+impl ServerMessageChannelData {
+    /// Data size (excluding header)
+    pub const DATA_SIZE: usize = 2;
+
+    /// Create Server Message Channel Data
+    pub fn new(mcs_channel_id: u16) -> Self {
+        Self { mcs_channel_id }
+    }
+}
+
+impl Pdu for ServerMessageChannelData {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        write_block_header(buffer, DataBlockType::ServerMessageChannel, Self::DATA_SIZE)?;
+        buffer.write_u16::<LittleEndian>(self.mcs_channel_id)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let data = read_block(buffer, DataBlockType::ServerMessageChannel)?;
+        if data.len() != Self::DATA_SIZE {
+            return Err(PduError::InvalidLength {
+                expected: Self::DATA_SIZE,
+                actual: data.len(),
+            });
+        }
+
+        Ok(Self {
+            mcs_channel_id: u16::from_le_bytes([data[0], data[1]]),
+        })
+    }
+
+    fn size(&self) -> usize {
+        UserDataHeader::SIZE + Self::DATA_SIZE
+    }
+}
+
+/// Server Multitransport Channel Data (TS_UD_SC_MULTITRANSPORT, MS-RDPBCGR 2.2.1.4.6)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServerMultitransportChannelData {
+    /// Supported transport types
+    pub flags: MultitransportFlags,
+}
+
+impl ServerMultitransportChannelData {
+    /// Data size (excluding header)
+    pub const DATA_SIZE: usize = 4;
+
+    /// Create Server Multitransport Channel Data
+    pub fn new(flags: MultitransportFlags) -> Self {
+        Self { flags }
+    }
+}
+
+impl Pdu for ServerMultitransportChannelData {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        write_block_header(buffer, DataBlockType::ServerMultitransport, Self::DATA_SIZE)?;
+        buffer.write_u32::<LittleEndian>(self.flags.bits())?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let data = read_block(buffer, DataBlockType::ServerMultitransport)?;
+        if data.len() != Self::DATA_SIZE {
+            return Err(PduError::InvalidLength {
+                expected: Self::DATA_SIZE,
+                actual: data.len(),
+            });
+        }
+
+        Ok(Self {
+            flags: MultitransportFlags::from_bits_retain(u32::from_le_bytes([
+                data[0], data[1], data[2], data[3],
+            ])),
+        })
+    }
+
+    fn size(&self) -> usize {
+        UserDataHeader::SIZE + Self::DATA_SIZE
+    }
+}
+
+/// Server data blocks carried in the GCC Conference Create Response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerData {
+    pub core: ServerCoreData,
+    pub network: ServerNetworkData,
+    pub security: ServerSecurityData,
+    pub message_channel: Option<ServerMessageChannelData>,
+    pub multitransport: Option<ServerMultitransportChannelData>,
+    /// Blocks without a typed representation, kept in received order
+    pub other: Vec<RawDataBlock>,
+}
+
+impl ServerData {
+    /// Create server data with core, network and security blocks
+    pub fn new(
+        core: ServerCoreData,
+        network: ServerNetworkData,
+        security: ServerSecurityData,
+    ) -> Self {
+        Self {
+            core,
+            network,
+            security,
+            message_channel: None,
+            multitransport: None,
+            other: Vec::new(),
+        }
+    }
+}
+
+impl Pdu for ServerData {
+    /// Blocks are written in the order used by Windows servers: core, network, security
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        self.core.encode(buffer)?;
+        self.network.encode(buffer)?;
+        self.security.encode(buffer)?;
+        if let Some(message_channel) = &self.message_channel {
+            message_channel.encode(buffer)?;
+        }
+        if let Some(multitransport) = &self.multitransport {
+            multitransport.encode(buffer)?;
+        }
+        for block in &self.other {
+            block.encode(buffer)?;
+        }
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let mut data = Vec::new();
+        buffer.read_to_end(&mut data)?;
+
+        let mut core = None;
+        let mut network = None;
+        let mut security = None;
+        let mut message_channel = None;
+        let mut multitransport = None;
+        let mut other = Vec::new();
+
+        let mut cursor = data.as_slice();
+        while !cursor.is_empty() {
+            match DataBlockType::from_u16(peek_block_type(cursor)?) {
+                Some(DataBlockType::ServerCore) => {
+                    core = Some(ServerCoreData::decode(&mut cursor)?)
+                }
+                Some(DataBlockType::ServerNetwork) => {
+                    network = Some(ServerNetworkData::decode(&mut cursor)?)
+                }
+                Some(DataBlockType::ServerSecurity) => {
+                    security = Some(ServerSecurityData::decode(&mut cursor)?)
+                }
+                Some(DataBlockType::ServerMessageChannel) => {
+                    message_channel = Some(ServerMessageChannelData::decode(&mut cursor)?)
+                }
+                Some(DataBlockType::ServerMultitransport) => {
+                    multitransport = Some(ServerMultitransportChannelData::decode(&mut cursor)?)
+                }
+                _ => other.push(RawDataBlock::decode(&mut cursor)?),
+            }
+        }
+
+        let missing =
+            |name: &str| PduError::ParseError(format!("Server data is missing {} block", name));
+
+        Ok(Self {
+            core: core.ok_or_else(|| missing("SC_CORE"))?,
+            network: network.ok_or_else(|| missing("SC_NET"))?,
+            security: security.ok_or_else(|| missing("SC_SECURITY"))?,
+            message_channel,
+            multitransport,
+            other,
+        })
+    }
+
+    fn size(&self) -> usize {
+        self.core.size()
+            + self.network.size()
+            + self.security.size()
+            + self.message_channel.as_ref().map_or(0, |b| b.size())
+            + self.multitransport.as_ref().map_or(0, |b| b.size())
+            + self.other.iter().map(|b| b.size()).sum::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn encode<T: Pdu>(pdu: &T) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        pdu.encode(&mut buffer).unwrap();
+        assert_eq!(pdu.size(), buffer.len());
+        buffer
+    }
+
+    #[test]
+    fn test_server_core_data() {
+        let core = ServerCoreData::new(0x0000_0003);
+
+        let buffer = encode(&core);
+        assert_eq!(
+            buffer,
+            vec![
+                0x01, 0x0C, 0x10, 0x00, 0x04, 0x00, 0x08, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00,
+            ]
+        );
+
+        let mut cursor = Cursor::new(buffer);
+        assert_eq!(ServerCoreData::decode(&mut cursor).unwrap(), core);
+    }
+
+    #[test]
+    fn test_server_core_data_truncated() {
+        // RDP 4.0/5.0 servers send only the version field
+        let buffer = vec![0x01, 0x0C, 0x08, 0x00, 0x04, 0x00, 0x08, 0x00];
+
+        let mut cursor = Cursor::new(buffer.clone());
+        let core = ServerCoreData::decode(&mut cursor).unwrap();
+        assert_eq!(core.client_requested_protocols, None);
+        assert_eq!(core.early_capability_flags, None);
+        assert_eq!(encode(&core), buffer);
+    }
+
+    #[test]
+    fn test_server_security_data_none() {
+        let security = ServerSecurityData::none();
+        assert!(security.certificate().unwrap().is_none());
+
+        let buffer = encode(&security);
+        assert_eq!(
+            buffer,
+            vec![
+                0x02, 0x0C, 0x0C, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+            ]
+        );
+
+        let mut cursor = Cursor::new(buffer);
+        assert_eq!(ServerSecurityData::decode(&mut cursor).unwrap(), security);
+    }
+
+    #[test]
+    fn test_server_security_data_encrypted() {
+        let security = ServerSecurityData::new(
+            EncryptionMethods::BIT_128,
+            EncryptionLevel::ClientCompatible,
+            vec![0x11; SERVER_RANDOM_LENGTH],
+            vec![0x01, 0x00, 0x00, 0x00],
+        );
+        assert!(security.is_encrypted());
+        // Truncated proprietary certificate
+        assert!(security.certificate().is_err());
+
+        let buffer = encode(&security);
+        assert_eq!(buffer.len(), 4 + 16 + 32 + 4);
+        assert_eq!(
+            &buffer[12..20],
+            &[0x20, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00]
+        );
+
+        let mut cursor = Cursor::new(buffer);
+        assert_eq!(ServerSecurityData::decode(&mut cursor).unwrap(), security);
+    }
+
+    #[test]
+    fn test_server_security_data_invalid_random() {
+        let security = ServerSecurityData::new(
+            EncryptionMethods::BIT_128,
+            EncryptionLevel::High,
+            vec![0x11; 16],
+            Vec::new(),
+        );
+
+        let mut buffer = Vec::new();
+        assert!(security.encode(&mut buffer).is_err());
+    }
+
+    #[test]
+    fn test_server_network_data() {
+        let network = ServerNetworkData::new(1003, vec![1004, 1005, 1006]);
+
+        let buffer = encode(&network);
+        assert_eq!(
+            buffer,
+            vec![
+                0x03, 0x0C, 0x10, 0x00, 0xEB, 0x03, 0x03, 0x00, 0xEC, 0x03, 0xED, 0x03, 0xEE, 0x03,
+                0x00, 0x00,
+            ]
+        );
+
+        let mut cursor = Cursor::new(buffer);
+        assert_eq!(ServerNetworkData::decode(&mut cursor).unwrap(), network);
+    }
+
+    #[test]
+    fn test_server_network_data_without_padding() {
+        let buffer = vec![0x03, 0x0C, 0x0A, 0x00, 0xEB, 0x03, 0x01, 0x00, 0xEC, 0x03];
+
+        let mut cursor = Cursor::new(buffer);
+        let network = ServerNetworkData::decode(&mut cursor).unwrap();
+        assert_eq!(network.channel_ids, vec![1004]);
+    }
+
+    #[test]
+    fn test_server_message_channel_and_multitransport() {
+        let message_channel = ServerMessageChannelData::new(1007);
+        let buffer = encode(&message_channel);
+        assert_eq!(buffer, vec![0x04, 0x0C, 0x06, 0x00, 0xEF, 0x03]);
+        let mut cursor = Cursor::new(buffer);
+        assert_eq!(
+            ServerMessageChannelData::decode(&mut cursor).unwrap(),
+            message_channel
+        );
+
+        let multitransport = ServerMultitransportChannelData::new(
+            MultitransportFlags::UDPFECR | MultitransportFlags::UDP_PREFERRED,
+        );
+        let buffer = encode(&multitransport);
+        assert_eq!(buffer, vec![0x08, 0x0C, 0x08, 0x00, 0x01, 0x01, 0x00, 0x00]);
+        let mut cursor = Cursor::new(buffer);
+        assert_eq!(
+            ServerMultitransportChannelData::decode(&mut cursor).unwrap(),
+            multitransport
+        );
+    }
+
+    #[test]
+    fn test_server_data_roundtrip() {
+        let mut server_data = ServerData::new(
+            ServerCoreData::new(0x0000_0001),
+            ServerNetworkData::new(1003, vec![1004, 1005]),
+            ServerSecurityData::none(),
+        );
+        server_data.message_channel = Some(ServerMessageChannelData::new(1006));
+        server_data.multitransport = Some(ServerMultitransportChannelData::new(
+            MultitransportFlags::UDPFECR,
+        ));
+
+        let buffer = encode(&server_data);
+        let mut cursor = Cursor::new(buffer);
+        assert_eq!(ServerData::decode(&mut cursor).unwrap(), server_data);
+    }
+
+    #[test]
+    fn test_server_data_missing_network() {
+        let buffer = encode(&ServerCoreData::new(0));
+        let mut cursor = Cursor::new(buffer);
+        assert!(ServerData::decode(&mut cursor).is_err());
+    }
+}
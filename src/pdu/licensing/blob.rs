@@ -0,0 +1,275 @@
+use crate::pdu::rdp::connection::client_info::{
+    decode_unicode_string, unicode_string_size, write_unicode_string,
+};
+use crate::pdu::{Pdu, PduError, Result};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Read, Write};
+
+/// Binary blob type (MS-RDPBCGR 2.2.1.12.1.2)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u16)]
+pub enum BlobType {
+    /// BB_DATA_BLOB
+    Data = 0x0001,
+    /// BB_RANDOM_BLOB
+    Random = 0x0002,
+    /// BB_CERTIFICATE_BLOB
+    Certificate = 0x0003,
+    /// BB_ERROR_BLOB
+    Error = 0x0004,
+    /// BB_ENCRYPTED_DATA_BLOB
+    EncryptedData = 0x0009,
+    /// BB_KEY_EXCHG_ALG_BLOB
+    KeyExchangeAlgorithm = 0x000D,
+    /// BB_SCOPE_BLOB
+    Scope = 0x000E,
+    /// BB_CLIENT_USER_NAME_BLOB
+    ClientUserName = 0x000F,
+    /// BB_CLIENT_MACHINE_NAME_BLOB
+    ClientMachineName = 0x0010,
+}
+
+impl BlobType {
+    pub fn from_u16(value: u16) -> Option<Self> {
+        match value {
+            0x0001 => Some(BlobType::Data),
+            0x0002 => Some(BlobType::Random),
+            0x0003 => Some(BlobType::Certificate),
+            0x0004 => Some(BlobType::Error),
+            0x0009 => Some(BlobType::EncryptedData),
+            0x000D => Some(BlobType::KeyExchangeAlgorithm),
+            0x000E => Some(BlobType::Scope),
+            0x000F => Some(BlobType::ClientUserName),
+            0x0010 => Some(BlobType::ClientMachineName),
+            _ => None,
+        }
+    }
+
+    pub fn as_u16(self) -> u16 {
+        self as u16
+    }
+}
+
+/// Licensing Binary Blob (LICENSE_BINARY_BLOB, MS-RDPBCGR 2.2.1.12.1.2)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LicenseBinaryBlob {
+    /// Blob type
+    pub blob_type: BlobType,
+    /// Blob data
+    pub data: Vec<u8>,
+}
+
+impl LicenseBinaryBlob {
+    /// Create blob
+    pub fn new(blob_type: BlobType, data: Vec<u8>) -> Self {
+        Self { blob_type, data }
+    }
+
+    /// Encode blob
+    pub fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        let length = u16::try_from(self.data.len()).map_err(|_| PduError::InvalidLength {
+            expected: u16::MAX as usize,
+            actual: self.data.len(),
+        })?;
+        buffer.write_u16::<LittleEndian>(self.blob_type.as_u16())?;
+        buffer.write_u16::<LittleEndian>(length)?;
+        buffer.write_all(&self.data)?;
+        Ok(())
+    }
+
+    /// Decode blob of the expected type
+    ///
+    /// Empty blobs may carry any type value and are accepted as `expected`.
+    pub fn decode(buffer: &mut dyn Read, expected: BlobType) -> Result<Self> {
+        let blob_type = buffer.read_u16::<LittleEndian>()?;
+        let length = buffer.read_u16::<LittleEndian>()? as usize;
+        if length > 0 && blob_type != expected.as_u16() {
+            return Err(PduError::ParseError(format!(
+                "Expected {:?} blob, got type {:#06x}",
+                expected, blob_type
+            )));
+        }
+
+        Ok(Self {
+            blob_type: expected,
+            data: read_vec(buffer, length)?,
+        })
+    }
+
+    /// Encoded size
+    pub fn size(&self) -> usize {
+        4 + self.data.len()
+    }
+}
+
+/// Product Information (PRODUCT_INFO, MS-RDPELE 2.2.2.1.1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProductInfo {
+    /// Product version
+    pub version: u32,
+    /// Company name
+    pub company_name: String,
+    /// Product ID
+    pub product_id: String,
+}
+
+impl ProductInfo {
+    /// Create product info
+    pub fn new(version: u32, company_name: &str, product_id: &str) -> Self {
+        Self {
+            version,
+            company_name: company_name.to_string(),
+            product_id: product_id.to_string(),
+        }
+    }
+}
+
+impl Pdu for ProductInfo {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_u32::<LittleEndian>(self.version)?;
+        write_unicode_field(buffer, &self.company_name)?;
+        write_unicode_field(buffer, &self.product_id)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let version = buffer.read_u32::<LittleEndian>()?;
+        let company_name = read_unicode_field(buffer)?;
+        let product_id = read_unicode_field(buffer)?;
+        Ok(Self {
+            version,
+            company_name,
+            product_id,
+        })
+    }
+
+    fn size(&self) -> usize {
+        4 + 4 + unicode_string_size(&self.company_name) + 4 + unicode_string_size(&self.product_id)
+    }
+}
+
+/// Scope List (SCOPE_LIST, MS-RDPELE 2.2.2.1.2)
+///
+/// Each scope is a BB_SCOPE_BLOB holding a null-terminated ANSI string.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ScopeList {
+    /// License issuer scopes
+    pub scopes: Vec<String>,
+}
+
+impl ScopeList {
+    /// Create scope list
+    pub fn new(scopes: Vec<String>) -> Self {
+        Self { scopes }
+    }
+}
+
+impl Pdu for ScopeList {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_u32::<LittleEndian>(self.scopes.len() as u32)?;
+        for scope in &self.scopes {
+            LicenseBinaryBlob::new(BlobType::Scope, ansi_string(scope)).encode(buffer)?;
+        }
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let count = buffer.read_u32::<LittleEndian>()?;
+        let mut scopes = Vec::new();
+        for _ in 0..count {
+            let blob = LicenseBinaryBlob::decode(buffer, BlobType::Scope)?;
+            scopes.push(decode_ansi_string(&blob.data));
+        }
+        Ok(Self { scopes })
+    }
+
+    fn size(&self) -> usize {
+        4 + self
+            .scopes
+            .iter()
+            .map(|scope| 4 + scope.len() + 1)
+            .sum::<usize>()
+    }
+}
+
+/// Read `length` bytes without trusting the length for the allocation
+pub(crate) fn read_vec(buffer: &mut dyn Read, length: usize) -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    buffer.take(length as u64).read_to_end(&mut data)?;
+    if data.len() != length {
+        return Err(PduError::InsufficientData {
+            needed: length,
+            available: data.len(),
+        });
+    }
+    Ok(data)
+}
+
+/// Null-terminated ANSI string bytes
+pub(crate) fn ansi_string(s: &str) -> Vec<u8> {
+    let mut bytes = s.as_bytes().to_vec();
+    bytes.push(0);
+    bytes
+}
+
+/// Decode an ANSI string up to the first null
+pub(crate) fn decode_ansi_string(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+/// Write a u32 byte count followed by a null-terminated UTF-16LE string
+pub(crate) fn write_unicode_field(buffer: &mut dyn Write, s: &str) -> Result<()> {
+    buffer.write_u32::<LittleEndian>(unicode_string_size(s) as u32)?;
+    write_unicode_string(buffer, s)
+}
+
+/// Read a u32 byte count followed by a UTF-16LE string
+pub(crate) fn read_unicode_field(buffer: &mut dyn Read) -> Result<String> {
+    let length = buffer.read_u32::<LittleEndian>()? as usize;
+    Ok(decode_unicode_string(&read_vec(buffer, length)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_product_info_and_scope_list() {
+        let product_info = ProductInfo::new(0x0006_0000, "Microsoft Corporation", "A02");
+        let mut buffer = Vec::new();
+        product_info.encode(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), product_info.size());
+        assert_eq!(&buffer[4..8], &[0x2C, 0x00, 0x00, 0x00]);
+        assert_eq!(
+            ProductInfo::decode(&mut buffer.as_slice()).unwrap(),
+            product_info
+        );
+
+        let scope_list = ScopeList::new(vec!["microsoft.com".to_string()]);
+        let mut buffer = Vec::new();
+        scope_list.encode(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), scope_list.size());
+        assert_eq!(
+            &buffer[..8],
+            &[0x01, 0x00, 0x00, 0x00, 0x0E, 0x00, 0x0E, 0x00]
+        );
+        assert_eq!(
+            ScopeList::decode(&mut buffer.as_slice()).unwrap(),
+            scope_list
+        );
+    }
+
+    #[test]
+    fn test_binary_blob_type() {
+        // Empty blobs are accepted whatever their type
+        let blob =
+            LicenseBinaryBlob::decode(&mut [0x00, 0x00, 0x00, 0x00].as_slice(), BlobType::Error)
+                .unwrap();
+        assert_eq!(blob, LicenseBinaryBlob::new(BlobType::Error, Vec::new()));
+
+        let data = [0x01, 0x00, 0x02, 0x00, 0xAA, 0xBB];
+        assert!(LicenseBinaryBlob::decode(&mut data.as_slice(), BlobType::Random).is_err());
+        assert!(LicenseBinaryBlob::decode(&mut &data[..5], BlobType::Data).is_err());
+    }
+}
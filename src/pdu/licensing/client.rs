@@ -0,0 +1,599 @@
+use super::error_alert::{LicenseErrorMessage, StateTransition};
+use super::keys::{LicenseKeys, PREMASTER_SECRET_LENGTH};
+use super::license_request::{KEY_EXCHANGE_ALG_RSA, ServerLicenseRequest};
+use super::new_license::{NewLicenseInfo, ServerNewLicense};
+use super::new_license_request::{ClientLicenseInfo, ClientNewLicenseRequest, DEFAULT_PLATFORM_ID};
+use super::pdu::LicenseMessage;
+use super::platform_challenge::{
+    ClientHardwareId, ClientPlatformChallengeResponse, PlatformChallengeResponseData,
+    ServerPlatformChallenge,
+};
+use crate::pdu::security::{RANDOM_LENGTH, ServerCertificate};
+use crate::pdu::{Pdu, PduError, Result};
+use rand::RngCore;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+/// Zero padding after the encrypted premaster secret
+const PREMASTER_PADDING_LENGTH: usize = 8;
+
+/// Identifies a license by issuer scope and product
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LicenseKey {
+    /// License issuer scope
+    pub scope: String,
+    /// Company name
+    pub company_name: String,
+    /// Product ID
+    pub product_id: String,
+}
+
+impl LicenseKey {
+    /// Create license key
+    pub fn new(scope: &str, company_name: &str, product_id: &str) -> Self {
+        Self {
+            scope: scope.to_string(),
+            company_name: company_name.to_string(),
+            product_id: product_id.to_string(),
+        }
+    }
+}
+
+/// Persistent storage for licenses issued by servers
+///
+/// Implementations use interior mutability so one store can be shared by
+/// several connections.
+pub trait LicenseStore: Debug + Send + Sync {
+    /// License previously issued for `key`
+    fn load(&self, key: &LicenseKey) -> Option<Vec<u8>>;
+
+    /// Save a license issued by the server, replacing any previous one
+    fn save(&self, key: &LicenseKey, license: &[u8]);
+}
+
+/// In-memory license store
+#[derive(Debug, Default)]
+pub struct MemoryLicenseStore {
+    licenses: Mutex<HashMap<LicenseKey, Vec<u8>>>,
+}
+
+impl MemoryLicenseStore {
+    /// Create empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of stored licenses
+    pub fn len(&self) -> usize {
+        self.licenses().len()
+    }
+
+    /// Whether no license is stored
+    pub fn is_empty(&self) -> bool {
+        self.licenses().is_empty()
+    }
+
+    fn licenses(&self) -> std::sync::MutexGuard<'_, HashMap<LicenseKey, Vec<u8>>> {
+        // A panic while holding the lock cannot leave the map inconsistent
+        self.licenses.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl LicenseStore for MemoryLicenseStore {
+    fn load(&self, key: &LicenseKey) -> Option<Vec<u8>> {
+        self.licenses().get(key).cloned()
+    }
+
+    fn save(&self, key: &LicenseKey, license: &[u8]) {
+        self.licenses().insert(key.clone(), license.to_vec());
+    }
+}
+
+/// Client licensing state (MS-RDPELE 1.3.3)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LicenseClientState {
+    /// Waiting for Server License Request (or STATUS_VALID_CLIENT)
+    AwaitingLicenseRequest,
+    /// License Information or New License Request sent
+    AwaitingPlatformChallenge,
+    /// Platform Challenge Response sent
+    AwaitingNewLicense,
+    /// Licensing finished, capability exchange follows
+    Completed,
+}
+
+/// Client licensing parameters
+#[derive(Debug, Clone)]
+pub struct LicenseClientConfig {
+    /// User name sent in New License Request
+    pub user_name: String,
+    /// Machine name sent in New License Request
+    pub machine_name: String,
+    /// Licensing client random
+    pub client_random: [u8; RANDOM_LENGTH],
+    /// Premaster secret
+    pub premaster_secret: [u8; PREMASTER_SECRET_LENGTH],
+    /// Client hardware ID
+    pub hardware_id: ClientHardwareId,
+    /// Certificate from SC_SECURITY, used when the License Request carries none
+    pub server_certificate: Option<ServerCertificate>,
+}
+
+impl LicenseClientConfig {
+    /// Create configuration with fresh random values
+    ///
+    /// The hardware ID is derived from the machine name so that stored
+    /// licenses stay valid across connections.
+    pub fn new(user_name: &str, machine_name: &str) -> Self {
+        let mut rng = rand::thread_rng();
+        let mut client_random = [0u8; RANDOM_LENGTH];
+        let mut premaster_secret = [0u8; PREMASTER_SECRET_LENGTH];
+        rng.fill_bytes(&mut client_random);
+        rng.fill_bytes(&mut premaster_secret);
+
+        Self {
+            user_name: user_name.to_string(),
+            machine_name: machine_name.to_string(),
+            client_random,
+            premaster_secret,
+            hardware_id: ClientHardwareId::from_machine_name(machine_name),
+            server_certificate: None,
+        }
+    }
+
+    /// Set certificate received in SC_SECURITY
+    pub fn with_server_certificate(mut self, certificate: ServerCertificate) -> Self {
+        self.server_certificate = Some(certificate);
+        self
+    }
+}
+
+/// Client side of the licensing exchange
+///
+/// Presents a stored license when the store has one for the server's scope
+/// and product, otherwise requests a new one, and saves issued licenses.
+#[derive(Debug, Clone)]
+pub struct LicenseClient {
+    config: LicenseClientConfig,
+    store: Arc<dyn LicenseStore>,
+    state: LicenseClientState,
+    keys: Option<LicenseKeys>,
+    last_message: Option<LicenseMessage>,
+}
+
+impl LicenseClient {
+    /// Create licensing client
+    pub fn new(config: LicenseClientConfig, store: Arc<dyn LicenseStore>) -> Self {
+        Self {
+            config,
+            store,
+            state: LicenseClientState::AwaitingLicenseRequest,
+            keys: None,
+            last_message: None,
+        }
+    }
+
+    /// Current state
+    pub fn state(&self) -> LicenseClientState {
+        self.state
+    }
+
+    /// Whether licensing has finished
+    pub fn is_completed(&self) -> bool {
+        self.state == LicenseClientState::Completed
+    }
+
+    /// Handle a server licensing message
+    ///
+    /// Returns the client message to send, if any.
+    pub fn process(&mut self, message: &LicenseMessage) -> Result<Option<LicenseMessage>> {
+        let reply = self.handle(message)?;
+        if reply.is_some() {
+            self.last_message.clone_from(&reply);
+        }
+        Ok(reply)
+    }
+
+    fn handle(&mut self, message: &LicenseMessage) -> Result<Option<LicenseMessage>> {
+        match (self.state, message) {
+            (_, LicenseMessage::ErrorAlert(error)) => self.on_error(error),
+            (
+                LicenseClientState::AwaitingLicenseRequest,
+                LicenseMessage::LicenseRequest(request),
+            ) => self.on_license_request(request).map(Some),
+            (
+                LicenseClientState::AwaitingPlatformChallenge,
+                LicenseMessage::PlatformChallenge(challenge),
+            ) => self.on_platform_challenge(challenge).map(Some),
+            (
+                LicenseClientState::AwaitingNewLicense,
+                LicenseMessage::NewLicense(license) | LicenseMessage::UpgradeLicense(license),
+            ) => {
+                self.on_new_license(license)?;
+                Ok(None)
+            }
+            (state, message) => Err(PduError::ParseError(format!(
+                "Unexpected {:?} licensing message in state {:?}",
+                message.message_type(),
+                state
+            ))),
+        }
+    }
+
+    /// Follow the server's state transition (MS-RDPBCGR 2.2.1.12.1.3)
+    fn on_error(&mut self, error: &LicenseErrorMessage) -> Result<Option<LicenseMessage>> {
+        match error.state_transition {
+            // Licensing ends and the connection continues, whatever the code
+            StateTransition::NoTransition => {
+                self.state = LicenseClientState::Completed;
+                Ok(None)
+            }
+            StateTransition::TotalAbort => Err(PduError::ParseError(format!(
+                "License error {:?}, connection aborted",
+                error.error_code
+            ))),
+            StateTransition::ResetPhaseToStart => {
+                self.state = LicenseClientState::AwaitingLicenseRequest;
+                self.keys = None;
+                self.last_message = None;
+                Ok(None)
+            }
+            StateTransition::ResendLastMessage => match &self.last_message {
+                Some(message) => Ok(Some(message.clone())),
+                None => Err(PduError::ParseError(format!(
+                    "License error {:?} asks to resend, but nothing was sent",
+                    error.error_code
+                ))),
+            },
+        }
+    }
+
+    fn on_license_request(&mut self, request: &ServerLicenseRequest) -> Result<LicenseMessage> {
+        if !request
+            .key_exchange_algorithms
+            .contains(&KEY_EXCHANGE_ALG_RSA)
+        {
+            return Err(PduError::ParseError(format!(
+                "Unsupported key exchange algorithms: {:?}",
+                request.key_exchange_algorithms
+            )));
+        }
+
+        let certificate = match request.certificate()? {
+            Some(certificate) => certificate,
+            None => self.config.server_certificate.clone().ok_or_else(|| {
+                PduError::ParseError("No server certificate for license key exchange".to_string())
+            })?,
+        };
+        let mut encrypted_premaster_secret = certificate
+            .public_key()?
+            .encrypt(&self.config.premaster_secret)?;
+        encrypted_premaster_secret.extend_from_slice(&[0u8; PREMASTER_PADDING_LENGTH]);
+
+        let keys = LicenseKeys::derive(
+            &self.config.premaster_secret,
+            &self.config.client_random,
+            &request.server_random,
+        );
+
+        let product = &request.product_info;
+        let stored = request.scope_list.scopes.iter().find_map(|scope| {
+            self.store.load(&LicenseKey::new(
+                scope,
+                &product.company_name,
+                &product.product_id,
+            ))
+        });
+
+        let message = match stored {
+            Some(license_info) => {
+                let hwid = self.config.hardware_id.to_bytes();
+                LicenseMessage::LicenseInfo(ClientLicenseInfo {
+                    key_exchange_algorithm: KEY_EXCHANGE_ALG_RSA,
+                    platform_id: DEFAULT_PLATFORM_ID,
+                    client_random: self.config.client_random,
+                    encrypted_premaster_secret,
+                    license_info,
+                    encrypted_hwid: keys.apply_cipher(&hwid),
+                    mac_data: keys.mac(&hwid),
+                })
+            }
+            None => LicenseMessage::NewLicenseRequest(ClientNewLicenseRequest::new(
+                self.config.client_random,
+                encrypted_premaster_secret,
+                &self.config.user_name,
+                &self.config.machine_name,
+            )),
+        };
+
+        self.keys = Some(keys);
+        self.state = LicenseClientState::AwaitingPlatformChallenge;
+        Ok(message)
+    }
+
+    fn on_platform_challenge(
+        &mut self,
+        challenge: &ServerPlatformChallenge,
+    ) -> Result<LicenseMessage> {
+        let keys = self.keys()?;
+        let decrypted = keys.apply_cipher(&challenge.encrypted_challenge);
+        if keys.mac(&decrypted) != challenge.mac_data {
+            return Err(PduError::ParseError(
+                "Invalid platform challenge MAC".to_string(),
+            ));
+        }
+
+        let response_data = PlatformChallengeResponseData::new(decrypted);
+        let mut response = Vec::with_capacity(response_data.size() + ClientHardwareId::SIZE);
+        response_data.encode(&mut response)?;
+        let hwid = self.config.hardware_id.to_bytes();
+
+        let encrypted_response = keys.apply_cipher(&response);
+        let encrypted_hwid = keys.apply_cipher(&hwid);
+        response.extend_from_slice(&hwid);
+        let mac_data = keys.mac(&response);
+
+        self.state = LicenseClientState::AwaitingNewLicense;
+        Ok(LicenseMessage::PlatformChallengeResponse(
+            ClientPlatformChallengeResponse {
+                encrypted_response,
+                encrypted_hwid,
+                mac_data,
+            },
+        ))
+    }
+
+    fn on_new_license(&mut self, license: &ServerNewLicense) -> Result<()> {
+        let keys = self.keys()?;
+        let decrypted = keys.apply_cipher(&license.encrypted_license_info);
+        if keys.mac(&decrypted) != license.mac_data {
+            return Err(PduError::ParseError("Invalid new license MAC".to_string()));
+        }
+
+        let info = NewLicenseInfo::decode(&mut decrypted.as_slice())?;
+        self.store.save(
+            &LicenseKey::new(&info.scope, &info.company_name, &info.product_id),
+            &info.license_info,
+        );
+
+        self.state = LicenseClientState::Completed;
+        Ok(())
+    }
+
+    fn keys(&self) -> Result<&LicenseKeys> {
+        self.keys
+            .as_ref()
+            .ok_or_else(|| PduError::ParseError("Licensing keys not derived".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdu::licensing::{LicenseErrorCode, ProductInfo, ScopeList};
+    use crate::pdu::security::{ProprietaryCertificate, RsaPublicKey};
+
+    fn config() -> LicenseClientConfig {
+        LicenseClientConfig {
+            user_name: "user".to_string(),
+            machine_name: "WORKSTATION".to_string(),
+            client_random: [0x11; RANDOM_LENGTH],
+            premaster_secret: [0x22; PREMASTER_SECRET_LENGTH],
+            hardware_id: ClientHardwareId::from_machine_name("WORKSTATION"),
+            server_certificate: None,
+        }
+    }
+
+    fn license_request() -> LicenseMessage {
+        // 512-bit key from the X.509 test certificate
+        let modulus = hex::decode(concat!(
+            "d04665436f4ef741502eedb6a7558c1397dab40818d33f288cc590761788399c",
+            "bc78594c60207abf3e8bc68c40f0998b3dbd6b073c8de7b263bfcf2677846931"
+        ))
+        .unwrap();
+        let certificate = ServerCertificate::Proprietary(ProprietaryCertificate::new(
+            RsaPublicKey::new(65537, &modulus).unwrap(),
+        ));
+        let mut certificate_bytes = Vec::new();
+        certificate.encode(&mut certificate_bytes).unwrap();
+
+        LicenseMessage::LicenseRequest(ServerLicenseRequest::new(
+            [0x33; RANDOM_LENGTH],
+            ProductInfo::new(0x0006_0000, "Microsoft Corporation", "A02"),
+            certificate_bytes,
+            ScopeList::new(vec!["microsoft.com".to_string()]),
+        ))
+    }
+
+    fn server_keys() -> LicenseKeys {
+        LicenseKeys::derive(
+            &[0x22; PREMASTER_SECRET_LENGTH],
+            &[0x11; RANDOM_LENGTH],
+            &[0x33; RANDOM_LENGTH],
+        )
+    }
+
+    fn platform_challenge(keys: &LicenseKeys) -> LicenseMessage {
+        LicenseMessage::PlatformChallenge(ServerPlatformChallenge {
+            connect_flags: 0,
+            encrypted_challenge: keys.apply_cipher(b"TEST\0"),
+            mac_data: keys.mac(b"TEST\0"),
+        })
+    }
+
+    #[test]
+    fn test_license_client_new_license() {
+        let store = Arc::new(MemoryLicenseStore::new());
+        let mut client = LicenseClient::new(config(), store.clone());
+        let keys = server_keys();
+        let hwid = config().hardware_id.to_bytes();
+
+        let Some(LicenseMessage::NewLicenseRequest(request)) =
+            client.process(&license_request()).unwrap()
+        else {
+            panic!("expected new license request");
+        };
+        assert_eq!(request.client_random, [0x11; RANDOM_LENGTH]);
+        assert_eq!(request.encrypted_premaster_secret.len(), 64 + 8);
+        assert_eq!(request.user_name, "user");
+        assert_eq!(request.machine_name, "WORKSTATION");
+        assert_eq!(
+            client.state(),
+            LicenseClientState::AwaitingPlatformChallenge
+        );
+
+        let Some(LicenseMessage::PlatformChallengeResponse(response)) =
+            client.process(&platform_challenge(&keys)).unwrap()
+        else {
+            panic!("expected platform challenge response");
+        };
+        let mut response_data = keys.apply_cipher(&response.encrypted_response);
+        let decoded = PlatformChallengeResponseData::decode(&mut response_data.as_slice()).unwrap();
+        assert_eq!(
+            decoded,
+            PlatformChallengeResponseData::new(b"TEST\0".to_vec())
+        );
+        assert_eq!(keys.apply_cipher(&response.encrypted_hwid), hwid);
+        response_data.extend_from_slice(&hwid);
+        assert_eq!(response.mac_data, keys.mac(&response_data));
+
+        let info = NewLicenseInfo {
+            version: 0x0006_0000,
+            scope: "microsoft.com".to_string(),
+            company_name: "Microsoft Corporation".to_string(),
+            product_id: "A02".to_string(),
+            license_info: b"CAL".to_vec(),
+        };
+        let mut info_bytes = Vec::new();
+        info.encode(&mut info_bytes).unwrap();
+        let new_license = LicenseMessage::NewLicense(ServerNewLicense {
+            encrypted_license_info: keys.apply_cipher(&info_bytes),
+            mac_data: keys.mac(&info_bytes),
+        });
+        assert!(client.process(&new_license).unwrap().is_none());
+        assert!(client.is_completed());
+        assert_eq!(store.len(), 1);
+
+        // The next connection presents the stored license
+        let mut client = LicenseClient::new(config(), store.clone());
+        let Some(LicenseMessage::LicenseInfo(license_info)) =
+            client.process(&license_request()).unwrap()
+        else {
+            panic!("expected license information");
+        };
+        assert_eq!(license_info.license_info, b"CAL");
+        assert_eq!(keys.apply_cipher(&license_info.encrypted_hwid), hwid);
+        assert_eq!(license_info.mac_data, keys.mac(&hwid));
+    }
+
+    #[test]
+    fn test_license_client_errors() {
+        let store: Arc<dyn LicenseStore> = Arc::new(MemoryLicenseStore::new());
+
+        let mut client = LicenseClient::new(config(), store.clone());
+        let valid_client = LicenseMessage::ErrorAlert(LicenseErrorMessage::valid_client());
+        assert!(client.process(&valid_client).unwrap().is_none());
+        assert!(client.is_completed());
+
+        // Any code with ST_NO_TRANSITION ends licensing
+        let mut client = LicenseClient::new(config(), store.clone());
+        let no_license_server = LicenseMessage::ErrorAlert(LicenseErrorMessage::new(
+            LicenseErrorCode::NoLicenseServer,
+            StateTransition::NoTransition,
+        ));
+        assert!(client.process(&no_license_server).unwrap().is_none());
+        assert!(client.is_completed());
+
+        let mut client = LicenseClient::new(config(), store.clone());
+        let invalid_client = LicenseMessage::ErrorAlert(LicenseErrorMessage::new(
+            LicenseErrorCode::InvalidClient,
+            StateTransition::TotalAbort,
+        ));
+        assert!(client.process(&invalid_client).is_err());
+
+        // Nothing sent yet, so there is nothing to resend
+        let resend = LicenseMessage::ErrorAlert(LicenseErrorMessage::new(
+            LicenseErrorCode::InvalidMac,
+            StateTransition::ResendLastMessage,
+        ));
+        let mut client = LicenseClient::new(config(), store.clone());
+        assert!(client.process(&resend).is_err());
+
+        // Platform challenge before the license request
+        let mut client = LicenseClient::new(config(), store.clone());
+        assert!(client.process(&platform_challenge(&server_keys())).is_err());
+
+        // Challenge MAC computed with the wrong keys
+        client.process(&license_request()).unwrap();
+        let wrong_keys = LicenseKeys::derive(
+            &[0x44; PREMASTER_SECRET_LENGTH],
+            &[0x11; RANDOM_LENGTH],
+            &[0x33; RANDOM_LENGTH],
+        );
+        assert!(client.process(&platform_challenge(&wrong_keys)).is_err());
+    }
+
+    #[test]
+    fn test_license_client_state_transitions() {
+        let store: Arc<dyn LicenseStore> = Arc::new(MemoryLicenseStore::new());
+        let mut client = LicenseClient::new(config(), store);
+        let request = client.process(&license_request()).unwrap();
+        assert!(request.is_some());
+
+        let resend = LicenseMessage::ErrorAlert(LicenseErrorMessage::new(
+            LicenseErrorCode::InvalidMac,
+            StateTransition::ResendLastMessage,
+        ));
+        assert_eq!(client.process(&resend).unwrap(), request);
+        assert_eq!(
+            client.state(),
+            LicenseClientState::AwaitingPlatformChallenge
+        );
+
+        let reset = LicenseMessage::ErrorAlert(LicenseErrorMessage::new(
+            LicenseErrorCode::InvalidScope,
+            StateTransition::ResetPhaseToStart,
+        ));
+        assert!(client.process(&reset).unwrap().is_none());
+        assert_eq!(client.state(), LicenseClientState::AwaitingLicenseRequest);
+        assert!(client.process(&resend).is_err());
+        assert_eq!(client.process(&license_request()).unwrap(), request);
+    }
+
+    #[test]
+    fn test_license_client_zero_modulus() {
+        let store: Arc<dyn LicenseStore> = Arc::new(MemoryLicenseStore::new());
+        let LicenseMessage::LicenseRequest(request) = license_request() else {
+            unreachable!();
+        };
+
+        // Certificate in the License Request with the modulus zeroed out
+        let mut zeroed = request.clone();
+        zeroed.server_certificate[36..100].fill(0);
+        let mut client = LicenseClient::new(config(), store.clone());
+        assert!(
+            client
+                .process(&LicenseMessage::LicenseRequest(zeroed))
+                .is_err()
+        );
+        assert_eq!(client.state(), LicenseClientState::AwaitingLicenseRequest);
+
+        // SC_SECURITY certificate built around a zero modulus
+        let ServerCertificate::Proprietary(mut certificate) =
+            request.certificate().unwrap().unwrap()
+        else {
+            unreachable!();
+        };
+        certificate.public_key.modulus.fill(0);
+        let config = config().with_server_certificate(ServerCertificate::Proprietary(certificate));
+        let mut empty = request.clone();
+        empty.server_certificate.clear();
+        let mut client = LicenseClient::new(config, store);
+        assert!(
+            client
+                .process(&LicenseMessage::LicenseRequest(empty))
+                .is_err()
+        );
+    }
+}
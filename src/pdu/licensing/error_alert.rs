@@ -0,0 +1,161 @@
+use super::blob::{BlobType, LicenseBinaryBlob};
+use crate::pdu::{Pdu, PduError, Result};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Read, Write};
+
+/// License error code (MS-RDPBCGR 2.2.1.12.1.3)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LicenseErrorCode {
+    /// ERR_INVALID_SERVER_CERTIFICATE
+    InvalidServerCertificate,
+    /// ERR_NO_LICENSE
+    NoLicense,
+    /// ERR_INVALID_MAC
+    InvalidMac,
+    /// ERR_INVALID_SCOPE
+    InvalidScope,
+    /// ERR_NO_LICENSE_SERVER
+    NoLicenseServer,
+    /// STATUS_VALID_CLIENT - No further licensing messages follow
+    StatusValidClient,
+    /// ERR_INVALID_CLIENT
+    InvalidClient,
+    /// ERR_INVALID_PRODUCTID
+    InvalidProductId,
+    /// ERR_INVALID_MESSAGE_LEN
+    InvalidMessageLength,
+    /// Code not defined by MS-RDPBCGR
+    Other(u32),
+}
+
+impl LicenseErrorCode {
+    pub fn from_u32(value: u32) -> Self {
+        match value {
+            0x0000_0001 => LicenseErrorCode::InvalidServerCertificate,
+            0x0000_0002 => LicenseErrorCode::NoLicense,
+            0x0000_0003 => LicenseErrorCode::InvalidMac,
+            0x0000_0004 => LicenseErrorCode::InvalidScope,
+            0x0000_0006 => LicenseErrorCode::NoLicenseServer,
+            0x0000_0007 => LicenseErrorCode::StatusValidClient,
+            0x0000_0008 => LicenseErrorCode::InvalidClient,
+            0x0000_000B => LicenseErrorCode::InvalidProductId,
+            0x0000_000C => LicenseErrorCode::InvalidMessageLength,
+            other => LicenseErrorCode::Other(other),
+        }
+    }
+
+    pub fn as_u32(self) -> u32 {
+        match self {
+            LicenseErrorCode::InvalidServerCertificate => 0x0000_0001,
+            LicenseErrorCode::NoLicense => 0x0000_0002,
+            LicenseErrorCode::InvalidMac => 0x0000_0003,
+            LicenseErrorCode::InvalidScope => 0x0000_0004,
+            LicenseErrorCode::NoLicenseServer => 0x0000_0006,
+            LicenseErrorCode::StatusValidClient => 0x0000_0007,
+            LicenseErrorCode::InvalidClient => 0x0000_0008,
+            LicenseErrorCode::InvalidProductId => 0x0000_000B,
+            LicenseErrorCode::InvalidMessageLength => 0x0000_000C,
+            LicenseErrorCode::Other(value) => value,
+        }
+    }
+}
+
+/// Licensing state transition (MS-RDPBCGR 2.2.1.12.1.3)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum StateTransition {
+    /// ST_TOTAL_ABORT
+    TotalAbort = 0x0000_0001,
+    /// ST_NO_TRANSITION
+    NoTransition = 0x0000_0002,
+    /// ST_RESET_PHASE_TO_START
+    ResetPhaseToStart = 0x0000_0003,
+    /// ST_RESEND_LAST_MESSAGE
+    ResendLastMessage = 0x0000_0004,
+}
+
+impl StateTransition {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0x0000_0001 => Some(StateTransition::TotalAbort),
+            0x0000_0002 => Some(StateTransition::NoTransition),
+            0x0000_0003 => Some(StateTransition::ResetPhaseToStart),
+            0x0000_0004 => Some(StateTransition::ResendLastMessage),
+            _ => None,
+        }
+    }
+
+    pub fn as_u32(self) -> u32 {
+        self as u32
+    }
+}
+
+/// License Error Message (LICENSE_ERROR_MESSAGE, MS-RDPBCGR 2.2.1.12.1.3)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LicenseErrorMessage {
+    /// Error or status code
+    pub error_code: LicenseErrorCode,
+    /// Licensing state transition
+    pub state_transition: StateTransition,
+    /// Error description (BB_ERROR_BLOB data, usually empty)
+    pub error_info: Vec<u8>,
+}
+
+impl LicenseErrorMessage {
+    /// Create error message
+    pub fn new(error_code: LicenseErrorCode, state_transition: StateTransition) -> Self {
+        Self {
+            error_code,
+            state_transition,
+            error_info: Vec::new(),
+        }
+    }
+
+    /// STATUS_VALID_CLIENT with ST_NO_TRANSITION (MS-RDPBCGR 2.2.1.12.1.3)
+    ///
+    /// Servers send this instead of a full licensing exchange when no license
+    /// is required, and to accept a presented license.
+    pub fn valid_client() -> Self {
+        Self::new(
+            LicenseErrorCode::StatusValidClient,
+            StateTransition::NoTransition,
+        )
+    }
+
+    /// Whether this is the STATUS_VALID_CLIENT shortcut
+    pub fn is_valid_client(&self) -> bool {
+        self.error_code == LicenseErrorCode::StatusValidClient
+            && self.state_transition == StateTransition::NoTransition
+    }
+}
+
+impl Pdu for LicenseErrorMessage {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_u32::<LittleEndian>(self.error_code.as_u32())?;
+        buffer.write_u32::<LittleEndian>(self.state_transition.as_u32())?;
+        LicenseBinaryBlob::new(BlobType::Error, self.error_info.clone()).encode(buffer)
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let error_code = LicenseErrorCode::from_u32(buffer.read_u32::<LittleEndian>()?);
+
+        let state_transition = buffer.read_u32::<LittleEndian>()?;
+        let state_transition = StateTransition::from_u32(state_transition).ok_or_else(|| {
+            PduError::ParseError(format!(
+                "Unknown license state transition: {:#x}",
+                state_transition
+            ))
+        })?;
+
+        let error_info = LicenseBinaryBlob::decode(buffer, BlobType::Error)?.data;
+        Ok(Self {
+            error_code,
+            state_transition,
+            error_info,
+        })
+    }
+
+    fn size(&self) -> usize {
+        8 + 4 + self.error_info.len()
+    }
+}
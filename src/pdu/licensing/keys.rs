@@ -0,0 +1,86 @@
+use crate::pdu::security::RANDOM_LENGTH;
+use crate::pdu::security::rc4::Rc4;
+use crate::pdu::security::keys::{hash_blob, mac_digest};
+use md5::{Digest, Md5};
+
+/// Licensing premaster secret length (bytes)
+pub const PREMASTER_SECRET_LENGTH: usize = 48;
+
+/// Licensing session keys (MS-RDPELE 5.1.3)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LicenseKeys {
+    /// MAC salt key
+    pub mac_salt_key: [u8; 16],
+    /// RC4 licensing encryption key
+    pub encryption_key: [u8; 16],
+}
+
+impl LicenseKeys {
+    /// Derive keys from the premaster secret and the licensing randoms
+    pub fn derive(
+        premaster_secret: &[u8; PREMASTER_SECRET_LENGTH],
+        client_random: &[u8; RANDOM_LENGTH],
+        server_random: &[u8; RANDOM_LENGTH],
+    ) -> Self {
+        let inputs: [&[u8]; 3] = [b"A", b"BB", b"CCC"];
+        let master_secret = hash_blob(premaster_secret, inputs, client_random, server_random);
+        // MasterHash salts with the server random first
+        let session_key_blob = hash_blob(&master_secret, inputs, server_random, client_random);
+
+        let mut mac_salt_key = [0u8; 16];
+        mac_salt_key.copy_from_slice(&session_key_blob[..16]);
+        let encryption_key = Md5::new()
+            .chain_update(&session_key_blob[16..32])
+            .chain_update(client_random)
+            .chain_update(server_random)
+            .finalize()
+            .into();
+
+        Self {
+            mac_salt_key,
+            encryption_key,
+        }
+    }
+
+    /// Encrypt or decrypt licensing data (each blob starts a fresh RC4 stream)
+    pub fn apply_cipher(&self, data: &[u8]) -> Vec<u8> {
+        let mut output = data.to_vec();
+        Rc4::new(&self.encryption_key).process(&mut output);
+        output
+    }
+
+    /// MACData over unencrypted licensing data
+    pub fn mac(&self, data: &[u8]) -> [u8; 16] {
+        mac_digest(&self.mac_salt_key, data, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_license_keys() {
+        let premaster_secret: [u8; PREMASTER_SECRET_LENGTH] = std::array::from_fn(|i| i as u8);
+        let client_random: [u8; RANDOM_LENGTH] = std::array::from_fn(|i| 0x40 + i as u8);
+        let server_random: [u8; RANDOM_LENGTH] = std::array::from_fn(|i| 0x80 + i as u8);
+
+        let keys = LicenseKeys::derive(&premaster_secret, &client_random, &server_random);
+        assert_eq!(
+            hex::encode(keys.mac_salt_key),
+            "ea67e1ce54bcfd92bafae8354bed6a14"
+        );
+        assert_eq!(
+            hex::encode(keys.encryption_key),
+            "5559feaf7c86915b7c27e10c63357ef3"
+        );
+        assert_eq!(
+            hex::encode(keys.mac(b"challenge")),
+            "cb45f7eb217382828445b1350e395955"
+        );
+
+        let encrypted = keys.apply_cipher(b"challenge");
+        assert_eq!(hex::encode(&encrypted), "67c9f464f54c3ef99b");
+        assert_eq!(keys.apply_cipher(&encrypted), b"challenge");
+    }
+}
@@ -0,0 +1,110 @@
+use super::blob::{BlobType, LicenseBinaryBlob, ProductInfo, ScopeList};
+use crate::pdu::security::{RANDOM_LENGTH, ServerCertificate};
+use crate::pdu::{Pdu, PduError, Result};
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::{Read, Write};
+
+/// KEY_EXCHANGE_ALG_RSA
+pub const KEY_EXCHANGE_ALG_RSA: u32 = 0x0000_0001;
+
+/// Server License Request (SERVER_LICENSE_REQUEST, MS-RDPELE 2.2.2.1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerLicenseRequest {
+    /// Server random
+    pub server_random: [u8; RANDOM_LENGTH],
+    /// Product information
+    pub product_info: ProductInfo,
+    /// Supported key exchange algorithms
+    pub key_exchange_algorithms: Vec<u32>,
+    /// Server certificate (may be empty when sent in SC_SECURITY)
+    pub server_certificate: Vec<u8>,
+    /// License issuer scopes
+    pub scope_list: ScopeList,
+}
+
+impl ServerLicenseRequest {
+    /// Create license request supporting RSA key exchange
+    pub fn new(
+        server_random: [u8; RANDOM_LENGTH],
+        product_info: ProductInfo,
+        server_certificate: Vec<u8>,
+        scope_list: ScopeList,
+    ) -> Self {
+        Self {
+            server_random,
+            product_info,
+            key_exchange_algorithms: vec![KEY_EXCHANGE_ALG_RSA],
+            server_certificate,
+            scope_list,
+        }
+    }
+
+    /// Parse the server certificate (None when the blob is empty)
+    pub fn certificate(&self) -> Result<Option<ServerCertificate>> {
+        if self.server_certificate.is_empty() {
+            return Ok(None);
+        }
+        ServerCertificate::decode(&mut self.server_certificate.as_slice()).map(Some)
+    }
+
+    fn key_exchange_blob(&self) -> LicenseBinaryBlob {
+        let data = self
+            .key_exchange_algorithms
+            .iter()
+            .flat_map(|algorithm| algorithm.to_le_bytes())
+            .collect();
+        LicenseBinaryBlob::new(BlobType::KeyExchangeAlgorithm, data)
+    }
+}
+
+impl Pdu for ServerLicenseRequest {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_all(&self.server_random)?;
+        self.product_info.encode(buffer)?;
+        self.key_exchange_blob().encode(buffer)?;
+        LicenseBinaryBlob::new(BlobType::Certificate, self.server_certificate.clone())
+            .encode(buffer)?;
+        self.scope_list.encode(buffer)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let mut server_random = [0u8; RANDOM_LENGTH];
+        buffer.read_exact(&mut server_random)?;
+        let product_info = ProductInfo::decode(buffer)?;
+
+        let key_exchange = LicenseBinaryBlob::decode(buffer, BlobType::KeyExchangeAlgorithm)?;
+        if !key_exchange.data.len().is_multiple_of(4) {
+            return Err(PduError::ParseError(format!(
+                "Invalid key exchange list length: {}",
+                key_exchange.data.len()
+            )));
+        }
+        let mut cursor = key_exchange.data.as_slice();
+        let mut key_exchange_algorithms = Vec::new();
+        while !cursor.is_empty() {
+            key_exchange_algorithms.push(cursor.read_u32::<LittleEndian>()?);
+        }
+
+        let server_certificate = LicenseBinaryBlob::decode(buffer, BlobType::Certificate)?.data;
+        let scope_list = ScopeList::decode(buffer)?;
+
+        Ok(Self {
+            server_random,
+            product_info,
+            key_exchange_algorithms,
+            server_certificate,
+            scope_list,
+        })
+    }
+
+    fn size(&self) -> usize {
+        RANDOM_LENGTH
+            + self.product_info.size()
+            + 4
+            + self.key_exchange_algorithms.len() * 4
+            + 4
+            + self.server_certificate.len()
+            + self.scope_list.size()
+    }
+}
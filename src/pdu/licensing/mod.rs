@@ -0,0 +1,30 @@
+// RDP Licensing (MS-RDPELE)
+pub mod blob;
+pub mod client;
+pub mod error_alert;
+pub mod keys;
+pub mod license_request;
+pub mod new_license;
+pub mod new_license_request;
+pub mod pdu;
+pub mod platform_challenge;
+pub mod preamble;
+
+pub use blob::{BlobType, LicenseBinaryBlob, ProductInfo, ScopeList};
+pub use client::{
+    LicenseClient, LicenseClientConfig, LicenseClientState, LicenseKey, LicenseStore,
+    MemoryLicenseStore,
+};
+pub use error_alert::{LicenseErrorCode, LicenseErrorMessage, StateTransition};
+pub use keys::{LicenseKeys, PREMASTER_SECRET_LENGTH};
+pub use license_request::{KEY_EXCHANGE_ALG_RSA, ServerLicenseRequest};
+pub use new_license::{NewLicenseInfo, ServerNewLicense};
+pub use new_license_request::{ClientLicenseInfo, ClientNewLicenseRequest, DEFAULT_PLATFORM_ID};
+pub use pdu::{LicenseMessage, LicensePdu};
+pub use platform_challenge::{
+    ClientHardwareId, ClientPlatformChallengeResponse, PlatformChallengeResponseData,
+    ServerPlatformChallenge,
+};
+pub use preamble::{
+    LicenseMessageType, LicensePreamble, PREAMBLE_VERSION_2_0, PREAMBLE_VERSION_3_0, PreambleFlags,
+};
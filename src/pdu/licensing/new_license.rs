@@ -0,0 +1,100 @@
+use super::blob::{
+    BlobType, LicenseBinaryBlob, ansi_string, decode_ansi_string, read_unicode_field, read_vec,
+    write_unicode_field,
+};
+use crate::pdu::rdp::connection::client_info::unicode_string_size;
+use crate::pdu::{Pdu, Result};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Read, Write};
+
+/// Server New License and Server Upgrade License (MS-RDPELE 2.2.2.6, 2.2.2.7)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerNewLicense {
+    /// New License Information encrypted with the licensing encryption key
+    pub encrypted_license_info: Vec<u8>,
+    /// MAC of the unencrypted license information
+    pub mac_data: [u8; 16],
+}
+
+impl Pdu for ServerNewLicense {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        LicenseBinaryBlob::new(BlobType::EncryptedData, self.encrypted_license_info.clone())
+            .encode(buffer)?;
+        buffer.write_all(&self.mac_data)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let encrypted_license_info =
+            LicenseBinaryBlob::decode(buffer, BlobType::EncryptedData)?.data;
+        let mut mac_data = [0u8; 16];
+        buffer.read_exact(&mut mac_data)?;
+        Ok(Self {
+            encrypted_license_info,
+            mac_data,
+        })
+    }
+
+    fn size(&self) -> usize {
+        4 + self.encrypted_license_info.len() + 16
+    }
+}
+
+/// New License Information (NEW_LICENSE_INFO, MS-RDPELE 2.2.2.6.1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewLicenseInfo {
+    /// License version
+    pub version: u32,
+    /// License issuer scope
+    pub scope: String,
+    /// Company name
+    pub company_name: String,
+    /// Product ID
+    pub product_id: String,
+    /// License (CAL) to store and present in later connections
+    pub license_info: Vec<u8>,
+}
+
+impl Pdu for NewLicenseInfo {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        let scope = ansi_string(&self.scope);
+        buffer.write_u32::<LittleEndian>(self.version)?;
+        buffer.write_u32::<LittleEndian>(scope.len() as u32)?;
+        buffer.write_all(&scope)?;
+        write_unicode_field(buffer, &self.company_name)?;
+        write_unicode_field(buffer, &self.product_id)?;
+        buffer.write_u32::<LittleEndian>(self.license_info.len() as u32)?;
+        buffer.write_all(&self.license_info)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let version = buffer.read_u32::<LittleEndian>()?;
+        let scope_length = buffer.read_u32::<LittleEndian>()? as usize;
+        let scope = decode_ansi_string(&read_vec(buffer, scope_length)?);
+        let company_name = read_unicode_field(buffer)?;
+        let product_id = read_unicode_field(buffer)?;
+        let license_length = buffer.read_u32::<LittleEndian>()? as usize;
+        let license_info = read_vec(buffer, license_length)?;
+
+        Ok(Self {
+            version,
+            scope,
+            company_name,
+            product_id,
+            license_info,
+        })
+    }
+
+    fn size(&self) -> usize {
+        4 + 4
+            + self.scope.len()
+            + 1
+            + 4
+            + unicode_string_size(&self.company_name)
+            + 4
+            + unicode_string_size(&self.product_id)
+            + 4
+            + self.license_info.len()
+    }
+}
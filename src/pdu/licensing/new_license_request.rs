@@ -0,0 +1,168 @@
+use super::blob::{BlobType, LicenseBinaryBlob, ansi_string, decode_ansi_string};
+use super::license_request::KEY_EXCHANGE_ALG_RSA;
+use crate::pdu::security::RANDOM_LENGTH;
+use crate::pdu::{Pdu, Result};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Read, Write};
+
+/// CLIENT_OS_ID_WINNT_POST_52
+pub const CLIENT_OS_ID_WINNT_POST_52: u32 = 0x0400_0000;
+
+/// CLIENT_IMAGE_ID_MICROSOFT
+pub const CLIENT_IMAGE_ID_MICROSOFT: u32 = 0x0001_0000;
+
+/// Platform ID sent by this client
+pub const DEFAULT_PLATFORM_ID: u32 = CLIENT_OS_ID_WINNT_POST_52 | CLIENT_IMAGE_ID_MICROSOFT;
+
+/// Client New License Request (CLIENT_NEW_LICENSE_REQUEST, MS-RDPELE 2.2.2.2)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientNewLicenseRequest {
+    /// Selected key exchange algorithm
+    pub key_exchange_algorithm: u32,
+    /// Client operating system and ISV
+    pub platform_id: u32,
+    /// Client random
+    pub client_random: [u8; RANDOM_LENGTH],
+    /// Premaster secret encrypted with the server public key, 8 zero bytes appended
+    pub encrypted_premaster_secret: Vec<u8>,
+    /// Client user name
+    pub user_name: String,
+    /// Client machine name
+    pub machine_name: String,
+}
+
+impl ClientNewLicenseRequest {
+    /// Create new license request with RSA key exchange
+    pub fn new(
+        client_random: [u8; RANDOM_LENGTH],
+        encrypted_premaster_secret: Vec<u8>,
+        user_name: &str,
+        machine_name: &str,
+    ) -> Self {
+        Self {
+            key_exchange_algorithm: KEY_EXCHANGE_ALG_RSA,
+            platform_id: DEFAULT_PLATFORM_ID,
+            client_random,
+            encrypted_premaster_secret,
+            user_name: user_name.to_string(),
+            machine_name: machine_name.to_string(),
+        }
+    }
+}
+
+impl Pdu for ClientNewLicenseRequest {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_u32::<LittleEndian>(self.key_exchange_algorithm)?;
+        buffer.write_u32::<LittleEndian>(self.platform_id)?;
+        buffer.write_all(&self.client_random)?;
+        LicenseBinaryBlob::new(BlobType::Random, self.encrypted_premaster_secret.clone())
+            .encode(buffer)?;
+        LicenseBinaryBlob::new(BlobType::ClientUserName, ansi_string(&self.user_name))
+            .encode(buffer)?;
+        LicenseBinaryBlob::new(BlobType::ClientMachineName, ansi_string(&self.machine_name))
+            .encode(buffer)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let key_exchange_algorithm = buffer.read_u32::<LittleEndian>()?;
+        let platform_id = buffer.read_u32::<LittleEndian>()?;
+        let mut client_random = [0u8; RANDOM_LENGTH];
+        buffer.read_exact(&mut client_random)?;
+
+        let encrypted_premaster_secret = LicenseBinaryBlob::decode(buffer, BlobType::Random)?.data;
+        let user_name = LicenseBinaryBlob::decode(buffer, BlobType::ClientUserName)?;
+        let machine_name = LicenseBinaryBlob::decode(buffer, BlobType::ClientMachineName)?;
+
+        Ok(Self {
+            key_exchange_algorithm,
+            platform_id,
+            client_random,
+            encrypted_premaster_secret,
+            user_name: decode_ansi_string(&user_name.data),
+            machine_name: decode_ansi_string(&machine_name.data),
+        })
+    }
+
+    fn size(&self) -> usize {
+        8 + RANDOM_LENGTH
+            + 4
+            + self.encrypted_premaster_secret.len()
+            + 4
+            + self.user_name.len()
+            + 1
+            + 4
+            + self.machine_name.len()
+            + 1
+    }
+}
+
+/// Client License Information (CLIENT_LICENSE_INFO, MS-RDPELE 2.2.2.3)
+///
+/// Presents a license issued in an earlier connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientLicenseInfo {
+    /// Selected key exchange algorithm
+    pub key_exchange_algorithm: u32,
+    /// Client operating system and ISV
+    pub platform_id: u32,
+    /// Client random
+    pub client_random: [u8; RANDOM_LENGTH],
+    /// Premaster secret encrypted with the server public key, 8 zero bytes appended
+    pub encrypted_premaster_secret: Vec<u8>,
+    /// Stored license (pbLicenseInfo of the New License message)
+    pub license_info: Vec<u8>,
+    /// Client hardware ID encrypted with the licensing encryption key
+    pub encrypted_hwid: Vec<u8>,
+    /// MAC of the unencrypted hardware ID
+    pub mac_data: [u8; 16],
+}
+
+impl Pdu for ClientLicenseInfo {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_u32::<LittleEndian>(self.key_exchange_algorithm)?;
+        buffer.write_u32::<LittleEndian>(self.platform_id)?;
+        buffer.write_all(&self.client_random)?;
+        LicenseBinaryBlob::new(BlobType::Random, self.encrypted_premaster_secret.clone())
+            .encode(buffer)?;
+        LicenseBinaryBlob::new(BlobType::Data, self.license_info.clone()).encode(buffer)?;
+        LicenseBinaryBlob::new(BlobType::EncryptedData, self.encrypted_hwid.clone())
+            .encode(buffer)?;
+        buffer.write_all(&self.mac_data)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let key_exchange_algorithm = buffer.read_u32::<LittleEndian>()?;
+        let platform_id = buffer.read_u32::<LittleEndian>()?;
+        let mut client_random = [0u8; RANDOM_LENGTH];
+        buffer.read_exact(&mut client_random)?;
+
+        let encrypted_premaster_secret = LicenseBinaryBlob::decode(buffer, BlobType::Random)?.data;
+        let license_info = LicenseBinaryBlob::decode(buffer, BlobType::Data)?.data;
+        let encrypted_hwid = LicenseBinaryBlob::decode(buffer, BlobType::EncryptedData)?.data;
+        let mut mac_data = [0u8; 16];
+        buffer.read_exact(&mut mac_data)?;
+
+        Ok(Self {
+            key_exchange_algorithm,
+            platform_id,
+            client_random,
+            encrypted_premaster_secret,
+            license_info,
+            encrypted_hwid,
+            mac_data,
+        })
+    }
+
+    fn size(&self) -> usize {
+        8 + RANDOM_LENGTH
+            + 4
+            + self.encrypted_premaster_secret.len()
+            + 4
+            + self.license_info.len()
+            + 4
+            + self.encrypted_hwid.len()
+            + 16
+    }
+}
@@ -0,0 +1,246 @@
+use super::blob::read_vec;
+use super::error_alert::LicenseErrorMessage;
+use super::license_request::ServerLicenseRequest;
+use super::new_license::ServerNewLicense;
+use super::new_license_request::{ClientLicenseInfo, ClientNewLicenseRequest};
+use super::platform_challenge::{ClientPlatformChallengeResponse, ServerPlatformChallenge};
+use super::preamble::{LicenseMessageType, LicensePreamble, PREAMBLE_VERSION_3_0, PreambleFlags};
+use crate::pdu::{Pdu, PduError, Result};
+use std::io::{Read, Write};
+
+/// Licensing message body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LicenseMessage {
+    /// Server License Request
+    LicenseRequest(ServerLicenseRequest),
+    /// Server Platform Challenge
+    PlatformChallenge(ServerPlatformChallenge),
+    /// Server New License
+    NewLicense(ServerNewLicense),
+    /// Server Upgrade License
+    UpgradeLicense(ServerNewLicense),
+    /// Client License Information
+    LicenseInfo(ClientLicenseInfo),
+    /// Client New License Request
+    NewLicenseRequest(ClientNewLicenseRequest),
+    /// Client Platform Challenge Response
+    PlatformChallengeResponse(ClientPlatformChallengeResponse),
+    /// License Error Message
+    ErrorAlert(LicenseErrorMessage),
+}
+
+impl LicenseMessage {
+    /// Preamble message type
+    pub fn message_type(&self) -> LicenseMessageType {
+        match self {
+            LicenseMessage::LicenseRequest(_) => LicenseMessageType::LicenseRequest,
+            LicenseMessage::PlatformChallenge(_) => LicenseMessageType::PlatformChallenge,
+            LicenseMessage::NewLicense(_) => LicenseMessageType::NewLicense,
+            LicenseMessage::UpgradeLicense(_) => LicenseMessageType::UpgradeLicense,
+            LicenseMessage::LicenseInfo(_) => LicenseMessageType::LicenseInfo,
+            LicenseMessage::NewLicenseRequest(_) => LicenseMessageType::NewLicenseRequest,
+            LicenseMessage::PlatformChallengeResponse(_) => {
+                LicenseMessageType::PlatformChallengeResponse
+            }
+            LicenseMessage::ErrorAlert(_) => LicenseMessageType::ErrorAlert,
+        }
+    }
+
+    fn body_size(&self) -> usize {
+        match self {
+            LicenseMessage::LicenseRequest(pdu) => pdu.size(),
+            LicenseMessage::PlatformChallenge(pdu) => pdu.size(),
+            LicenseMessage::NewLicense(pdu) | LicenseMessage::UpgradeLicense(pdu) => pdu.size(),
+            LicenseMessage::LicenseInfo(pdu) => pdu.size(),
+            LicenseMessage::NewLicenseRequest(pdu) => pdu.size(),
+            LicenseMessage::PlatformChallengeResponse(pdu) => pdu.size(),
+            LicenseMessage::ErrorAlert(pdu) => pdu.size(),
+        }
+    }
+
+    fn encode_body(&self, buffer: &mut dyn Write) -> Result<()> {
+        match self {
+            LicenseMessage::LicenseRequest(pdu) => pdu.encode(buffer),
+            LicenseMessage::PlatformChallenge(pdu) => pdu.encode(buffer),
+            LicenseMessage::NewLicense(pdu) | LicenseMessage::UpgradeLicense(pdu) => {
+                pdu.encode(buffer)
+            }
+            LicenseMessage::LicenseInfo(pdu) => pdu.encode(buffer),
+            LicenseMessage::NewLicenseRequest(pdu) => pdu.encode(buffer),
+            LicenseMessage::PlatformChallengeResponse(pdu) => pdu.encode(buffer),
+            LicenseMessage::ErrorAlert(pdu) => pdu.encode(buffer),
+        }
+    }
+
+    fn decode_body(msg_type: LicenseMessageType, buffer: &mut dyn Read) -> Result<Self> {
+        Ok(match msg_type {
+            LicenseMessageType::LicenseRequest => {
+                LicenseMessage::LicenseRequest(ServerLicenseRequest::decode(buffer)?)
+            }
+            LicenseMessageType::PlatformChallenge => {
+                LicenseMessage::PlatformChallenge(ServerPlatformChallenge::decode(buffer)?)
+            }
+            LicenseMessageType::NewLicense => {
+                LicenseMessage::NewLicense(ServerNewLicense::decode(buffer)?)
+            }
+            LicenseMessageType::UpgradeLicense => {
+                LicenseMessage::UpgradeLicense(ServerNewLicense::decode(buffer)?)
+            }
+            LicenseMessageType::LicenseInfo => {
+                LicenseMessage::LicenseInfo(ClientLicenseInfo::decode(buffer)?)
+            }
+            LicenseMessageType::NewLicenseRequest => {
+                LicenseMessage::NewLicenseRequest(ClientNewLicenseRequest::decode(buffer)?)
+            }
+            LicenseMessageType::PlatformChallengeResponse => {
+                LicenseMessage::PlatformChallengeResponse(ClientPlatformChallengeResponse::decode(
+                    buffer,
+                )?)
+            }
+            LicenseMessageType::ErrorAlert => {
+                LicenseMessage::ErrorAlert(LicenseErrorMessage::decode(buffer)?)
+            }
+        })
+    }
+}
+
+/// Licensing PDU: preamble and message (MS-RDPBCGR 2.2.1.12)
+///
+/// The basic security header with SEC_LICENSE_PKT is not included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LicensePdu {
+    /// Licensing protocol version (PREAMBLE_VERSION_*)
+    pub version: u8,
+    /// Preamble flags
+    pub flags: PreambleFlags,
+    /// Message body
+    pub message: LicenseMessage,
+}
+
+impl LicensePdu {
+    /// Create PDU with PREAMBLE_VERSION_3_0
+    pub fn new(message: LicenseMessage) -> Self {
+        Self {
+            version: PREAMBLE_VERSION_3_0,
+            flags: PreambleFlags::empty(),
+            message,
+        }
+    }
+}
+
+impl Pdu for LicensePdu {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        let msg_size = u16::try_from(self.size()).map_err(|_| PduError::InvalidLength {
+            expected: u16::MAX as usize,
+            actual: self.size(),
+        })?;
+
+        LicensePreamble {
+            msg_type: self.message.message_type(),
+            version: self.version,
+            flags: self.flags,
+            msg_size,
+        }
+        .encode(buffer)?;
+        self.message.encode_body(buffer)
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let preamble = LicensePreamble::decode(buffer)?;
+        let body = read_vec(buffer, preamble.msg_size as usize - LicensePreamble::SIZE)?;
+        let message = LicenseMessage::decode_body(preamble.msg_type, &mut body.as_slice())?;
+
+        Ok(Self {
+            version: preamble.version,
+            flags: preamble.flags,
+            message,
+        })
+    }
+
+    fn size(&self) -> usize {
+        LicensePreamble::SIZE + self.message.body_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdu::licensing::{
+        LicenseErrorCode, ProductInfo, ScopeList, ServerLicenseRequest, StateTransition,
+    };
+
+    #[test]
+    fn test_license_error_valid_client() {
+        let pdu = LicensePdu::new(LicenseMessage::ErrorAlert(
+            LicenseErrorMessage::valid_client(),
+        ));
+
+        let mut buffer = Vec::new();
+        pdu.encode(&mut buffer).unwrap();
+        assert_eq!(
+            buffer,
+            vec![
+                0xFF, 0x03, 0x10, 0x00, 0x07, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x04, 0x00,
+                0x00, 0x00
+            ]
+        );
+
+        let decoded = LicensePdu::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded, pdu);
+        let LicenseMessage::ErrorAlert(error) = decoded.message else {
+            panic!("expected error alert");
+        };
+        assert!(error.is_valid_client());
+
+        // Windows sends the shortcut with version 2 and the extended error flag
+        buffer[1] = 0x82;
+        let decoded = LicensePdu::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded.version, 0x02);
+        assert_eq!(decoded.flags, PreambleFlags::EXTENDED_ERROR_MSG_SUPPORTED);
+        let LicenseMessage::ErrorAlert(error) = &decoded.message else {
+            panic!("expected error alert");
+        };
+        assert!(error.is_valid_client());
+
+        // Codes outside MS-RDPBCGR are kept rather than failing the PDU
+        buffer[4] = 0x42;
+        let decoded = LicensePdu::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(
+            decoded.message,
+            LicenseMessage::ErrorAlert(LicenseErrorMessage::new(
+                LicenseErrorCode::Other(0x42),
+                StateTransition::NoTransition
+            ))
+        );
+        let mut encoded = Vec::new();
+        decoded.encode(&mut encoded).unwrap();
+        assert_eq!(encoded, buffer);
+    }
+
+    #[test]
+    fn test_license_request_roundtrip() {
+        let request = ServerLicenseRequest::new(
+            [0x5A; 32],
+            ProductInfo::new(0x0006_0000, "Microsoft Corporation", "A02"),
+            Vec::new(),
+            ScopeList::new(vec!["microsoft.com".to_string()]),
+        );
+        let pdu = LicensePdu::new(LicenseMessage::LicenseRequest(request));
+
+        let mut buffer = Vec::new();
+        pdu.encode(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), pdu.size());
+        assert_eq!(&buffer[..4], &[0x01, 0x03, buffer.len() as u8, 0x00]);
+
+        let decoded = LicensePdu::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded, pdu);
+
+        let LicenseMessage::LicenseRequest(request) = decoded.message else {
+            panic!("expected license request");
+        };
+        assert!(request.certificate().unwrap().is_none());
+
+        buffer[2] -= 1;
+        assert!(LicensePdu::decode(&mut buffer.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,221 @@
+use super::blob::{BlobType, LicenseBinaryBlob, read_vec};
+use super::new_license_request::DEFAULT_PLATFORM_ID;
+use crate::pdu::{Pdu, PduError, Result};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use md5::{Digest, Md5};
+use std::io::{Read, Write};
+
+/// PLATFORM_CHALLENGE_RESPONSE_VERSION
+const PLATFORM_CHALLENGE_RESPONSE_VERSION: u16 = 0x0100;
+
+/// WIN32_PLATFORM_CHALLENGE_TYPE
+pub const WIN32_PLATFORM_CHALLENGE_TYPE: u16 = 0x0100;
+
+/// WIN16_PLATFORM_CHALLENGE_TYPE
+pub const WIN16_PLATFORM_CHALLENGE_TYPE: u16 = 0x0200;
+
+/// WINCE_PLATFORM_CHALLENGE_TYPE
+pub const WINCE_PLATFORM_CHALLENGE_TYPE: u16 = 0x0300;
+
+/// OTHER_PLATFORM_CHALLENGE_TYPE
+pub const OTHER_PLATFORM_CHALLENGE_TYPE: u16 = 0xFF00;
+
+/// LICENSE_DETAIL_SIMPLE
+pub const LICENSE_DETAIL_SIMPLE: u16 = 0x0001;
+
+/// LICENSE_DETAIL_MODERATE
+pub const LICENSE_DETAIL_MODERATE: u16 = 0x0002;
+
+/// LICENSE_DETAIL_DETAIL
+pub const LICENSE_DETAIL_DETAIL: u16 = 0x0003;
+
+/// Server Platform Challenge (SERVER_PLATFORM_CHALLENGE, MS-RDPELE 2.2.2.4)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerPlatformChallenge {
+    /// Reserved
+    pub connect_flags: u32,
+    /// Challenge encrypted with the licensing encryption key
+    pub encrypted_challenge: Vec<u8>,
+    /// MAC of the unencrypted challenge
+    pub mac_data: [u8; 16],
+}
+
+impl Pdu for ServerPlatformChallenge {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_u32::<LittleEndian>(self.connect_flags)?;
+        LicenseBinaryBlob::new(BlobType::EncryptedData, self.encrypted_challenge.clone())
+            .encode(buffer)?;
+        buffer.write_all(&self.mac_data)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let connect_flags = buffer.read_u32::<LittleEndian>()?;
+
+        // Some servers send BB_ANY_BLOB (0x0000) here, so the type is not checked
+        let _blob_type = buffer.read_u16::<LittleEndian>()?;
+        let length = buffer.read_u16::<LittleEndian>()? as usize;
+        let encrypted_challenge = read_vec(buffer, length)?;
+
+        let mut mac_data = [0u8; 16];
+        buffer.read_exact(&mut mac_data)?;
+        Ok(Self {
+            connect_flags,
+            encrypted_challenge,
+            mac_data,
+        })
+    }
+
+    fn size(&self) -> usize {
+        4 + 4 + self.encrypted_challenge.len() + 16
+    }
+}
+
+/// Platform Challenge Response Data (PLATFORM_CHALLENGE_RESPONSE_DATA, MS-RDPELE 2.2.2.5.1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlatformChallengeResponseData {
+    /// Client operating system (*_PLATFORM_CHALLENGE_TYPE)
+    pub client_type: u16,
+    /// License detail level (LICENSE_DETAIL_*)
+    pub license_detail_level: u16,
+    /// Decrypted server challenge
+    pub challenge: Vec<u8>,
+}
+
+impl PlatformChallengeResponseData {
+    /// Create response data for a non-Windows client
+    pub fn new(challenge: Vec<u8>) -> Self {
+        Self {
+            client_type: OTHER_PLATFORM_CHALLENGE_TYPE,
+            license_detail_level: LICENSE_DETAIL_DETAIL,
+            challenge,
+        }
+    }
+}
+
+impl Pdu for PlatformChallengeResponseData {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        let length = u16::try_from(self.challenge.len()).map_err(|_| PduError::InvalidLength {
+            expected: u16::MAX as usize,
+            actual: self.challenge.len(),
+        })?;
+        buffer.write_u16::<LittleEndian>(PLATFORM_CHALLENGE_RESPONSE_VERSION)?;
+        buffer.write_u16::<LittleEndian>(self.client_type)?;
+        buffer.write_u16::<LittleEndian>(self.license_detail_level)?;
+        buffer.write_u16::<LittleEndian>(length)?;
+        buffer.write_all(&self.challenge)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let version = buffer.read_u16::<LittleEndian>()?;
+        if version != PLATFORM_CHALLENGE_RESPONSE_VERSION {
+            return Err(PduError::ParseError(format!(
+                "Unsupported platform challenge response version: {:#06x}",
+                version
+            )));
+        }
+
+        let client_type = buffer.read_u16::<LittleEndian>()?;
+        let license_detail_level = buffer.read_u16::<LittleEndian>()?;
+        let length = buffer.read_u16::<LittleEndian>()? as usize;
+        let challenge = read_vec(buffer, length)?;
+        Ok(Self {
+            client_type,
+            license_detail_level,
+            challenge,
+        })
+    }
+
+    fn size(&self) -> usize {
+        8 + self.challenge.len()
+    }
+}
+
+/// Client Hardware Identification (CLIENT_HARDWARE_ID, MS-RDPELE 2.2.2.3.1)
+///
+/// 20 bytes total
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientHardwareId {
+    /// Client platform ID
+    pub platform_id: u32,
+    /// Hardware identifier (Data1..Data4)
+    pub data: [u8; 16],
+}
+
+impl ClientHardwareId {
+    /// Structure size (20 bytes)
+    pub const SIZE: usize = 20;
+
+    /// Derive a stable hardware ID from the machine name
+    pub fn from_machine_name(machine_name: &str) -> Self {
+        Self {
+            platform_id: DEFAULT_PLATFORM_ID,
+            data: Md5::digest(machine_name.as_bytes()).into(),
+        }
+    }
+
+    /// Encoded bytes
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0u8; Self::SIZE];
+        bytes[..4].copy_from_slice(&self.platform_id.to_le_bytes());
+        bytes[4..].copy_from_slice(&self.data);
+        bytes
+    }
+}
+
+impl Pdu for ClientHardwareId {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_all(&self.to_bytes())?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let platform_id = buffer.read_u32::<LittleEndian>()?;
+        let mut data = [0u8; 16];
+        buffer.read_exact(&mut data)?;
+        Ok(Self { platform_id, data })
+    }
+
+    fn size(&self) -> usize {
+        Self::SIZE
+    }
+}
+
+/// Client Platform Challenge Response (CLIENT_PLATFORM_CHALLENGE_RESPONSE, MS-RDPELE 2.2.2.5)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientPlatformChallengeResponse {
+    /// Platform Challenge Response Data encrypted with the licensing encryption key
+    pub encrypted_response: Vec<u8>,
+    /// Client hardware ID encrypted with the licensing encryption key
+    pub encrypted_hwid: Vec<u8>,
+    /// MAC of the unencrypted response data followed by the hardware ID
+    pub mac_data: [u8; 16],
+}
+
+impl Pdu for ClientPlatformChallengeResponse {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        LicenseBinaryBlob::new(BlobType::EncryptedData, self.encrypted_response.clone())
+            .encode(buffer)?;
+        LicenseBinaryBlob::new(BlobType::EncryptedData, self.encrypted_hwid.clone())
+            .encode(buffer)?;
+        buffer.write_all(&self.mac_data)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let encrypted_response = LicenseBinaryBlob::decode(buffer, BlobType::EncryptedData)?.data;
+        let encrypted_hwid = LicenseBinaryBlob::decode(buffer, BlobType::EncryptedData)?.data;
+        let mut mac_data = [0u8; 16];
+        buffer.read_exact(&mut mac_data)?;
+        Ok(Self {
+            encrypted_response,
+            encrypted_hwid,
+            mac_data,
+        })
+    }
+
+    fn size(&self) -> usize {
+        4 + self.encrypted_response.len() + 4 + self.encrypted_hwid.len() + 16
+    }
+}
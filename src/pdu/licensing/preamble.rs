@@ -0,0 +1,120 @@
+use crate::pdu::{Pdu, PduError, Result};
+use bitflags::bitflags;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Read, Write};
+
+/// PREAMBLE_VERSION_2_0 - RDP 4.0
+pub const PREAMBLE_VERSION_2_0: u8 = 0x02;
+
+/// PREAMBLE_VERSION_3_0 - RDP 5.0 and later
+pub const PREAMBLE_VERSION_3_0: u8 = 0x03;
+
+/// LicenseProtocolVersionMask
+const VERSION_MASK: u8 = 0x0F;
+
+/// Licensing message type (MS-RDPBCGR 2.2.1.12.1.1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum LicenseMessageType {
+    /// LICENSE_REQUEST - Server License Request
+    LicenseRequest = 0x01,
+    /// PLATFORM_CHALLENGE - Server Platform Challenge
+    PlatformChallenge = 0x02,
+    /// NEW_LICENSE - Server New License
+    NewLicense = 0x03,
+    /// UPGRADE_LICENSE - Server Upgrade License
+    UpgradeLicense = 0x04,
+    /// LICENSE_INFO - Client License Information
+    LicenseInfo = 0x12,
+    /// NEW_LICENSE_REQUEST - Client New License Request
+    NewLicenseRequest = 0x13,
+    /// PLATFORM_CHALLENGE_RESPONSE - Client Platform Challenge Response
+    PlatformChallengeResponse = 0x15,
+    /// ERROR_ALERT - License Error Message
+    ErrorAlert = 0xFF,
+}
+
+impl LicenseMessageType {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0x01 => Some(LicenseMessageType::LicenseRequest),
+            0x02 => Some(LicenseMessageType::PlatformChallenge),
+            0x03 => Some(LicenseMessageType::NewLicense),
+            0x04 => Some(LicenseMessageType::UpgradeLicense),
+            0x12 => Some(LicenseMessageType::LicenseInfo),
+            0x13 => Some(LicenseMessageType::NewLicenseRequest),
+            0x15 => Some(LicenseMessageType::PlatformChallengeResponse),
+            0xFF => Some(LicenseMessageType::ErrorAlert),
+            _ => None,
+        }
+    }
+
+    pub fn as_u8(self) -> u8 {
+        self as u8
+    }
+}
+
+bitflags! {
+    /// Licensing preamble flags (upper bits of the flags field)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct PreambleFlags: u8 {
+        /// EXTENDED_ERROR_MSG_SUPPORTED
+        const EXTENDED_ERROR_MSG_SUPPORTED = 0x80;
+    }
+}
+
+/// Licensing Preamble (LICENSE_PREAMBLE, MS-RDPBCGR 2.2.1.12.1.1)
+///
+/// 4 bytes total
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LicensePreamble {
+    /// Message type
+    pub msg_type: LicenseMessageType,
+    /// Licensing protocol version (PREAMBLE_VERSION_*)
+    pub version: u8,
+    /// Preamble flags
+    pub flags: PreambleFlags,
+    /// Message size including the preamble
+    pub msg_size: u16,
+}
+
+impl LicensePreamble {
+    /// Preamble size (4 bytes)
+    pub const SIZE: usize = 4;
+}
+
+impl Pdu for LicensePreamble {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_u8(self.msg_type.as_u8())?;
+        buffer.write_u8((self.version & VERSION_MASK) | self.flags.bits())?;
+        buffer.write_u16::<LittleEndian>(self.msg_size)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let msg_type = buffer.read_u8()?;
+        let msg_type = LicenseMessageType::from_u8(msg_type).ok_or_else(|| {
+            PduError::ParseError(format!("Unknown licensing message type: {:#04x}", msg_type))
+        })?;
+
+        let flags = buffer.read_u8()?;
+        let msg_size = buffer.read_u16::<LittleEndian>()?;
+        if (msg_size as usize) < Self::SIZE {
+            return Err(PduError::InvalidLength {
+                expected: Self::SIZE,
+                actual: msg_size as usize,
+            });
+        }
+
+        Ok(Self {
+            msg_type,
+            version: flags & VERSION_MASK,
+            flags: PreambleFlags::from_bits_retain(flags & !VERSION_MASK),
+            msg_size,
+        })
+    }
+
+    fn size(&self) -> usize {
+        Self::SIZE
+    }
+}
@@ -1,13 +1,15 @@
-use crate::codec::{BerReader, BerWriter};
-use crate::pdu::{Pdu, PduError, Result};
+use crate::codec::{PerReader, PerWriter};
+use crate::pdu::{Pdu, Result};
 use std::io::{Read, Write};
 
-use super::domain::McsResult;
+use super::domain::{
+    MCS_BASE_CHANNEL_ID, McsResult, read_domain_header, read_result, write_domain_header,
+};
 
-/// MCS Channel Join Request TAG ([APPLICATION 14])
+/// MCS Channel Join Request (DomainMCSPDU choice 14)
 pub const MCS_CHANNEL_JOIN_REQUEST: u8 = 14;
 
-/// MCS Channel Join Confirm TAG ([APPLICATION 15])
+/// MCS Channel Join Confirm (DomainMCSPDU choice 15)
 pub const MCS_CHANNEL_JOIN_CONFIRM: u8 = 15;
 
 /// MCS Channel Join Request
@@ -36,12 +38,11 @@ impl ChannelJoinRequest {
 
 impl Pdu for ChannelJoinRequest {
     fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
-        let mut writer = BerWriter::new();
+        let mut writer = PerWriter::new();
 
-        writer.write_application_tag(MCS_CHANNEL_JOIN_REQUEST, |w| {
-            w.write_integer(self.user_id as u32);
-            w.write_integer(self.channel_id as u32);
-        });
+        write_domain_header(&mut writer, MCS_CHANNEL_JOIN_REQUEST, 0);
+        writer.write_integer16(self.user_id, MCS_BASE_CHANNEL_ID)?;
+        writer.write_integer16(self.channel_id, 0)?;
 
         buffer.write_all(writer.as_bytes())?;
         Ok(())
@@ -51,11 +52,11 @@ impl Pdu for ChannelJoinRequest {
         let mut data = Vec::new();
         buffer.read_to_end(&mut data)?;
 
-        let mut reader = BerReader::new(&data);
-        reader.read_application_tag(MCS_CHANNEL_JOIN_REQUEST)?;
+        let mut reader = PerReader::new(&data);
+        read_domain_header(&mut reader, MCS_CHANNEL_JOIN_REQUEST)?;
 
-        let user_id = reader.read_integer()? as u16;
-        let channel_id = reader.read_integer()? as u16;
+        let user_id = reader.read_integer16(MCS_BASE_CHANNEL_ID)?;
+        let channel_id = reader.read_integer16(0)?;
 
         Ok(Self {
             user_id,
@@ -64,9 +65,8 @@ impl Pdu for ChannelJoinRequest {
     }
 
     fn size(&self) -> usize {
-        let mut writer = BerWriter::new();
-        self.encode(&mut writer).unwrap();
-        writer.as_bytes().len()
+        // Header octet, then initiator and channelId as INTEGER16
+        1 + 2 + 2
     }
 }
 
@@ -124,16 +124,21 @@ impl ChannelJoinConfirm {
 
 impl Pdu for ChannelJoinConfirm {
     fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
-        let mut writer = BerWriter::new();
+        let mut writer = PerWriter::new();
 
-        writer.write_application_tag(MCS_CHANNEL_JOIN_CONFIRM, |w| {
-            w.write_enumerated(self.result as u8);
-            w.write_integer(self.user_id as u32);
-            w.write_integer(self.requested_channel_id as u32);
-            if let Some(channel_id) = self.channel_id {
-                w.write_integer(channel_id as u32);
-            }
-        });
+        // Option bit 0x02 marks the presence of channelId
+        let options = if self.channel_id.is_some() {
+            0x02
+        } else {
+            0x00
+        };
+        write_domain_header(&mut writer, MCS_CHANNEL_JOIN_CONFIRM, options);
+        writer.write_enumerated(self.result as u8);
+        writer.write_integer16(self.user_id, MCS_BASE_CHANNEL_ID)?;
+        writer.write_integer16(self.requested_channel_id, 0)?;
+        if let Some(channel_id) = self.channel_id {
+            writer.write_integer16(channel_id, 0)?;
+        }
 
         buffer.write_all(writer.as_bytes())?;
         Ok(())
@@ -143,19 +148,15 @@ impl Pdu for ChannelJoinConfirm {
         let mut data = Vec::new();
         buffer.read_to_end(&mut data)?;
 
-        let mut reader = BerReader::new(&data);
-        reader.read_application_tag(MCS_CHANNEL_JOIN_CONFIRM)?;
+        let mut reader = PerReader::new(&data);
+        let options = read_domain_header(&mut reader, MCS_CHANNEL_JOIN_CONFIRM)?;
 
-        let result_code = reader.read_enumerated()?;
-        let result = McsResult::from_u8(result_code).ok_or_else(|| {
-            PduError::ParseError(format!("Invalid MCS result code: {}", result_code))
-        })?;
+        let result = read_result(&mut reader)?;
+        let user_id = reader.read_integer16(MCS_BASE_CHANNEL_ID)?;
+        let requested_channel_id = reader.read_integer16(0)?;
 
-        let user_id = reader.read_integer()? as u16;
-        let requested_channel_id = reader.read_integer()? as u16;
-
-        let channel_id = if reader.remaining() > 0 {
-            Some(reader.read_integer()? as u16)
+        let channel_id = if options & 0x02 != 0 {
+            Some(reader.read_integer16(0)?)
         } else {
             None
         };
@@ -169,15 +170,15 @@ impl Pdu for ChannelJoinConfirm {
     }
 
     fn size(&self) -> usize {
-        let mut writer = BerWriter::new();
-        self.encode(&mut writer).unwrap();
-        writer.as_bytes().len()
+        // Header octet, result, initiator and requested as INTEGER16, then channelId
+        2 + 2 + 2 + if self.channel_id.is_some() { 2 } else { 0 }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::pdu::framing::encode_pdu;
     use std::io::Cursor;
 
     #[test]
@@ -195,10 +196,22 @@ mod tests {
         assert_eq!(decoded.channel_id, 1003);
     }
 
+    #[test]
+    fn test_channel_join_request_bytes() {
+        // MS-RDPBCGR 4.1.8 Client MCS Channel Join Request PDU (user 1007 joins I/O channel 1003)
+        let request = ChannelJoinRequest::new(1007, 1003);
+
+        let mut buffer = Vec::new();
+        request.encode(&mut buffer).unwrap();
+
+        assert_eq!(buffer, vec![0x38, 0x00, 0x06, 0x03, 0xEB]);
+        assert_eq!(request.size(), buffer.len());
+    }
+
     #[test]
     fn test_channel_join_request_roundtrip() {
         let test_cases = vec![
-            ChannelJoinRequest::new(1, 1),
+            ChannelJoinRequest::new(1001, 1),
             ChannelJoinRequest::new(1001, 1003),
             ChannelJoinRequest::new(65535, 65535),
         ];
@@ -231,6 +244,27 @@ mod tests {
         assert_eq!(decoded.channel_id, Some(1003));
     }
 
+    #[test]
+    fn test_channel_join_confirm_bytes() {
+        // MS-RDPBCGR 4.1.8 Server MCS Channel Join Confirm PDU (user channel 1007)
+        let bytes = vec![0x3E, 0x00, 0x00, 0x06, 0x03, 0xEF, 0x03, 0xEF];
+
+        let mut cursor = Cursor::new(&bytes);
+        let decoded = ChannelJoinConfirm::decode(&mut cursor).unwrap();
+        assert_eq!(decoded, ChannelJoinConfirm::success(1007, 1007));
+
+        let mut buffer = Vec::new();
+        decoded.encode(&mut buffer).unwrap();
+        assert_eq!(buffer, bytes);
+        assert_eq!(decoded.size(), bytes.len());
+    }
+
+    #[test]
+    fn test_channel_join_invalid_user_id() {
+        assert!(encode_pdu(&ChannelJoinRequest::new(1000, 1003)).is_err());
+        assert!(encode_pdu(&ChannelJoinConfirm::success(1000, 1003)).is_err());
+    }
+
     #[test]
     fn test_channel_join_confirm_failure() {
         let confirm = ChannelJoinConfirm::failure(McsResult::RtNoSuchChannel, 1001, 1003);
@@ -251,10 +285,10 @@ mod tests {
     #[test]
     fn test_channel_join_confirm_roundtrip() {
         let test_cases = vec![
-            ChannelJoinConfirm::success(1, 1),
+            ChannelJoinConfirm::success(1001, 1),
             ChannelJoinConfirm::success(1001, 1003),
             ChannelJoinConfirm::failure(McsResult::RtNoSuchChannel, 1001, 1003),
-            ChannelJoinConfirm::failure(McsResult::RtTooManyChannels, 500, 600),
+            ChannelJoinConfirm::failure(McsResult::RtTooManyChannels, 1500, 1600),
         ];
 
         for confirm in test_cases {
@@ -1,4 +1,5 @@
 use crate::codec::{BerReader, BerWriter};
+use crate::pdu::gcc::{ConferenceCreateRequest, ConferenceCreateResponse};
 use crate::pdu::{Pdu, PduError, Result};
 use std::io::{Read, Write};
 
@@ -36,6 +37,7 @@ pub struct DomainParameters {
 
 impl DomainParameters {
     /// Create new DomainParameters
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         max_channel_ids: u32,
         max_user_ids: u32,
@@ -161,20 +163,19 @@ pub struct ConnectInitial {
 }
 
 impl ConnectInitial {
-    /// Create new ConnectInitial
-    pub fn new(user_data: Vec<u8>) -> Self {
-        Self {
-            calling_domain: vec![1],
-            called_domain: vec![1],
-            upward_flag: true,
-            target_parameters: DomainParameters::target(),
-            minimum_parameters: DomainParameters::minimum(),
-            maximum_parameters: DomainParameters::maximum(),
+    /// Create ConnectInitial carrying a GCC Conference Create Request
+    pub fn new(request: &ConferenceCreateRequest) -> Result<Self> {
+        let mut user_data = Vec::with_capacity(request.size());
+        request.encode(&mut user_data)?;
+        Ok(Self::with_parameters(
+            DomainParameters::target(),
+            DomainParameters::minimum(),
+            DomainParameters::maximum(),
             user_data,
-        }
+        ))
     }
 
-    /// Create with custom parameters
+    /// Create with custom parameters and raw userData
     pub fn with_parameters(
         target: DomainParameters,
         minimum: DomainParameters,
@@ -191,6 +192,11 @@ impl ConnectInitial {
             user_data,
         }
     }
+
+    /// Parse userData as a GCC Conference Create Request
+    pub fn conference_request(&self) -> Result<ConferenceCreateRequest> {
+        ConferenceCreateRequest::decode(&mut self.user_data.as_slice())
+    }
 }
 
 impl Pdu for ConnectInitial {
@@ -265,7 +271,7 @@ pub struct ConnectResponse {
 }
 
 impl ConnectResponse {
-    /// Create new ConnectResponse
+    /// Create new ConnectResponse with raw userData
     pub fn new(
         result: McsResult,
         called_connect_id: u32,
@@ -280,14 +286,21 @@ impl ConnectResponse {
         }
     }
 
-    /// Create success response
-    pub fn success(user_data: Vec<u8>) -> Self {
-        Self {
-            result: McsResult::RtSuccessful,
-            called_connect_id: 0,
-            domain_parameters: DomainParameters::target(),
+    /// Create success response carrying a GCC Conference Create Response
+    pub fn success(response: &ConferenceCreateResponse) -> Result<Self> {
+        let mut user_data = Vec::with_capacity(response.size());
+        response.encode(&mut user_data)?;
+        Ok(Self::new(
+            McsResult::RtSuccessful,
+            0,
+            DomainParameters::target(),
             user_data,
-        }
+        ))
+    }
+
+    /// Parse userData as a GCC Conference Create Response
+    pub fn conference_response(&self) -> Result<ConferenceCreateResponse> {
+        ConferenceCreateResponse::decode(&mut self.user_data.as_slice())
     }
 }
 
@@ -369,8 +382,8 @@ mod tests {
 
     #[test]
     fn test_connect_initial() {
-        let user_data = b"test_gcc_data".to_vec();
-        let initial = ConnectInitial::new(user_data.clone());
+        let request = ConferenceCreateRequest::new(b"test_gcc_data".to_vec());
+        let initial = ConnectInitial::new(&request).unwrap();
 
         let mut buffer = Vec::new();
         initial.encode(&mut buffer).unwrap();
@@ -379,6 +392,7 @@ mod tests {
         let decoded = ConnectInitial::decode(&mut cursor).unwrap();
 
         assert_eq!(initial.user_data, decoded.user_data);
+        assert_eq!(decoded.conference_request().unwrap(), request);
         assert_eq!(initial.upward_flag, decoded.upward_flag);
         assert_eq!(
             initial.target_parameters.max_channel_ids,
@@ -409,8 +423,8 @@ mod tests {
 
     #[test]
     fn test_connect_response() {
-        let user_data = b"response_data".to_vec();
-        let response = ConnectResponse::success(user_data.clone());
+        let conference = ConferenceCreateResponse::new(b"response_data".to_vec());
+        let response = ConnectResponse::success(&conference).unwrap();
 
         let mut buffer = Vec::new();
         response.encode(&mut buffer).unwrap();
@@ -420,6 +434,7 @@ mod tests {
 
         assert_eq!(response.result, decoded.result);
         assert_eq!(response.user_data, decoded.user_data);
+        assert_eq!(decoded.conference_response().unwrap(), conference);
         assert_eq!(
             response.domain_parameters.max_channel_ids,
             decoded.domain_parameters.max_channel_ids
@@ -441,4 +456,25 @@ mod tests {
         assert_eq!(response.called_connect_id, decoded.called_connect_id);
         assert_eq!(response.result, decoded.result);
     }
+
+    #[test]
+    fn test_connect_conference_wrapping() {
+        let request = ConferenceCreateRequest::new(vec![0x01, 0xC0, 0x08, 0x00]);
+        let initial = ConnectInitial::new(&request).unwrap();
+
+        let mut buffer = Vec::new();
+        initial.encode(&mut buffer).unwrap();
+
+        let mut cursor = Cursor::new(buffer);
+        let decoded = ConnectInitial::decode(&mut cursor).unwrap();
+        assert_eq!(decoded.conference_request().unwrap(), request);
+
+        let response = ConferenceCreateResponse::new(vec![0x01, 0x0C, 0x08, 0x00]);
+        let connect_response = ConnectResponse::success(&response).unwrap();
+        assert_eq!(connect_response.result, McsResult::RtSuccessful);
+        assert_eq!(connect_response.conference_response().unwrap(), response);
+        assert!(
+            ConferenceCreateRequest::decode(&mut connect_response.user_data.as_slice()).is_err()
+        );
+    }
 }
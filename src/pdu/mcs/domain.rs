@@ -1,16 +1,47 @@
-use crate::codec::{BerReader, BerWriter};
+use crate::codec::{PerReader, PerWriter};
 use crate::pdu::{Pdu, PduError, Result};
 use std::io::{Read, Write};
 
-/// MCS Erect Domain Request TAG ([APPLICATION 1])
+/// MCS Erect Domain Request (DomainMCSPDU choice 1)
 pub const MCS_ERECT_DOMAIN_REQUEST: u8 = 1;
 
-/// MCS Attach User Request TAG ([APPLICATION 10])
+/// MCS Disconnect Provider Ultimatum (DomainMCSPDU choice 8)
+pub const MCS_DISCONNECT_PROVIDER_ULTIMATUM: u8 = 8;
+
+/// MCS Attach User Request (DomainMCSPDU choice 10)
 pub const MCS_ATTACH_USER_REQUEST: u8 = 10;
 
-/// MCS Attach User Confirm TAG ([APPLICATION 11])
+/// MCS Attach User Confirm (DomainMCSPDU choice 11)
 pub const MCS_ATTACH_USER_CONFIRM: u8 = 11;
 
+/// Base value of dynamically assigned user and channel IDs (UserId lower bound)
+pub const MCS_BASE_CHANNEL_ID: u16 = 1001;
+
+/// Number of values in the MCS Result enumeration
+pub const MCS_RESULT_COUNT: u8 = 16;
+
+/// Write DomainMCSPDU header octet (CHOICE index in the upper 6 bits, option bits below)
+pub(crate) fn write_domain_header(writer: &mut PerWriter, choice: u8, options: u8) {
+    writer.write_choice((choice << 2) | (options & 0x03));
+}
+
+/// Read DomainMCSPDU header octet, verify CHOICE index and return the option bits
+pub(crate) fn read_domain_header(reader: &mut PerReader, expected: u8) -> Result<u8> {
+    let header = reader.read_choice()?;
+    if header >> 2 != expected {
+        return Err(PduError::InvalidPduType(header >> 2));
+    }
+
+    Ok(header & 0x03)
+}
+
+/// Read MCS Result enumeration
+pub(crate) fn read_result(reader: &mut PerReader) -> Result<McsResult> {
+    let result_code = reader.read_enumerated(MCS_RESULT_COUNT)?;
+    McsResult::from_u8(result_code)
+        .ok_or_else(|| PduError::ParseError(format!("Invalid MCS result code: {}", result_code)))
+}
+
 /// MCS Result codes
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
@@ -60,8 +91,8 @@ impl McsResult {
 /// MCS Erect Domain Request
 ///
 /// ErectDomainRequest ::= [APPLICATION 1] IMPLICIT SEQUENCE {
-///     subHeight   Integer,
-///     subInterval Integer
+///     subHeight   INTEGER (0..MAX),
+///     subInterval INTEGER (0..MAX)
 /// }
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ErectDomainRequest {
@@ -88,12 +119,11 @@ impl ErectDomainRequest {
 
 impl Pdu for ErectDomainRequest {
     fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
-        let mut writer = BerWriter::new();
+        let mut writer = PerWriter::new();
 
-        writer.write_application_tag(MCS_ERECT_DOMAIN_REQUEST, |w| {
-            w.write_integer(self.sub_height);
-            w.write_integer(self.sub_interval);
-        });
+        write_domain_header(&mut writer, MCS_ERECT_DOMAIN_REQUEST, 0);
+        writer.write_integer(self.sub_height);
+        writer.write_integer(self.sub_interval);
 
         buffer.write_all(writer.as_bytes())?;
         Ok(())
@@ -103,8 +133,8 @@ impl Pdu for ErectDomainRequest {
         let mut data = Vec::new();
         buffer.read_to_end(&mut data)?;
 
-        let mut reader = BerReader::new(&data);
-        reader.read_application_tag(MCS_ERECT_DOMAIN_REQUEST)?;
+        let mut reader = PerReader::new(&data);
+        read_domain_header(&mut reader, MCS_ERECT_DOMAIN_REQUEST)?;
 
         let sub_height = reader.read_integer()?;
         let sub_interval = reader.read_integer()?;
@@ -116,7 +146,7 @@ impl Pdu for ErectDomainRequest {
     }
 
     fn size(&self) -> usize {
-        let mut writer = BerWriter::new();
+        let mut writer = PerWriter::new();
         self.encode(&mut writer).unwrap();
         writer.as_bytes().len()
     }
@@ -143,11 +173,10 @@ impl Default for AttachUserRequest {
 
 impl Pdu for AttachUserRequest {
     fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
-        let mut writer = BerWriter::new();
+        let mut writer = PerWriter::new();
 
-        writer.write_application_tag(MCS_ATTACH_USER_REQUEST, |_w| {
-            // Empty sequence
-        });
+        // Empty sequence: header octet only
+        write_domain_header(&mut writer, MCS_ATTACH_USER_REQUEST, 0);
 
         buffer.write_all(writer.as_bytes())?;
         Ok(())
@@ -157,14 +186,14 @@ impl Pdu for AttachUserRequest {
         let mut data = Vec::new();
         buffer.read_to_end(&mut data)?;
 
-        let mut reader = BerReader::new(&data);
-        reader.read_application_tag(MCS_ATTACH_USER_REQUEST)?;
+        let mut reader = PerReader::new(&data);
+        read_domain_header(&mut reader, MCS_ATTACH_USER_REQUEST)?;
 
         Ok(Self)
     }
 
     fn size(&self) -> usize {
-        let mut writer = BerWriter::new();
+        let mut writer = PerWriter::new();
         self.encode(&mut writer).unwrap();
         writer.as_bytes().len()
     }
@@ -176,6 +205,8 @@ impl Pdu for AttachUserRequest {
 ///     result     Result,
 ///     initiator  UserId OPTIONAL
 /// }
+///
+/// UserId ::= DynamicChannelId (1001..65535)
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AttachUserConfirm {
     /// Result code
@@ -203,14 +234,15 @@ impl AttachUserConfirm {
 
 impl Pdu for AttachUserConfirm {
     fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
-        let mut writer = BerWriter::new();
-
-        writer.write_application_tag(MCS_ATTACH_USER_CONFIRM, |w| {
-            w.write_enumerated(self.result as u8);
-            if let Some(user_id) = self.user_id {
-                w.write_integer(user_id as u32);
-            }
-        });
+        let mut writer = PerWriter::new();
+
+        // Option bit 0x02 marks the presence of initiator
+        let options = if self.user_id.is_some() { 0x02 } else { 0x00 };
+        write_domain_header(&mut writer, MCS_ATTACH_USER_CONFIRM, options);
+        writer.write_enumerated(self.result as u8);
+        if let Some(user_id) = self.user_id {
+            writer.write_integer16(user_id, MCS_BASE_CHANNEL_ID)?;
+        }
 
         buffer.write_all(writer.as_bytes())?;
         Ok(())
@@ -220,16 +252,13 @@ impl Pdu for AttachUserConfirm {
         let mut data = Vec::new();
         buffer.read_to_end(&mut data)?;
 
-        let mut reader = BerReader::new(&data);
-        reader.read_application_tag(MCS_ATTACH_USER_CONFIRM)?;
+        let mut reader = PerReader::new(&data);
+        let options = read_domain_header(&mut reader, MCS_ATTACH_USER_CONFIRM)?;
 
-        let result_code = reader.read_enumerated()?;
-        let result = McsResult::from_u8(result_code).ok_or_else(|| {
-            PduError::ParseError(format!("Invalid MCS result code: {}", result_code))
-        })?;
+        let result = read_result(&mut reader)?;
 
-        let user_id = if reader.remaining() > 0 {
-            Some(reader.read_integer()? as u16)
+        let user_id = if options & 0x02 != 0 {
+            Some(reader.read_integer16(MCS_BASE_CHANNEL_ID)?)
         } else {
             None
         };
@@ -238,15 +267,111 @@ impl Pdu for AttachUserConfirm {
     }
 
     fn size(&self) -> usize {
-        let mut writer = BerWriter::new();
-        self.encode(&mut writer).unwrap();
-        writer.as_bytes().len()
+        // Header octet, result, then initiator as INTEGER16
+        2 + if self.user_id.is_some() { 2 } else { 0 }
+    }
+}
+
+/// MCS Disconnect Reason
+///
+/// Reason ::= ENUMERATED {
+///     rn-domain-disconnected, rn-provider-initiated, rn-token-purged,
+///     rn-user-requested, rn-channel-purged
+/// }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum DisconnectReason {
+    RnDomainDisconnected = 0,
+    RnProviderInitiated = 1,
+    RnTokenPurged = 2,
+    RnUserRequested = 3,
+    RnChannelPurged = 4,
+}
+
+impl DisconnectReason {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(DisconnectReason::RnDomainDisconnected),
+            1 => Some(DisconnectReason::RnProviderInitiated),
+            2 => Some(DisconnectReason::RnTokenPurged),
+            3 => Some(DisconnectReason::RnUserRequested),
+            4 => Some(DisconnectReason::RnChannelPurged),
+            _ => None,
+        }
+    }
+
+    /// Human-readable description
+    pub fn description(self) -> &'static str {
+        match self {
+            DisconnectReason::RnDomainDisconnected => "MCS domain disconnected",
+            DisconnectReason::RnProviderInitiated => "Disconnect initiated by the MCS provider",
+            DisconnectReason::RnTokenPurged => "MCS token purged",
+            DisconnectReason::RnUserRequested => "Disconnect requested by the user",
+            DisconnectReason::RnChannelPurged => "MCS channel purged",
+        }
+    }
+}
+
+/// MCS Disconnect Provider Ultimatum
+///
+/// DisconnectProviderUltimatum ::= [APPLICATION 8] IMPLICIT SEQUENCE {
+///     reason  Reason
+/// }
+///
+/// The 3-bit reason straddles the header octet: its two high bits occupy the
+/// option bits and the lowest bit opens the following octet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisconnectProviderUltimatum {
+    /// Disconnect reason
+    pub reason: DisconnectReason,
+}
+
+impl DisconnectProviderUltimatum {
+    /// Create new DisconnectProviderUltimatum
+    pub fn new(reason: DisconnectReason) -> Self {
+        Self { reason }
+    }
+
+    /// PDU size (2 bytes)
+    pub const SIZE: usize = 2;
+}
+
+impl Pdu for DisconnectProviderUltimatum {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        let mut writer = PerWriter::new();
+
+        let reason = self.reason as u8;
+        write_domain_header(&mut writer, MCS_DISCONNECT_PROVIDER_ULTIMATUM, reason >> 1);
+        writer.write_u8((reason & 0x01) << 7);
+
+        buffer.write_all(writer.as_bytes())?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let mut data = Vec::new();
+        buffer.read_to_end(&mut data)?;
+
+        let mut reader = PerReader::new(&data);
+        let options = read_domain_header(&mut reader, MCS_DISCONNECT_PROVIDER_ULTIMATUM)?;
+
+        let reason_code = ((options & 0x03) << 1) | (reader.read_u8()? >> 7);
+        let reason = DisconnectReason::from_u8(reason_code).ok_or_else(|| {
+            PduError::ParseError(format!("Invalid MCS disconnect reason: {}", reason_code))
+        })?;
+
+        Ok(Self { reason })
+    }
+
+    fn size(&self) -> usize {
+        Self::SIZE
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::pdu::framing::encode_pdu;
     use std::io::Cursor;
 
     #[test]
@@ -264,6 +389,18 @@ mod tests {
         assert_eq!(decoded.sub_interval, 0);
     }
 
+    #[test]
+    fn test_erect_domain_request_bytes() {
+        // MS-RDPBCGR 4.1.5 Client MCS Erect Domain Request PDU
+        let request = ErectDomainRequest::default_request();
+
+        let mut buffer = Vec::new();
+        request.encode(&mut buffer).unwrap();
+
+        assert_eq!(buffer, vec![0x04, 0x01, 0x00, 0x01, 0x00]);
+        assert_eq!(request.size(), buffer.len());
+    }
+
     #[test]
     fn test_erect_domain_request_default() {
         let request = ErectDomainRequest::default_request();
@@ -293,7 +430,7 @@ mod tests {
         let mut buffer = Vec::new();
         request.encode(&mut buffer).unwrap();
 
-        assert!(buffer.len() > 0);
+        assert!(!buffer.is_empty());
 
         let mut cursor = Cursor::new(buffer);
         let decoded = AttachUserRequest::decode(&mut cursor).unwrap();
@@ -301,6 +438,86 @@ mod tests {
         assert_eq!(request, decoded);
     }
 
+    #[test]
+    fn test_attach_user_request_bytes() {
+        // MS-RDPBCGR 4.1.6 Client MCS Attach User Request PDU
+        let mut buffer = Vec::new();
+        AttachUserRequest::new().encode(&mut buffer).unwrap();
+
+        assert_eq!(buffer, vec![0x28]);
+    }
+
+    #[test]
+    fn test_attach_user_confirm_bytes() {
+        // MS-RDPBCGR 4.1.7 Server MCS Attach User Confirm PDU (initiator = 1007)
+        let bytes = vec![0x2E, 0x00, 0x00, 0x06];
+
+        let mut cursor = Cursor::new(&bytes);
+        let decoded = AttachUserConfirm::decode(&mut cursor).unwrap();
+        assert_eq!(decoded, AttachUserConfirm::success(1007));
+
+        let mut buffer = Vec::new();
+        decoded.encode(&mut buffer).unwrap();
+        assert_eq!(buffer, bytes);
+        assert_eq!(decoded.size(), bytes.len());
+        assert_eq!(
+            AttachUserConfirm::failure(McsResult::RtUnspecifiedFailure).size(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_attach_user_confirm_invalid_user_id() {
+        let confirm = AttachUserConfirm::success(1000);
+
+        let mut buffer = Vec::new();
+        assert!(confirm.encode(&mut buffer).is_err());
+        // size() is computed without encoding, so framing gets the error
+        assert!(encode_pdu(&confirm).is_err());
+    }
+
+    #[test]
+    fn test_domain_header_mismatch() {
+        let mut cursor = Cursor::new(vec![0x28]);
+        let result = ErectDomainRequest::decode(&mut cursor);
+
+        assert!(matches!(
+            result,
+            Err(PduError::InvalidPduType(MCS_ATTACH_USER_REQUEST))
+        ));
+    }
+
+    #[test]
+    fn test_disconnect_provider_ultimatum_bytes() {
+        let test_cases = vec![
+            (DisconnectReason::RnDomainDisconnected, [0x20, 0x00]),
+            (DisconnectReason::RnProviderInitiated, [0x20, 0x80]),
+            (DisconnectReason::RnTokenPurged, [0x21, 0x00]),
+            (DisconnectReason::RnUserRequested, [0x21, 0x80]),
+            (DisconnectReason::RnChannelPurged, [0x22, 0x00]),
+        ];
+
+        for (reason, expected) in test_cases {
+            let pdu = DisconnectProviderUltimatum::new(reason);
+
+            let mut buffer = Vec::new();
+            pdu.encode(&mut buffer).unwrap();
+            assert_eq!(buffer, expected);
+            assert_eq!(pdu.size(), buffer.len());
+
+            let mut cursor = Cursor::new(buffer);
+            let decoded = DisconnectProviderUltimatum::decode(&mut cursor).unwrap();
+            assert_eq!(decoded.reason, reason);
+        }
+    }
+
+    #[test]
+    fn test_disconnect_provider_ultimatum_invalid_reason() {
+        // reason 7 is outside the enumeration
+        let mut cursor = Cursor::new(vec![0x23, 0x80]);
+        assert!(DisconnectProviderUltimatum::decode(&mut cursor).is_err());
+    }
+
     #[test]
     fn test_attach_user_confirm_success() {
         let confirm = AttachUserConfirm::success(1001);
@@ -334,7 +551,7 @@ mod tests {
     #[test]
     fn test_attach_user_confirm_roundtrip() {
         let test_cases = vec![
-            AttachUserConfirm::success(1),
+            AttachUserConfirm::success(1001),
             AttachUserConfirm::success(65535),
             AttachUserConfirm::failure(McsResult::RtNoSuchUser),
             AttachUserConfirm::failure(McsResult::RtUserRejected),
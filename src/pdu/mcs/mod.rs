@@ -1,7 +1,12 @@
 pub mod channel;
 pub mod connect;
 pub mod domain;
+pub mod send_data;
 
 pub use channel::{ChannelJoinConfirm, ChannelJoinRequest};
 pub use connect::{ConnectInitial, ConnectResponse, DomainParameters};
-pub use domain::{AttachUserConfirm, AttachUserRequest, ErectDomainRequest, McsResult};
+pub use domain::{
+    AttachUserConfirm, AttachUserRequest, DisconnectProviderUltimatum, DisconnectReason,
+    ErectDomainRequest, McsResult, MCS_BASE_CHANNEL_ID,
+};
+pub use send_data::{DataPriority, SendDataIndication, SendDataRequest, Segmentation};
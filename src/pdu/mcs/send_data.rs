@@ -0,0 +1,425 @@
+use crate::codec::{PerReader, PerWriter};
+use crate::pdu::rdp::{PduType, ShareControlHeader};
+use crate::pdu::{Pdu, PduError, Result};
+use bitflags::bitflags;
+use std::io::{Cursor, Read, Write};
+
+use super::domain::{MCS_BASE_CHANNEL_ID, read_domain_header, write_domain_header};
+
+/// MCS Send Data Request (DomainMCSPDU choice 25)
+pub const MCS_SEND_DATA_REQUEST: u8 = 25;
+
+/// MCS Send Data Indication (DomainMCSPDU choice 26)
+pub const MCS_SEND_DATA_INDICATION: u8 = 26;
+
+/// MCS Data Priority
+///
+/// DataPriority ::= ENUMERATED { top, high, medium, low }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum DataPriority {
+    Top = 0,
+    High = 1,
+    Medium = 2,
+    Low = 3,
+}
+
+impl DataPriority {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(DataPriority::Top),
+            1 => Some(DataPriority::High),
+            2 => Some(DataPriority::Medium),
+            3 => Some(DataPriority::Low),
+            _ => None,
+        }
+    }
+}
+
+bitflags! {
+    /// MCS Segmentation (bit positions within the priority/segmentation octet)
+    ///
+    /// Segmentation ::= BIT STRING { begin(0), end(1) } (SIZE(2))
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Segmentation: u8 {
+        /// First segment of the user data
+        const BEGIN = 0x20;
+        /// Last segment of the user data
+        const END = 0x10;
+    }
+}
+
+/// Fields shared by Send Data Request and Send Data Indication
+///
+/// SendDataRequest/Indication ::= IMPLICIT SEQUENCE {
+///     initiator     UserId,
+///     channelId     ChannelId,
+///     dataPriority  DataPriority,
+///     segmentation  Segmentation,
+///     userData      OCTET STRING
+/// }
+fn encode_send_data(
+    choice: u8,
+    initiator: u16,
+    channel_id: u16,
+    data_priority: DataPriority,
+    segmentation: Segmentation,
+    user_data: &[u8],
+    buffer: &mut dyn Write,
+) -> Result<()> {
+    let mut writer = PerWriter::new();
+
+    write_domain_header(&mut writer, choice, 0);
+    writer.write_integer16(initiator, MCS_BASE_CHANNEL_ID)?;
+    writer.write_integer16(channel_id, 0)?;
+    writer.write_u8(((data_priority as u8) << 6) | segmentation.bits());
+    writer.write_octet_string(user_data, 0)?;
+
+    buffer.write_all(writer.as_bytes())?;
+    Ok(())
+}
+
+type SendDataFields = (u16, u16, DataPriority, Segmentation, Vec<u8>);
+
+fn decode_send_data(choice: u8, buffer: &mut dyn Read) -> Result<SendDataFields> {
+    let mut data = Vec::new();
+    buffer.read_to_end(&mut data)?;
+
+    let mut reader = PerReader::new(&data);
+    read_domain_header(&mut reader, choice)?;
+
+    let initiator = reader.read_integer16(MCS_BASE_CHANNEL_ID)?;
+    let channel_id = reader.read_integer16(0)?;
+
+    let flags = reader.read_u8()?;
+    let data_priority = DataPriority::from_u8(flags >> 6).ok_or_else(|| {
+        PduError::ParseError(format!("Invalid MCS data priority: {}", flags >> 6))
+    })?;
+    let segmentation = Segmentation::from_bits_truncate(flags);
+
+    let user_data = reader.read_octet_string(0)?;
+
+    Ok((
+        initiator,
+        channel_id,
+        data_priority,
+        segmentation,
+        user_data,
+    ))
+}
+
+fn send_data_size(user_data_len: usize) -> usize {
+    // header + initiator + channelId + priority/segmentation + length determinant
+    let length_size = if user_data_len < 0x80 { 1 } else { 2 };
+    1 + 2 + 2 + 1 + length_size + user_data_len
+}
+
+/// Build Share Control Header + payload bytes with pduSource set to the sender's user channel
+fn wrap_share_control(pdu_type: PduType, pdu_source: u16, payload: &[u8]) -> Result<Vec<u8>> {
+    let total_length = ShareControlHeader::SIZE + payload.len();
+    if total_length > u16::MAX as usize {
+        return Err(PduError::InvalidLength {
+            expected: u16::MAX as usize,
+            actual: total_length,
+        });
+    }
+
+    let header = ShareControlHeader::new(total_length as u16, pdu_type, pdu_source);
+
+    let mut user_data = Vec::with_capacity(total_length);
+    header.encode(&mut user_data)?;
+    user_data.extend_from_slice(payload);
+    Ok(user_data)
+}
+
+/// Split user data into Share Control Header and payload after checking the channel
+fn unwrap_share_control(
+    actual_channel_id: u16,
+    expected_channel_id: u16,
+    user_data: &[u8],
+) -> Result<(ShareControlHeader, Vec<u8>)> {
+    if actual_channel_id != expected_channel_id {
+        return Err(PduError::ParseError(format!(
+            "Unexpected MCS channel: expected {}, got {}",
+            expected_channel_id, actual_channel_id
+        )));
+    }
+
+    let mut cursor = Cursor::new(user_data);
+    let header = ShareControlHeader::decode(&mut cursor)?;
+
+    let total_length = header.total_length as usize;
+    if total_length < ShareControlHeader::SIZE || total_length > user_data.len() {
+        return Err(PduError::InvalidLength {
+            expected: total_length,
+            actual: user_data.len(),
+        });
+    }
+
+    let payload = user_data[ShareControlHeader::SIZE..total_length].to_vec();
+    Ok((header, payload))
+}
+
+/// MCS Send Data Request (client -> server)
+///
+/// SendDataRequest ::= [APPLICATION 25] IMPLICIT SEQUENCE { ... }
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SendDataRequest {
+    /// User ID of the sender (initiator)
+    pub initiator: u16,
+    /// Destination channel ID
+    pub channel_id: u16,
+    /// Data priority
+    pub data_priority: DataPriority,
+    /// Segmentation flags
+    pub segmentation: Segmentation,
+    /// User data (RDP payload)
+    pub user_data: Vec<u8>,
+}
+
+impl SendDataRequest {
+    /// Create new unsegmented SendDataRequest with high priority
+    pub fn new(initiator: u16, channel_id: u16, user_data: Vec<u8>) -> Self {
+        Self {
+            initiator,
+            channel_id,
+            data_priority: DataPriority::High,
+            segmentation: Segmentation::BEGIN | Segmentation::END,
+            user_data,
+        }
+    }
+
+    /// Wrap a Share Control payload for the given channel
+    ///
+    /// The Share Control Header is built here with `pduSource` set to `initiator`.
+    pub fn with_share_control(
+        initiator: u16,
+        channel_id: u16,
+        pdu_type: PduType,
+        payload: &[u8],
+    ) -> Result<Self> {
+        let user_data = wrap_share_control(pdu_type, initiator, payload)?;
+        Ok(Self::new(initiator, channel_id, user_data))
+    }
+
+    /// Unwrap the Share Control Header and payload carried on the given channel
+    pub fn share_control(&self, channel_id: u16) -> Result<(ShareControlHeader, Vec<u8>)> {
+        unwrap_share_control(self.channel_id, channel_id, &self.user_data)
+    }
+}
+
+impl Pdu for SendDataRequest {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        encode_send_data(
+            MCS_SEND_DATA_REQUEST,
+            self.initiator,
+            self.channel_id,
+            self.data_priority,
+            self.segmentation,
+            &self.user_data,
+            buffer,
+        )
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let (initiator, channel_id, data_priority, segmentation, user_data) =
+            decode_send_data(MCS_SEND_DATA_REQUEST, buffer)?;
+
+        Ok(Self {
+            initiator,
+            channel_id,
+            data_priority,
+            segmentation,
+            user_data,
+        })
+    }
+
+    fn size(&self) -> usize {
+        send_data_size(self.user_data.len())
+    }
+}
+
+/// MCS Send Data Indication (server -> client)
+///
+/// SendDataIndication ::= [APPLICATION 26] IMPLICIT SEQUENCE { ... }
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SendDataIndication {
+    /// User ID of the sender (initiator)
+    pub initiator: u16,
+    /// Source channel ID
+    pub channel_id: u16,
+    /// Data priority
+    pub data_priority: DataPriority,
+    /// Segmentation flags
+    pub segmentation: Segmentation,
+    /// User data (RDP payload)
+    pub user_data: Vec<u8>,
+}
+
+impl SendDataIndication {
+    /// Create new unsegmented SendDataIndication with high priority
+    pub fn new(initiator: u16, channel_id: u16, user_data: Vec<u8>) -> Self {
+        Self {
+            initiator,
+            channel_id,
+            data_priority: DataPriority::High,
+            segmentation: Segmentation::BEGIN | Segmentation::END,
+            user_data,
+        }
+    }
+
+    /// Wrap a Share Control payload for the given channel
+    ///
+    /// The Share Control Header is built here with `pduSource` set to `initiator`.
+    pub fn with_share_control(
+        initiator: u16,
+        channel_id: u16,
+        pdu_type: PduType,
+        payload: &[u8],
+    ) -> Result<Self> {
+        let user_data = wrap_share_control(pdu_type, initiator, payload)?;
+        Ok(Self::new(initiator, channel_id, user_data))
+    }
+
+    /// Unwrap the Share Control Header and payload carried on the given channel
+    pub fn share_control(&self, channel_id: u16) -> Result<(ShareControlHeader, Vec<u8>)> {
+        unwrap_share_control(self.channel_id, channel_id, &self.user_data)
+    }
+}
+
+impl Pdu for SendDataIndication {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        encode_send_data(
+            MCS_SEND_DATA_INDICATION,
+            self.initiator,
+            self.channel_id,
+            self.data_priority,
+            self.segmentation,
+            &self.user_data,
+            buffer,
+        )
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let (initiator, channel_id, data_priority, segmentation, user_data) =
+            decode_send_data(MCS_SEND_DATA_INDICATION, buffer)?;
+
+        Ok(Self {
+            initiator,
+            channel_id,
+            data_priority,
+            segmentation,
+            user_data,
+        })
+    }
+
+    fn size(&self) -> usize {
+        send_data_size(self.user_data.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdu::rdp::SynchronizePdu;
+
+    #[test]
+    fn test_send_data_request_header_bytes() {
+        // MS-RDPBCGR 4.1.10 Client Info PDU: 64 00 06 03 eb 70 81 ..
+        let request = SendDataRequest::new(1007, 1003, vec![0xAA; 0x120]);
+
+        let mut buffer = Vec::new();
+        request.encode(&mut buffer).unwrap();
+
+        assert_eq!(
+            &buffer[..8],
+            &[0x64, 0x00, 0x06, 0x03, 0xEB, 0x70, 0x81, 0x20]
+        );
+        assert_eq!(buffer.len(), request.size());
+    }
+
+    #[test]
+    fn test_send_data_indication_header_bytes() {
+        // Server initiator is 1002 (MCS server channel)
+        let indication = SendDataIndication::new(1002, 1003, vec![0x01, 0x02]);
+
+        let mut buffer = Vec::new();
+        indication.encode(&mut buffer).unwrap();
+
+        assert_eq!(
+            buffer,
+            vec![0x68, 0x00, 0x01, 0x03, 0xEB, 0x70, 0x02, 0x01, 0x02]
+        );
+        assert_eq!(buffer.len(), indication.size());
+    }
+
+    #[test]
+    fn test_send_data_request_roundtrip() {
+        let mut request = SendDataRequest::new(1007, 1004, b"payload".to_vec());
+        request.data_priority = DataPriority::Low;
+        request.segmentation = Segmentation::BEGIN;
+
+        let mut buffer = Vec::new();
+        request.encode(&mut buffer).unwrap();
+
+        let mut cursor = Cursor::new(buffer);
+        let decoded = SendDataRequest::decode(&mut cursor).unwrap();
+
+        assert_eq!(request, decoded);
+    }
+
+    #[test]
+    fn test_send_data_request_too_long() {
+        // userData above 16K needs PER fragmentation, which is not supported
+        let request = SendDataRequest::new(1007, 1003, vec![0x00; 0x4000]);
+
+        let mut buffer = Vec::new();
+        assert!(matches!(
+            request.encode(&mut buffer),
+            Err(PduError::InvalidLength { .. })
+        ));
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_send_data_indication_rejects_request() {
+        let request = SendDataRequest::new(1007, 1003, vec![0x00]);
+
+        let mut buffer = Vec::new();
+        request.encode(&mut buffer).unwrap();
+
+        let mut cursor = Cursor::new(buffer);
+        assert!(matches!(
+            SendDataIndication::decode(&mut cursor),
+            Err(PduError::InvalidPduType(MCS_SEND_DATA_REQUEST))
+        ));
+    }
+
+    #[test]
+    fn test_share_control_wrap_unwrap() {
+        let mut body = Vec::new();
+        SynchronizePdu::new(1002).encode(&mut body).unwrap();
+
+        let request =
+            SendDataRequest::with_share_control(1007, 1003, PduType::Data, &body).unwrap();
+
+        let (header, payload) = request.share_control(1003).unwrap();
+        assert_eq!(header.pdu_type, PduType::Data);
+        assert_eq!(header.pdu_source, 1007);
+        assert_eq!(
+            header.total_length as usize,
+            ShareControlHeader::SIZE + body.len()
+        );
+        assert_eq!(payload, body);
+    }
+
+    #[test]
+    fn test_share_control_unwrap_wrong_channel() {
+        let indication =
+            SendDataIndication::with_share_control(1002, 1003, PduType::DemandActive, &[0; 4])
+                .unwrap();
+
+        assert!(indication.share_control(1004).is_err());
+        assert!(indication.share_control(1003).is_ok());
+    }
+}
@@ -1,8 +1,15 @@
 use std::io::{Read, Write};
 use thiserror::Error;
 
+pub mod credssp;
+pub mod fastpath;
+pub mod frame;
+pub(crate) mod framing;
+pub mod gcc;
+pub mod licensing;
 pub mod mcs;
 pub mod rdp;
+pub mod security;
 pub mod tpkt;
 pub mod x224;
 
@@ -32,6 +39,9 @@ pub enum PduError {
 
     #[error("Invalid PDU type: {0:#x}")]
     InvalidPduType(u8),
+
+    #[error("Frame too large: {size} bytes exceeds limit of {max}")]
+    FrameTooLarge { size: usize, max: usize },
 }
 
 /// PDU common interface
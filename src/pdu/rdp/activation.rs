@@ -0,0 +1,639 @@
+use crate::pdu::{Pdu, PduError, Result};
+use bitflags::bitflags;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Read, Write};
+
+use super::capability::{CapabilitySet, CapabilitySetType};
+use super::control::{ControlAction, ControlPdu, FontListPdu, FontMapPdu, SynchronizePdu};
+use super::header::{PduType, ShareControlHeader};
+
+/// MCS channel ID of the server (originatorId in Confirm Active)
+pub const SERVER_CHANNEL_ID: u16 = 0x03EA;
+
+/// Source descriptor sent by servers
+pub const SERVER_SOURCE_DESCRIPTOR: &[u8] = b"RDP\0";
+
+/// Source descriptor sent by mstsc
+pub const CLIENT_SOURCE_DESCRIPTOR: &[u8] = b"MSTSC\0";
+
+/// Encode numberCapabilities, pad2Octets and capability sets
+fn encode_capability_sets(buffer: &mut dyn Write, capability_sets: &[CapabilitySet]) -> Result<()> {
+    let number_capabilities =
+        u16::try_from(capability_sets.len()).map_err(|_| PduError::InvalidLength {
+            expected: u16::MAX as usize,
+            actual: capability_sets.len(),
+        })?;
+    buffer.write_u16::<LittleEndian>(number_capabilities)?;
+    buffer.write_u16::<LittleEndian>(0)?; // pad2Octets
+    for capability_set in capability_sets {
+        capability_set.encode(buffer)?;
+    }
+    Ok(())
+}
+
+/// Decode numberCapabilities, pad2Octets and capability sets from lengthCombinedCapabilities bytes
+fn decode_capability_sets(
+    buffer: &mut dyn Read,
+    combined_length: usize,
+) -> Result<Vec<CapabilitySet>> {
+    let mut data = vec![0u8; combined_length];
+    buffer.read_exact(&mut data)?;
+
+    let mut cursor = data.as_slice();
+    let number_capabilities = cursor.read_u16::<LittleEndian>()?;
+    let _pad = cursor.read_u16::<LittleEndian>()?;
+
+    let capability_sets = (0..number_capabilities)
+        .map(|_| CapabilitySet::decode(&mut cursor))
+        .collect::<Result<Vec<_>>>()?;
+
+    if !cursor.is_empty() {
+        return Err(PduError::InvalidLength {
+            expected: combined_length - cursor.len(),
+            actual: combined_length,
+        });
+    }
+
+    Ok(capability_sets)
+}
+
+/// lengthCombinedCapabilities value (numberCapabilities + pad2Octets + sets)
+fn combined_capabilities_length(capability_sets: &[CapabilitySet]) -> Result<u16> {
+    let length = 4 + capability_sets.iter().map(|c| c.size()).sum::<usize>();
+    if length > u16::MAX as usize {
+        return Err(PduError::InvalidLength {
+            expected: u16::MAX as usize,
+            actual: length,
+        });
+    }
+    Ok(length as u16)
+}
+
+/// Length of source descriptor as u16
+fn source_descriptor_length(source_descriptor: &[u8]) -> Result<u16> {
+    if source_descriptor.len() > u16::MAX as usize {
+        return Err(PduError::InvalidLength {
+            expected: u16::MAX as usize,
+            actual: source_descriptor.len(),
+        });
+    }
+    Ok(source_descriptor.len() as u16)
+}
+
+/// Find capability set of the given type
+fn find_capability(
+    capability_sets: &[CapabilitySet],
+    capability_type: CapabilitySetType,
+) -> Option<&CapabilitySet> {
+    capability_sets
+        .iter()
+        .find(|c| c.type_value() == capability_type.as_u16())
+}
+
+/// Build a Share Control Header for a body of the given size
+fn share_control_header(
+    pdu_type: PduType,
+    pdu_source: u16,
+    body_size: usize,
+) -> Result<ShareControlHeader> {
+    let total_length = ShareControlHeader::SIZE + body_size;
+    if total_length > u16::MAX as usize {
+        return Err(PduError::InvalidLength {
+            expected: u16::MAX as usize,
+            actual: total_length,
+        });
+    }
+    Ok(ShareControlHeader::new(
+        total_length as u16,
+        pdu_type,
+        pdu_source,
+    ))
+}
+
+/// Demand Active PDU (MS-RDPBCGR 2.2.1.13.1)
+///
+/// Sent by the server after licensing; carried after a Share Control Header
+/// of type PDUTYPE_DEMANDACTIVEPDU.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DemandActivePdu {
+    /// Share ID assigned by the server
+    pub share_id: u32,
+    /// Source descriptor (usually "RDP\0")
+    pub source_descriptor: Vec<u8>,
+    /// Server capability sets
+    pub capability_sets: Vec<CapabilitySet>,
+    /// Session ID
+    pub session_id: u32,
+}
+
+impl DemandActivePdu {
+    /// Share Control Header PDU type
+    pub const PDU_TYPE: PduType = PduType::DemandActive;
+
+    /// Create new Demand Active PDU
+    pub fn new(share_id: u32, capability_sets: Vec<CapabilitySet>) -> Self {
+        Self {
+            share_id,
+            source_descriptor: SERVER_SOURCE_DESCRIPTOR.to_vec(),
+            capability_sets,
+            session_id: 0,
+        }
+    }
+
+    /// Find capability set of the given type
+    pub fn capability(&self, capability_type: CapabilitySetType) -> Option<&CapabilitySet> {
+        find_capability(&self.capability_sets, capability_type)
+    }
+
+    /// Share Control Header for this PDU
+    pub fn share_control_header(&self, pdu_source: u16) -> Result<ShareControlHeader> {
+        share_control_header(Self::PDU_TYPE, pdu_source, self.size())
+    }
+}
+
+impl Pdu for DemandActivePdu {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_u32::<LittleEndian>(self.share_id)?;
+        buffer.write_u16::<LittleEndian>(source_descriptor_length(&self.source_descriptor)?)?;
+        buffer.write_u16::<LittleEndian>(combined_capabilities_length(&self.capability_sets)?)?;
+        buffer.write_all(&self.source_descriptor)?;
+        encode_capability_sets(buffer, &self.capability_sets)?;
+        buffer.write_u32::<LittleEndian>(self.session_id)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let share_id = buffer.read_u32::<LittleEndian>()?;
+        let source_descriptor_len = buffer.read_u16::<LittleEndian>()? as usize;
+        let combined_length = buffer.read_u16::<LittleEndian>()? as usize;
+
+        let mut source_descriptor = vec![0u8; source_descriptor_len];
+        buffer.read_exact(&mut source_descriptor)?;
+
+        let capability_sets = decode_capability_sets(buffer, combined_length)?;
+        let session_id = buffer.read_u32::<LittleEndian>()?;
+
+        Ok(Self {
+            share_id,
+            source_descriptor,
+            capability_sets,
+            session_id,
+        })
+    }
+
+    fn size(&self) -> usize {
+        4 + 2
+            + 2
+            + self.source_descriptor.len()
+            + 4
+            + self.capability_sets.iter().map(|c| c.size()).sum::<usize>()
+            + 4
+    }
+}
+
+/// Confirm Active PDU (MS-RDPBCGR 2.2.1.13.2)
+///
+/// Sent by the client in response to Demand Active; carried after a Share
+/// Control Header of type PDUTYPE_CONFIRMACTIVEPDU.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfirmActivePdu {
+    /// Share ID from the Demand Active PDU
+    pub share_id: u32,
+    /// Originator ID (server channel ID, 0x03EA)
+    pub originator_id: u16,
+    /// Source descriptor (mstsc sends "MSTSC\0")
+    pub source_descriptor: Vec<u8>,
+    /// Client capability sets
+    pub capability_sets: Vec<CapabilitySet>,
+}
+
+impl ConfirmActivePdu {
+    /// Share Control Header PDU type
+    pub const PDU_TYPE: PduType = PduType::ConfirmActive;
+
+    /// Create new Confirm Active PDU
+    pub fn new(share_id: u32, capability_sets: Vec<CapabilitySet>) -> Self {
+        Self {
+            share_id,
+            originator_id: SERVER_CHANNEL_ID,
+            source_descriptor: CLIENT_SOURCE_DESCRIPTOR.to_vec(),
+            capability_sets,
+        }
+    }
+
+    /// Find capability set of the given type
+    pub fn capability(&self, capability_type: CapabilitySetType) -> Option<&CapabilitySet> {
+        find_capability(&self.capability_sets, capability_type)
+    }
+
+    /// Share Control Header for this PDU
+    pub fn share_control_header(&self, pdu_source: u16) -> Result<ShareControlHeader> {
+        share_control_header(Self::PDU_TYPE, pdu_source, self.size())
+    }
+}
+
+impl Pdu for ConfirmActivePdu {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_u32::<LittleEndian>(self.share_id)?;
+        buffer.write_u16::<LittleEndian>(self.originator_id)?;
+        buffer.write_u16::<LittleEndian>(source_descriptor_length(&self.source_descriptor)?)?;
+        buffer.write_u16::<LittleEndian>(combined_capabilities_length(&self.capability_sets)?)?;
+        buffer.write_all(&self.source_descriptor)?;
+        encode_capability_sets(buffer, &self.capability_sets)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let share_id = buffer.read_u32::<LittleEndian>()?;
+        let originator_id = buffer.read_u16::<LittleEndian>()?;
+        let source_descriptor_len = buffer.read_u16::<LittleEndian>()? as usize;
+        let combined_length = buffer.read_u16::<LittleEndian>()? as usize;
+
+        let mut source_descriptor = vec![0u8; source_descriptor_len];
+        buffer.read_exact(&mut source_descriptor)?;
+
+        let capability_sets = decode_capability_sets(buffer, combined_length)?;
+
+        Ok(Self {
+            share_id,
+            originator_id,
+            source_descriptor,
+            capability_sets,
+        })
+    }
+
+    fn size(&self) -> usize {
+        4 + 2
+            + 2
+            + 2
+            + self.source_descriptor.len()
+            + 4
+            + self.capability_sets.iter().map(|c| c.size()).sum::<usize>()
+    }
+}
+
+/// Deactivate All PDU (MS-RDPBCGR 2.2.3.1)
+///
+/// Sent by the server to tear down the current share (resolution change,
+/// reconnect). A new Demand Active round follows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeactivateAllPdu {
+    /// Share ID being deactivated
+    pub share_id: u32,
+    /// Source descriptor (servers usually send a single zero byte)
+    pub source_descriptor: Vec<u8>,
+}
+
+impl DeactivateAllPdu {
+    /// Share Control Header PDU type
+    pub const PDU_TYPE: PduType = PduType::DeactivateAll;
+
+    /// Create new Deactivate All PDU
+    pub fn new(share_id: u32) -> Self {
+        Self {
+            share_id,
+            source_descriptor: vec![0],
+        }
+    }
+
+    /// Share Control Header for this PDU
+    pub fn share_control_header(&self, pdu_source: u16) -> Result<ShareControlHeader> {
+        share_control_header(Self::PDU_TYPE, pdu_source, self.size())
+    }
+}
+
+impl Pdu for DeactivateAllPdu {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_u32::<LittleEndian>(self.share_id)?;
+        buffer.write_u16::<LittleEndian>(source_descriptor_length(&self.source_descriptor)?)?;
+        buffer.write_all(&self.source_descriptor)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let share_id = buffer.read_u32::<LittleEndian>()?;
+        let source_descriptor_len = buffer.read_u16::<LittleEndian>()? as usize;
+
+        let mut source_descriptor = vec![0u8; source_descriptor_len];
+        buffer.read_exact(&mut source_descriptor)?;
+
+        Ok(Self {
+            share_id,
+            source_descriptor,
+        })
+    }
+
+    fn size(&self) -> usize {
+        4 + 2 + self.source_descriptor.len()
+    }
+}
+
+/// Client activation state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivationState {
+    /// Waiting for Demand Active from the server
+    AwaitingDemandActive,
+    /// Confirm Active sent, waiting for the server finalization PDUs
+    Finalizing,
+    /// Server finalization complete, share is active
+    Active,
+}
+
+bitflags! {
+    /// Server finalization PDUs received (MS-RDPBCGR 1.3.1.1 phases 9-10)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct FinalizationFlags: u8 {
+        /// Server Synchronize PDU
+        const SYNCHRONIZE = 0x01;
+        /// Server Control (Cooperate) PDU
+        const CONTROL_COOPERATE = 0x02;
+        /// Server Control (Granted Control) PDU
+        const CONTROL_GRANTED = 0x04;
+        /// Server Font Map PDU
+        const FONT_MAP = 0x08;
+    }
+}
+
+/// PDU sent by the client during capability exchange and finalization
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientActivationPdu {
+    /// Confirm Active PDU (Share Control PDU)
+    ConfirmActive(ConfirmActivePdu),
+    /// Synchronize PDU (Share Data PDU)
+    Synchronize(SynchronizePdu),
+    /// Control PDU (Share Data PDU)
+    Control(ControlPdu),
+    /// Font List PDU (Share Data PDU)
+    FontList(FontListPdu),
+}
+
+/// Client side of the capability exchange and connection finalization
+///
+/// Tracks the share ID, server capabilities and finalization progress so the
+/// exchange can be repeated after a Deactivate All PDU.
+#[derive(Debug, Clone)]
+pub struct ClientActivation {
+    /// Capability sets advertised in Confirm Active
+    pub client_capabilities: Vec<CapabilitySet>,
+    state: ActivationState,
+    share_id: Option<u32>,
+    server_capabilities: Vec<CapabilitySet>,
+    finalization: FinalizationFlags,
+}
+
+impl ClientActivation {
+    /// Create new client activation with the capability sets to advertise
+    pub fn new(client_capabilities: Vec<CapabilitySet>) -> Self {
+        Self {
+            client_capabilities,
+            state: ActivationState::AwaitingDemandActive,
+            share_id: None,
+            server_capabilities: Vec::new(),
+            finalization: FinalizationFlags::empty(),
+        }
+    }
+
+    /// Current state
+    pub fn state(&self) -> ActivationState {
+        self.state
+    }
+
+    /// Share ID from the last Demand Active PDU
+    pub fn share_id(&self) -> Option<u32> {
+        self.share_id
+    }
+
+    /// Capability sets from the last Demand Active PDU
+    pub fn server_capabilities(&self) -> &[CapabilitySet] {
+        &self.server_capabilities
+    }
+
+    /// Server finalization PDUs received so far
+    pub fn finalization(&self) -> FinalizationFlags {
+        self.finalization
+    }
+
+    /// Whether the share is active
+    pub fn is_active(&self) -> bool {
+        self.state == ActivationState::Active
+    }
+
+    /// Handle Demand Active PDU
+    ///
+    /// Returns the Confirm Active, Synchronize, Control (Cooperate),
+    /// Control (Request Control) and Font List PDUs to send, in order.
+    pub fn on_demand_active(&mut self, pdu: &DemandActivePdu) -> Result<Vec<ClientActivationPdu>> {
+        if self.state != ActivationState::AwaitingDemandActive {
+            return Err(PduError::ParseError(format!(
+                "Unexpected Demand Active PDU in state {:?}",
+                self.state
+            )));
+        }
+
+        self.share_id = Some(pdu.share_id);
+        self.server_capabilities = pdu.capability_sets.clone();
+        self.finalization = FinalizationFlags::empty();
+        self.state = ActivationState::Finalizing;
+
+        Ok(vec![
+            ClientActivationPdu::ConfirmActive(ConfirmActivePdu::new(
+                pdu.share_id,
+                self.client_capabilities.clone(),
+            )),
+            ClientActivationPdu::Synchronize(SynchronizePdu::new(SERVER_CHANNEL_ID)),
+            ClientActivationPdu::Control(ControlPdu::cooperate()),
+            ClientActivationPdu::Control(ControlPdu::request_control()),
+            ClientActivationPdu::FontList(FontListPdu::new()),
+        ])
+    }
+
+    /// Handle server Synchronize PDU
+    pub fn on_synchronize(&mut self, _pdu: &SynchronizePdu) -> Result<()> {
+        self.finalize(FinalizationFlags::SYNCHRONIZE)
+    }
+
+    /// Handle server Control PDU (Cooperate or Granted Control)
+    pub fn on_control(&mut self, pdu: &ControlPdu) -> Result<()> {
+        match pdu.action {
+            ControlAction::Cooperate => self.finalize(FinalizationFlags::CONTROL_COOPERATE),
+            ControlAction::GrantedControl => self.finalize(FinalizationFlags::CONTROL_GRANTED),
+            action => Err(PduError::ParseError(format!(
+                "Unexpected server control action: {:?}",
+                action
+            ))),
+        }
+    }
+
+    /// Handle server Font Map PDU
+    pub fn on_font_map(&mut self, _pdu: &FontMapPdu) -> Result<()> {
+        self.finalize(FinalizationFlags::FONT_MAP)
+    }
+
+    /// Handle Deactivate All PDU
+    ///
+    /// Drops the server capabilities and finalization progress; the next
+    /// Demand Active PDU starts a fresh exchange.
+    pub fn on_deactivate_all(&mut self, _pdu: &DeactivateAllPdu) {
+        self.reset();
+    }
+
+    /// Reset to the initial state, keeping the client capability sets
+    pub fn reset(&mut self) {
+        self.state = ActivationState::AwaitingDemandActive;
+        self.share_id = None;
+        self.server_capabilities.clear();
+        self.finalization = FinalizationFlags::empty();
+    }
+
+    fn finalize(&mut self, flag: FinalizationFlags) -> Result<()> {
+        if self.state != ActivationState::Finalizing {
+            return Err(PduError::ParseError(format!(
+                "Unexpected finalization PDU in state {:?}",
+                self.state
+            )));
+        }
+
+        self.finalization |= flag;
+        if self.finalization.is_all() {
+            self.state = ActivationState::Active;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdu::rdp::capability::{BitmapCapability, GeneralCapability};
+    use std::io::Cursor;
+
+    fn capability_sets() -> Vec<CapabilitySet> {
+        vec![
+            CapabilitySet::General(GeneralCapability::new()),
+            CapabilitySet::Bitmap(BitmapCapability::new(1024, 768, 16)),
+            // Pointer capability set (not typed yet)
+            CapabilitySet::Unknown(0x0008, vec![0x01, 0x00, 0x14, 0x00, 0x15, 0x00]),
+            // Unassigned capability set type
+            CapabilitySet::Unknown(0x00F0, vec![0xDE, 0xAD]),
+        ]
+    }
+
+    #[test]
+    fn test_demand_active_pdu_roundtrip() {
+        let mut pdu = DemandActivePdu::new(0x0001_03EA, capability_sets());
+        pdu.session_id = 3;
+
+        let mut buffer = Vec::new();
+        pdu.encode(&mut buffer).unwrap();
+
+        assert_eq!(buffer.len(), pdu.size());
+        assert_eq!(
+            &buffer[..8],
+            &[0xEA, 0x03, 0x01, 0x00, 0x04, 0x00, 0x48, 0x00]
+        );
+        assert_eq!(&buffer[8..12], b"RDP\0");
+        assert_eq!(&buffer[12..16], &[0x04, 0x00, 0x00, 0x00]);
+        assert_eq!(&buffer[buffer.len() - 4..], &[0x03, 0x00, 0x00, 0x00]);
+
+        let mut cursor = Cursor::new(buffer);
+        let decoded = DemandActivePdu::decode(&mut cursor).unwrap();
+
+        assert_eq!(decoded, pdu);
+        assert!(decoded.capability(CapabilitySetType::Pointer).is_some());
+        assert!(decoded.capability(CapabilitySetType::Sound).is_none());
+    }
+
+    #[test]
+    fn test_confirm_active_pdu_roundtrip() {
+        let pdu = ConfirmActivePdu::new(0x0001_03EA, capability_sets());
+
+        let mut buffer = Vec::new();
+        pdu.encode(&mut buffer).unwrap();
+
+        assert_eq!(buffer.len(), pdu.size());
+        assert_eq!(&buffer[4..6], &[0xEA, 0x03]);
+        assert_eq!(&buffer[10..16], b"MSTSC\0");
+
+        let mut cursor = Cursor::new(buffer);
+        let decoded = ConfirmActivePdu::decode(&mut cursor).unwrap();
+
+        assert_eq!(decoded, pdu);
+        assert!(matches!(
+            decoded.capability(CapabilitySetType::General),
+            Some(CapabilitySet::General(_))
+        ));
+    }
+
+    #[test]
+    fn test_demand_active_pdu_length_mismatch() {
+        let pdu = DemandActivePdu::new(1, capability_sets());
+
+        let mut buffer = Vec::new();
+        pdu.encode(&mut buffer).unwrap();
+        // Claim one capability set fewer than present
+        buffer[12] = 3;
+
+        let mut cursor = Cursor::new(buffer);
+        assert!(DemandActivePdu::decode(&mut cursor).is_err());
+    }
+
+    #[test]
+    fn test_deactivate_all_pdu_roundtrip() {
+        let pdu = DeactivateAllPdu::new(0x0001_03EA);
+
+        let mut buffer = Vec::new();
+        pdu.encode(&mut buffer).unwrap();
+
+        assert_eq!(buffer, vec![0xEA, 0x03, 0x01, 0x00, 0x01, 0x00, 0x00]);
+        assert_eq!(buffer.len(), pdu.size());
+
+        let header = pdu.share_control_header(SERVER_CHANNEL_ID).unwrap();
+        assert_eq!(header.pdu_type, PduType::DeactivateAll);
+        assert_eq!(header.total_length, 13);
+
+        let mut cursor = Cursor::new(buffer);
+        assert_eq!(DeactivateAllPdu::decode(&mut cursor).unwrap(), pdu);
+    }
+
+    #[test]
+    fn test_client_activation_reactivation() {
+        let mut activation = ClientActivation::new(capability_sets());
+
+        let pdus = activation
+            .on_demand_active(&DemandActivePdu::new(1, Vec::new()))
+            .unwrap();
+        assert_eq!(pdus.len(), 5);
+        assert!(matches!(&pdus[0], ClientActivationPdu::ConfirmActive(p) if p.share_id == 1));
+        assert!(matches!(&pdus[4], ClientActivationPdu::FontList(_)));
+
+        activation
+            .on_synchronize(&SynchronizePdu::new(1007))
+            .unwrap();
+        activation.on_control(&ControlPdu::cooperate()).unwrap();
+        activation
+            .on_control(&ControlPdu::granted_control(1007))
+            .unwrap();
+        assert_eq!(activation.state(), ActivationState::Finalizing);
+        activation.on_font_map(&FontMapPdu::new()).unwrap();
+        assert!(activation.is_active());
+
+        // Demand Active is only valid after deactivation
+        assert!(
+            activation
+                .on_demand_active(&DemandActivePdu::new(2, Vec::new()))
+                .is_err()
+        );
+
+        activation.on_deactivate_all(&DeactivateAllPdu::new(1));
+        assert_eq!(activation.state(), ActivationState::AwaitingDemandActive);
+        assert_eq!(activation.share_id(), None);
+        assert!(activation.on_font_map(&FontMapPdu::new()).is_err());
+
+        let pdus = activation
+            .on_demand_active(&DemandActivePdu::new(2, capability_sets()))
+            .unwrap();
+        assert!(matches!(&pdus[0], ClientActivationPdu::ConfirmActive(p) if p.share_id == 2));
+        assert_eq!(activation.server_capabilities().len(), 4);
+        assert!(activation.finalization().is_empty());
+    }
+}
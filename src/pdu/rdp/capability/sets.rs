@@ -166,6 +166,14 @@ pub enum CapabilitySet {
 }
 
 impl CapabilitySet {
+    /// Get raw capability set type value (preserved for unknown sets)
+    pub fn type_value(&self) -> u16 {
+        match self {
+            CapabilitySet::Unknown(type_val, _) => *type_val,
+            _ => self.capability_type().as_u16(),
+        }
+    }
+
     /// Get capability set type
     pub fn capability_type(&self) -> CapabilitySetType {
         match self {
@@ -187,11 +195,17 @@ impl CapabilitySet {
             CapabilitySet::Order(cap) => cap.encode(buffer),
             CapabilitySet::Input(cap) => cap.encode(buffer),
             CapabilitySet::Unknown(type_val, data) => {
-                let header = CapabilitySetHeader::new(
-                    CapabilitySetType::from_u16(*type_val).unwrap_or(CapabilitySetType::General),
-                    (CapabilitySetHeader::SIZE + data.len()) as u16,
-                );
-                header.encode(buffer)?;
+                let length = CapabilitySetHeader::SIZE + data.len();
+                if length > u16::MAX as usize {
+                    return Err(PduError::InvalidLength {
+                        expected: u16::MAX as usize,
+                        actual: length,
+                    });
+                }
+
+                // Raw type value is written as-is so unrecognized sets round-trip
+                buffer.write_u16::<LittleEndian>(*type_val)?;
+                buffer.write_u16::<LittleEndian>(length as u16)?;
                 buffer.write_all(data)?;
                 Ok(())
             }
@@ -199,36 +213,50 @@ impl CapabilitySet {
     }
 
     /// Decode capability set from buffer
+    ///
+    /// Sets without a typed representation, and typed sets whose length does
+    /// not match the fixed layout, are kept as `Unknown` so they re-encode
+    /// byte-for-byte.
     pub fn decode(buffer: &mut dyn Read) -> Result<Self> {
-        let header = CapabilitySetHeader::decode(buffer)?;
-        let data_len = header.length_capability as usize - CapabilitySetHeader::SIZE;
+        let type_val = buffer.read_u16::<LittleEndian>()?;
+        let length = buffer.read_u16::<LittleEndian>()? as usize;
+        let data_len =
+            length
+                .checked_sub(CapabilitySetHeader::SIZE)
+                .ok_or(PduError::InvalidLength {
+                    expected: CapabilitySetHeader::SIZE,
+                    actual: length,
+                })?;
 
-        match header.capability_set_type {
-            CapabilitySetType::General => {
-                let cap = GeneralCapability::decode_data(buffer, data_len)?;
-                Ok(CapabilitySet::General(cap))
-            }
-            CapabilitySetType::Bitmap => {
-                let cap = BitmapCapability::decode_data(buffer, data_len)?;
-                Ok(CapabilitySet::Bitmap(cap))
-            }
-            CapabilitySetType::Order => {
-                let cap = OrderCapability::decode_data(buffer, data_len)?;
-                Ok(CapabilitySet::Order(cap))
-            }
-            CapabilitySetType::Input => {
-                let cap = InputCapability::decode_data(buffer, data_len)?;
-                Ok(CapabilitySet::Input(cap))
-            }
-            _ => {
-                // Unknown capability - read as raw data
-                let mut data = vec![0u8; data_len];
-                buffer.read_exact(&mut data)?;
-                Ok(CapabilitySet::Unknown(
-                    header.capability_set_type.as_u16(),
-                    data,
-                ))
-            }
+        let mut data = vec![0u8; data_len];
+        buffer.read_exact(&mut data)?;
+
+        let fixed_size = match CapabilitySetType::from_u16(type_val) {
+            Some(CapabilitySetType::General) => Some(GeneralCapability::DATA_SIZE),
+            Some(CapabilitySetType::Bitmap) => Some(BitmapCapability::DATA_SIZE),
+            Some(CapabilitySetType::Order) => Some(OrderCapability::DATA_SIZE),
+            Some(CapabilitySetType::Input) => Some(InputCapability::DATA_SIZE),
+            _ => None,
+        };
+        if fixed_size != Some(data_len) {
+            return Ok(CapabilitySet::Unknown(type_val, data));
+        }
+
+        let mut cursor = data.as_slice();
+        match CapabilitySetType::from_u16(type_val) {
+            Some(CapabilitySetType::General) => Ok(CapabilitySet::General(
+                GeneralCapability::decode_data(&mut cursor, data_len)?,
+            )),
+            Some(CapabilitySetType::Bitmap) => Ok(CapabilitySet::Bitmap(
+                BitmapCapability::decode_data(&mut cursor, data_len)?,
+            )),
+            Some(CapabilitySetType::Order) => Ok(CapabilitySet::Order(
+                OrderCapability::decode_data(&mut cursor, data_len)?,
+            )),
+            Some(CapabilitySetType::Input) => Ok(CapabilitySet::Input(
+                InputCapability::decode_data(&mut cursor, data_len)?,
+            )),
+            _ => Ok(CapabilitySet::Unknown(type_val, data)),
         }
     }
 
@@ -284,5 +312,41 @@ mod tests {
         cap.encode(&mut buffer).unwrap();
 
         assert_eq!(cap.size(), CapabilitySetHeader::SIZE + data.len());
+        assert_eq!(&buffer[..4], &[0xFF, 0x00, 0x08, 0x00]);
+
+        let mut cursor = Cursor::new(buffer);
+        let decoded = CapabilitySet::decode(&mut cursor).unwrap();
+        assert_eq!(decoded, cap);
+        assert_eq!(decoded.type_value(), 0x00FF);
+    }
+
+    #[test]
+    fn test_known_capability_set_with_unexpected_length() {
+        // General capability set with 2 extra bytes is preserved as-is
+        let mut buffer = Vec::new();
+        CapabilitySet::General(GeneralCapability::new())
+            .encode(&mut buffer)
+            .unwrap();
+        buffer.extend_from_slice(&[0xAA, 0xBB]);
+        buffer[2] += 2;
+
+        let mut cursor = Cursor::new(buffer.clone());
+        let decoded = CapabilitySet::decode(&mut cursor).unwrap();
+        assert!(matches!(decoded, CapabilitySet::Unknown(0x0001, _)));
+
+        let mut reencoded = Vec::new();
+        decoded.encode(&mut reencoded).unwrap();
+        assert_eq!(reencoded, buffer);
+    }
+
+    #[test]
+    fn test_known_capability_set_roundtrip() {
+        let cap = CapabilitySet::Bitmap(BitmapCapability::new(1024, 768, 16));
+
+        let mut buffer = Vec::new();
+        cap.encode(&mut buffer).unwrap();
+
+        let mut cursor = Cursor::new(buffer);
+        assert_eq!(CapabilitySet::decode(&mut cursor).unwrap(), cap);
     }
 }
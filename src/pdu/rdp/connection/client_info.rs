@@ -1,7 +1,24 @@
+use crate::pdu::rdp::session_info::{
+    ARC_PRIVATE_PACKET_SIZE, ArcScPrivatePacket, decode_arc_private_packet,
+    encode_arc_private_packet,
+};
 use crate::pdu::{Pdu, PduError, Result};
 use bitflags::bitflags;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
-use std::io::{Read, Write};
+use encoding_rs::Encoding;
+use hmac::{Hmac, Mac};
+use md5::Md5;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// Maximum size of dynamicDSTTimeZoneKeyName (bytes)
+const DYNAMIC_DST_TIME_ZONE_KEY_NAME_MAX_SIZE: usize = 254;
+
+/// Client address family: IPv4
+pub const AF_INET: u16 = 0x0002;
+
+/// Client address family: IPv6
+pub const AF_INET6: u16 = 0x0017;
 
 bitflags! {
     /// Client Info PDU Flags (MS-RDPBCGR 2.2.1.11.1.1)
@@ -196,7 +213,7 @@ pub struct ClientInfoPdu {
 /// Extended Client Info (MS-RDPBCGR 2.2.1.11.1.1.1)
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ExtendedInfo {
-    /// Client address family (AF_INET=2, AF_INET6=23)
+    /// Client address family (AF_INET or AF_INET6)
     pub client_address_family: u16,
     /// Client address
     pub client_address: String,
@@ -208,6 +225,168 @@ pub struct ExtendedInfo {
     pub client_session_id: u32,
     /// Performance flags
     pub performance_flags: PerformanceFlags,
+    /// Auto-reconnect cookie (optional)
+    pub auto_reconnect_cookie: Option<ArcCsPrivatePacket>,
+    /// Reserved (optional)
+    pub reserved1: Option<u16>,
+    /// Reserved (optional)
+    pub reserved2: Option<u16>,
+    /// Dynamic DST time zone key name (optional, up to 127 characters)
+    pub dynamic_dst_time_zone_key_name: Option<String>,
+    /// Dynamic daylight saving time disabled (optional)
+    pub dynamic_daylight_time_disabled: Option<bool>,
+}
+
+impl Default for ExtendedInfo {
+    fn default() -> Self {
+        Self {
+            client_address_family: AF_INET,
+            client_address: String::new(),
+            client_dir: String::new(),
+            client_time_zone: TimeZoneInformation::utc(),
+            client_session_id: 0,
+            performance_flags: PerformanceFlags::empty(),
+            auto_reconnect_cookie: None,
+            reserved1: None,
+            reserved2: None,
+            dynamic_dst_time_zone_key_name: None,
+            dynamic_daylight_time_disabled: None,
+        }
+    }
+}
+
+impl ExtendedInfo {
+    /// Client address parsed according to the address family
+    pub fn client_ip_address(&self) -> Option<IpAddr> {
+        match self.client_address_family {
+            AF_INET => self.client_address.parse::<Ipv4Addr>().ok().map(IpAddr::V4),
+            AF_INET6 => self.client_address.parse::<Ipv6Addr>().ok().map(IpAddr::V6),
+            _ => None,
+        }
+    }
+
+    /// Set client address and matching address family
+    pub fn set_client_ip_address(&mut self, address: IpAddr) {
+        self.client_address_family = match address {
+            IpAddr::V4(_) => AF_INET,
+            IpAddr::V6(_) => AF_INET6,
+        };
+        self.client_address = address.to_string();
+    }
+
+    /// Number of optional fields after autoReconnectCookie to write
+    ///
+    /// Each field is only present if all preceding ones are, so missing
+    /// earlier fields are written as zero.
+    fn tail_field_count(&self) -> usize {
+        if self.dynamic_daylight_time_disabled.is_some() {
+            4
+        } else if self.dynamic_dst_time_zone_key_name.is_some() {
+            3
+        } else if self.reserved2.is_some() {
+            2
+        } else if self.reserved1.is_some() {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Encode optional fields after autoReconnectCookie
+    fn encode_tail(&self, buffer: &mut dyn Write) -> Result<()> {
+        let count = self.tail_field_count();
+
+        if count >= 1 {
+            buffer.write_u16::<LittleEndian>(self.reserved1.unwrap_or(0))?;
+        }
+        if count >= 2 {
+            buffer.write_u16::<LittleEndian>(self.reserved2.unwrap_or(0))?;
+        }
+        if count >= 3 {
+            // No null terminator
+            let key_name = self.dynamic_dst_time_zone_key_name.as_deref().unwrap_or("");
+            let cb_key_name = key_name.encode_utf16().count() * 2;
+            if cb_key_name > DYNAMIC_DST_TIME_ZONE_KEY_NAME_MAX_SIZE {
+                return Err(PduError::InvalidLength {
+                    expected: DYNAMIC_DST_TIME_ZONE_KEY_NAME_MAX_SIZE,
+                    actual: cb_key_name,
+                });
+            }
+            buffer.write_u16::<LittleEndian>(cb_key_name as u16)?;
+            for ch in key_name.encode_utf16() {
+                buffer.write_u16::<LittleEndian>(ch)?;
+            }
+        }
+        if count >= 4 {
+            let disabled = self.dynamic_daylight_time_disabled.unwrap_or(false);
+            buffer.write_u16::<LittleEndian>(u16::from(disabled))?;
+        }
+
+        Ok(())
+    }
+
+    /// Size of optional fields after autoReconnectCookie
+    fn tail_size(&self) -> usize {
+        let count = self.tail_field_count();
+        let mut size = 2 * count;
+        if count >= 3 {
+            size += self
+                .dynamic_dst_time_zone_key_name
+                .as_deref()
+                .map_or(0, |name| name.encode_utf16().count() * 2);
+        }
+        size
+    }
+}
+
+/// Client auto-reconnect cookie (ARC_CS_PRIVATE_PACKET, MS-RDPBCGR 2.2.1.11.1.1.1)
+///
+/// 28 bytes total
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArcCsPrivatePacket {
+    /// Session logon ID from the server cookie
+    pub logon_id: u32,
+    /// HMAC-MD5 of the client random keyed with the server's ARC random bits
+    pub security_verifier: [u8; 16],
+}
+
+impl ArcCsPrivatePacket {
+    /// Size in bytes (28)
+    pub const SIZE: usize = ARC_PRIVATE_PACKET_SIZE;
+
+    /// Derive the client cookie from a stored server cookie
+    ///
+    /// `client_random` is the 32-byte client random sent in the Security
+    /// Exchange PDU, or 32 zero bytes when Enhanced RDP Security is used
+    /// (MS-RDPBCGR 5.5).
+    pub fn from_server_cookie(cookie: &ArcScPrivatePacket, client_random: &[u8]) -> Self {
+        Self {
+            logon_id: cookie.logon_id,
+            security_verifier: compute_security_verifier(&cookie.arc_random_bits, client_random),
+        }
+    }
+
+    /// Encode to buffer
+    pub fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        encode_arc_private_packet(buffer, self.logon_id, &self.security_verifier)
+    }
+
+    /// Decode from buffer
+    pub fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let (logon_id, security_verifier) = decode_arc_private_packet(buffer)?;
+        Ok(Self {
+            logon_id,
+            security_verifier,
+        })
+    }
+}
+
+/// Compute the ARC security verifier: HMAC-MD5(ArcRandomBits, ClientRandom)
+pub fn compute_security_verifier(arc_random_bits: &[u8; 16], client_random: &[u8]) -> [u8; 16] {
+    let mut mac = <Hmac<Md5> as Mac>::new_from_slice(arc_random_bits)
+        .expect("HMAC accepts keys of any length");
+    mac.update(client_random);
+    mac.finalize().into_bytes().into()
 }
 
 impl ClientInfoPdu {
@@ -241,43 +420,139 @@ impl ClientInfoPdu {
         self.domain = domain;
         self
     }
+
+    /// Send strings in an ANSI code page instead of UTF-16LE
+    ///
+    /// Fails, leaving the PDU unchanged, when the code page is unsupported
+    /// or one of the strings cannot be represented in it.
+    pub fn set_code_page(&mut self, code_page: u32) -> Result<()> {
+        let mut ansi = self.clone();
+        ansi.flags.remove(ClientInfoFlags::UNICODE);
+        ansi.code_page = code_page;
+        for s in ansi.info_strings() {
+            ansi.encode_info_string(s)?;
+        }
+
+        *self = ansi;
+        Ok(())
+    }
+
+    /// Check whether strings are sent as UTF-16LE
+    pub fn is_unicode(&self) -> bool {
+        self.flags.contains(ClientInfoFlags::UNICODE)
+    }
+
+    /// Size of the string null terminator per the UNICODE flag
+    fn terminator_size(&self) -> usize {
+        if self.is_unicode() { 2 } else { 1 }
+    }
+
+    /// Encode a string with its null terminator per the UNICODE flag
+    fn encode_info_string(&self, s: &str) -> Result<Vec<u8>> {
+        if self.is_unicode() {
+            let mut bytes = Vec::with_capacity(unicode_string_size(s));
+            write_unicode_string(&mut bytes, s)?;
+            return Ok(bytes);
+        }
+
+        let encoding = code_page_encoding(self.code_page)?;
+        let (encoded, _, had_errors) = encoding.encode(s);
+        if had_errors {
+            return Err(PduError::ParseError(format!(
+                "String {:?} cannot be represented in code page {}",
+                s, self.code_page
+            )));
+        }
+        let mut bytes = encoded.into_owned();
+        bytes.push(0);
+        Ok(bytes)
+    }
+
+    /// Domain, user name, password, alternate shell and working directory
+    fn info_strings(&self) -> [&str; 5] {
+        [
+            &self.domain,
+            &self.user_name,
+            &self.password,
+            &self.alternate_shell,
+            &self.working_dir,
+        ]
+    }
+
+    /// Encoded size of a string with its null terminator
+    ///
+    /// `set_code_page` guarantees the strings are encodable. A string made
+    /// unrepresentable afterwards through the public fields makes `encode`
+    /// fail; it is counted at its UTF-8 length here.
+    fn info_string_size(&self, s: &str) -> usize {
+        match self.encode_info_string(s) {
+            Ok(bytes) => bytes.len(),
+            Err(_) => s.len() + self.terminator_size(),
+        }
+    }
+
+    /// Prepare for auto-reconnect using a stored server cookie
+    ///
+    /// The server cookie arrives in the Save Session Info PDU. Extended info
+    /// is added when missing. The password is kept so the server can fall
+    /// back to a normal logon if it rejects the cookie.
+    pub fn with_auto_reconnect(
+        mut self,
+        cookie: &ArcScPrivatePacket,
+        client_random: &[u8],
+    ) -> Self {
+        let extended_info = self.extended_info.get_or_insert_with(ExtendedInfo::default);
+        extended_info.auto_reconnect_cookie = Some(ArcCsPrivatePacket::from_server_cookie(
+            cookie,
+            client_random,
+        ));
+        self
+    }
+
+    /// Send no password and clear AUTOLOGON
+    ///
+    /// Opt-in for reconnects that should rely on the auto-reconnect cookie
+    /// alone; if the server rejects the cookie, the user has to log on
+    /// interactively.
+    pub fn without_password(mut self) -> Self {
+        self.password = String::new();
+        self.flags.remove(ClientInfoFlags::AUTOLOGON);
+        self
+    }
 }
 
 impl Pdu for ClientInfoPdu {
     fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        // Strings (UTF-16LE or code page, with null terminator), encoded
+        // first so an unsupported code page fails before any output
+        let strings = self
+            .info_strings()
+            .into_iter()
+            .map(|s| self.encode_info_string(s))
+            .collect::<Result<Vec<_>>>()?;
+
         // Code page and flags
         buffer.write_u32::<LittleEndian>(self.code_page)?;
         buffer.write_u32::<LittleEndian>(self.flags.bits())?;
 
-        // String lengths (in bytes, including null terminator)
-        let domain_bytes = encode_string_length(&self.domain);
-        let user_bytes = encode_string_length(&self.user_name);
-        let password_bytes = encode_string_length(&self.password);
-        let alt_shell_bytes = encode_string_length(&self.alternate_shell);
-        let work_dir_bytes = encode_string_length(&self.working_dir);
-
-        buffer.write_u16::<LittleEndian>(domain_bytes)?;
-        buffer.write_u16::<LittleEndian>(user_bytes)?;
-        buffer.write_u16::<LittleEndian>(password_bytes)?;
-        buffer.write_u16::<LittleEndian>(alt_shell_bytes)?;
-        buffer.write_u16::<LittleEndian>(work_dir_bytes)?;
-
-        // Write strings (UTF-16LE with null terminator)
-        write_unicode_string(buffer, &self.domain)?;
-        write_unicode_string(buffer, &self.user_name)?;
-        write_unicode_string(buffer, &self.password)?;
-        write_unicode_string(buffer, &self.alternate_shell)?;
-        write_unicode_string(buffer, &self.working_dir)?;
-
-        // Extended info (if present)
+        // String lengths (in bytes, excluding null terminator)
+        for bytes in &strings {
+            let cb_string = string_length(bytes.len() - self.terminator_size())?;
+            buffer.write_u16::<LittleEndian>(cb_string)?;
+        }
+        for bytes in &strings {
+            buffer.write_all(bytes)?;
+        }
+
+        // Extended info (if present, strings always UTF-16LE)
         if let Some(ref ext) = self.extended_info {
             buffer.write_u16::<LittleEndian>(ext.client_address_family)?;
 
-            let client_addr_bytes = encode_string_length(&ext.client_address);
+            let client_addr_bytes = string_length(unicode_string_size(&ext.client_address))?;
             buffer.write_u16::<LittleEndian>(client_addr_bytes)?;
             write_unicode_string(buffer, &ext.client_address)?;
 
-            let client_dir_bytes = encode_string_length(&ext.client_dir);
+            let client_dir_bytes = string_length(unicode_string_size(&ext.client_dir))?;
             buffer.write_u16::<LittleEndian>(client_dir_bytes)?;
             write_unicode_string(buffer, &ext.client_dir)?;
 
@@ -286,8 +561,15 @@ impl Pdu for ClientInfoPdu {
             buffer.write_u32::<LittleEndian>(ext.client_session_id)?;
             buffer.write_u32::<LittleEndian>(ext.performance_flags.bits())?;
 
-            // cbAutoReconnectLen (0 - no auto reconnect cookie)
-            buffer.write_u16::<LittleEndian>(0)?;
+            // cbAutoReconnectLen and autoReconnectCookie
+            if let Some(ref cookie) = ext.auto_reconnect_cookie {
+                buffer.write_u16::<LittleEndian>(ArcCsPrivatePacket::SIZE as u16)?;
+                cookie.encode(buffer)?;
+            } else {
+                buffer.write_u16::<LittleEndian>(0)?;
+            }
+
+            ext.encode_tail(buffer)?;
         }
 
         Ok(())
@@ -305,15 +587,24 @@ impl Pdu for ClientInfoPdu {
         let cb_alternate_shell = buffer.read_u16::<LittleEndian>()?;
         let cb_working_dir = buffer.read_u16::<LittleEndian>()?;
 
-        // Read strings
-        let domain = read_unicode_string(buffer, cb_domain)?;
-        let user_name = read_unicode_string(buffer, cb_user_name)?;
-        let password = read_unicode_string(buffer, cb_password)?;
-        let alternate_shell = read_unicode_string(buffer, cb_alternate_shell)?;
-        let working_dir = read_unicode_string(buffer, cb_working_dir)?;
-
-        // Try to read extended info (may not be present)
-        let extended_info = if let Ok(client_address_family) = buffer.read_u16::<LittleEndian>() {
+        // Read strings, each followed by a null terminator not counted above
+        let read_string = |buffer: &mut dyn Read, byte_count: u16| {
+            let (s, terminator_size) = if flags.contains(ClientInfoFlags::UNICODE) {
+                (read_unicode_string(buffer, byte_count)?, 2)
+            } else {
+                (read_ansi_string(buffer, byte_count, code_page)?, 1)
+            };
+            read_null_terminator(buffer, terminator_size)?;
+            Ok::<_, PduError>(s)
+        };
+        let domain = read_string(buffer, cb_domain)?;
+        let user_name = read_string(buffer, cb_user_name)?;
+        let password = read_string(buffer, cb_password)?;
+        let alternate_shell = read_string(buffer, cb_alternate_shell)?;
+        let working_dir = read_string(buffer, cb_working_dir)?;
+
+        // Extended info (may not be present)
+        let extended_info = if let Some(client_address_family) = read_optional_u16(buffer)? {
             let cb_client_address = buffer.read_u16::<LittleEndian>()?;
             let client_address = read_unicode_string(buffer, cb_client_address)?;
 
@@ -325,13 +616,37 @@ impl Pdu for ClientInfoPdu {
             let performance_flags_bits = buffer.read_u32::<LittleEndian>()?;
             let performance_flags = PerformanceFlags::from_bits_truncate(performance_flags_bits);
 
-            // Skip auto reconnect cookie (if present)
-            if let Ok(cb_auto_reconnect) = buffer.read_u16::<LittleEndian>() {
-                if cb_auto_reconnect > 0 {
+            // Auto reconnect cookie (if present)
+            let cb_auto_reconnect = read_optional_u16(buffer)?;
+            let auto_reconnect_cookie = match cb_auto_reconnect {
+                None | Some(0) => None,
+                Some(cb_auto_reconnect) => {
                     let mut cookie = vec![0u8; cb_auto_reconnect as usize];
-                    let _ = buffer.read_exact(&mut cookie);
+                    buffer.read_exact(&mut cookie)?;
+                    Some(ArcCsPrivatePacket::decode(&mut cookie.as_slice())?)
                 }
-            }
+            };
+
+            // Each remaining field is present only if the previous one is
+            let reserved1 = match cb_auto_reconnect {
+                Some(_) => read_optional_u16(buffer)?,
+                None => None,
+            };
+            let reserved2 = match reserved1 {
+                Some(_) => read_optional_u16(buffer)?,
+                None => None,
+            };
+            let dynamic_dst_time_zone_key_name = match reserved2 {
+                Some(_) => match read_optional_u16(buffer)? {
+                    Some(cb_key_name) => Some(read_time_zone_key_name(buffer, cb_key_name)?),
+                    None => None,
+                },
+                None => None,
+            };
+            let dynamic_daylight_time_disabled = match dynamic_dst_time_zone_key_name {
+                Some(_) => read_optional_u16(buffer)?.map(|disabled| disabled != 0),
+                None => None,
+            };
 
             Some(ExtendedInfo {
                 client_address_family,
@@ -340,6 +655,11 @@ impl Pdu for ClientInfoPdu {
                 client_time_zone,
                 client_session_id,
                 performance_flags,
+                auto_reconnect_cookie,
+                reserved1,
+                reserved2,
+                dynamic_dst_time_zone_key_name,
+                dynamic_daylight_time_disabled,
             })
         } else {
             None
@@ -360,34 +680,142 @@ impl Pdu for ClientInfoPdu {
     fn size(&self) -> usize {
         let mut size = 4 + 4 + 2 + 2 + 2 + 2 + 2; // Fixed header fields
 
-        // String lengths (UTF-16LE with null terminator)
-        size += (self.domain.len() + 1) * 2;
-        size += (self.user_name.len() + 1) * 2;
-        size += (self.password.len() + 1) * 2;
-        size += (self.alternate_shell.len() + 1) * 2;
-        size += (self.working_dir.len() + 1) * 2;
+        // Strings (with null terminator)
+        for s in self.info_strings() {
+            size += self.info_string_size(s);
+        }
 
         if let Some(ref ext) = self.extended_info {
             size += 2; // clientAddressFamily
-            size += 2 + (ext.client_address.len() + 1) * 2;
-            size += 2 + (ext.client_dir.len() + 1) * 2;
+            size += 2 + unicode_string_size(&ext.client_address);
+            size += 2 + unicode_string_size(&ext.client_dir);
             size += TimeZoneInformation::SIZE;
             size += 4; // clientSessionId
             size += 4; // performanceFlags
             size += 2; // cbAutoReconnectLen
+            if ext.auto_reconnect_cookie.is_some() {
+                size += ArcCsPrivatePacket::SIZE;
+            }
+            size += ext.tail_size();
         }
 
         size
     }
 }
 
-// Helper functions for Unicode string encoding/decoding
+// Helper functions for string encoding/decoding
+
+/// Encoding for a Windows ANSI code page (0 selects Windows-1252)
+pub fn code_page_encoding(code_page: u32) -> Result<&'static Encoding> {
+    let encoding = match code_page {
+        0 | 1252 => encoding_rs::WINDOWS_1252,
+        874 => encoding_rs::WINDOWS_874,
+        932 => encoding_rs::SHIFT_JIS,
+        936 => encoding_rs::GBK,
+        949 => encoding_rs::EUC_KR,
+        950 => encoding_rs::BIG5,
+        1250 => encoding_rs::WINDOWS_1250,
+        1251 => encoding_rs::WINDOWS_1251,
+        1253 => encoding_rs::WINDOWS_1253,
+        1254 => encoding_rs::WINDOWS_1254,
+        1255 => encoding_rs::WINDOWS_1255,
+        1256 => encoding_rs::WINDOWS_1256,
+        1257 => encoding_rs::WINDOWS_1257,
+        1258 => encoding_rs::WINDOWS_1258,
+        20866 => encoding_rs::KOI8_R,
+        54936 => encoding_rs::GB18030,
+        65001 => encoding_rs::UTF_8,
+        _ => {
+            return Err(PduError::ParseError(format!(
+                "Unsupported code page: {}",
+                code_page
+            )));
+        }
+    };
+    Ok(encoding)
+}
+
+/// Convert an encoded string size to a cb* length field
+fn string_length(size: usize) -> Result<u16> {
+    u16::try_from(size).map_err(|_| PduError::InvalidLength {
+        expected: u16::MAX as usize,
+        actual: size,
+    })
+}
+
+/// Size in bytes of a null-terminated UTF-16LE string
+pub(crate) fn unicode_string_size(s: &str) -> usize {
+    (s.encode_utf16().count() + 1) * 2
+}
+
+fn read_ansi_string(buffer: &mut dyn Read, byte_count: u16, code_page: u32) -> Result<String> {
+    if byte_count == 0 {
+        return Ok(String::new());
+    }
+
+    let mut bytes = vec![0u8; byte_count as usize];
+    buffer.read_exact(&mut bytes)?;
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+
+    code_page_encoding(code_page)?
+        .decode_without_bom_handling_and_without_replacement(&bytes[..end])
+        .map(|s| s.into_owned())
+        .ok_or_else(|| PduError::ParseError(format!("Invalid string for code page {}", code_page)))
+}
+
+/// Read and check the null terminator following a string
+fn read_null_terminator(buffer: &mut dyn Read, size: usize) -> Result<()> {
+    let mut terminator = [0u8; 2];
+    buffer.read_exact(&mut terminator[..size])?;
+    if terminator.iter().any(|&b| b != 0) {
+        return Err(PduError::ParseError(
+            "String is missing its null terminator".to_string(),
+        ));
+    }
+    Ok(())
+}
 
-fn encode_string_length(s: &str) -> u16 {
-    ((s.len() + 1) * 2) as u16 // UTF-16LE + null terminator
+/// Read an optional trailing u16 field
+///
+/// Returns `None` when the input ends exactly at the field boundary and an
+/// error when it ends inside the field.
+fn read_optional_u16(buffer: &mut dyn Read) -> Result<Option<u16>> {
+    let mut bytes = [0u8; 2];
+    let mut read = 0;
+    while read < bytes.len() {
+        match buffer.read(&mut bytes[read..]) {
+            Ok(0) => break,
+            Ok(n) => read += n,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    match read {
+        0 => Ok(None),
+        2 => Ok(Some(u16::from_le_bytes(bytes))),
+        _ => Err(PduError::InvalidLength {
+            expected: 2,
+            actual: read,
+        }),
+    }
+}
+
+fn read_time_zone_key_name(buffer: &mut dyn Read, cb_key_name: u16) -> Result<String> {
+    let cb_key_name = cb_key_name as usize;
+    if cb_key_name > DYNAMIC_DST_TIME_ZONE_KEY_NAME_MAX_SIZE || !cb_key_name.is_multiple_of(2) {
+        return Err(PduError::InvalidLength {
+            expected: DYNAMIC_DST_TIME_ZONE_KEY_NAME_MAX_SIZE,
+            actual: cb_key_name,
+        });
+    }
+
+    let mut key_name = vec![0u8; cb_key_name];
+    buffer.read_exact(&mut key_name)?;
+    Ok(decode_unicode_string(&key_name))
 }
 
-fn write_unicode_string(buffer: &mut dyn Write, s: &str) -> Result<()> {
+pub(crate) fn write_unicode_string(buffer: &mut dyn Write, s: &str) -> Result<()> {
     for ch in s.encode_utf16() {
         buffer.write_u16::<LittleEndian>(ch)?;
     }
@@ -395,7 +823,7 @@ fn write_unicode_string(buffer: &mut dyn Write, s: &str) -> Result<()> {
     Ok(())
 }
 
-fn read_unicode_string(buffer: &mut dyn Read, byte_count: u16) -> Result<String> {
+pub(crate) fn read_unicode_string(buffer: &mut dyn Read, byte_count: u16) -> Result<String> {
     if byte_count == 0 {
         return Ok(String::new());
     }
@@ -414,7 +842,7 @@ fn read_unicode_string(buffer: &mut dyn Read, byte_count: u16) -> Result<String>
         .map_err(|e| PduError::ParseError(format!("Invalid UTF-16 string: {}", e)))
 }
 
-fn encode_unicode_string(s: &str, buffer: &mut [u8], max_chars: usize) -> Result<()> {
+pub(crate) fn encode_unicode_string(s: &str, buffer: &mut [u8], max_chars: usize) -> Result<()> {
     let chars: Vec<u16> = s.encode_utf16().take(max_chars - 1).collect();
 
     for (i, &ch) in chars.iter().enumerate() {
@@ -426,7 +854,7 @@ fn encode_unicode_string(s: &str, buffer: &mut [u8], max_chars: usize) -> Result
     Ok(())
 }
 
-fn decode_unicode_string(buffer: &[u8]) -> String {
+pub(crate) fn decode_unicode_string(buffer: &[u8]) -> String {
     let mut chars = Vec::new();
 
     for i in (0..buffer.len()).step_by(2) {
@@ -519,6 +947,11 @@ mod tests {
             client_time_zone: TimeZoneInformation::utc(),
             client_session_id: 0,
             performance_flags: PerformanceFlags::DISABLE_WALLPAPER,
+            auto_reconnect_cookie: None,
+            reserved1: None,
+            reserved2: None,
+            dynamic_dst_time_zone_key_name: None,
+            dynamic_daylight_time_disabled: None,
         };
 
         let pdu = ClientInfoPdu::new("user".to_string(), "pass".to_string())
@@ -555,6 +988,9 @@ mod tests {
 
         let mut buffer = Vec::new();
         pdu.encode(&mut buffer).unwrap();
+        // Zero cb* lengths, each string still carries its null terminator
+        assert_eq!(&buffer[8..28], &[0u8; 20]);
+        assert_eq!(buffer.len(), pdu.size());
 
         let mut cursor = Cursor::new(buffer);
         let decoded = ClientInfoPdu::decode(&mut cursor).unwrap();
@@ -562,4 +998,161 @@ mod tests {
         assert_eq!(decoded.user_name, "");
         assert_eq!(decoded.password, "");
     }
+
+    #[test]
+    fn test_security_verifier() {
+        // RFC 2104 HMAC-MD5 test case 1
+        let verifier = compute_security_verifier(&[0x0b; 16], b"Hi There");
+        assert_eq!(hex::encode(verifier), "9294727a3638bb1c13f48ef8158bfc9d");
+    }
+
+    #[test]
+    fn test_client_info_pdu_auto_reconnect() {
+        let server_cookie = ArcScPrivatePacket::new(0x0000_0003, [0x0b; 16]);
+        let pdu = ClientInfoPdu::new("user".to_string(), "pass".to_string())
+            .with_auto_reconnect(&server_cookie, &[0u8; 32]);
+        assert_eq!(pdu.password, "pass");
+
+        let no_password = pdu.clone().without_password();
+        assert_eq!(no_password.password, "");
+        assert!(!no_password.flags.contains(ClientInfoFlags::AUTOLOGON));
+
+        let mut buffer = Vec::new();
+        pdu.encode(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), pdu.size());
+        assert_eq!(
+            &buffer[buffer.len() - 30..buffer.len() - 16],
+            &[
+                0x1C, 0x00, 0x1C, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00
+            ]
+        );
+
+        let decoded = ClientInfoPdu::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded, pdu);
+        let cookie = decoded
+            .extended_info
+            .unwrap()
+            .auto_reconnect_cookie
+            .unwrap();
+        assert_eq!(
+            cookie.security_verifier,
+            compute_security_verifier(&[0x0b; 16], &[0u8; 32])
+        );
+    }
+
+    #[test]
+    fn test_client_info_pdu_extended_tail() {
+        let mut ext = ExtendedInfo {
+            reserved1: Some(0),
+            reserved2: Some(0),
+            dynamic_dst_time_zone_key_name: Some("Korea Standard Time".to_string()),
+            dynamic_daylight_time_disabled: Some(true),
+            ..ExtendedInfo::default()
+        };
+        ext.set_client_ip_address("fe80::1".parse().unwrap());
+
+        let pdu = ClientInfoPdu::new("user".to_string(), "pass".to_string())
+            .with_extended_info(ext.clone());
+
+        let mut buffer = Vec::new();
+        pdu.encode(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), pdu.size());
+        // cbDynamicDSTTimeZoneKeyName (no terminator), key name, disabled
+        assert_eq!(
+            &buffer[buffer.len() - 42..buffer.len() - 38],
+            &[0x26, 0x00, b'K', 0x00]
+        );
+        assert_eq!(&buffer[buffer.len() - 2..], &[0x01, 0x00]);
+
+        let decoded = ClientInfoPdu::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded, pdu);
+
+        // Every field boundary is a valid end of input
+        let mut partial = ext.clone();
+        partial.dynamic_daylight_time_disabled = None;
+        let pdu =
+            ClientInfoPdu::new("user".to_string(), "pass".to_string()).with_extended_info(partial);
+        let mut buffer = Vec::new();
+        pdu.encode(&mut buffer).unwrap();
+        assert_eq!(ClientInfoPdu::decode(&mut buffer.as_slice()).unwrap(), pdu);
+
+        // Ending inside a field is an error
+        assert!(ClientInfoPdu::decode(&mut &buffer[..buffer.len() - 1]).is_err());
+        assert!(ClientInfoPdu::decode(&mut &buffer[..buffer.len() - 41]).is_err());
+        buffer.push(0x01);
+        assert!(ClientInfoPdu::decode(&mut buffer.as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_extended_info_client_ip_address() {
+        let mut ext = ExtendedInfo {
+            client_address: "192.168.1.100".to_string(),
+            ..ExtendedInfo::default()
+        };
+        assert_eq!(
+            ext.client_ip_address(),
+            Some("192.168.1.100".parse().unwrap())
+        );
+
+        ext.client_address_family = AF_INET6;
+        assert_eq!(ext.client_ip_address(), None);
+
+        ext.set_client_ip_address("2001:db8::1".parse().unwrap());
+        assert_eq!(ext.client_address_family, AF_INET6);
+        assert_eq!(
+            ext.client_ip_address(),
+            Some("2001:db8::1".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_client_info_pdu_ansi() {
+        let mut pdu = ClientInfoPdu::new("홍길동".to_string(), "pass".to_string())
+            .with_domain("WORKGROUP".to_string());
+        pdu.set_code_page(949).unwrap();
+        assert!(!pdu.is_unicode());
+
+        let mut buffer = Vec::new();
+        pdu.encode(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), pdu.size());
+        // cbDomain, cbUserName (EUC-KR, 2 bytes per character, without null)
+        assert_eq!(&buffer[8..12], &[0x09, 0x00, 0x06, 0x00]);
+        assert_eq!(&buffer[28..35], &[0xC8, 0xAB, 0xB1, 0xE6, 0xB5, 0xBF, 0x00]);
+
+        let decoded = ClientInfoPdu::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded, pdu);
+
+        // Unmappable characters and unknown code pages are rejected up front
+        for code_page in [1252, 12345] {
+            let mut rejected = pdu.clone();
+            assert!(rejected.set_code_page(code_page).is_err());
+            assert_eq!(rejected, pdu);
+        }
+
+        // Fields edited past set_code_page still fail before any output
+        for code_page in [1252, 12345] {
+            let mut rejected = pdu.clone();
+            rejected.code_page = code_page;
+            let mut buffer = Vec::new();
+            assert!(rejected.encode(&mut buffer).is_err());
+            assert!(buffer.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_client_info_pdu_unicode_size() {
+        let pdu = ClientInfoPdu::new("홍길동".to_string(), "비밀번호".to_string())
+            .with_extended_info(ExtendedInfo {
+                client_dir: "C:\\사용자".to_string(),
+                ..ExtendedInfo::default()
+            });
+
+        let mut buffer = Vec::new();
+        pdu.encode(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), pdu.size());
+        assert_eq!(&buffer[10..14], &[0x06, 0x00, 0x08, 0x00]);
+
+        let decoded = ClientInfoPdu::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded, pdu);
+    }
 }
@@ -1,4 +1,7 @@
 // RDP Connection Sequence PDUs
 pub mod client_info;
 
-pub use client_info::{ClientInfoFlags, ClientInfoPdu, PerformanceFlags, TimeZoneInformation};
+pub use client_info::{
+    ArcCsPrivatePacket, ClientInfoFlags, ClientInfoPdu, ExtendedInfo, PerformanceFlags,
+    TimeZoneInformation,
+};
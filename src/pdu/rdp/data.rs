@@ -0,0 +1,378 @@
+use crate::pdu::fastpath::output::PACKET_COMPRESSED;
+use crate::pdu::framing::encode_share_data_header;
+use crate::pdu::rdp::control::{ControlPdu, FontListPdu, FontMapPdu, SynchronizePdu};
+use crate::pdu::rdp::error_info::SetErrorInfoPdu;
+use crate::pdu::rdp::graphics::UpdatePdu;
+use crate::pdu::rdp::header::{DataPduType, PduType, ShareControlHeader, ShareDataHeader};
+use crate::pdu::rdp::input::InputEventPdu;
+use crate::pdu::rdp::monitor::MonitorLayoutPdu;
+use crate::pdu::rdp::session_info::SaveSessionInfoPdu;
+use crate::pdu::{Pdu, PduError, Result};
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::{Read, Write};
+
+/// Share Data PDU body, selected by pduType2
+///
+/// Types without a typed body are kept as raw bytes in `Unknown`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShareDataPdu {
+    /// Update PDU (graphics)
+    Update(UpdatePdu),
+    /// Control PDU
+    Control(ControlPdu),
+    /// Input Event PDU
+    Input(InputEventPdu),
+    /// Synchronize PDU
+    Synchronize(SynchronizePdu),
+    /// Font List PDU
+    FontList(FontListPdu),
+    /// Font Map PDU
+    FontMap(FontMapPdu),
+    /// Shutdown Request PDU (no body)
+    ShutdownRequest,
+    /// Shutdown Request Denied PDU (no body)
+    ShutdownDenied,
+    /// Save Session Info PDU
+    SaveSessionInfo(SaveSessionInfoPdu),
+    /// Set Error Info PDU
+    SetErrorInfo(SetErrorInfoPdu),
+    /// Monitor Layout PDU
+    MonitorLayout(MonitorLayoutPdu),
+    /// Data PDU type without a typed body
+    Unknown { pdu_type2: u8, bytes: Vec<u8> },
+}
+
+impl ShareDataPdu {
+    /// Raw pduType2 value
+    pub fn pdu_type2(&self) -> u8 {
+        let pdu_type2 = match self {
+            ShareDataPdu::Update(_) => DataPduType::Update,
+            ShareDataPdu::Control(_) => DataPduType::Control,
+            ShareDataPdu::Input(_) => DataPduType::Input,
+            ShareDataPdu::Synchronize(_) => DataPduType::Synchronize,
+            ShareDataPdu::FontList(_) => DataPduType::FontList,
+            ShareDataPdu::FontMap(_) => DataPduType::FontMap,
+            ShareDataPdu::ShutdownRequest => DataPduType::ShutdownRequest,
+            ShareDataPdu::ShutdownDenied => DataPduType::ShutdownDenied,
+            ShareDataPdu::SaveSessionInfo(_) => DataPduType::SaveSessionInfo,
+            ShareDataPdu::SetErrorInfo(_) => DataPduType::SetErrorInfo,
+            ShareDataPdu::MonitorLayout(_) => DataPduType::MonitorLayout,
+            ShareDataPdu::Unknown { pdu_type2, .. } => return *pdu_type2,
+        };
+        pdu_type2.as_u8()
+    }
+
+    /// Typed pduType2, if known
+    pub fn data_pdu_type(&self) -> Option<DataPduType> {
+        DataPduType::from_u8(self.pdu_type2())
+    }
+
+    /// Encode body (without headers)
+    pub fn encode_body(&self, buffer: &mut dyn Write) -> Result<()> {
+        match self {
+            ShareDataPdu::Update(pdu) => pdu.encode(buffer),
+            ShareDataPdu::Control(pdu) => pdu.encode(buffer),
+            ShareDataPdu::Input(pdu) => pdu.encode(buffer),
+            ShareDataPdu::Synchronize(pdu) => pdu.encode(buffer),
+            ShareDataPdu::FontList(pdu) => pdu.encode(buffer),
+            ShareDataPdu::FontMap(pdu) => pdu.encode(buffer),
+            ShareDataPdu::ShutdownRequest | ShareDataPdu::ShutdownDenied => Ok(()),
+            ShareDataPdu::SaveSessionInfo(pdu) => pdu.encode(buffer),
+            ShareDataPdu::SetErrorInfo(pdu) => pdu.encode(buffer),
+            ShareDataPdu::MonitorLayout(pdu) => pdu.encode(buffer),
+            ShareDataPdu::Unknown { bytes, .. } => {
+                buffer.write_all(bytes)?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Decode body for the given pduType2
+    ///
+    /// Typed bodies must use every byte of `body`.
+    pub fn decode_body(pdu_type2: u8, body: &[u8]) -> Result<Self> {
+        let mut buffer = body;
+        let pdu = match DataPduType::from_u8(pdu_type2) {
+            Some(DataPduType::Update) => ShareDataPdu::Update(UpdatePdu::decode(&mut buffer)?),
+            Some(DataPduType::Control) => ShareDataPdu::Control(ControlPdu::decode(&mut buffer)?),
+            Some(DataPduType::Input) => ShareDataPdu::Input(InputEventPdu::decode(&mut buffer)?),
+            Some(DataPduType::Synchronize) => {
+                ShareDataPdu::Synchronize(SynchronizePdu::decode(&mut buffer)?)
+            }
+            Some(DataPduType::FontList) => {
+                ShareDataPdu::FontList(FontListPdu::decode(&mut buffer)?)
+            }
+            Some(DataPduType::FontMap) => ShareDataPdu::FontMap(FontMapPdu::decode(&mut buffer)?),
+            Some(DataPduType::ShutdownRequest) => ShareDataPdu::ShutdownRequest,
+            Some(DataPduType::ShutdownDenied) => ShareDataPdu::ShutdownDenied,
+            Some(DataPduType::SaveSessionInfo) => {
+                ShareDataPdu::SaveSessionInfo(SaveSessionInfoPdu::decode(&mut buffer)?)
+            }
+            Some(DataPduType::SetErrorInfo) => {
+                ShareDataPdu::SetErrorInfo(SetErrorInfoPdu::decode(&mut buffer)?)
+            }
+            Some(DataPduType::MonitorLayout) => {
+                ShareDataPdu::MonitorLayout(MonitorLayoutPdu::decode(&mut buffer)?)
+            }
+            _ => ShareDataPdu::Unknown {
+                pdu_type2,
+                bytes: std::mem::take(&mut buffer).to_vec(),
+            },
+        };
+
+        if !buffer.is_empty() {
+            return Err(PduError::InvalidLength {
+                expected: body.len() - buffer.len(),
+                actual: body.len(),
+            });
+        }
+        Ok(pdu)
+    }
+
+    /// Body size (without headers)
+    pub fn body_size(&self) -> usize {
+        match self {
+            ShareDataPdu::Update(pdu) => pdu.size(),
+            ShareDataPdu::Control(pdu) => pdu.size(),
+            ShareDataPdu::Input(pdu) => pdu.size(),
+            ShareDataPdu::Synchronize(pdu) => pdu.size(),
+            ShareDataPdu::FontList(pdu) => pdu.size(),
+            ShareDataPdu::FontMap(pdu) => pdu.size(),
+            ShareDataPdu::ShutdownRequest | ShareDataPdu::ShutdownDenied => 0,
+            ShareDataPdu::SaveSessionInfo(pdu) => pdu.size(),
+            ShareDataPdu::SetErrorInfo(pdu) => pdu.size(),
+            ShareDataPdu::MonitorLayout(pdu) => pdu.size(),
+            ShareDataPdu::Unknown { bytes, .. } => bytes.len(),
+        }
+    }
+}
+
+/// Share Data PDU with its Share Control and Share Data headers
+///
+/// ```text
+/// +--------------------+-----------------+------------------+
+/// | ShareControlHeader | ShareDataHeader | body (pduType2)  |
+/// | 6 bytes            | 12 bytes        | variable         |
+/// +--------------------+-----------------+------------------+
+/// ```
+///
+/// Compressed Share Data PDUs are rejected on decode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShareDataMessage {
+    /// PDU source (MCS user channel ID of the sender)
+    pub pdu_source: u16,
+    /// Share ID
+    pub share_id: u32,
+    /// Stream ID
+    pub stream_id: u8,
+    /// Body
+    pub pdu: ShareDataPdu,
+}
+
+impl ShareDataMessage {
+    /// Create message on the low-priority stream
+    pub fn new(share_id: u32, pdu_source: u16, pdu: ShareDataPdu) -> Self {
+        Self {
+            pdu_source,
+            share_id,
+            stream_id: ShareDataHeader::STREAM_LOW,
+            pdu,
+        }
+    }
+
+    /// Size of everything after the Share Control Header
+    fn data_size(&self) -> usize {
+        ShareDataHeader::SIZE + self.pdu.body_size()
+    }
+}
+
+impl Pdu for ShareDataMessage {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        let total_length = self.size();
+        if total_length > u16::MAX as usize {
+            return Err(PduError::InvalidLength {
+                expected: u16::MAX as usize,
+                actual: total_length,
+            });
+        }
+
+        ShareControlHeader::new(total_length as u16, PduType::Data, self.pdu_source)
+            .encode(buffer)?;
+
+        encode_share_data_header(
+            buffer,
+            self.share_id,
+            self.stream_id,
+            self.pdu.pdu_type2(),
+            self.pdu.body_size(),
+        )?;
+        self.pdu.encode_body(buffer)
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let header = ShareControlHeader::decode(buffer)?;
+        if header.pdu_type != PduType::Data {
+            return Err(PduError::InvalidPduType(header.pdu_type.as_u16() as u8));
+        }
+
+        let data_length = (header.total_length as usize).saturating_sub(ShareControlHeader::SIZE);
+        if data_length < ShareDataHeader::SIZE {
+            return Err(PduError::InvalidLength {
+                expected: ShareControlHeader::SIZE + ShareDataHeader::SIZE,
+                actual: header.total_length as usize,
+            });
+        }
+        let mut data = vec![0u8; data_length];
+        buffer.read_exact(&mut data)?;
+
+        let mut cursor = data.as_slice();
+        let share_id = cursor.read_u32::<LittleEndian>()?;
+        let _pad1 = cursor.read_u8()?;
+        let stream_id = cursor.read_u8()?;
+        let uncompressed_length = cursor.read_u16::<LittleEndian>()? as usize;
+        let pdu_type2 = cursor.read_u8()?;
+        let compressed_type = cursor.read_u8()?;
+        let _compressed_length = cursor.read_u16::<LittleEndian>()?;
+
+        if compressed_type & PACKET_COMPRESSED != 0 {
+            return Err(PduError::ParseError(format!(
+                "Compressed Share Data PDU not supported (compressedType {:#x})",
+                compressed_type
+            )));
+        }
+
+        // uncompressedLength counts from pduType2 to the end of the body
+        if uncompressed_length != 4 + cursor.len() {
+            return Err(PduError::InvalidLength {
+                expected: 4 + cursor.len(),
+                actual: uncompressed_length,
+            });
+        }
+
+        Ok(Self {
+            pdu_source: header.pdu_source,
+            share_id,
+            stream_id,
+            pdu: ShareDataPdu::decode_body(pdu_type2, cursor)?,
+        })
+    }
+
+    fn size(&self) -> usize {
+        ShareControlHeader::SIZE + self.data_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdu::rdp::control::ControlAction;
+
+    #[test]
+    fn test_share_data_message_control() {
+        let message = ShareDataMessage::new(
+            0x0001_03EA,
+            1007,
+            ShareDataPdu::Control(ControlPdu::cooperate()),
+        );
+
+        let mut buffer = Vec::new();
+        message.encode(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), message.size());
+        assert_eq!(
+            &buffer[..18],
+            &[
+                0x1A, 0x00, 0x17, 0x00, 0xEF, 0x03, // share control header
+                0xEA, 0x03, 0x01, 0x00, 0x00, 0x01, 0x0C, 0x00, 0x14, 0x00, 0x00, 0x00,
+            ]
+        );
+
+        let decoded = ShareDataMessage::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded, message);
+        let ShareDataPdu::Control(control) = decoded.pdu else {
+            panic!("expected control PDU");
+        };
+        assert_eq!(control.action, ControlAction::Cooperate);
+    }
+
+    #[test]
+    fn test_share_data_message_unknown_roundtrip() {
+        for pdu_type2 in [DataPduType::RefreshRect.as_u8(), 0x7E] {
+            let message = ShareDataMessage::new(
+                1,
+                1002,
+                ShareDataPdu::Unknown {
+                    pdu_type2,
+                    bytes: vec![1, 2, 3, 4],
+                },
+            );
+
+            let mut buffer = Vec::new();
+            message.encode(&mut buffer).unwrap();
+            let decoded = ShareDataMessage::decode(&mut buffer.as_slice()).unwrap();
+
+            assert_eq!(decoded, message);
+            assert_eq!(decoded.pdu.pdu_type2(), pdu_type2);
+        }
+    }
+
+    #[test]
+    fn test_share_data_message_empty_bodies() {
+        let message = ShareDataMessage::new(1, 1007, ShareDataPdu::ShutdownRequest);
+
+        let mut buffer = Vec::new();
+        message.encode(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), 18);
+
+        let decoded = ShareDataMessage::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded.pdu, ShareDataPdu::ShutdownRequest);
+        assert_eq!(
+            decoded.pdu.data_pdu_type(),
+            Some(DataPduType::ShutdownRequest)
+        );
+    }
+
+    #[test]
+    fn test_share_data_message_rejects_compressed() {
+        let message = ShareDataMessage::new(1, 1002, ShareDataPdu::ShutdownDenied);
+        let mut buffer = Vec::new();
+        message.encode(&mut buffer).unwrap();
+        buffer[15] = PACKET_COMPRESSED | 0x01;
+
+        assert!(matches!(
+            ShareDataMessage::decode(&mut buffer.as_slice()),
+            Err(PduError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_share_data_message_length_mismatch() {
+        let message =
+            ShareDataMessage::new(1, 1002, ShareDataPdu::Control(ControlPdu::cooperate()));
+        let mut buffer = Vec::new();
+        message.encode(&mut buffer).unwrap();
+
+        // uncompressedLength one byte short and one byte long
+        for uncompressed_length in [11u16, 13] {
+            let mut mismatched = buffer.clone();
+            mismatched[12..14].copy_from_slice(&uncompressed_length.to_le_bytes());
+            assert!(matches!(
+                ShareDataMessage::decode(&mut mismatched.as_slice()),
+                Err(PduError::InvalidLength { expected: 12, actual })
+                    if actual == uncompressed_length as usize
+            ));
+        }
+
+        // Trailing byte after the typed body, with consistent lengths
+        let mut trailing = buffer.clone();
+        trailing.push(0);
+        trailing[0] += 1;
+        trailing[12] += 1;
+        assert!(matches!(
+            ShareDataMessage::decode(&mut trailing.as_slice()),
+            Err(PduError::InvalidLength {
+                expected: 8,
+                actual: 9
+            })
+        ));
+    }
+}
@@ -0,0 +1,1011 @@
+use crate::pdu::{Pdu, Result};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Read, Write};
+
+/// Error info code ranges (MS-RDPBCGR 2.2.5.1.1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorInfoCategory {
+    /// Protocol-independent codes (0x0000 - 0x00FF)
+    ProtocolIndependent,
+    /// Licensing codes (0x0100 - 0x03FF)
+    Licensing,
+    /// Connection Broker codes (0x0400 - 0x0FFF)
+    ConnectionBroker,
+    /// RDP-specific protocol codes (0x1000 and above)
+    Protocol,
+}
+
+impl ErrorInfoCategory {
+    /// Category of a raw error info value
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            0x0000..=0x00FF => ErrorInfoCategory::ProtocolIndependent,
+            0x0100..=0x03FF => ErrorInfoCategory::Licensing,
+            0x0400..=0x0FFF => ErrorInfoCategory::ConnectionBroker,
+            _ => ErrorInfoCategory::Protocol,
+        }
+    }
+}
+
+/// Error info codes (MS-RDPBCGR 2.2.5.1.1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum ErrorInfo {
+    /// ERRINFO_NONE
+    None = 0x0000,
+    /// ERRINFO_RPC_INITIATED_DISCONNECT
+    RpcInitiatedDisconnect = 0x0001,
+    /// ERRINFO_RPC_INITIATED_LOGOFF
+    RpcInitiatedLogoff = 0x0002,
+    /// ERRINFO_IDLE_TIMEOUT
+    IdleTimeout = 0x0003,
+    /// ERRINFO_LOGON_TIMEOUT
+    LogonTimeout = 0x0004,
+    /// ERRINFO_DISCONNECTED_BY_OTHERCONNECTION
+    DisconnectedByOtherConnection = 0x0005,
+    /// ERRINFO_OUT_OF_MEMORY
+    OutOfMemory = 0x0006,
+    /// ERRINFO_SERVER_DENIED_CONNECTION
+    ServerDeniedConnection = 0x0007,
+    /// ERRINFO_SERVER_INSUFFICIENT_PRIVILEGES
+    ServerInsufficientPrivileges = 0x0009,
+    /// ERRINFO_SERVER_FRESH_CREDENTIALS_REQUIRED
+    ServerFreshCredentialsRequired = 0x000A,
+    /// ERRINFO_RPC_INITIATED_DISCONNECT_BYUSER
+    RpcInitiatedDisconnectByUser = 0x000B,
+    /// ERRINFO_LOGOFF_BY_USER
+    LogoffByUser = 0x000C,
+    /// ERRINFO_CLOSE_STACK_ON_DRIVER_NOT_READY
+    CloseStackOnDriverNotReady = 0x000F,
+    /// ERRINFO_SERVER_DWM_CRASH
+    ServerDwmCrash = 0x0010,
+    /// ERRINFO_CLOSE_STACK_ON_DRIVER_FAILURE
+    CloseStackOnDriverFailure = 0x0011,
+    /// ERRINFO_CLOSE_STACK_ON_DRIVER_IFACE_FAILURE
+    CloseStackOnDriverIfaceFailure = 0x0012,
+    /// ERRINFO_SERVER_WINLOGON_CRASH
+    ServerWinlogonCrash = 0x0017,
+    /// ERRINFO_SERVER_CSRSS_CRASH
+    ServerCsrssCrash = 0x0018,
+    /// ERRINFO_SERVER_SHUTDOWN
+    ServerShutdown = 0x0019,
+    /// ERRINFO_SERVER_REBOOT
+    ServerReboot = 0x001A,
+    /// ERRINFO_LICENSE_INTERNAL
+    LicenseInternal = 0x0100,
+    /// ERRINFO_LICENSE_NO_LICENSE_SERVER
+    LicenseNoLicenseServer = 0x0101,
+    /// ERRINFO_LICENSE_NO_LICENSE
+    LicenseNoLicense = 0x0102,
+    /// ERRINFO_LICENSE_BAD_CLIENT_MSG
+    LicenseBadClientMsg = 0x0103,
+    /// ERRINFO_LICENSE_HWID_DOESNT_MATCH_LICENSE
+    LicenseHwidDoesntMatchLicense = 0x0104,
+    /// ERRINFO_LICENSE_BAD_CLIENT_LICENSE
+    LicenseBadClientLicense = 0x0105,
+    /// ERRINFO_LICENSE_CANT_FINISH_PROTOCOL
+    LicenseCantFinishProtocol = 0x0106,
+    /// ERRINFO_LICENSE_CLIENT_ENDED_PROTOCOL
+    LicenseClientEndedProtocol = 0x0107,
+    /// ERRINFO_LICENSE_BAD_CLIENT_ENCRYPTION
+    LicenseBadClientEncryption = 0x0108,
+    /// ERRINFO_LICENSE_CANT_UPGRADE_LICENSE
+    LicenseCantUpgradeLicense = 0x0109,
+    /// ERRINFO_LICENSE_NO_REMOTE_CONNECTIONS
+    LicenseNoRemoteConnections = 0x010A,
+    /// ERRINFO_CB_DESTINATION_NOT_FOUND
+    CbDestinationNotFound = 0x0400,
+    /// ERRINFO_CB_LOADING_DESTINATION
+    CbLoadingDestination = 0x0402,
+    /// ERRINFO_CB_REDIRECTING_TO_DESTINATION
+    CbRedirectingToDestination = 0x0404,
+    /// ERRINFO_CB_SESSION_ONLINE_VM_WAKE
+    CbSessionOnlineVmWake = 0x0405,
+    /// ERRINFO_CB_SESSION_ONLINE_VM_BOOT
+    CbSessionOnlineVmBoot = 0x0406,
+    /// ERRINFO_CB_SESSION_ONLINE_VM_NO_DNS
+    CbSessionOnlineVmNoDns = 0x0407,
+    /// ERRINFO_CB_DESTINATION_POOL_NOT_FREE
+    CbDestinationPoolNotFree = 0x0408,
+    /// ERRINFO_CB_CONNECTION_CANCELLED
+    CbConnectionCancelled = 0x0409,
+    /// ERRINFO_CB_CONNECTION_ERROR_INVALID_SETTINGS
+    CbConnectionErrorInvalidSettings = 0x0410,
+    /// ERRINFO_CB_SESSION_ONLINE_VM_BOOT_TIMEOUT
+    CbSessionOnlineVmBootTimeout = 0x0411,
+    /// ERRINFO_CB_SESSION_ONLINE_VM_SESSMON_FAILED
+    CbSessionOnlineVmSessmonFailed = 0x0412,
+    /// ERRINFO_UNKNOWNPDUTYPE2
+    UnknownPduType2 = 0x10C9,
+    /// ERRINFO_UNKNOWNPDUTYPE
+    UnknownPduType = 0x10CA,
+    /// ERRINFO_DATAPDUSEQUENCE
+    DataPduSequence = 0x10CB,
+    /// ERRINFO_CONTROLPDUSEQUENCE
+    ControlPduSequence = 0x10CD,
+    /// ERRINFO_INVALIDCONTROLPDUACTION
+    InvalidControlPduAction = 0x10CE,
+    /// ERRINFO_INVALIDINPUTPDUTYPE
+    InvalidInputPduType = 0x10CF,
+    /// ERRINFO_INVALIDINPUTPDUMOUSE
+    InvalidInputPduMouse = 0x10D0,
+    /// ERRINFO_INVALIDREFRESHRECTPDU
+    InvalidRefreshRectPdu = 0x10D1,
+    /// ERRINFO_CREATEUSERDATAFAILED
+    CreateUserDataFailed = 0x10D2,
+    /// ERRINFO_CONNECTFAILED
+    ConnectFailed = 0x10D3,
+    /// ERRINFO_CONFIRMACTIVEWRONGSHAREID
+    ConfirmActiveWrongShareId = 0x10D4,
+    /// ERRINFO_CONFIRMACTIVEWRONGORIGINATOR
+    ConfirmActiveWrongOriginator = 0x10D5,
+    /// ERRINFO_PERSISTENTKEYPDUBADLENGTH
+    PersistentKeyPduBadLength = 0x10DA,
+    /// ERRINFO_PERSISTENTKEYPDUILLEGALFIRST
+    PersistentKeyPduIllegalFirst = 0x10DB,
+    /// ERRINFO_PERSISTENTKEYPDUTOOMANYTOTALKEYS
+    PersistentKeyPduTooManyTotalKeys = 0x10DC,
+    /// ERRINFO_PERSISTENTKEYPDUTOOMANYCACHEKEYS
+    PersistentKeyPduTooManyCacheKeys = 0x10DD,
+    /// ERRINFO_INPUTPDUBADLENGTH
+    InputPduBadLength = 0x10DE,
+    /// ERRINFO_BITMAPCACHEERRORPDUBADLENGTH
+    BitmapCacheErrorPduBadLength = 0x10DF,
+    /// ERRINFO_SECURITYDATATOOSHORT
+    SecurityDataTooShort = 0x10E0,
+    /// ERRINFO_VCHANNELDATATOOSHORT
+    VChannelDataTooShort = 0x10E1,
+    /// ERRINFO_SHAREDATATOOSHORT
+    ShareDataTooShort = 0x10E2,
+    /// ERRINFO_BADSUPRESSOUTPUTPDU
+    BadSuppressOutputPdu = 0x10E3,
+    /// ERRINFO_CONFIRMACTIVEPDUTOOSHORT
+    ConfirmActivePduTooShort = 0x10E5,
+    /// ERRINFO_CAPABILITYSETTOOSMALL
+    CapabilitySetTooSmall = 0x10E7,
+    /// ERRINFO_CAPABILITYSETTOOLARGE
+    CapabilitySetTooLarge = 0x10E8,
+    /// ERRINFO_NOCURSORCACHE
+    NoCursorCache = 0x10E9,
+    /// ERRINFO_BADCAPABILITIES
+    BadCapabilities = 0x10EA,
+    /// ERRINFO_VIRTUALCHANNELDECOMPRESSIONERR
+    VirtualChannelDecompressionErr = 0x10EC,
+    /// ERRINFO_INVALIDVCCOMPRESSIONTYPE
+    InvalidVcCompressionType = 0x10ED,
+    /// ERRINFO_INVALIDCHANNELID
+    InvalidChannelId = 0x10EF,
+    /// ERRINFO_VCHANNELSTOOMANY
+    VChannelsTooMany = 0x10F0,
+    /// ERRINFO_REMOTEAPPSNOTENABLED
+    RemoteAppsNotEnabled = 0x10F3,
+    /// ERRINFO_CACHECAPNOTSET
+    CacheCapNotSet = 0x10F4,
+    /// ERRINFO_BITMAPCACHEERRORPDUBADLENGTH2
+    BitmapCacheErrorPduBadLength2 = 0x10F5,
+    /// ERRINFO_OFFSCRCACHEERRORPDUBADLENGTH
+    OffscrCacheErrorPduBadLength = 0x10F6,
+    /// ERRINFO_DNGCACHEERRORPDUBADLENGTH
+    DngCacheErrorPduBadLength = 0x10F7,
+    /// ERRINFO_GDIPLUSPDUBADLENGTH
+    GdiPlusPduBadLength = 0x10F8,
+    /// ERRINFO_SECURITYDATATOOSHORT2
+    SecurityDataTooShort2 = 0x1111,
+    /// ERRINFO_SECURITYDATATOOSHORT3
+    SecurityDataTooShort3 = 0x1112,
+    /// ERRINFO_SECURITYDATATOOSHORT4
+    SecurityDataTooShort4 = 0x1113,
+    /// ERRINFO_SECURITYDATATOOSHORT5
+    SecurityDataTooShort5 = 0x1114,
+    /// ERRINFO_SECURITYDATATOOSHORT6
+    SecurityDataTooShort6 = 0x1115,
+    /// ERRINFO_SECURITYDATATOOSHORT7
+    SecurityDataTooShort7 = 0x1116,
+    /// ERRINFO_SECURITYDATATOOSHORT8
+    SecurityDataTooShort8 = 0x1117,
+    /// ERRINFO_SECURITYDATATOOSHORT9
+    SecurityDataTooShort9 = 0x1118,
+    /// ERRINFO_SECURITYDATATOOSHORT10
+    SecurityDataTooShort10 = 0x1119,
+    /// ERRINFO_SECURITYDATATOOSHORT11
+    SecurityDataTooShort11 = 0x111A,
+    /// ERRINFO_SECURITYDATATOOSHORT12
+    SecurityDataTooShort12 = 0x111B,
+    /// ERRINFO_SECURITYDATATOOSHORT13
+    SecurityDataTooShort13 = 0x111C,
+    /// ERRINFO_SECURITYDATATOOSHORT14
+    SecurityDataTooShort14 = 0x111D,
+    /// ERRINFO_SECURITYDATATOOSHORT15
+    SecurityDataTooShort15 = 0x111E,
+    /// ERRINFO_SECURITYDATATOOSHORT16
+    SecurityDataTooShort16 = 0x111F,
+    /// ERRINFO_SECURITYDATATOOSHORT17
+    SecurityDataTooShort17 = 0x1120,
+    /// ERRINFO_SECURITYDATATOOSHORT18
+    SecurityDataTooShort18 = 0x1121,
+    /// ERRINFO_SECURITYDATATOOSHORT19
+    SecurityDataTooShort19 = 0x1122,
+    /// ERRINFO_SECURITYDATATOOSHORT20
+    SecurityDataTooShort20 = 0x1123,
+    /// ERRINFO_SECURITYDATATOOSHORT21
+    SecurityDataTooShort21 = 0x1124,
+    /// ERRINFO_SECURITYDATATOOSHORT22
+    SecurityDataTooShort22 = 0x1125,
+    /// ERRINFO_SECURITYDATATOOSHORT23
+    SecurityDataTooShort23 = 0x1126,
+    /// ERRINFO_BADMONITORDATA
+    BadMonitorData = 0x1129,
+    /// ERRINFO_VCDECOMPRESSEDREASSEMBLEFAILED
+    VcDecompressedReassembleFailed = 0x112A,
+    /// ERRINFO_VCDATATOOLONG
+    VcDataTooLong = 0x112B,
+    /// ERRINFO_BAD_FRAME_ACK_DATA
+    BadFrameAckData = 0x112C,
+    /// ERRINFO_GRAPHICSMODENOTSUPPORTED
+    GraphicsModeNotSupported = 0x112D,
+    /// ERRINFO_GRAPHICSSUBSYSTEMRESETFAILED
+    GraphicsSubsystemResetFailed = 0x112E,
+    /// ERRINFO_GRAPHICSSUBSYSTEMFAILED
+    GraphicsSubsystemFailed = 0x112F,
+    /// ERRINFO_TIMEZONEKEYNAMELENGTHTOOSHORT
+    TimezoneKeyNameLengthTooShort = 0x1130,
+    /// ERRINFO_TIMEZONEKEYNAMELENGTHTOOLONG
+    TimezoneKeyNameLengthTooLong = 0x1131,
+    /// ERRINFO_DYNAMICDSTDISABLEDFIELDMISSING
+    DynamicDstDisabledFieldMissing = 0x1132,
+    /// ERRINFO_VCDECODINGERROR
+    VcDecodingError = 0x1133,
+    /// ERRINFO_VIRTUALDESKTOPTOOLARGE
+    VirtualDesktopTooLarge = 0x1134,
+    /// ERRINFO_MONITORGEOMETRYVALIDATIONFAILED
+    MonitorGeometryValidationFailed = 0x1135,
+    /// ERRINFO_INVALIDMONITORCOUNT
+    InvalidMonitorCount = 0x1136,
+    /// ERRINFO_UPDATESESSIONKEYFAILED
+    UpdateSessionKeyFailed = 0x1191,
+    /// ERRINFO_DECRYPTFAILED
+    DecryptFailed = 0x1192,
+    /// ERRINFO_ENCRYPTFAILED
+    EncryptFailed = 0x1193,
+    /// ERRINFO_ENCPKGMISMATCH
+    EncPkgMismatch = 0x1194,
+    /// ERRINFO_DECRYPTFAILED2
+    DecryptFailed2 = 0x1195,
+}
+
+impl ErrorInfo {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0x0000 => Some(ErrorInfo::None),
+            0x0001 => Some(ErrorInfo::RpcInitiatedDisconnect),
+            0x0002 => Some(ErrorInfo::RpcInitiatedLogoff),
+            0x0003 => Some(ErrorInfo::IdleTimeout),
+            0x0004 => Some(ErrorInfo::LogonTimeout),
+            0x0005 => Some(ErrorInfo::DisconnectedByOtherConnection),
+            0x0006 => Some(ErrorInfo::OutOfMemory),
+            0x0007 => Some(ErrorInfo::ServerDeniedConnection),
+            0x0009 => Some(ErrorInfo::ServerInsufficientPrivileges),
+            0x000A => Some(ErrorInfo::ServerFreshCredentialsRequired),
+            0x000B => Some(ErrorInfo::RpcInitiatedDisconnectByUser),
+            0x000C => Some(ErrorInfo::LogoffByUser),
+            0x000F => Some(ErrorInfo::CloseStackOnDriverNotReady),
+            0x0010 => Some(ErrorInfo::ServerDwmCrash),
+            0x0011 => Some(ErrorInfo::CloseStackOnDriverFailure),
+            0x0012 => Some(ErrorInfo::CloseStackOnDriverIfaceFailure),
+            0x0017 => Some(ErrorInfo::ServerWinlogonCrash),
+            0x0018 => Some(ErrorInfo::ServerCsrssCrash),
+            0x0019 => Some(ErrorInfo::ServerShutdown),
+            0x001A => Some(ErrorInfo::ServerReboot),
+            0x0100 => Some(ErrorInfo::LicenseInternal),
+            0x0101 => Some(ErrorInfo::LicenseNoLicenseServer),
+            0x0102 => Some(ErrorInfo::LicenseNoLicense),
+            0x0103 => Some(ErrorInfo::LicenseBadClientMsg),
+            0x0104 => Some(ErrorInfo::LicenseHwidDoesntMatchLicense),
+            0x0105 => Some(ErrorInfo::LicenseBadClientLicense),
+            0x0106 => Some(ErrorInfo::LicenseCantFinishProtocol),
+            0x0107 => Some(ErrorInfo::LicenseClientEndedProtocol),
+            0x0108 => Some(ErrorInfo::LicenseBadClientEncryption),
+            0x0109 => Some(ErrorInfo::LicenseCantUpgradeLicense),
+            0x010A => Some(ErrorInfo::LicenseNoRemoteConnections),
+            0x0400 => Some(ErrorInfo::CbDestinationNotFound),
+            0x0402 => Some(ErrorInfo::CbLoadingDestination),
+            0x0404 => Some(ErrorInfo::CbRedirectingToDestination),
+            0x0405 => Some(ErrorInfo::CbSessionOnlineVmWake),
+            0x0406 => Some(ErrorInfo::CbSessionOnlineVmBoot),
+            0x0407 => Some(ErrorInfo::CbSessionOnlineVmNoDns),
+            0x0408 => Some(ErrorInfo::CbDestinationPoolNotFree),
+            0x0409 => Some(ErrorInfo::CbConnectionCancelled),
+            0x0410 => Some(ErrorInfo::CbConnectionErrorInvalidSettings),
+            0x0411 => Some(ErrorInfo::CbSessionOnlineVmBootTimeout),
+            0x0412 => Some(ErrorInfo::CbSessionOnlineVmSessmonFailed),
+            0x10C9 => Some(ErrorInfo::UnknownPduType2),
+            0x10CA => Some(ErrorInfo::UnknownPduType),
+            0x10CB => Some(ErrorInfo::DataPduSequence),
+            0x10CD => Some(ErrorInfo::ControlPduSequence),
+            0x10CE => Some(ErrorInfo::InvalidControlPduAction),
+            0x10CF => Some(ErrorInfo::InvalidInputPduType),
+            0x10D0 => Some(ErrorInfo::InvalidInputPduMouse),
+            0x10D1 => Some(ErrorInfo::InvalidRefreshRectPdu),
+            0x10D2 => Some(ErrorInfo::CreateUserDataFailed),
+            0x10D3 => Some(ErrorInfo::ConnectFailed),
+            0x10D4 => Some(ErrorInfo::ConfirmActiveWrongShareId),
+            0x10D5 => Some(ErrorInfo::ConfirmActiveWrongOriginator),
+            0x10DA => Some(ErrorInfo::PersistentKeyPduBadLength),
+            0x10DB => Some(ErrorInfo::PersistentKeyPduIllegalFirst),
+            0x10DC => Some(ErrorInfo::PersistentKeyPduTooManyTotalKeys),
+            0x10DD => Some(ErrorInfo::PersistentKeyPduTooManyCacheKeys),
+            0x10DE => Some(ErrorInfo::InputPduBadLength),
+            0x10DF => Some(ErrorInfo::BitmapCacheErrorPduBadLength),
+            0x10E0 => Some(ErrorInfo::SecurityDataTooShort),
+            0x10E1 => Some(ErrorInfo::VChannelDataTooShort),
+            0x10E2 => Some(ErrorInfo::ShareDataTooShort),
+            0x10E3 => Some(ErrorInfo::BadSuppressOutputPdu),
+            0x10E5 => Some(ErrorInfo::ConfirmActivePduTooShort),
+            0x10E7 => Some(ErrorInfo::CapabilitySetTooSmall),
+            0x10E8 => Some(ErrorInfo::CapabilitySetTooLarge),
+            0x10E9 => Some(ErrorInfo::NoCursorCache),
+            0x10EA => Some(ErrorInfo::BadCapabilities),
+            0x10EC => Some(ErrorInfo::VirtualChannelDecompressionErr),
+            0x10ED => Some(ErrorInfo::InvalidVcCompressionType),
+            0x10EF => Some(ErrorInfo::InvalidChannelId),
+            0x10F0 => Some(ErrorInfo::VChannelsTooMany),
+            0x10F3 => Some(ErrorInfo::RemoteAppsNotEnabled),
+            0x10F4 => Some(ErrorInfo::CacheCapNotSet),
+            0x10F5 => Some(ErrorInfo::BitmapCacheErrorPduBadLength2),
+            0x10F6 => Some(ErrorInfo::OffscrCacheErrorPduBadLength),
+            0x10F7 => Some(ErrorInfo::DngCacheErrorPduBadLength),
+            0x10F8 => Some(ErrorInfo::GdiPlusPduBadLength),
+            0x1111 => Some(ErrorInfo::SecurityDataTooShort2),
+            0x1112 => Some(ErrorInfo::SecurityDataTooShort3),
+            0x1113 => Some(ErrorInfo::SecurityDataTooShort4),
+            0x1114 => Some(ErrorInfo::SecurityDataTooShort5),
+            0x1115 => Some(ErrorInfo::SecurityDataTooShort6),
+            0x1116 => Some(ErrorInfo::SecurityDataTooShort7),
+            0x1117 => Some(ErrorInfo::SecurityDataTooShort8),
+            0x1118 => Some(ErrorInfo::SecurityDataTooShort9),
+            0x1119 => Some(ErrorInfo::SecurityDataTooShort10),
+            0x111A => Some(ErrorInfo::SecurityDataTooShort11),
+            0x111B => Some(ErrorInfo::SecurityDataTooShort12),
+            0x111C => Some(ErrorInfo::SecurityDataTooShort13),
+            0x111D => Some(ErrorInfo::SecurityDataTooShort14),
+            0x111E => Some(ErrorInfo::SecurityDataTooShort15),
+            0x111F => Some(ErrorInfo::SecurityDataTooShort16),
+            0x1120 => Some(ErrorInfo::SecurityDataTooShort17),
+            0x1121 => Some(ErrorInfo::SecurityDataTooShort18),
+            0x1122 => Some(ErrorInfo::SecurityDataTooShort19),
+            0x1123 => Some(ErrorInfo::SecurityDataTooShort20),
+            0x1124 => Some(ErrorInfo::SecurityDataTooShort21),
+            0x1125 => Some(ErrorInfo::SecurityDataTooShort22),
+            0x1126 => Some(ErrorInfo::SecurityDataTooShort23),
+            0x1129 => Some(ErrorInfo::BadMonitorData),
+            0x112A => Some(ErrorInfo::VcDecompressedReassembleFailed),
+            0x112B => Some(ErrorInfo::VcDataTooLong),
+            0x112C => Some(ErrorInfo::BadFrameAckData),
+            0x112D => Some(ErrorInfo::GraphicsModeNotSupported),
+            0x112E => Some(ErrorInfo::GraphicsSubsystemResetFailed),
+            0x112F => Some(ErrorInfo::GraphicsSubsystemFailed),
+            0x1130 => Some(ErrorInfo::TimezoneKeyNameLengthTooShort),
+            0x1131 => Some(ErrorInfo::TimezoneKeyNameLengthTooLong),
+            0x1132 => Some(ErrorInfo::DynamicDstDisabledFieldMissing),
+            0x1133 => Some(ErrorInfo::VcDecodingError),
+            0x1134 => Some(ErrorInfo::VirtualDesktopTooLarge),
+            0x1135 => Some(ErrorInfo::MonitorGeometryValidationFailed),
+            0x1136 => Some(ErrorInfo::InvalidMonitorCount),
+            0x1191 => Some(ErrorInfo::UpdateSessionKeyFailed),
+            0x1192 => Some(ErrorInfo::DecryptFailed),
+            0x1193 => Some(ErrorInfo::EncryptFailed),
+            0x1194 => Some(ErrorInfo::EncPkgMismatch),
+            0x1195 => Some(ErrorInfo::DecryptFailed2),
+            _ => None,
+        }
+    }
+
+    pub fn as_u32(self) -> u32 {
+        self as u32
+    }
+
+    /// Protocol constant name (e.g. "ERRINFO_IDLE_TIMEOUT")
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorInfo::None => "ERRINFO_NONE",
+            ErrorInfo::RpcInitiatedDisconnect => "ERRINFO_RPC_INITIATED_DISCONNECT",
+            ErrorInfo::RpcInitiatedLogoff => "ERRINFO_RPC_INITIATED_LOGOFF",
+            ErrorInfo::IdleTimeout => "ERRINFO_IDLE_TIMEOUT",
+            ErrorInfo::LogonTimeout => "ERRINFO_LOGON_TIMEOUT",
+            ErrorInfo::DisconnectedByOtherConnection => "ERRINFO_DISCONNECTED_BY_OTHERCONNECTION",
+            ErrorInfo::OutOfMemory => "ERRINFO_OUT_OF_MEMORY",
+            ErrorInfo::ServerDeniedConnection => "ERRINFO_SERVER_DENIED_CONNECTION",
+            ErrorInfo::ServerInsufficientPrivileges => "ERRINFO_SERVER_INSUFFICIENT_PRIVILEGES",
+            ErrorInfo::ServerFreshCredentialsRequired => {
+                "ERRINFO_SERVER_FRESH_CREDENTIALS_REQUIRED"
+            }
+            ErrorInfo::RpcInitiatedDisconnectByUser => "ERRINFO_RPC_INITIATED_DISCONNECT_BYUSER",
+            ErrorInfo::LogoffByUser => "ERRINFO_LOGOFF_BY_USER",
+            ErrorInfo::CloseStackOnDriverNotReady => "ERRINFO_CLOSE_STACK_ON_DRIVER_NOT_READY",
+            ErrorInfo::ServerDwmCrash => "ERRINFO_SERVER_DWM_CRASH",
+            ErrorInfo::CloseStackOnDriverFailure => "ERRINFO_CLOSE_STACK_ON_DRIVER_FAILURE",
+            ErrorInfo::CloseStackOnDriverIfaceFailure => {
+                "ERRINFO_CLOSE_STACK_ON_DRIVER_IFACE_FAILURE"
+            }
+            ErrorInfo::ServerWinlogonCrash => "ERRINFO_SERVER_WINLOGON_CRASH",
+            ErrorInfo::ServerCsrssCrash => "ERRINFO_SERVER_CSRSS_CRASH",
+            ErrorInfo::ServerShutdown => "ERRINFO_SERVER_SHUTDOWN",
+            ErrorInfo::ServerReboot => "ERRINFO_SERVER_REBOOT",
+            ErrorInfo::LicenseInternal => "ERRINFO_LICENSE_INTERNAL",
+            ErrorInfo::LicenseNoLicenseServer => "ERRINFO_LICENSE_NO_LICENSE_SERVER",
+            ErrorInfo::LicenseNoLicense => "ERRINFO_LICENSE_NO_LICENSE",
+            ErrorInfo::LicenseBadClientMsg => "ERRINFO_LICENSE_BAD_CLIENT_MSG",
+            ErrorInfo::LicenseHwidDoesntMatchLicense => "ERRINFO_LICENSE_HWID_DOESNT_MATCH_LICENSE",
+            ErrorInfo::LicenseBadClientLicense => "ERRINFO_LICENSE_BAD_CLIENT_LICENSE",
+            ErrorInfo::LicenseCantFinishProtocol => "ERRINFO_LICENSE_CANT_FINISH_PROTOCOL",
+            ErrorInfo::LicenseClientEndedProtocol => "ERRINFO_LICENSE_CLIENT_ENDED_PROTOCOL",
+            ErrorInfo::LicenseBadClientEncryption => "ERRINFO_LICENSE_BAD_CLIENT_ENCRYPTION",
+            ErrorInfo::LicenseCantUpgradeLicense => "ERRINFO_LICENSE_CANT_UPGRADE_LICENSE",
+            ErrorInfo::LicenseNoRemoteConnections => "ERRINFO_LICENSE_NO_REMOTE_CONNECTIONS",
+            ErrorInfo::CbDestinationNotFound => "ERRINFO_CB_DESTINATION_NOT_FOUND",
+            ErrorInfo::CbLoadingDestination => "ERRINFO_CB_LOADING_DESTINATION",
+            ErrorInfo::CbRedirectingToDestination => "ERRINFO_CB_REDIRECTING_TO_DESTINATION",
+            ErrorInfo::CbSessionOnlineVmWake => "ERRINFO_CB_SESSION_ONLINE_VM_WAKE",
+            ErrorInfo::CbSessionOnlineVmBoot => "ERRINFO_CB_SESSION_ONLINE_VM_BOOT",
+            ErrorInfo::CbSessionOnlineVmNoDns => "ERRINFO_CB_SESSION_ONLINE_VM_NO_DNS",
+            ErrorInfo::CbDestinationPoolNotFree => "ERRINFO_CB_DESTINATION_POOL_NOT_FREE",
+            ErrorInfo::CbConnectionCancelled => "ERRINFO_CB_CONNECTION_CANCELLED",
+            ErrorInfo::CbConnectionErrorInvalidSettings => {
+                "ERRINFO_CB_CONNECTION_ERROR_INVALID_SETTINGS"
+            }
+            ErrorInfo::CbSessionOnlineVmBootTimeout => "ERRINFO_CB_SESSION_ONLINE_VM_BOOT_TIMEOUT",
+            ErrorInfo::CbSessionOnlineVmSessmonFailed => {
+                "ERRINFO_CB_SESSION_ONLINE_VM_SESSMON_FAILED"
+            }
+            ErrorInfo::UnknownPduType2 => "ERRINFO_UNKNOWNPDUTYPE2",
+            ErrorInfo::UnknownPduType => "ERRINFO_UNKNOWNPDUTYPE",
+            ErrorInfo::DataPduSequence => "ERRINFO_DATAPDUSEQUENCE",
+            ErrorInfo::ControlPduSequence => "ERRINFO_CONTROLPDUSEQUENCE",
+            ErrorInfo::InvalidControlPduAction => "ERRINFO_INVALIDCONTROLPDUACTION",
+            ErrorInfo::InvalidInputPduType => "ERRINFO_INVALIDINPUTPDUTYPE",
+            ErrorInfo::InvalidInputPduMouse => "ERRINFO_INVALIDINPUTPDUMOUSE",
+            ErrorInfo::InvalidRefreshRectPdu => "ERRINFO_INVALIDREFRESHRECTPDU",
+            ErrorInfo::CreateUserDataFailed => "ERRINFO_CREATEUSERDATAFAILED",
+            ErrorInfo::ConnectFailed => "ERRINFO_CONNECTFAILED",
+            ErrorInfo::ConfirmActiveWrongShareId => "ERRINFO_CONFIRMACTIVEWRONGSHAREID",
+            ErrorInfo::ConfirmActiveWrongOriginator => "ERRINFO_CONFIRMACTIVEWRONGORIGINATOR",
+            ErrorInfo::PersistentKeyPduBadLength => "ERRINFO_PERSISTENTKEYPDUBADLENGTH",
+            ErrorInfo::PersistentKeyPduIllegalFirst => "ERRINFO_PERSISTENTKEYPDUILLEGALFIRST",
+            ErrorInfo::PersistentKeyPduTooManyTotalKeys => {
+                "ERRINFO_PERSISTENTKEYPDUTOOMANYTOTALKEYS"
+            }
+            ErrorInfo::PersistentKeyPduTooManyCacheKeys => {
+                "ERRINFO_PERSISTENTKEYPDUTOOMANYCACHEKEYS"
+            }
+            ErrorInfo::InputPduBadLength => "ERRINFO_INPUTPDUBADLENGTH",
+            ErrorInfo::BitmapCacheErrorPduBadLength => "ERRINFO_BITMAPCACHEERRORPDUBADLENGTH",
+            ErrorInfo::SecurityDataTooShort => "ERRINFO_SECURITYDATATOOSHORT",
+            ErrorInfo::VChannelDataTooShort => "ERRINFO_VCHANNELDATATOOSHORT",
+            ErrorInfo::ShareDataTooShort => "ERRINFO_SHAREDATATOOSHORT",
+            ErrorInfo::BadSuppressOutputPdu => "ERRINFO_BADSUPRESSOUTPUTPDU",
+            ErrorInfo::ConfirmActivePduTooShort => "ERRINFO_CONFIRMACTIVEPDUTOOSHORT",
+            ErrorInfo::CapabilitySetTooSmall => "ERRINFO_CAPABILITYSETTOOSMALL",
+            ErrorInfo::CapabilitySetTooLarge => "ERRINFO_CAPABILITYSETTOOLARGE",
+            ErrorInfo::NoCursorCache => "ERRINFO_NOCURSORCACHE",
+            ErrorInfo::BadCapabilities => "ERRINFO_BADCAPABILITIES",
+            ErrorInfo::VirtualChannelDecompressionErr => "ERRINFO_VIRTUALCHANNELDECOMPRESSIONERR",
+            ErrorInfo::InvalidVcCompressionType => "ERRINFO_INVALIDVCCOMPRESSIONTYPE",
+            ErrorInfo::InvalidChannelId => "ERRINFO_INVALIDCHANNELID",
+            ErrorInfo::VChannelsTooMany => "ERRINFO_VCHANNELSTOOMANY",
+            ErrorInfo::RemoteAppsNotEnabled => "ERRINFO_REMOTEAPPSNOTENABLED",
+            ErrorInfo::CacheCapNotSet => "ERRINFO_CACHECAPNOTSET",
+            ErrorInfo::BitmapCacheErrorPduBadLength2 => "ERRINFO_BITMAPCACHEERRORPDUBADLENGTH2",
+            ErrorInfo::OffscrCacheErrorPduBadLength => "ERRINFO_OFFSCRCACHEERRORPDUBADLENGTH",
+            ErrorInfo::DngCacheErrorPduBadLength => "ERRINFO_DNGCACHEERRORPDUBADLENGTH",
+            ErrorInfo::GdiPlusPduBadLength => "ERRINFO_GDIPLUSPDUBADLENGTH",
+            ErrorInfo::SecurityDataTooShort2 => "ERRINFO_SECURITYDATATOOSHORT2",
+            ErrorInfo::SecurityDataTooShort3 => "ERRINFO_SECURITYDATATOOSHORT3",
+            ErrorInfo::SecurityDataTooShort4 => "ERRINFO_SECURITYDATATOOSHORT4",
+            ErrorInfo::SecurityDataTooShort5 => "ERRINFO_SECURITYDATATOOSHORT5",
+            ErrorInfo::SecurityDataTooShort6 => "ERRINFO_SECURITYDATATOOSHORT6",
+            ErrorInfo::SecurityDataTooShort7 => "ERRINFO_SECURITYDATATOOSHORT7",
+            ErrorInfo::SecurityDataTooShort8 => "ERRINFO_SECURITYDATATOOSHORT8",
+            ErrorInfo::SecurityDataTooShort9 => "ERRINFO_SECURITYDATATOOSHORT9",
+            ErrorInfo::SecurityDataTooShort10 => "ERRINFO_SECURITYDATATOOSHORT10",
+            ErrorInfo::SecurityDataTooShort11 => "ERRINFO_SECURITYDATATOOSHORT11",
+            ErrorInfo::SecurityDataTooShort12 => "ERRINFO_SECURITYDATATOOSHORT12",
+            ErrorInfo::SecurityDataTooShort13 => "ERRINFO_SECURITYDATATOOSHORT13",
+            ErrorInfo::SecurityDataTooShort14 => "ERRINFO_SECURITYDATATOOSHORT14",
+            ErrorInfo::SecurityDataTooShort15 => "ERRINFO_SECURITYDATATOOSHORT15",
+            ErrorInfo::SecurityDataTooShort16 => "ERRINFO_SECURITYDATATOOSHORT16",
+            ErrorInfo::SecurityDataTooShort17 => "ERRINFO_SECURITYDATATOOSHORT17",
+            ErrorInfo::SecurityDataTooShort18 => "ERRINFO_SECURITYDATATOOSHORT18",
+            ErrorInfo::SecurityDataTooShort19 => "ERRINFO_SECURITYDATATOOSHORT19",
+            ErrorInfo::SecurityDataTooShort20 => "ERRINFO_SECURITYDATATOOSHORT20",
+            ErrorInfo::SecurityDataTooShort21 => "ERRINFO_SECURITYDATATOOSHORT21",
+            ErrorInfo::SecurityDataTooShort22 => "ERRINFO_SECURITYDATATOOSHORT22",
+            ErrorInfo::SecurityDataTooShort23 => "ERRINFO_SECURITYDATATOOSHORT23",
+            ErrorInfo::BadMonitorData => "ERRINFO_BADMONITORDATA",
+            ErrorInfo::VcDecompressedReassembleFailed => "ERRINFO_VCDECOMPRESSEDREASSEMBLEFAILED",
+            ErrorInfo::VcDataTooLong => "ERRINFO_VCDATATOOLONG",
+            ErrorInfo::BadFrameAckData => "ERRINFO_BAD_FRAME_ACK_DATA",
+            ErrorInfo::GraphicsModeNotSupported => "ERRINFO_GRAPHICSMODENOTSUPPORTED",
+            ErrorInfo::GraphicsSubsystemResetFailed => "ERRINFO_GRAPHICSSUBSYSTEMRESETFAILED",
+            ErrorInfo::GraphicsSubsystemFailed => "ERRINFO_GRAPHICSSUBSYSTEMFAILED",
+            ErrorInfo::TimezoneKeyNameLengthTooShort => "ERRINFO_TIMEZONEKEYNAMELENGTHTOOSHORT",
+            ErrorInfo::TimezoneKeyNameLengthTooLong => "ERRINFO_TIMEZONEKEYNAMELENGTHTOOLONG",
+            ErrorInfo::DynamicDstDisabledFieldMissing => "ERRINFO_DYNAMICDSTDISABLEDFIELDMISSING",
+            ErrorInfo::VcDecodingError => "ERRINFO_VCDECODINGERROR",
+            ErrorInfo::VirtualDesktopTooLarge => "ERRINFO_VIRTUALDESKTOPTOOLARGE",
+            ErrorInfo::MonitorGeometryValidationFailed => "ERRINFO_MONITORGEOMETRYVALIDATIONFAILED",
+            ErrorInfo::InvalidMonitorCount => "ERRINFO_INVALIDMONITORCOUNT",
+            ErrorInfo::UpdateSessionKeyFailed => "ERRINFO_UPDATESESSIONKEYFAILED",
+            ErrorInfo::DecryptFailed => "ERRINFO_DECRYPTFAILED",
+            ErrorInfo::EncryptFailed => "ERRINFO_ENCRYPTFAILED",
+            ErrorInfo::EncPkgMismatch => "ERRINFO_ENCPKGMISMATCH",
+            ErrorInfo::DecryptFailed2 => "ERRINFO_DECRYPTFAILED2",
+        }
+    }
+
+    /// Human-readable description
+    pub fn description(self) -> &'static str {
+        match self {
+            ErrorInfo::None => "No error",
+            ErrorInfo::RpcInitiatedDisconnect => {
+                "The disconnection was initiated by an administrative tool on the server in another session"
+            }
+            ErrorInfo::RpcInitiatedLogoff => {
+                "The disconnection was due to a forced logoff initiated by an administrative tool on the server in another session"
+            }
+            ErrorInfo::IdleTimeout => "The idle session limit timer on the server has elapsed",
+            ErrorInfo::LogonTimeout => "The active session limit timer on the server has elapsed",
+            ErrorInfo::DisconnectedByOtherConnection => {
+                "Another user connected to the server, forcing the disconnection of the current connection"
+            }
+            ErrorInfo::OutOfMemory => "The server ran out of available memory resources",
+            ErrorInfo::ServerDeniedConnection => "The server denied the connection",
+            ErrorInfo::ServerInsufficientPrivileges => {
+                "The user cannot connect to the server due to insufficient access privileges"
+            }
+            ErrorInfo::ServerFreshCredentialsRequired => {
+                "The server does not accept saved user credentials and requires that the user enter their credentials for each connection"
+            }
+            ErrorInfo::RpcInitiatedDisconnectByUser => {
+                "The disconnection was initiated by the user disconnecting their session on the server or by an administrative tool"
+            }
+            ErrorInfo::LogoffByUser => {
+                "The disconnection was initiated by the user logging off their session on the server"
+            }
+            ErrorInfo::CloseStackOnDriverNotReady => {
+                "The display driver in the remote session did not report any status within the time allotted for startup"
+            }
+            ErrorInfo::ServerDwmCrash => {
+                "The Desktop Window Manager (DWM) process in the remote session terminated unexpectedly"
+            }
+            ErrorInfo::CloseStackOnDriverFailure => {
+                "The display driver in the remote session was unable to complete all the tasks required for startup"
+            }
+            ErrorInfo::CloseStackOnDriverIfaceFailure => {
+                "The display driver in the remote session started up successfully, but due to internal failures was not usable by the remoting stack"
+            }
+            ErrorInfo::ServerWinlogonCrash => {
+                "The Winlogon process in the remote session terminated unexpectedly"
+            }
+            ErrorInfo::ServerCsrssCrash => {
+                "The CSRSS process in the remote session terminated unexpectedly"
+            }
+            ErrorInfo::ServerShutdown => "The server is being shut down",
+            ErrorInfo::ServerReboot => "The server is being rebooted",
+            ErrorInfo::LicenseInternal => {
+                "An internal error has occurred in the Terminal Services licensing component"
+            }
+            ErrorInfo::LicenseNoLicenseServer => {
+                "A Remote Desktop License Server could not be found to provide a license"
+            }
+            ErrorInfo::LicenseNoLicense => {
+                "There are no Client Access Licenses available for the target remote computer"
+            }
+            ErrorInfo::LicenseBadClientMsg => {
+                "The remote computer received an invalid licensing message from the client"
+            }
+            ErrorInfo::LicenseHwidDoesntMatchLicense => {
+                "The Client Access License stored by the client has been modified"
+            }
+            ErrorInfo::LicenseBadClientLicense => {
+                "The Client Access License stored by the client is in an invalid format"
+            }
+            ErrorInfo::LicenseCantFinishProtocol => {
+                "Network problems have caused the licensing protocol to be terminated"
+            }
+            ErrorInfo::LicenseClientEndedProtocol => {
+                "The client prematurely ended the licensing protocol"
+            }
+            ErrorInfo::LicenseBadClientEncryption => {
+                "A licensing message was incorrectly encrypted"
+            }
+            ErrorInfo::LicenseCantUpgradeLicense => {
+                "The Client Access License stored by the client could not be upgraded or renewed"
+            }
+            ErrorInfo::LicenseNoRemoteConnections => {
+                "The remote computer is not licensed to accept remote connections"
+            }
+            ErrorInfo::CbDestinationNotFound => "The target endpoint could not be found",
+            ErrorInfo::CbLoadingDestination => {
+                "The target endpoint to which the client is being redirected is disconnecting from the Connection Broker"
+            }
+            ErrorInfo::CbRedirectingToDestination => {
+                "An error occurred while the connection was being redirected to the target endpoint"
+            }
+            ErrorInfo::CbSessionOnlineVmWake => {
+                "An error occurred while the target endpoint (a virtual machine) was being awakened"
+            }
+            ErrorInfo::CbSessionOnlineVmBoot => {
+                "An error occurred while the target endpoint (a virtual machine) was being started"
+            }
+            ErrorInfo::CbSessionOnlineVmNoDns => {
+                "The IP address of the target endpoint (a virtual machine) cannot be determined"
+            }
+            ErrorInfo::CbDestinationPoolNotFree => {
+                "There are no available endpoints in the pool managed by the Connection Broker"
+            }
+            ErrorInfo::CbConnectionCancelled => "Processing of the connection has been canceled",
+            ErrorInfo::CbConnectionErrorInvalidSettings => {
+                "The settings contained in the routing token or load balance info are invalid"
+            }
+            ErrorInfo::CbSessionOnlineVmBootTimeout => {
+                "A time-out occurred while the target endpoint (a virtual machine) was being started"
+            }
+            ErrorInfo::CbSessionOnlineVmSessmonFailed => {
+                "A session monitoring error occurred while the target endpoint (a virtual machine) was being started"
+            }
+            ErrorInfo::UnknownPduType2 => "Unknown pduType2 field in a received Share Data Header",
+            ErrorInfo::UnknownPduType => "Unknown pduType field in a received Share Control Header",
+            ErrorInfo::DataPduSequence => {
+                "An out-of-sequence Slow-Path Data PDU or Slow-Path Input Event PDU has been received"
+            }
+            ErrorInfo::ControlPduSequence => "An out-of-sequence Control PDU has been received",
+            ErrorInfo::InvalidControlPduAction => {
+                "A Control PDU has been received with an invalid action field"
+            }
+            ErrorInfo::InvalidInputPduType => {
+                "A Slow-Path Input Event PDU or Fast-Path Input Event PDU has been received with an invalid event type"
+            }
+            ErrorInfo::InvalidInputPduMouse => {
+                "A mouse input event has been received with invalid pointer flags"
+            }
+            ErrorInfo::InvalidRefreshRectPdu => "An invalid Refresh Rect PDU has been received",
+            ErrorInfo::CreateUserDataFailed => {
+                "The server failed to construct the GCC Conference Create Response user data"
+            }
+            ErrorInfo::ConnectFailed => {
+                "Processing during the Channel Connection phase of the connection sequence has failed"
+            }
+            ErrorInfo::ConfirmActiveWrongShareId => {
+                "A Confirm Active PDU was received from the client with an invalid shareID field"
+            }
+            ErrorInfo::ConfirmActiveWrongOriginator => {
+                "A Confirm Active PDU was received from the client with an invalid originatorID field"
+            }
+            ErrorInfo::PersistentKeyPduBadLength => {
+                "There is not enough data to process a Persistent Key List PDU"
+            }
+            ErrorInfo::PersistentKeyPduIllegalFirst => {
+                "A Persistent Key List PDU marked as PERSIST_PDU_FIRST was received after the reception of a prior one"
+            }
+            ErrorInfo::PersistentKeyPduTooManyTotalKeys => {
+                "A Persistent Key List PDU was received which specified a total number of bitmap cache entries larger than the allowed maximum"
+            }
+            ErrorInfo::PersistentKeyPduTooManyCacheKeys => {
+                "A Persistent Key List PDU was received which specified an invalid total number of keys for a bitmap cache"
+            }
+            ErrorInfo::InputPduBadLength => {
+                "There is not enough data to process Input Event PDU Data or a Fast-Path Input Event PDU"
+            }
+            ErrorInfo::BitmapCacheErrorPduBadLength => {
+                "There is not enough data to process the shareDataHeader, NumInfoBlocks, Pad1, and Pad2 fields of the Bitmap Cache Error PDU Data"
+            }
+            ErrorInfo::SecurityDataTooShort => {
+                "The dataSignature field of the Fast-Path Input Event PDU does not contain enough data, or the fipsInformation and dataSignature fields do not contain enough data"
+            }
+            ErrorInfo::VChannelDataTooShort => {
+                "There is not enough data in the Client Network Data to read the virtual channel configuration data, or the data in a Virtual Channel PDU is too short"
+            }
+            ErrorInfo::ShareDataTooShort => {
+                "There is not enough data to process Control PDU Data, Refresh Rect PDU Data, Suppress Output PDU Data or the Share Data Header"
+            }
+            ErrorInfo::BadSuppressOutputPdu => "The Suppress Output PDU is not valid",
+            ErrorInfo::ConfirmActivePduTooShort => {
+                "There is not enough data to read the Confirm Active PDU or its capability sets"
+            }
+            ErrorInfo::CapabilitySetTooSmall => {
+                "There is not enough data to read the capabilitySetType and lengthCapability fields of a received capability set"
+            }
+            ErrorInfo::CapabilitySetTooLarge => {
+                "A capability set has been received with a lengthCapability field that exceeds the remaining data"
+            }
+            ErrorInfo::NoCursorCache => {
+                "Both the colorPointerCacheSize and pointerCacheSize fields in the Pointer Capability Set are set to zero"
+            }
+            ErrorInfo::BadCapabilities => {
+                "The capabilities received from the client in the Confirm Active PDU were not accepted by the server"
+            }
+            ErrorInfo::VirtualChannelDecompressionErr => {
+                "An error occurred while using the bulk compressor to decompress a Virtual Channel PDU"
+            }
+            ErrorInfo::InvalidVcCompressionType => {
+                "An invalid bulk compression package was specified in the flags field of the Channel PDU Header"
+            }
+            ErrorInfo::InvalidChannelId => {
+                "An invalid MCS channel ID was specified in the mcsPdu field of a Virtual Channel PDU"
+            }
+            ErrorInfo::VChannelsTooMany => {
+                "The client requested more than the maximum allowed 31 static virtual channels"
+            }
+            ErrorInfo::RemoteAppsNotEnabled => {
+                "The INFO_RAIL flag was set in the Client Info PDU but the server does not support RemoteApp"
+            }
+            ErrorInfo::CacheCapNotSet => {
+                "The client sent a Persistent Key List PDU without including the prerequisite Revision 2 Bitmap Cache Capability Set"
+            }
+            ErrorInfo::BitmapCacheErrorPduBadLength2 => {
+                "The NumInfoBlocks field in the Bitmap Cache Error PDU Data is inconsistent with the amount of data in the Info field"
+            }
+            ErrorInfo::OffscrCacheErrorPduBadLength => {
+                "There is not enough data to process an Offscreen Bitmap Cache Error PDU"
+            }
+            ErrorInfo::DngCacheErrorPduBadLength => {
+                "There is not enough data to process a DrawNineGrid Cache Error PDU"
+            }
+            ErrorInfo::GdiPlusPduBadLength => {
+                "There is not enough data to process a GDI+ Error PDU"
+            }
+            ErrorInfo::SecurityDataTooShort2 => {
+                "There is not enough data to read a Basic Security Header"
+            }
+            ErrorInfo::SecurityDataTooShort3 => {
+                "There is not enough data to read a Non-FIPS Security Header or FIPS Security Header"
+            }
+            ErrorInfo::SecurityDataTooShort4 => {
+                "There is not enough data to read the basicSecurityHeader and length fields of the Security Exchange PDU Data"
+            }
+            ErrorInfo::SecurityDataTooShort5 => {
+                "There is not enough data to read the CodePage, flags, cbDomain, cbUserName, cbPassword, cbAlternateShell, cbWorkingDir, Domain, UserName, Password, AlternateShell, and WorkingDir fields in the Info Packet"
+            }
+            ErrorInfo::SecurityDataTooShort6 => {
+                "There is not enough data to read the clientAddressFamily and cbClientAddress fields in the Extended Info Packet"
+            }
+            ErrorInfo::SecurityDataTooShort7 => {
+                "There is not enough data to read the clientAddress field in the Extended Info Packet"
+            }
+            ErrorInfo::SecurityDataTooShort8 => {
+                "There is not enough data to read the cbClientDir field in the Extended Info Packet"
+            }
+            ErrorInfo::SecurityDataTooShort9 => {
+                "There is not enough data to read the clientDir field in the Extended Info Packet"
+            }
+            ErrorInfo::SecurityDataTooShort10 => {
+                "There is not enough data to read the clientTimeZone field in the Extended Info Packet"
+            }
+            ErrorInfo::SecurityDataTooShort11 => {
+                "There is not enough data to read the clientSessionId field in the Extended Info Packet"
+            }
+            ErrorInfo::SecurityDataTooShort12 => {
+                "There is not enough data to read the performanceFlags field in the Extended Info Packet"
+            }
+            ErrorInfo::SecurityDataTooShort13 => {
+                "There is not enough data to read the cbAutoReconnectCookie field in the Extended Info Packet"
+            }
+            ErrorInfo::SecurityDataTooShort14 => {
+                "There is not enough data to read the autoReconnectCookie field in the Extended Info Packet"
+            }
+            ErrorInfo::SecurityDataTooShort15 => {
+                "The cbAutoReconnectCookie field in the Extended Info Packet contains a value which is larger than the maximum allowed length of 128 bytes"
+            }
+            ErrorInfo::SecurityDataTooShort16 => {
+                "There is not enough data to read the clientAddressFamily and cbClientAddress fields in the Extended Info Packet"
+            }
+            ErrorInfo::SecurityDataTooShort17 => {
+                "There is not enough data to read the clientAddress field in the Extended Info Packet"
+            }
+            ErrorInfo::SecurityDataTooShort18 => {
+                "There is not enough data to read the cbClientDir field in the Extended Info Packet"
+            }
+            ErrorInfo::SecurityDataTooShort19 => {
+                "There is not enough data to read the clientDir field in the Extended Info Packet"
+            }
+            ErrorInfo::SecurityDataTooShort20 => {
+                "There is not enough data to read the clientTimeZone field in the Extended Info Packet"
+            }
+            ErrorInfo::SecurityDataTooShort21 => {
+                "There is not enough data to read the clientSessionId field in the Extended Info Packet"
+            }
+            ErrorInfo::SecurityDataTooShort22 => {
+                "There is not enough data to read the Client Info PDU Data"
+            }
+            ErrorInfo::SecurityDataTooShort23 => {
+                "The number of TS_MONITOR_DEF structures in the Client Monitor Data is larger than the monitorCount field indicates"
+            }
+            ErrorInfo::BadMonitorData => {
+                "The monitorCount field in the Client Monitor Data is invalid"
+            }
+            ErrorInfo::VcDecompressedReassembleFailed => {
+                "The server-side decompression buffer is invalid or too small"
+            }
+            ErrorInfo::VcDataTooLong => {
+                "The size of a received Virtual Channel PDU exceeds the chunking size specified in the Virtual Channel Capability Set"
+            }
+            ErrorInfo::BadFrameAckData => {
+                "There is not enough data to read a Frame Acknowledge PDU"
+            }
+            ErrorInfo::GraphicsModeNotSupported => {
+                "The graphics mode requested by the client is not supported by the server"
+            }
+            ErrorInfo::GraphicsSubsystemResetFailed => {
+                "The server-side graphics subsystem failed to reset"
+            }
+            ErrorInfo::GraphicsSubsystemFailed => {
+                "The server-side graphics subsystem is in an error state and unable to continue graphics encoding"
+            }
+            ErrorInfo::TimezoneKeyNameLengthTooShort => {
+                "There is not enough data to read the cbDynamicDSTTimeZoneKeyName field in the Extended Info Packet"
+            }
+            ErrorInfo::TimezoneKeyNameLengthTooLong => {
+                "The length reported in the cbDynamicDSTTimeZoneKeyName field of the Extended Info Packet is too long"
+            }
+            ErrorInfo::DynamicDstDisabledFieldMissing => {
+                "The dynamicDaylightTimeDisabled field is not present in the Extended Info Packet"
+            }
+            ErrorInfo::VcDecodingError => {
+                "An error occurred when processing dynamic virtual channel data"
+            }
+            ErrorInfo::VirtualDesktopTooLarge => {
+                "The width or height of the virtual desktop defined by the monitor layout exceeds the maximum allowed"
+            }
+            ErrorInfo::MonitorGeometryValidationFailed => {
+                "The monitor geometry defined by the monitor layout is invalid"
+            }
+            ErrorInfo::InvalidMonitorCount => {
+                "The monitorCount field in the monitor layout is too large"
+            }
+            ErrorInfo::UpdateSessionKeyFailed => {
+                "An attempt to update the session keys while using Standard RDP Security mechanisms failed"
+            }
+            ErrorInfo::DecryptFailed => "Decryption using Standard RDP Security mechanisms failed",
+            ErrorInfo::EncryptFailed => "Encryption using Standard RDP Security mechanisms failed",
+            ErrorInfo::EncPkgMismatch => {
+                "Failed to find a usable Encryption Method in the encryptionMethods field of the Client Security Data"
+            }
+            ErrorInfo::DecryptFailed2 => {
+                "Unencrypted data was encountered in a protocol stream which is meant to be encrypted with Standard RDP Security mechanisms"
+            }
+        }
+    }
+
+    /// Code range the error belongs to
+    pub fn category(self) -> ErrorInfoCategory {
+        ErrorInfoCategory::from_code(self.as_u32())
+    }
+}
+
+/// Set Error Info PDU (MS-RDPBCGR 2.2.5.1.1)
+///
+/// Sent by the server ahead of a disconnect to explain it.
+/// The raw value is kept so codes missing from `ErrorInfo` are not lost.
+///
+/// This PDU is sent within a Share Data Header (pdu_type2 = SetErrorInfo)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetErrorInfoPdu {
+    /// Error info code
+    pub error_info: u32,
+}
+
+impl SetErrorInfoPdu {
+    /// Create Set Error Info PDU
+    pub fn new(error_info: ErrorInfo) -> Self {
+        Self {
+            error_info: error_info.as_u32(),
+        }
+    }
+
+    /// PDU size (4 bytes)
+    pub const SIZE: usize = 4;
+
+    /// Typed error info, if the code is known
+    pub fn error_info(&self) -> Option<ErrorInfo> {
+        ErrorInfo::from_u32(self.error_info)
+    }
+
+    /// Check whether the server reported no error
+    pub fn is_none(&self) -> bool {
+        self.error_info == ErrorInfo::None.as_u32()
+    }
+
+    /// Code range the error belongs to
+    pub fn category(&self) -> ErrorInfoCategory {
+        ErrorInfoCategory::from_code(self.error_info)
+    }
+
+    /// Description suitable for showing to a user
+    pub fn description(&self) -> String {
+        match self.error_info() {
+            Some(error_info) => format!(
+                "{} ({}, {:#010x})",
+                error_info.description(),
+                error_info.name(),
+                self.error_info
+            ),
+            None => format!(
+                "Unknown error ({:?}, {:#010x})",
+                self.category(),
+                self.error_info
+            ),
+        }
+    }
+}
+
+impl Pdu for SetErrorInfoPdu {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_u32::<LittleEndian>(self.error_info)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let error_info = buffer.read_u32::<LittleEndian>()?;
+        Ok(Self { error_info })
+    }
+
+    fn size(&self) -> usize {
+        Self::SIZE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_info_codes() {
+        assert_eq!(ErrorInfo::from_u32(0x0003), Some(ErrorInfo::IdleTimeout));
+        assert_eq!(ErrorInfo::LicenseNoLicense.as_u32(), 0x0102);
+        assert_eq!(ErrorInfo::from_u32(0x0008), None);
+        assert_eq!(ErrorInfo::DecryptFailed2.name(), "ERRINFO_DECRYPTFAILED2");
+
+        assert_eq!(
+            ErrorInfo::CbConnectionCancelled.category(),
+            ErrorInfoCategory::ConnectionBroker
+        );
+        assert_eq!(
+            ErrorInfo::BadMonitorData.category(),
+            ErrorInfoCategory::Protocol
+        );
+        assert_eq!(
+            ErrorInfo::LogoffByUser.category(),
+            ErrorInfoCategory::ProtocolIndependent
+        );
+    }
+
+    #[test]
+    fn test_set_error_info_pdu_encode_decode() {
+        let pdu = SetErrorInfoPdu::new(ErrorInfo::ServerDeniedConnection);
+
+        let mut buffer = Vec::new();
+        pdu.encode(&mut buffer).unwrap();
+        assert_eq!(buffer, vec![0x07, 0x00, 0x00, 0x00]);
+
+        let decoded = SetErrorInfoPdu::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded, pdu);
+        assert_eq!(
+            decoded.error_info(),
+            Some(ErrorInfo::ServerDeniedConnection)
+        );
+        assert_eq!(
+            decoded.description(),
+            "The server denied the connection (ERRINFO_SERVER_DENIED_CONNECTION, 0x00000007)"
+        );
+    }
+
+    #[test]
+    fn test_set_error_info_pdu_unknown_code() {
+        let pdu = SetErrorInfoPdu::decode(&mut [0x27, 0x01, 0x00, 0x00].as_slice()).unwrap();
+
+        assert_eq!(pdu.error_info, 0x0127);
+        assert_eq!(pdu.error_info(), None);
+        assert!(!pdu.is_none());
+        assert_eq!(pdu.description(), "Unknown error (Licensing, 0x00000127)");
+    }
+}
@@ -77,6 +77,7 @@ pub struct BitmapData {
 
 impl BitmapData {
     /// Create new bitmap data
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         dest_left: u16,
         dest_top: u16,
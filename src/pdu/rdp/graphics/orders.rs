@@ -400,6 +400,7 @@ pub struct MemBltOrder {
 
 impl MemBltOrder {
     /// Create new MemBlt order
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         cache_id: u16,
         dest_x: i16,
@@ -1,17 +1,31 @@
 // RDP Core Layer
+pub mod activation;
 pub mod capability;
 pub mod connection;
 pub mod control;
+pub mod data;
+pub mod error_info;
 pub mod graphics;
 pub mod header;
 pub mod input;
+pub mod monitor;
+pub mod session_info;
 
+pub use activation::{
+    ActivationState, ClientActivation, ClientActivationPdu, ConfirmActivePdu, DeactivateAllPdu,
+    DemandActivePdu, FinalizationFlags,
+};
 pub use capability::{
     BitmapCapability, CapabilitySet, CapabilitySetHeader, CapabilitySetType, GeneralCapability,
     InputCapability, OrderCapability,
 };
-pub use connection::{ClientInfoFlags, ClientInfoPdu, PerformanceFlags, TimeZoneInformation};
+pub use connection::{
+    ArcCsPrivatePacket, ClientInfoFlags, ClientInfoPdu, ExtendedInfo, PerformanceFlags,
+    TimeZoneInformation,
+};
 pub use control::{ControlAction, ControlPdu, FontListPdu, FontMapPdu, SynchronizePdu};
+pub use data::{ShareDataMessage, ShareDataPdu};
+pub use error_info::{ErrorInfo, ErrorInfoCategory, SetErrorInfoPdu};
 pub use graphics::{
     BitmapData, BitmapFlags, BitmapUpdate, DstBltOrder, MemBltOrder, OpaqueRectOrder,
     OrdersUpdate, OrderType, PatBltOrder, ScrBltOrder, UpdatePdu, UpdateType,
@@ -22,3 +36,8 @@ pub use input::{
     KeyboardEvent, KeyboardFlags, MouseEvent, MouseFlags, SyncEvent, UnicodeKeyboardEvent,
     UnicodeKeyboardFlags,
 };
+pub use monitor::MonitorLayoutPdu;
+pub use session_info::{
+    ArcScPrivatePacket, LogonErrorData, LogonErrorType, LogonErrorsInfo, LogonExtendedFields,
+    LogonExtendedInfo, LogonInfo, SaveSessionInfoPdu, SaveSessionInfoType,
+};
@@ -0,0 +1,89 @@
+use crate::pdu::gcc::monitor::{MonitorDef, check_monitor_count};
+use crate::pdu::{Pdu, Result};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Read, Write};
+
+/// Monitor Layout PDU (MS-RDPBCGR 2.2.12.1)
+///
+/// Sent by the server after the Demand Active PDU when the client advertised
+/// RNS_UD_CS_SUPPORT_MONITOR_LAYOUT_PDU.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonitorLayoutPdu {
+    /// Monitor definitions as arranged by the server
+    pub monitors: Vec<MonitorDef>,
+}
+
+impl MonitorLayoutPdu {
+    /// Create new Monitor Layout PDU
+    pub fn new(monitors: Vec<MonitorDef>) -> Self {
+        Self { monitors }
+    }
+
+    /// Primary monitor, if any
+    pub fn primary(&self) -> Option<&MonitorDef> {
+        self.monitors.iter().find(|m| m.is_primary())
+    }
+}
+
+impl Pdu for MonitorLayoutPdu {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        check_monitor_count(self.monitors.len())?;
+
+        buffer.write_u32::<LittleEndian>(self.monitors.len() as u32)?;
+        for monitor in &self.monitors {
+            monitor.encode(buffer)?;
+        }
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let monitor_count = buffer.read_u32::<LittleEndian>()? as usize;
+        check_monitor_count(monitor_count)?;
+
+        let monitors = (0..monitor_count)
+            .map(|_| MonitorDef::decode(buffer))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self { monitors })
+    }
+
+    fn size(&self) -> usize {
+        4 + self.monitors.len() * MonitorDef::SIZE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdu::gcc::MonitorLayoutBuilder;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_monitor_layout_pdu_roundtrip() {
+        let monitors = MonitorLayoutBuilder::new()
+            .primary(1920, 1080)
+            .secondary(-1280, 56, 1280, 1024)
+            .build()
+            .unwrap();
+        let pdu = MonitorLayoutPdu::new(monitors);
+
+        let mut buffer = Vec::new();
+        pdu.encode(&mut buffer).unwrap();
+
+        assert_eq!(buffer.len(), pdu.size());
+        assert_eq!(&buffer[..4], &[0x02, 0x00, 0x00, 0x00]);
+
+        let mut cursor = Cursor::new(buffer);
+        let decoded = MonitorLayoutPdu::decode(&mut cursor).unwrap();
+
+        assert_eq!(decoded, pdu);
+        assert_eq!(decoded.primary().unwrap().width(), 1920);
+        assert_eq!(decoded.monitors[1].top, 56);
+    }
+
+    #[test]
+    fn test_monitor_layout_pdu_count_limit() {
+        let mut cursor = Cursor::new(vec![0x20, 0x00, 0x00, 0x00]);
+        assert!(MonitorLayoutPdu::decode(&mut cursor).is_err());
+    }
+}
@@ -0,0 +1,692 @@
+use crate::pdu::rdp::connection::client_info::{
+    decode_unicode_string, encode_unicode_string, read_unicode_string, write_unicode_string,
+};
+use crate::pdu::{Pdu, PduError, Result};
+use bitflags::bitflags;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Read, Write};
+
+/// Size of the Domain field in Logon Info Version 1 (bytes)
+const LOGON_INFO_V1_DOMAIN_SIZE: usize = 52;
+
+/// Size of the UserName field in Logon Info Version 1 (bytes)
+const LOGON_INFO_V1_USER_NAME_SIZE: usize = 512;
+
+/// SAVE_SESSION_PDU_VERSION_ONE
+const SAVE_SESSION_PDU_VERSION_ONE: u16 = 0x0001;
+
+/// Fixed part of Logon Info Version 2 (Version..cbUserName)
+const LOGON_INFO_V2_HEADER_SIZE: usize = 18;
+
+/// Padding after the Logon Info Version 2 fixed part
+const LOGON_INFO_V2_PAD_SIZE: usize = 558;
+
+/// Padding in Plain Notify
+const PLAIN_NOTIFY_PAD_SIZE: usize = 576;
+
+/// Padding after Logon Extended Info fields
+const LOGON_EXTENDED_PAD_SIZE: usize = 570;
+
+/// AUTO_RECONNECT_VERSION_1
+const AUTO_RECONNECT_VERSION_1: u32 = 0x0000_0001;
+
+/// Size of ARC_SC_PRIVATE_PACKET and ARC_CS_PRIVATE_PACKET (bytes)
+pub(crate) const ARC_PRIVATE_PACKET_SIZE: usize = 28;
+
+/// Save Session Info type (MS-RDPBCGR 2.2.10.1.1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum SaveSessionInfoType {
+    /// INFOTYPE_LOGON - Logon Info Version 1
+    Logon = 0x0000_0000,
+    /// INFOTYPE_LOGON_LONG - Logon Info Version 2
+    LogonLong = 0x0000_0001,
+    /// INFOTYPE_LOGON_PLAINNOTIFY - Plain Notify
+    PlainNotify = 0x0000_0002,
+    /// INFOTYPE_LOGON_EXTENDED_INFO - Logon Extended Info
+    LogonExtendedInfo = 0x0000_0003,
+}
+
+impl SaveSessionInfoType {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0x0000_0000 => Some(SaveSessionInfoType::Logon),
+            0x0000_0001 => Some(SaveSessionInfoType::LogonLong),
+            0x0000_0002 => Some(SaveSessionInfoType::PlainNotify),
+            0x0000_0003 => Some(SaveSessionInfoType::LogonExtendedInfo),
+            _ => None,
+        }
+    }
+
+    pub fn as_u32(self) -> u32 {
+        self as u32
+    }
+}
+
+bitflags! {
+    /// Logon Extended Info fields present (MS-RDPBCGR 2.2.10.1.1.4)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct LogonExtendedFields: u32 {
+        /// LOGON_EX_AUTORECONNECTCOOKIE - Auto-reconnect cookie
+        const AUTORECONNECT_COOKIE = 0x0000_0001;
+        /// LOGON_EX_LOGONERRORS - Logon errors
+        const LOGON_ERRORS = 0x0000_0002;
+    }
+}
+
+/// Logon error notification type (MS-RDPBCGR 2.2.10.1.1.4.1.1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum LogonErrorType {
+    /// LOGON_MSG_DISCONNECT_REFUSED - Disconnect refused by Winlogon
+    DisconnectRefused = 0xFFFF_FFF9,
+    /// LOGON_MSG_NO_PERMISSION - User lacks permission to connect
+    NoPermission = 0xFFFF_FFFA,
+    /// LOGON_MSG_BUMP_OPTIONS - Another user is connected to the session
+    BumpOptions = 0xFFFF_FFFB,
+    /// LOGON_MSG_RECONNECT_OPTIONS - Reconnecting to an existing session
+    ReconnectOptions = 0xFFFF_FFFC,
+    /// LOGON_MSG_SESSION_TERMINATE - Session is being terminated
+    SessionTerminate = 0xFFFF_FFFD,
+    /// LOGON_MSG_SESSION_CONTINUE - Logon process continues
+    SessionContinue = 0xFFFF_FFFE,
+}
+
+impl LogonErrorType {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0xFFFF_FFF9 => Some(LogonErrorType::DisconnectRefused),
+            0xFFFF_FFFA => Some(LogonErrorType::NoPermission),
+            0xFFFF_FFFB => Some(LogonErrorType::BumpOptions),
+            0xFFFF_FFFC => Some(LogonErrorType::ReconnectOptions),
+            0xFFFF_FFFD => Some(LogonErrorType::SessionTerminate),
+            0xFFFF_FFFE => Some(LogonErrorType::SessionContinue),
+            _ => None,
+        }
+    }
+
+    pub fn as_u32(self) -> u32 {
+        self as u32
+    }
+}
+
+/// Logon error notification data (MS-RDPBCGR 2.2.10.1.1.4.1.1)
+///
+/// Any other value is the ID of the session being connected to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum LogonErrorData {
+    /// LOGON_FAILED_BAD_PASSWORD - Bad user name or password
+    FailedBadPassword = 0x0000_0000,
+    /// LOGON_FAILED_UPDATE_PASSWORD - Password must be changed
+    FailedUpdatePassword = 0x0000_0001,
+    /// LOGON_FAILED_OTHER - Logon failed for another reason
+    FailedOther = 0x0000_0002,
+    /// LOGON_WARNING - Warning or message shown to the user
+    Warning = 0x0000_0003,
+}
+
+impl LogonErrorData {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0x0000_0000 => Some(LogonErrorData::FailedBadPassword),
+            0x0000_0001 => Some(LogonErrorData::FailedUpdatePassword),
+            0x0000_0002 => Some(LogonErrorData::FailedOther),
+            0x0000_0003 => Some(LogonErrorData::Warning),
+            _ => None,
+        }
+    }
+
+    pub fn as_u32(self) -> u32 {
+        self as u32
+    }
+}
+
+/// Size in bytes of a null-terminated UTF-16LE string
+fn unicode_size(s: &str) -> usize {
+    (s.encode_utf16().count() + 1) * 2
+}
+
+/// Fail if a string does not fit in a field of `max` bytes
+fn check_unicode_size(s: &str, max: usize) -> Result<usize> {
+    let size = unicode_size(s);
+    if size > max {
+        return Err(PduError::InvalidLength {
+            expected: max,
+            actual: size,
+        });
+    }
+    Ok(size)
+}
+
+/// Logon notification contents (Logon Info Version 1 and 2)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogonInfo {
+    /// Session ID
+    pub session_id: u32,
+    /// Logon domain
+    pub domain: String,
+    /// Logon user name
+    pub user_name: String,
+}
+
+impl LogonInfo {
+    /// Create logon info
+    pub fn new(session_id: u32, domain: &str, user_name: &str) -> Self {
+        Self {
+            session_id,
+            domain: domain.to_string(),
+            user_name: user_name.to_string(),
+        }
+    }
+
+    /// Logon Info Version 1 size (576 bytes)
+    pub const V1_SIZE: usize = 576;
+
+    /// Encode as TS_LOGON_INFO (MS-RDPBCGR 2.2.10.1.1.1)
+    fn encode_v1(&self, buffer: &mut dyn Write) -> Result<()> {
+        let cb_domain = check_unicode_size(&self.domain, LOGON_INFO_V1_DOMAIN_SIZE)?;
+        let cb_user_name = check_unicode_size(&self.user_name, LOGON_INFO_V1_USER_NAME_SIZE)?;
+
+        buffer.write_u32::<LittleEndian>(cb_domain as u32)?;
+        let mut domain = [0u8; LOGON_INFO_V1_DOMAIN_SIZE];
+        encode_unicode_string(&self.domain, &mut domain, LOGON_INFO_V1_DOMAIN_SIZE / 2)?;
+        buffer.write_all(&domain)?;
+
+        buffer.write_u32::<LittleEndian>(cb_user_name as u32)?;
+        let mut user_name = [0u8; LOGON_INFO_V1_USER_NAME_SIZE];
+        encode_unicode_string(
+            &self.user_name,
+            &mut user_name,
+            LOGON_INFO_V1_USER_NAME_SIZE / 2,
+        )?;
+        buffer.write_all(&user_name)?;
+
+        buffer.write_u32::<LittleEndian>(self.session_id)?;
+        Ok(())
+    }
+
+    /// Decode TS_LOGON_INFO
+    fn decode_v1(buffer: &mut dyn Read) -> Result<Self> {
+        let cb_domain = buffer.read_u32::<LittleEndian>()? as usize;
+        let mut domain = [0u8; LOGON_INFO_V1_DOMAIN_SIZE];
+        buffer.read_exact(&mut domain)?;
+
+        let cb_user_name = buffer.read_u32::<LittleEndian>()? as usize;
+        let mut user_name = [0u8; LOGON_INFO_V1_USER_NAME_SIZE];
+        buffer.read_exact(&mut user_name)?;
+
+        let session_id = buffer.read_u32::<LittleEndian>()?;
+
+        Ok(Self {
+            session_id,
+            domain: decode_unicode_string(&domain[..cb_domain.min(domain.len())]),
+            user_name: decode_unicode_string(&user_name[..cb_user_name.min(user_name.len())]),
+        })
+    }
+
+    /// Encode as TS_LOGON_INFO_VERSION_2 (MS-RDPBCGR 2.2.10.1.1.2)
+    fn encode_v2(&self, buffer: &mut dyn Write) -> Result<()> {
+        let cb_domain = check_unicode_size(&self.domain, LOGON_INFO_V1_DOMAIN_SIZE)?;
+        let cb_user_name = check_unicode_size(&self.user_name, LOGON_INFO_V1_USER_NAME_SIZE)?;
+
+        buffer.write_u16::<LittleEndian>(SAVE_SESSION_PDU_VERSION_ONE)?;
+        buffer.write_u32::<LittleEndian>(LOGON_INFO_V2_HEADER_SIZE as u32)?;
+        buffer.write_u32::<LittleEndian>(self.session_id)?;
+        buffer.write_u32::<LittleEndian>(cb_domain as u32)?;
+        buffer.write_u32::<LittleEndian>(cb_user_name as u32)?;
+        buffer.write_all(&[0u8; LOGON_INFO_V2_PAD_SIZE])?;
+        write_unicode_string(buffer, &self.domain)?;
+        write_unicode_string(buffer, &self.user_name)?;
+        Ok(())
+    }
+
+    /// Decode TS_LOGON_INFO_VERSION_2
+    fn decode_v2(buffer: &mut dyn Read) -> Result<Self> {
+        let version = buffer.read_u16::<LittleEndian>()?;
+        if version != SAVE_SESSION_PDU_VERSION_ONE {
+            return Err(PduError::ParseError(format!(
+                "Unsupported Logon Info Version 2 version: {:#x}",
+                version
+            )));
+        }
+        let size = buffer.read_u32::<LittleEndian>()? as usize;
+        if size != LOGON_INFO_V2_HEADER_SIZE {
+            return Err(PduError::InvalidLength {
+                expected: LOGON_INFO_V2_HEADER_SIZE,
+                actual: size,
+            });
+        }
+
+        let session_id = buffer.read_u32::<LittleEndian>()?;
+        let cb_domain = buffer.read_u32::<LittleEndian>()? as usize;
+        let cb_user_name = buffer.read_u32::<LittleEndian>()? as usize;
+        if cb_domain > LOGON_INFO_V1_DOMAIN_SIZE {
+            return Err(PduError::InvalidLength {
+                expected: LOGON_INFO_V1_DOMAIN_SIZE,
+                actual: cb_domain,
+            });
+        }
+        if cb_user_name > LOGON_INFO_V1_USER_NAME_SIZE {
+            return Err(PduError::InvalidLength {
+                expected: LOGON_INFO_V1_USER_NAME_SIZE,
+                actual: cb_user_name,
+            });
+        }
+
+        let mut pad = [0u8; LOGON_INFO_V2_PAD_SIZE];
+        buffer.read_exact(&mut pad)?;
+
+        Ok(Self {
+            session_id,
+            domain: read_unicode_string(buffer, cb_domain as u16)?,
+            user_name: read_unicode_string(buffer, cb_user_name as u16)?,
+        })
+    }
+
+    /// Logon Info Version 2 size
+    fn v2_size(&self) -> usize {
+        LOGON_INFO_V2_HEADER_SIZE
+            + LOGON_INFO_V2_PAD_SIZE
+            + unicode_size(&self.domain)
+            + unicode_size(&self.user_name)
+    }
+}
+
+/// Server auto-reconnect cookie (ARC_SC_PRIVATE_PACKET, MS-RDPBCGR 2.2.4.2)
+///
+/// Stored by the client and later turned into an ARC_CS_PRIVATE_PACKET
+/// to reconnect to the same session without credentials.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArcScPrivatePacket {
+    /// Session logon ID
+    pub logon_id: u32,
+    /// Server-generated random bits used to derive the client verifier
+    pub arc_random_bits: [u8; 16],
+}
+
+impl ArcScPrivatePacket {
+    /// Create auto-reconnect cookie
+    pub fn new(logon_id: u32, arc_random_bits: [u8; 16]) -> Self {
+        Self {
+            logon_id,
+            arc_random_bits,
+        }
+    }
+
+    /// Packet size (28 bytes)
+    pub const SIZE: usize = ARC_PRIVATE_PACKET_SIZE;
+
+    /// Encode
+    pub fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        encode_arc_private_packet(buffer, self.logon_id, &self.arc_random_bits)
+    }
+
+    /// Decode
+    pub fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let (logon_id, arc_random_bits) = decode_arc_private_packet(buffer)?;
+        Ok(Self {
+            logon_id,
+            arc_random_bits,
+        })
+    }
+}
+
+/// Encode the auto-reconnect packet layout shared by the server and
+/// client cookies (cbLen, Version, LogonId, 16-byte random or verifier)
+pub(crate) fn encode_arc_private_packet(
+    buffer: &mut dyn Write,
+    logon_id: u32,
+    bits: &[u8; 16],
+) -> Result<()> {
+    buffer.write_u32::<LittleEndian>(ARC_PRIVATE_PACKET_SIZE as u32)?;
+    buffer.write_u32::<LittleEndian>(AUTO_RECONNECT_VERSION_1)?;
+    buffer.write_u32::<LittleEndian>(logon_id)?;
+    buffer.write_all(bits)?;
+    Ok(())
+}
+
+/// Decode the shared auto-reconnect packet layout into LogonId and the
+/// 16-byte random or verifier
+pub(crate) fn decode_arc_private_packet(buffer: &mut dyn Read) -> Result<(u32, [u8; 16])> {
+    let cb_len = buffer.read_u32::<LittleEndian>()? as usize;
+    if cb_len != ARC_PRIVATE_PACKET_SIZE {
+        return Err(PduError::InvalidLength {
+            expected: ARC_PRIVATE_PACKET_SIZE,
+            actual: cb_len,
+        });
+    }
+    let version = buffer.read_u32::<LittleEndian>()?;
+    if version != AUTO_RECONNECT_VERSION_1 {
+        return Err(PduError::ParseError(format!(
+            "Unsupported auto-reconnect version: {:#x}",
+            version
+        )));
+    }
+    let logon_id = buffer.read_u32::<LittleEndian>()?;
+    let mut bits = [0u8; 16];
+    buffer.read_exact(&mut bits)?;
+    Ok((logon_id, bits))
+}
+
+/// Logon errors notification (TS_LOGON_ERRORS_INFO, MS-RDPBCGR 2.2.10.1.1.4.1.1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogonErrorsInfo {
+    /// Error notification type (see `LogonErrorType`)
+    pub error_notification_type: u32,
+    /// Error notification data (see `LogonErrorData`) or session ID
+    pub error_notification_data: u32,
+}
+
+impl LogonErrorsInfo {
+    /// Create logon errors notification
+    pub fn new(error_type: LogonErrorType, error_data: u32) -> Self {
+        Self {
+            error_notification_type: error_type.as_u32(),
+            error_notification_data: error_data,
+        }
+    }
+
+    /// Structure size (8 bytes)
+    pub const SIZE: usize = 8;
+
+    /// Typed notification type, if known
+    pub fn error_type(&self) -> Option<LogonErrorType> {
+        LogonErrorType::from_u32(self.error_notification_type)
+    }
+
+    /// Typed notification data, if it is not a session ID
+    pub fn error_data(&self) -> Option<LogonErrorData> {
+        LogonErrorData::from_u32(self.error_notification_data)
+    }
+
+    /// Encode
+    pub fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_u32::<LittleEndian>(self.error_notification_type)?;
+        buffer.write_u32::<LittleEndian>(self.error_notification_data)?;
+        Ok(())
+    }
+
+    /// Decode
+    pub fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let error_notification_type = buffer.read_u32::<LittleEndian>()?;
+        let error_notification_data = buffer.read_u32::<LittleEndian>()?;
+        Ok(Self {
+            error_notification_type,
+            error_notification_data,
+        })
+    }
+}
+
+/// Logon Extended Info (TS_LOGON_INFO_EXTENDED, MS-RDPBCGR 2.2.10.1.1.4)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LogonExtendedInfo {
+    /// Auto-reconnect cookie
+    pub auto_reconnect: Option<ArcScPrivatePacket>,
+    /// Logon errors notification
+    pub logon_errors: Option<LogonErrorsInfo>,
+}
+
+impl LogonExtendedInfo {
+    /// FieldsPresent value for the populated fields
+    pub fn fields_present(&self) -> LogonExtendedFields {
+        let mut fields = LogonExtendedFields::empty();
+        fields.set(
+            LogonExtendedFields::AUTORECONNECT_COOKIE,
+            self.auto_reconnect.is_some(),
+        );
+        fields.set(
+            LogonExtendedFields::LOGON_ERRORS,
+            self.logon_errors.is_some(),
+        );
+        fields
+    }
+
+    /// Length field value (structure without padding)
+    fn length(&self) -> usize {
+        2 + 4
+            + self
+                .auto_reconnect
+                .as_ref()
+                .map_or(0, |_| 4 + ArcScPrivatePacket::SIZE)
+            + self
+                .logon_errors
+                .as_ref()
+                .map_or(0, |_| 4 + LogonErrorsInfo::SIZE)
+    }
+
+    /// Encode
+    pub fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_u16::<LittleEndian>(self.length() as u16)?;
+        buffer.write_u32::<LittleEndian>(self.fields_present().bits())?;
+
+        if let Some(auto_reconnect) = &self.auto_reconnect {
+            buffer.write_u32::<LittleEndian>(ArcScPrivatePacket::SIZE as u32)?;
+            auto_reconnect.encode(buffer)?;
+        }
+        if let Some(logon_errors) = &self.logon_errors {
+            buffer.write_u32::<LittleEndian>(LogonErrorsInfo::SIZE as u32)?;
+            logon_errors.encode(buffer)?;
+        }
+
+        buffer.write_all(&[0u8; LOGON_EXTENDED_PAD_SIZE])?;
+        Ok(())
+    }
+
+    /// Decode
+    pub fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let _length = buffer.read_u16::<LittleEndian>()?;
+        let fields = LogonExtendedFields::from_bits_retain(buffer.read_u32::<LittleEndian>()?);
+
+        let auto_reconnect = if fields.contains(LogonExtendedFields::AUTORECONNECT_COOKIE) {
+            let field = read_logon_field(buffer)?;
+            Some(ArcScPrivatePacket::decode(&mut field.as_slice())?)
+        } else {
+            None
+        };
+        let logon_errors = if fields.contains(LogonExtendedFields::LOGON_ERRORS) {
+            let field = read_logon_field(buffer)?;
+            Some(LogonErrorsInfo::decode(&mut field.as_slice())?)
+        } else {
+            None
+        };
+
+        let mut pad = [0u8; LOGON_EXTENDED_PAD_SIZE];
+        buffer.read_exact(&mut pad)?;
+
+        Ok(Self {
+            auto_reconnect,
+            logon_errors,
+        })
+    }
+
+    /// Return size (including padding)
+    pub fn size(&self) -> usize {
+        self.length() + LOGON_EXTENDED_PAD_SIZE
+    }
+}
+
+/// Read a TS_LOGON_INFO_FIELD body
+fn read_logon_field(buffer: &mut dyn Read) -> Result<Vec<u8>> {
+    let cb_field_data = buffer.read_u32::<LittleEndian>()? as usize;
+    if cb_field_data > ArcScPrivatePacket::SIZE {
+        return Err(PduError::InvalidLength {
+            expected: ArcScPrivatePacket::SIZE,
+            actual: cb_field_data,
+        });
+    }
+    let mut field = vec![0u8; cb_field_data];
+    buffer.read_exact(&mut field)?;
+    Ok(field)
+}
+
+/// Save Session Info PDU (MS-RDPBCGR 2.2.10.1)
+///
+/// Sent by the server after logon when the client set LOGONNOTIFY or
+/// LOGONERRORS in the Client Info PDU.
+///
+/// This PDU is sent within a Share Data Header (pdu_type2 = SaveSessionInfo)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SaveSessionInfoPdu {
+    /// Logon Info Version 1 (INFOTYPE_LOGON)
+    LogonV1(LogonInfo),
+    /// Logon Info Version 2 (INFOTYPE_LOGON_LONG)
+    LogonV2(LogonInfo),
+    /// Plain Notify (INFOTYPE_LOGON_PLAINNOTIFY)
+    PlainNotify,
+    /// Logon Extended Info (INFOTYPE_LOGON_EXTENDED_INFO)
+    LogonExtended(LogonExtendedInfo),
+}
+
+impl SaveSessionInfoPdu {
+    /// Get info type
+    pub fn info_type(&self) -> SaveSessionInfoType {
+        match self {
+            SaveSessionInfoPdu::LogonV1(_) => SaveSessionInfoType::Logon,
+            SaveSessionInfoPdu::LogonV2(_) => SaveSessionInfoType::LogonLong,
+            SaveSessionInfoPdu::PlainNotify => SaveSessionInfoType::PlainNotify,
+            SaveSessionInfoPdu::LogonExtended(_) => SaveSessionInfoType::LogonExtendedInfo,
+        }
+    }
+
+    /// Logged-on session, user name and domain (Logon Info v1/v2)
+    pub fn logon_info(&self) -> Option<&LogonInfo> {
+        match self {
+            SaveSessionInfoPdu::LogonV1(info) | SaveSessionInfoPdu::LogonV2(info) => Some(info),
+            _ => None,
+        }
+    }
+
+    /// Auto-reconnect cookie, if sent
+    pub fn auto_reconnect(&self) -> Option<&ArcScPrivatePacket> {
+        match self {
+            SaveSessionInfoPdu::LogonExtended(info) => info.auto_reconnect.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Logon errors notification, if sent
+    pub fn logon_errors(&self) -> Option<&LogonErrorsInfo> {
+        match self {
+            SaveSessionInfoPdu::LogonExtended(info) => info.logon_errors.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl Pdu for SaveSessionInfoPdu {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_u32::<LittleEndian>(self.info_type().as_u32())?;
+
+        match self {
+            SaveSessionInfoPdu::LogonV1(info) => info.encode_v1(buffer)?,
+            SaveSessionInfoPdu::LogonV2(info) => info.encode_v2(buffer)?,
+            SaveSessionInfoPdu::PlainNotify => buffer.write_all(&[0u8; PLAIN_NOTIFY_PAD_SIZE])?,
+            SaveSessionInfoPdu::LogonExtended(info) => info.encode(buffer)?,
+        }
+
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let info_type = buffer.read_u32::<LittleEndian>()?;
+        let info_type = SaveSessionInfoType::from_u32(info_type).ok_or_else(|| {
+            PduError::ParseError(format!("Invalid Save Session Info type: {:#x}", info_type))
+        })?;
+
+        match info_type {
+            SaveSessionInfoType::Logon => {
+                Ok(SaveSessionInfoPdu::LogonV1(LogonInfo::decode_v1(buffer)?))
+            }
+            SaveSessionInfoType::LogonLong => {
+                Ok(SaveSessionInfoPdu::LogonV2(LogonInfo::decode_v2(buffer)?))
+            }
+            SaveSessionInfoType::PlainNotify => {
+                let mut pad = [0u8; PLAIN_NOTIFY_PAD_SIZE];
+                buffer.read_exact(&mut pad)?;
+                Ok(SaveSessionInfoPdu::PlainNotify)
+            }
+            SaveSessionInfoType::LogonExtendedInfo => Ok(SaveSessionInfoPdu::LogonExtended(
+                LogonExtendedInfo::decode(buffer)?,
+            )),
+        }
+    }
+
+    fn size(&self) -> usize {
+        4 + match self {
+            SaveSessionInfoPdu::LogonV1(_) => LogonInfo::V1_SIZE,
+            SaveSessionInfoPdu::LogonV2(info) => info.v2_size(),
+            SaveSessionInfoPdu::PlainNotify => PLAIN_NOTIFY_PAD_SIZE,
+            SaveSessionInfoPdu::LogonExtended(info) => info.size(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(pdu: &SaveSessionInfoPdu) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        pdu.encode(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), pdu.size());
+
+        let decoded = SaveSessionInfoPdu::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(&decoded, pdu);
+        buffer
+    }
+
+    #[test]
+    fn test_save_session_info_logon_v1() {
+        let pdu = SaveSessionInfoPdu::LogonV1(LogonInfo::new(3, "CORP", "alice"));
+
+        let buffer = roundtrip(&pdu);
+        assert_eq!(&buffer[..8], &[0, 0, 0, 0, 0x0A, 0, 0, 0]);
+        assert_eq!(&buffer[576..], &[3, 0, 0, 0]);
+        assert_eq!(pdu.logon_info().unwrap().user_name, "alice");
+    }
+
+    #[test]
+    fn test_save_session_info_logon_v2() {
+        let pdu = SaveSessionInfoPdu::LogonV2(LogonInfo::new(7, "CONTOSO", "administrator"));
+
+        let buffer = roundtrip(&pdu);
+        assert_eq!(&buffer[4..10], &[0x01, 0x00, 0x12, 0x00, 0x00, 0x00]);
+        assert_eq!(pdu.logon_info().unwrap().session_id, 7);
+
+        let too_long = SaveSessionInfoPdu::LogonV2(LogonInfo::new(1, &"D".repeat(26), "u"));
+        assert!(too_long.encode(&mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_save_session_info_plain_notify() {
+        let buffer = roundtrip(&SaveSessionInfoPdu::PlainNotify);
+        assert_eq!(buffer.len(), 580);
+    }
+
+    #[test]
+    fn test_save_session_info_logon_extended() {
+        let pdu = SaveSessionInfoPdu::LogonExtended(LogonExtendedInfo {
+            auto_reconnect: Some(ArcScPrivatePacket::new(0x1234, [0xA5; 16])),
+            logon_errors: Some(LogonErrorsInfo::new(
+                LogonErrorType::SessionContinue,
+                LogonErrorData::Warning.as_u32(),
+            )),
+        });
+
+        let buffer = roundtrip(&pdu);
+        // Length, FieldsPresent, cbFieldData, cbLen, Version
+        assert_eq!(
+            &buffer[4..22],
+            &[
+                0x32, 0x00, 0x03, 0x00, 0x00, 0x00, 0x1C, 0x00, 0x00, 0x00, 0x1C, 0x00, 0x00, 0x00,
+                0x01, 0x00, 0x00, 0x00,
+            ]
+        );
+
+        assert_eq!(pdu.auto_reconnect().unwrap().logon_id, 0x1234);
+        let errors = pdu.logon_errors().unwrap();
+        assert_eq!(errors.error_type(), Some(LogonErrorType::SessionContinue));
+        assert_eq!(errors.error_data(), Some(LogonErrorData::Warning));
+    }
+}
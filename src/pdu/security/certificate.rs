@@ -0,0 +1,962 @@
+use crate::codec::{BerReader, BerTag};
+use crate::pdu::{Pdu, PduError, Result};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use md5::{Digest, Md5};
+use num_bigint::BigUint;
+use std::io::{Read, Write};
+
+/// CERT_CHAIN_VERSION_1 - Proprietary certificate
+pub const CERT_CHAIN_VERSION_1: u32 = 0x0000_0001;
+
+/// CERT_CHAIN_VERSION_2 - X.509 certificate chain
+pub const CERT_CHAIN_VERSION_2: u32 = 0x0000_0002;
+
+/// Certificate is temporary (high bit of dwVersion)
+const CERT_TEMPORARY: u32 = 0x8000_0000;
+
+/// SIGNATURE_ALG_RSA
+const SIGNATURE_ALG_RSA: u32 = 0x0000_0001;
+
+/// KEY_EXCHANGE_ALG_RSA
+const KEY_EXCHANGE_ALG_RSA: u32 = 0x0000_0001;
+
+/// BB_RSA_KEY_BLOB
+const BB_RSA_KEY_BLOB: u16 = 0x0006;
+
+/// BB_RSA_SIGNATURE_BLOB
+const BB_RSA_SIGNATURE_BLOB: u16 = 0x0008;
+
+/// RSA1 magic of the public key blob
+const RSA1_MAGIC: u32 = 0x3141_5352;
+
+/// Zero padding appended to RSA values on the wire
+const RSA_PADDING_LENGTH: usize = 8;
+
+/// Terminal Services signing key length (bytes)
+const TSSK_LENGTH: usize = 64;
+
+/// Terminal Services signing key public exponent (MS-RDPBCGR 5.3.3.1.1)
+const TSSK_PUBLIC_EXPONENT: u32 = 0xC088_7B5B;
+
+/// Terminal Services signing key modulus, little-endian (MS-RDPBCGR 5.3.3.1.1)
+const TSSK_MODULUS: [u8; TSSK_LENGTH] = [
+    0x3d, 0x3a, 0x5e, 0xbd, 0x72, 0x43, 0x3e, 0xc9, 0x4d, 0xbb, 0xc1, 0x1e, 0x4a, 0xba, 0x5f, 0xcb,
+    0x3e, 0x88, 0x20, 0x87, 0xef, 0xf5, 0xc1, 0xe2, 0xd7, 0xb7, 0x6b, 0x9a, 0xf2, 0x52, 0x45, 0x95,
+    0xce, 0x63, 0x65, 0x6b, 0x58, 0x3a, 0xfe, 0xef, 0x7c, 0xe7, 0xbf, 0xfe, 0x3d, 0xf6, 0x5c, 0x7d,
+    0x6c, 0x5e, 0x06, 0x09, 0x1a, 0xf5, 0x61, 0xbb, 0x20, 0x93, 0x09, 0x5f, 0x05, 0x6d, 0xea, 0x87,
+];
+
+/// Terminal Services signing key private exponent, little-endian (MS-RDPBCGR 5.3.3.1.1)
+const TSSK_PRIVATE_EXPONENT: [u8; TSSK_LENGTH] = [
+    0x87, 0xa7, 0x19, 0x32, 0xda, 0x11, 0x87, 0x55, 0x58, 0x00, 0x16, 0x16, 0x25, 0x65, 0x68, 0xf8,
+    0x24, 0x3e, 0xe6, 0xfa, 0xe9, 0x67, 0x49, 0x94, 0xcf, 0x92, 0xcc, 0x33, 0x99, 0xe8, 0x08, 0x60,
+    0x17, 0x9a, 0x12, 0x9f, 0x24, 0xdd, 0xb1, 0x24, 0x99, 0xc7, 0x3a, 0xb8, 0x0a, 0x7b, 0x0d, 0xdd,
+    0x35, 0x07, 0x79, 0x17, 0x0b, 0x51, 0x9b, 0xb3, 0xc7, 0x10, 0x01, 0x13, 0xe7, 0x3f, 0xf3, 0x5f,
+];
+
+/// rsaEncryption (1.2.840.113549.1.1.1)
+const OID_RSA_ENCRYPTION: &str = "1.2.840.113549.1.1.1";
+
+/// DER tags not covered by BerTag
+const TAG_BIT_STRING: u8 = 0x03;
+const TAG_OID: u8 = 0x06;
+const TAG_BMP_STRING: u8 = 0x1E;
+const TAG_UTC_TIME: u8 = 0x17;
+const TAG_GENERALIZED_TIME: u8 = 0x18;
+const TAG_SET: u8 = 0x31;
+const TAG_EXPLICIT_VERSION: u8 = 0xA0;
+
+/// Raw RSA on little-endian integers (no padding, MS-RDPBCGR 5.3.4.1)
+fn rsa_raw(input: &[u8], exponent: &BigUint, modulus: &BigUint, length: usize) -> Vec<u8> {
+    let result = BigUint::from_bytes_le(input).modpow(exponent, modulus);
+    let mut output = result.to_bytes_le();
+    output.resize(length, 0);
+    output
+}
+
+/// RSA public key
+///
+/// Encodes as RSA_PUBLIC_KEY (MS-RDPBCGR 2.2.1.4.3.1.1.1).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RsaPublicKey {
+    /// Public exponent
+    pub exponent: u32,
+    /// Modulus, big-endian without leading zeros
+    pub modulus: Vec<u8>,
+}
+
+impl RsaPublicKey {
+    /// Create public key from a big-endian modulus
+    ///
+    /// Fails for a zero modulus or exponent.
+    pub fn new(exponent: u32, modulus: &[u8]) -> Result<Self> {
+        let key = Self {
+            exponent,
+            modulus: strip_leading_zeros(modulus).to_vec(),
+        };
+        key.validate()?;
+        Ok(key)
+    }
+
+    /// Modulus length in bits
+    pub fn bit_len(&self) -> usize {
+        match self.modulus.first() {
+            Some(&top) => self.modulus.len() * 8 - top.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    /// Modulus length in bytes
+    pub fn modulus_len(&self) -> usize {
+        self.modulus.len()
+    }
+
+    /// Encrypt a little-endian value (e.g. the client random)
+    ///
+    /// Returns the little-endian ciphertext, modulus_len() bytes long.
+    pub fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>> {
+        self.validate()?;
+        Ok(rsa_raw(
+            data,
+            &BigUint::from(self.exponent),
+            &BigUint::from_bytes_be(&self.modulus),
+            self.modulus_len(),
+        ))
+    }
+
+    /// Reject keys that cannot encrypt (modpow panics on a zero modulus)
+    fn validate(&self) -> Result<()> {
+        if self.modulus.iter().all(|&b| b == 0) {
+            return Err(PduError::ParseError("Zero RSA modulus".to_string()));
+        }
+        if self.exponent == 0 {
+            return Err(PduError::ParseError("Zero RSA exponent".to_string()));
+        }
+        Ok(())
+    }
+}
+
+impl Pdu for RsaPublicKey {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        let key_len = self.modulus_len() + RSA_PADDING_LENGTH;
+        buffer.write_u32::<LittleEndian>(RSA1_MAGIC)?;
+        buffer.write_u32::<LittleEndian>(key_len as u32)?;
+        buffer.write_u32::<LittleEndian>((self.modulus_len() * 8) as u32)?;
+        buffer.write_u32::<LittleEndian>(self.modulus_len().saturating_sub(1) as u32)?;
+        buffer.write_u32::<LittleEndian>(self.exponent)?;
+
+        let mut modulus = self.modulus.clone();
+        modulus.reverse();
+        buffer.write_all(&modulus)?;
+        buffer.write_all(&[0u8; RSA_PADDING_LENGTH])?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let magic = buffer.read_u32::<LittleEndian>()?;
+        if magic != RSA1_MAGIC {
+            return Err(PduError::InvalidHeader(format!(
+                "Invalid RSA public key magic: {:#010x}",
+                magic
+            )));
+        }
+
+        let key_len = buffer.read_u32::<LittleEndian>()? as usize;
+        let bit_len = buffer.read_u32::<LittleEndian>()? as usize;
+        let _data_len = buffer.read_u32::<LittleEndian>()?;
+        let exponent = buffer.read_u32::<LittleEndian>()?;
+
+        let modulus_len = bit_len.div_ceil(8);
+        if modulus_len == 0 || modulus_len > key_len {
+            return Err(PduError::InvalidLength {
+                expected: key_len,
+                actual: modulus_len,
+            });
+        }
+
+        // key_len is untrusted, so read through take() instead of allocating it up front
+        let mut modulus = Vec::new();
+        buffer.take(key_len as u64).read_to_end(&mut modulus)?;
+        if modulus.len() != key_len {
+            return Err(PduError::InsufficientData {
+                needed: key_len,
+                available: modulus.len(),
+            });
+        }
+        modulus.truncate(modulus_len);
+        modulus.reverse();
+        Self::new(exponent, &modulus)
+    }
+
+    fn size(&self) -> usize {
+        20 + self.modulus_len() + RSA_PADDING_LENGTH
+    }
+}
+
+/// Proprietary Certificate (PROPRIETARYSERVERCERTIFICATE, MS-RDPBCGR 2.2.1.4.3.1.1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProprietaryCertificate {
+    /// Whether dwVersion carries the temporary flag
+    pub temporary: bool,
+    /// Server public key
+    pub public_key: RsaPublicKey,
+    /// Signature blob (little-endian signature followed by 8 zero bytes)
+    pub signature: Vec<u8>,
+    /// dwVersion through PublicKeyBlob as received, the data covered by the signature
+    signed_data: Vec<u8>,
+}
+
+impl ProprietaryCertificate {
+    /// Create certificate signed with the Terminal Services signing key
+    pub fn new(public_key: RsaPublicKey) -> Self {
+        let signed_data = encode_signed_data(false, &public_key);
+        let padded_hash = padded_hash(&signed_data);
+        let mut signature = rsa_raw(
+            &padded_hash,
+            &BigUint::from_bytes_le(&TSSK_PRIVATE_EXPONENT),
+            &BigUint::from_bytes_le(&TSSK_MODULUS),
+            TSSK_LENGTH,
+        );
+        signature.extend_from_slice(&[0u8; RSA_PADDING_LENGTH]);
+
+        Self {
+            temporary: false,
+            public_key,
+            signature,
+            signed_data,
+        }
+    }
+
+    /// Verify the signature against the Terminal Services signing key (MS-RDPBCGR 5.3.3.1.2)
+    pub fn verify_signature(&self) -> bool {
+        if self.signature.len() < TSSK_LENGTH {
+            return false;
+        }
+
+        let decrypted = rsa_raw(
+            &self.signature[..TSSK_LENGTH],
+            &BigUint::from(TSSK_PUBLIC_EXPONENT),
+            &BigUint::from_bytes_le(&TSSK_MODULUS),
+            TSSK_LENGTH,
+        );
+        decrypted == padded_hash(&self.signed_data)
+    }
+
+    /// Encode everything after dwVersion
+    fn encode_body(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_u32::<LittleEndian>(SIGNATURE_ALG_RSA)?;
+        buffer.write_u32::<LittleEndian>(KEY_EXCHANGE_ALG_RSA)?;
+        buffer.write_u16::<LittleEndian>(BB_RSA_KEY_BLOB)?;
+        buffer.write_u16::<LittleEndian>(self.public_key.size() as u16)?;
+        self.public_key.encode(buffer)?;
+        buffer.write_u16::<LittleEndian>(BB_RSA_SIGNATURE_BLOB)?;
+        buffer.write_u16::<LittleEndian>(self.signature.len() as u16)?;
+        buffer.write_all(&self.signature)?;
+        Ok(())
+    }
+
+    /// Decode everything after dwVersion
+    fn decode_body(buffer: &mut dyn Read, temporary: bool) -> Result<Self> {
+        let mut header = [0u8; 12];
+        buffer.read_exact(&mut header)?;
+        let mut fields = header.as_slice();
+
+        let sig_alg_id = fields.read_u32::<LittleEndian>()?;
+        let key_alg_id = fields.read_u32::<LittleEndian>()?;
+        if sig_alg_id != SIGNATURE_ALG_RSA || key_alg_id != KEY_EXCHANGE_ALG_RSA {
+            return Err(PduError::ParseError(format!(
+                "Unsupported proprietary certificate algorithms: sig={}, key={}",
+                sig_alg_id, key_alg_id
+            )));
+        }
+
+        let blob_type = fields.read_u16::<LittleEndian>()?;
+        if blob_type != BB_RSA_KEY_BLOB {
+            return Err(PduError::ParseError(format!(
+                "Invalid public key blob type: {:#06x}",
+                blob_type
+            )));
+        }
+        let blob_len = fields.read_u16::<LittleEndian>()? as usize;
+        let mut blob = vec![0u8; blob_len];
+        buffer.read_exact(&mut blob)?;
+        // The blob holds exactly the header and the declared keylen bytes
+        let mut reader = blob.as_slice();
+        let public_key = RsaPublicKey::decode(&mut reader)?;
+        if !reader.is_empty() {
+            return Err(PduError::InvalidLength {
+                expected: blob_len - reader.len(),
+                actual: blob_len,
+            });
+        }
+
+        let blob_type = buffer.read_u16::<LittleEndian>()?;
+        if blob_type != BB_RSA_SIGNATURE_BLOB {
+            return Err(PduError::ParseError(format!(
+                "Invalid signature blob type: {:#06x}",
+                blob_type
+            )));
+        }
+        let signature_len = buffer.read_u16::<LittleEndian>()? as usize;
+        let mut signature = vec![0u8; signature_len];
+        buffer.read_exact(&mut signature)?;
+
+        let mut signed_data = Vec::with_capacity(4 + header.len() + blob.len());
+        signed_data
+            .extend_from_slice(&chain_version(CERT_CHAIN_VERSION_1, temporary).to_le_bytes());
+        signed_data.extend_from_slice(&header);
+        signed_data.extend_from_slice(&blob);
+
+        Ok(Self {
+            temporary,
+            public_key,
+            signature,
+            signed_data,
+        })
+    }
+
+    fn body_size(&self) -> usize {
+        12 + self.public_key.size() + 4 + self.signature.len()
+    }
+}
+
+/// Canonical dwVersion through PublicKeyBlob for a freshly signed certificate
+fn encode_signed_data(temporary: bool, public_key: &RsaPublicKey) -> Vec<u8> {
+    let mut data = Vec::with_capacity(16 + public_key.size());
+    data.extend_from_slice(&chain_version(CERT_CHAIN_VERSION_1, temporary).to_le_bytes());
+    data.extend_from_slice(&SIGNATURE_ALG_RSA.to_le_bytes());
+    data.extend_from_slice(&KEY_EXCHANGE_ALG_RSA.to_le_bytes());
+    data.extend_from_slice(&BB_RSA_KEY_BLOB.to_le_bytes());
+    data.extend_from_slice(&(public_key.size() as u16).to_le_bytes());
+    // Vec writes cannot fail
+    let _ = public_key.encode(&mut data);
+    data
+}
+
+/// dwVersion with the temporary flag applied
+fn chain_version(version: u32, temporary: bool) -> u32 {
+    if temporary {
+        version | CERT_TEMPORARY
+    } else {
+        version
+    }
+}
+
+/// MD5 hash padded for the Terminal Services signature, little-endian
+fn padded_hash(data: &[u8]) -> [u8; TSSK_LENGTH] {
+    let mut padded = [0xFFu8; TSSK_LENGTH];
+    padded[..16].copy_from_slice(&Md5::digest(data));
+    padded[16] = 0x00;
+    padded[62] = 0x01;
+    padded[63] = 0x00;
+    padded
+}
+
+/// Parsed X.509 certificate metadata
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct X509Certificate {
+    /// Raw DER encoding
+    pub der: Vec<u8>,
+    /// Serial number, big-endian
+    pub serial_number: Vec<u8>,
+    /// Signature algorithm OID
+    pub signature_algorithm: String,
+    /// Issuer distinguished name, in encoded order ("C=.., O=.., CN=..")
+    pub issuer: String,
+    /// Subject distinguished name, in encoded order
+    pub subject: String,
+    /// Start of validity period (UTCTime or GeneralizedTime text)
+    pub not_before: String,
+    /// End of validity period (UTCTime or GeneralizedTime text)
+    pub not_after: String,
+    /// Public key algorithm OID
+    pub public_key_algorithm: String,
+    /// subjectPublicKey contents without the unused-bits octet
+    pub subject_public_key: Vec<u8>,
+    /// RSA public key (None for non-RSA keys)
+    pub public_key: Option<RsaPublicKey>,
+}
+
+impl X509Certificate {
+    /// Parse a DER encoded certificate (RFC 5280)
+    pub fn from_der(der: &[u8]) -> Result<Self> {
+        let mut reader = BerReader::new(der);
+        let certificate = read_expected(&mut reader, BerTag::Sequence as u8)?;
+        let mut reader = BerReader::new(&certificate);
+        let tbs = read_expected(&mut reader, BerTag::Sequence as u8)?;
+
+        let mut reader = BerReader::new(&tbs);
+        if tbs.get(reader.position()) == Some(&TAG_EXPLICIT_VERSION) {
+            read_element(&mut reader)?;
+        }
+
+        let serial_number = read_expected(&mut reader, BerTag::Integer as u8)?;
+        let serial_number = strip_leading_zeros(&serial_number).to_vec();
+        let signature_algorithm = read_algorithm(&mut reader)?;
+        let issuer = read_name(&read_expected(&mut reader, BerTag::Sequence as u8)?)?;
+
+        let validity = read_expected(&mut reader, BerTag::Sequence as u8)?;
+        let mut validity_reader = BerReader::new(&validity);
+        let not_before = read_time(&mut validity_reader)?;
+        let not_after = read_time(&mut validity_reader)?;
+
+        let subject = read_name(&read_expected(&mut reader, BerTag::Sequence as u8)?)?;
+
+        let key_info = read_expected(&mut reader, BerTag::Sequence as u8)?;
+        let mut key_reader = BerReader::new(&key_info);
+        let public_key_algorithm = read_algorithm(&mut key_reader)?;
+        let key_bits = read_expected(&mut key_reader, TAG_BIT_STRING)?;
+        let public_key = if public_key_algorithm == OID_RSA_ENCRYPTION {
+            Some(read_rsa_public_key(&key_bits)?)
+        } else {
+            None
+        };
+        let subject_public_key = key_bits.get(1..).unwrap_or_default().to_vec();
+
+        Ok(Self {
+            der: der.to_vec(),
+            serial_number,
+            signature_algorithm,
+            issuer,
+            subject,
+            not_before,
+            not_after,
+            public_key_algorithm,
+            subject_public_key,
+            public_key,
+        })
+    }
+}
+
+/// X.509 Certificate Chain (SERVER_CERTIFICATE with CERT_CHAIN_VERSION_2)
+///
+/// The last certificate in the chain belongs to the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct X509CertificateChain {
+    /// Whether dwVersion carries the temporary flag
+    pub temporary: bool,
+    /// Certificates, root first
+    pub certificates: Vec<X509Certificate>,
+}
+
+impl X509CertificateChain {
+    /// Build chain from DER encoded certificates, root first
+    pub fn new(certificates: &[Vec<u8>]) -> Result<Self> {
+        Ok(Self {
+            temporary: false,
+            certificates: certificates
+                .iter()
+                .map(|der| X509Certificate::from_der(der))
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// Server (leaf) certificate
+    pub fn server_certificate(&self) -> Option<&X509Certificate> {
+        self.certificates.last()
+    }
+
+    fn encode_body(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_u32::<LittleEndian>(self.certificates.len() as u32)?;
+        for certificate in &self.certificates {
+            buffer.write_u32::<LittleEndian>(certificate.der.len() as u32)?;
+            buffer.write_all(&certificate.der)?;
+        }
+        buffer.write_all(&vec![0u8; self.padding_len()])?;
+        Ok(())
+    }
+
+    fn decode_body(buffer: &mut dyn Read, temporary: bool) -> Result<Self> {
+        let count = buffer.read_u32::<LittleEndian>()? as usize;
+        let mut certificates = Vec::new();
+        for _ in 0..count {
+            let length = buffer.read_u32::<LittleEndian>()? as usize;
+            let mut der = Vec::new();
+            buffer.take(length as u64).read_to_end(&mut der)?;
+            if der.len() != length {
+                return Err(PduError::InsufficientData {
+                    needed: length,
+                    available: der.len(),
+                });
+            }
+            certificates.push(X509Certificate::from_der(&der)?);
+        }
+
+        // Trailing padding is not validated
+        Ok(Self {
+            temporary,
+            certificates,
+        })
+    }
+
+    /// Padding after the certificate blobs (8 + 4 * NumCertBlobs)
+    fn padding_len(&self) -> usize {
+        8 + 4 * self.certificates.len()
+    }
+
+    fn body_size(&self) -> usize {
+        4 + self
+            .certificates
+            .iter()
+            .map(|certificate| 4 + certificate.der.len())
+            .sum::<usize>()
+            + self.padding_len()
+    }
+}
+
+/// Server Certificate (SERVER_CERTIFICATE, MS-RDPBCGR 2.2.1.4.3.1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerCertificate {
+    /// CERT_CHAIN_VERSION_1
+    Proprietary(ProprietaryCertificate),
+    /// CERT_CHAIN_VERSION_2
+    X509Chain(X509CertificateChain),
+}
+
+impl ServerCertificate {
+    /// Whether the certificate is temporary
+    pub fn is_temporary(&self) -> bool {
+        match self {
+            ServerCertificate::Proprietary(certificate) => certificate.temporary,
+            ServerCertificate::X509Chain(chain) => chain.temporary,
+        }
+    }
+
+    /// Public key used to encrypt the client random
+    pub fn public_key(&self) -> Result<&RsaPublicKey> {
+        match self {
+            ServerCertificate::Proprietary(certificate) => Ok(&certificate.public_key),
+            ServerCertificate::X509Chain(chain) => chain
+                .server_certificate()
+                .ok_or_else(|| PduError::ParseError("Empty X.509 certificate chain".to_string()))?
+                .public_key
+                .as_ref()
+                .ok_or_else(|| {
+                    PduError::ParseError("Server certificate has no RSA public key".to_string())
+                }),
+        }
+    }
+
+    fn version(&self) -> u32 {
+        let version = match self {
+            ServerCertificate::Proprietary(_) => CERT_CHAIN_VERSION_1,
+            ServerCertificate::X509Chain(_) => CERT_CHAIN_VERSION_2,
+        };
+        chain_version(version, self.is_temporary())
+    }
+}
+
+impl Pdu for ServerCertificate {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_u32::<LittleEndian>(self.version())?;
+        match self {
+            ServerCertificate::Proprietary(certificate) => certificate.encode_body(buffer),
+            ServerCertificate::X509Chain(chain) => chain.encode_body(buffer),
+        }
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let version = buffer.read_u32::<LittleEndian>()?;
+        let temporary = version & CERT_TEMPORARY != 0;
+
+        match version & !CERT_TEMPORARY {
+            CERT_CHAIN_VERSION_1 => Ok(ServerCertificate::Proprietary(
+                ProprietaryCertificate::decode_body(buffer, temporary)?,
+            )),
+            CERT_CHAIN_VERSION_2 => Ok(ServerCertificate::X509Chain(
+                X509CertificateChain::decode_body(buffer, temporary)?,
+            )),
+            other => Err(PduError::ParseError(format!(
+                "Unknown certificate chain version: {}",
+                other
+            ))),
+        }
+    }
+
+    fn size(&self) -> usize {
+        4 + match self {
+            ServerCertificate::Proprietary(certificate) => certificate.body_size(),
+            ServerCertificate::X509Chain(chain) => chain.body_size(),
+        }
+    }
+}
+
+/// Read a DER element, returning tag and contents
+fn read_element(reader: &mut BerReader<'_>) -> Result<(u8, Vec<u8>)> {
+    let tag = reader.read_tag()?;
+    let length = reader.read_length()?;
+    Ok((tag, reader.read_bytes(length)?))
+}
+
+/// Read a DER element with the expected tag, returning its contents
+fn read_expected(reader: &mut BerReader<'_>, expected: u8) -> Result<Vec<u8>> {
+    let (tag, contents) = read_element(reader)?;
+    if tag != expected {
+        return Err(PduError::ParseError(format!(
+            "Expected DER tag 0x{:02x}, got 0x{:02x}",
+            expected, tag
+        )));
+    }
+    Ok(contents)
+}
+
+/// Read AlgorithmIdentifier, returning the algorithm OID
+fn read_algorithm(reader: &mut BerReader<'_>) -> Result<String> {
+    let algorithm = read_expected(reader, BerTag::Sequence as u8)?;
+    let mut reader = BerReader::new(&algorithm);
+    oid_to_string(&read_expected(&mut reader, TAG_OID)?)
+}
+
+/// Read UTCTime or GeneralizedTime as text
+fn read_time(reader: &mut BerReader<'_>) -> Result<String> {
+    let (tag, contents) = read_element(reader)?;
+    if tag != TAG_UTC_TIME && tag != TAG_GENERALIZED_TIME {
+        return Err(PduError::ParseError(format!(
+            "Expected time, got DER tag 0x{:02x}",
+            tag
+        )));
+    }
+    Ok(String::from_utf8_lossy(&contents).into_owned())
+}
+
+/// Read RSAPublicKey from the subjectPublicKey BIT STRING (RFC 8017 A.1.1)
+fn read_rsa_public_key(bits: &[u8]) -> Result<RsaPublicKey> {
+    let (&unused_bits, key) = bits
+        .split_first()
+        .ok_or_else(|| PduError::ParseError("Empty public key".to_string()))?;
+    if unused_bits != 0 {
+        return Err(PduError::ParseError(format!(
+            "Invalid public key unused bits: {}",
+            unused_bits
+        )));
+    }
+
+    let mut reader = BerReader::new(key);
+    let sequence = read_expected(&mut reader, BerTag::Sequence as u8)?;
+    let mut reader = BerReader::new(&sequence);
+    let modulus = read_expected(&mut reader, BerTag::Integer as u8)?;
+    let exponent = read_expected(&mut reader, BerTag::Integer as u8)?;
+
+    let exponent = strip_leading_zeros(&exponent);
+    if exponent.is_empty() || exponent.len() > 4 {
+        return Err(PduError::ParseError(format!(
+            "Unsupported RSA exponent length: {}",
+            exponent.len()
+        )));
+    }
+    let exponent = exponent
+        .iter()
+        .fold(0u32, |value, &b| (value << 8) | b as u32);
+    RsaPublicKey::new(exponent, &modulus)
+}
+
+/// Format a Name as "type=value" pairs joined by ", "
+fn read_name(name: &[u8]) -> Result<String> {
+    let mut parts = Vec::new();
+    let mut reader = BerReader::new(name);
+    while reader.remaining() > 0 {
+        let rdn = read_expected(&mut reader, TAG_SET)?;
+        let mut rdn_reader = BerReader::new(&rdn);
+        while rdn_reader.remaining() > 0 {
+            let attribute = read_expected(&mut rdn_reader, BerTag::Sequence as u8)?;
+            let mut attribute_reader = BerReader::new(&attribute);
+            let oid = oid_to_string(&read_expected(&mut attribute_reader, TAG_OID)?)?;
+            let (tag, value) = read_element(&mut attribute_reader)?;
+            parts.push(format!(
+                "{}={}",
+                attribute_name(&oid),
+                directory_string(tag, &value)
+            ));
+        }
+    }
+    Ok(parts.join(", "))
+}
+
+/// Short name for common attribute types, OID otherwise
+fn attribute_name(oid: &str) -> &str {
+    match oid {
+        "2.5.4.3" => "CN",
+        "2.5.4.6" => "C",
+        "2.5.4.7" => "L",
+        "2.5.4.8" => "ST",
+        "2.5.4.10" => "O",
+        "2.5.4.11" => "OU",
+        "0.9.2342.19200300.100.1.25" => "DC",
+        "1.2.840.113549.1.9.1" => "emailAddress",
+        _ => oid,
+    }
+}
+
+/// Decode DirectoryString value (BMPString is UTF-16BE, others are treated as UTF-8)
+fn directory_string(tag: u8, value: &[u8]) -> String {
+    match tag {
+        TAG_BMP_STRING => {
+            let units: Vec<u16> = value
+                .chunks_exact(2)
+                .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        _ => String::from_utf8_lossy(value).into_owned(),
+    }
+}
+
+/// Decode OBJECT IDENTIFIER to dotted notation
+fn oid_to_string(bytes: &[u8]) -> Result<String> {
+    let mut arcs: Vec<u64> = Vec::new();
+    let mut value: u64 = 0;
+    let mut pending = false;
+
+    for &b in bytes {
+        if value > u64::MAX >> 7 {
+            return Err(PduError::ParseError("OID arc too large".to_string()));
+        }
+        value = (value << 7) | (b & 0x7F) as u64;
+        pending = b & 0x80 != 0;
+        if !pending {
+            if arcs.is_empty() {
+                let first = (value / 40).min(2);
+                arcs.push(first);
+                arcs.push(value - first * 40);
+            } else {
+                arcs.push(value);
+            }
+            value = 0;
+        }
+    }
+
+    if arcs.is_empty() || pending {
+        return Err(PduError::ParseError("Invalid OID encoding".to_string()));
+    }
+
+    Ok(arcs
+        .iter()
+        .map(|arc| arc.to_string())
+        .collect::<Vec<_>>()
+        .join("."))
+}
+
+fn strip_leading_zeros(value: &[u8]) -> &[u8] {
+    let start = value.iter().position(|&b| b != 0).unwrap_or(value.len());
+    &value[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Self-signed 512-bit RSA certificate (C=KR, O=PentaRDP, CN=rdp.test)
+    const TEST_CERTIFICATE: &str = concat!(
+        "308201ab30820155a00302010202021234300d06092a864886f70d01010b05003033310b3009060355040613",
+        "024b523111300f060355040a0c0850656e74615244503111300f06035504030c087264702e74657374301e17",
+        "0d3236313031363230333034395a170d3237313031363230333034395a3033310b3009060355040613024b52",
+        "3111300f060355040a0c0850656e74615244503111300f06035504030c087264702e74657374305c300d0609",
+        "2a864886f70d0101010500034b003048024100d04665436f4ef741502eedb6a7558c1397dab40818d33f288c",
+        "c590761788399cbc78594c60207abf3e8bc68c40f0998b3dbd6b073c8de7b263bfcf26778469310203010001",
+        "a3533051301d0603551d0e04160414623a7b339ac47cee09fbf0745aaa54b785742f24301f0603551d230418",
+        "30168014623a7b339ac47cee09fbf0745aaa54b785742f24300f0603551d130101ff040530030101ff300d06",
+        "092a864886f70d01010b05000341000914f9e5a50ba0ce73a0edda021654f0da52e4987337fd04d8bb04f1bf",
+        "371893e91cce6c07918c3a21a175dbb15547ecdc8a73943aac774cf6383c922b32b17b",
+    );
+
+    fn terminal_services_key() -> RsaPublicKey {
+        let mut modulus = TSSK_MODULUS;
+        modulus.reverse();
+        RsaPublicKey::new(TSSK_PUBLIC_EXPONENT, &modulus).unwrap()
+    }
+
+    #[test]
+    fn test_proprietary_certificate() {
+        let certificate = ProprietaryCertificate::new(terminal_services_key());
+        assert_eq!(
+            hex::encode(&certificate.signature[..TSSK_LENGTH]),
+            concat!(
+                "3fa24c424305b3de3176e184b1db65988af76ec41bf063ed43311435a8bf3a9f",
+                "bde90479bfc0487bcb25940dec1656f6cff98e3ae14edef6f0d63b9944160465"
+            )
+        );
+        assert!(certificate.verify_signature());
+
+        let certificate = ServerCertificate::Proprietary(certificate);
+        let mut buffer = Vec::new();
+        certificate.encode(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), certificate.size());
+        assert_eq!(buffer.len(), 184);
+        assert_eq!(&buffer[..4], &[0x01, 0x00, 0x00, 0x00]);
+        assert_eq!(&buffer[12..16], &[0x06, 0x00, 0x5C, 0x00]);
+        assert_eq!(&buffer[16..20], b"RSA1");
+
+        let decoded = ServerCertificate::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded, certificate);
+        assert_eq!(decoded.public_key().unwrap().bit_len(), 512);
+        assert_eq!(decoded.public_key().unwrap().exponent, TSSK_PUBLIC_EXPONENT);
+
+        // Tampered modulus no longer matches the signature
+        buffer[60] ^= 0x01;
+        let ServerCertificate::Proprietary(tampered) =
+            ServerCertificate::decode(&mut buffer.as_slice()).unwrap()
+        else {
+            panic!("expected proprietary certificate");
+        };
+        assert!(!tampered.verify_signature());
+    }
+
+    #[test]
+    fn test_x509_certificate_chain() {
+        let der = hex::decode(TEST_CERTIFICATE).unwrap();
+        let mut chain = X509CertificateChain::new(std::slice::from_ref(&der)).unwrap();
+        chain.temporary = true;
+        let certificate = ServerCertificate::X509Chain(chain);
+
+        let mut buffer = Vec::new();
+        certificate.encode(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), certificate.size());
+        assert_eq!(
+            &buffer[..8],
+            &[0x02, 0x00, 0x00, 0x80, 0x01, 0x00, 0x00, 0x00]
+        );
+        assert_eq!(buffer.len(), 8 + 4 + der.len() + 12);
+
+        let decoded = ServerCertificate::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded, certificate);
+        assert!(decoded.is_temporary());
+
+        let ServerCertificate::X509Chain(chain) = decoded else {
+            panic!("expected X.509 chain");
+        };
+        let server = chain.server_certificate().unwrap();
+        assert_eq!(server.serial_number, vec![0x12, 0x34]);
+        assert_eq!(server.signature_algorithm, "1.2.840.113549.1.1.11");
+        assert_eq!(server.subject, "C=KR, O=PentaRDP, CN=rdp.test");
+        assert_eq!(server.issuer, server.subject);
+        assert_eq!(server.not_before, "261016203049Z");
+        assert_eq!(server.not_after, "271016203049Z");
+        assert_eq!(server.public_key_algorithm, OID_RSA_ENCRYPTION);
+
+        assert_eq!(server.subject_public_key[..2], [0x30, 0x48]);
+        let key = server.public_key.as_ref().unwrap();
+        assert_eq!(key.exponent, 65537);
+        assert_eq!(key.bit_len(), 512);
+        assert_eq!(&key.modulus[..4], &[0xd0, 0x46, 0x65, 0x43]);
+
+        assert!(X509Certificate::from_der(&der[..100]).is_err());
+    }
+
+    #[test]
+    fn test_rsa_encrypt() {
+        let client_random: Vec<u8> = (0..32).collect();
+        let encrypted = terminal_services_key().encrypt(&client_random).unwrap();
+        assert_eq!(
+            hex::encode(&encrypted),
+            concat!(
+                "e61d090fa39e3beba45dedb8128294c89643c48e4aedbb08c2afb3dfce0ac8db",
+                "32cc13a788076ab8dd6d845641158badd7e556d2784fc10482d2e563d6865224"
+            )
+        );
+
+        // Private exponent recovers the plaintext
+        let decrypted = rsa_raw(
+            &encrypted,
+            &BigUint::from_bytes_le(&TSSK_PRIVATE_EXPONENT),
+            &BigUint::from_bytes_le(&TSSK_MODULUS),
+            32,
+        );
+        assert_eq!(decrypted, client_random);
+    }
+
+    #[test]
+    fn test_proprietary_certificate_blob_length() {
+        let certificate =
+            ServerCertificate::Proprietary(ProprietaryCertificate::new(terminal_services_key()));
+        let mut buffer = Vec::new();
+        certificate.encode(&mut buffer).unwrap();
+
+        // Modulus with a zero top byte still fills the declared keylen
+        let mut short_modulus = buffer.clone();
+        short_modulus[99] = 0x00;
+        let decoded = ServerCertificate::decode(&mut short_modulus.as_slice()).unwrap();
+        assert_eq!(decoded.public_key().unwrap().modulus_len(), 63);
+
+        // wPublicKeyBlobLen larger than the declared keylen
+        let mut long_blob = buffer.clone();
+        long_blob[14] += 1;
+        long_blob.insert(108, 0x00);
+        assert!(ServerCertificate::decode(&mut long_blob.as_slice()).is_err());
+
+        // keylen far beyond the available bytes
+        let mut key = Vec::new();
+        terminal_services_key().encode(&mut key).unwrap();
+        key[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            RsaPublicKey::decode(&mut key.as_slice()),
+            Err(PduError::InsufficientData { needed, .. }) if needed == u32::MAX as usize
+        ));
+    }
+
+    #[test]
+    fn test_proprietary_certificate_received_layout() {
+        // keylen with extra padding beyond modulus + 8, signed as sent
+        let mut key = Vec::new();
+        terminal_services_key().encode(&mut key).unwrap();
+        key[4..8].copy_from_slice(&80u32.to_le_bytes());
+        key.extend_from_slice(&[0u8; 8]);
+
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(&CERT_CHAIN_VERSION_1.to_le_bytes());
+        buffer.extend_from_slice(&SIGNATURE_ALG_RSA.to_le_bytes());
+        buffer.extend_from_slice(&KEY_EXCHANGE_ALG_RSA.to_le_bytes());
+        buffer.extend_from_slice(&BB_RSA_KEY_BLOB.to_le_bytes());
+        buffer.extend_from_slice(&(key.len() as u16).to_le_bytes());
+        buffer.extend_from_slice(&key);
+
+        let signature = rsa_raw(
+            &padded_hash(&buffer),
+            &BigUint::from_bytes_le(&TSSK_PRIVATE_EXPONENT),
+            &BigUint::from_bytes_le(&TSSK_MODULUS),
+            TSSK_LENGTH,
+        );
+        buffer.extend_from_slice(&BB_RSA_SIGNATURE_BLOB.to_le_bytes());
+        buffer.extend_from_slice(&((TSSK_LENGTH + RSA_PADDING_LENGTH) as u16).to_le_bytes());
+        buffer.extend_from_slice(&signature);
+        buffer.extend_from_slice(&[0u8; RSA_PADDING_LENGTH]);
+
+        let ServerCertificate::Proprietary(certificate) =
+            ServerCertificate::decode(&mut buffer.as_slice()).unwrap()
+        else {
+            panic!("expected proprietary certificate");
+        };
+        assert_eq!(certificate.public_key, terminal_services_key());
+        assert!(certificate.verify_signature());
+    }
+
+    #[test]
+    fn test_rsa_zero_key() {
+        assert!(RsaPublicKey::new(65537, &[0x00, 0x00]).is_err());
+        assert!(RsaPublicKey::new(65537, &[]).is_err());
+        assert!(RsaPublicKey::new(0, &[0xC5]).is_err());
+
+        let key = RsaPublicKey {
+            exponent: 65537,
+            modulus: Vec::new(),
+        };
+        assert!(key.encrypt(&[0x01]).is_err());
+
+        // Proprietary certificate with the modulus zeroed out
+        let certificate =
+            ServerCertificate::Proprietary(ProprietaryCertificate::new(terminal_services_key()));
+        let mut buffer = Vec::new();
+        certificate.encode(&mut buffer).unwrap();
+        buffer[36..100].fill(0);
+        assert!(ServerCertificate::decode(&mut buffer.as_slice()).is_err());
+
+        // X.509 certificate with the modulus zeroed out
+        let mut der = hex::decode(TEST_CERTIFICATE).unwrap();
+        let start = der
+            .windows(3)
+            .position(|w| w == [0x02, 0x41, 0x00])
+            .unwrap()
+            + 3;
+        der[start..start + 64].fill(0);
+        assert!(X509Certificate::from_der(&der).is_err());
+    }
+}
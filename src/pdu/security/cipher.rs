@@ -0,0 +1,480 @@
+use super::header::{BasicSecurityHeader, FipsSecurityHeader, SecurityFlags, SignedSecurityHeader};
+use super::keys::{
+    EncryptionMethod, SessionKeys, compute_fips_signature, compute_mac, compute_salted_mac,
+    update_key,
+};
+use super::rc4::Rc4;
+use crate::pdu::{Pdu, PduError, Result};
+use cbc::cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use des::TdesEde3;
+
+/// Number of packets encrypted with one RC4 key before it is refreshed
+pub const KEY_UPDATE_INTERVAL: u32 = 4096;
+
+/// FIPS 3DES-CBC initialization vector (MS-RDPBCGR 5.3.6.2)
+const FIPS_IV: [u8; 8] = [0x12, 0x34, 0x56, 0x78, 0x90, 0xAB, 0xCD, 0xEF];
+
+/// 3DES block size (bytes)
+const FIPS_BLOCK_SIZE: usize = 8;
+
+// Key material is secret, keep it out of debug output
+impl std::fmt::Debug for RdpCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RdpCipher")
+            .field("method", &self.method)
+            .field("use_count", &self.use_count)
+            .field("encryption_count", &self.encryption_count)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Cipher state, chained across packets
+///
+/// Only two exist per connection, so the variants are not boxed.
+#[derive(Clone)]
+#[allow(clippy::large_enum_variant)]
+enum CipherState {
+    Rc4(Rc4),
+    Fips {
+        encryptor: cbc::Encryptor<TdesEde3>,
+        decryptor: cbc::Decryptor<TdesEde3>,
+    },
+}
+
+impl CipherState {
+    /// Key length must already match the method
+    fn new(method: EncryptionMethod, key: &[u8]) -> Self {
+        if method.is_fips() {
+            let key = key.into();
+            CipherState::Fips {
+                encryptor: cbc::Encryptor::new(key, &FIPS_IV.into()),
+                decryptor: cbc::Decryptor::new(key, &FIPS_IV.into()),
+            }
+        } else {
+            CipherState::Rc4(Rc4::new(key))
+        }
+    }
+}
+
+// Key stream state is secret, keep it out of debug output
+impl std::fmt::Debug for CipherState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CipherState::Rc4(rc4) => rc4.fmt(f),
+            CipherState::Fips { .. } => f.debug_struct("Fips").finish_non_exhaustive(),
+        }
+    }
+}
+
+/// One direction of Standard RDP Security encryption
+///
+/// Uses RC4 with the 4096-packet key refresh (MS-RDPBCGR 5.3.7) and
+/// MD5/SHA-1 MACs, or 3DES-CBC with HMAC-SHA1 signatures for FIPS. Tracks
+/// the encryption count used for salted MACs and FIPS signatures.
+#[derive(Clone)]
+pub struct RdpCipher {
+    method: EncryptionMethod,
+    mac_key: Vec<u8>,
+    initial_key: Vec<u8>,
+    current_key: Vec<u8>,
+    state: CipherState,
+    use_count: u32,
+    encryption_count: u32,
+}
+
+impl RdpCipher {
+    /// Create cipher from a MAC key and an initial encryption key
+    ///
+    /// The key must be `method.key_length()` bytes long.
+    pub fn new(method: EncryptionMethod, mac_key: Vec<u8>, key: Vec<u8>) -> Result<Self> {
+        if key.len() != method.key_length() {
+            return Err(PduError::InvalidLength {
+                expected: method.key_length(),
+                actual: key.len(),
+            });
+        }
+
+        Ok(Self {
+            method,
+            mac_key,
+            state: CipherState::new(method, &key),
+            initial_key: key.clone(),
+            current_key: key,
+            use_count: 0,
+            encryption_count: 0,
+        })
+    }
+
+    /// Encryption method
+    pub fn method(&self) -> EncryptionMethod {
+        self.method
+    }
+
+    /// Number of packets processed so far
+    pub fn encryption_count(&self) -> u32 {
+        self.encryption_count
+    }
+
+    /// Current encryption key
+    pub fn current_key(&self) -> &[u8] {
+        &self.current_key
+    }
+
+    /// Compute the signature of a packet encrypted as number `count`
+    fn signature(&self, data: &[u8], salted: bool, count: u32) -> [u8; 8] {
+        if self.method.is_fips() {
+            compute_fips_signature(&self.mac_key, data, count)
+        } else if salted {
+            compute_salted_mac(&self.mac_key, data, count)
+        } else {
+            compute_mac(&self.mac_key, data)
+        }
+    }
+
+    /// Count one processed packet, refreshing the RC4 key when due
+    fn next_packet(&mut self) {
+        if let CipherState::Rc4(rc4) = &mut self.state
+            && self.use_count == KEY_UPDATE_INTERVAL
+        {
+            self.current_key = update_key(&self.initial_key, &self.current_key, self.method);
+            *rc4 = Rc4::new(&self.current_key);
+            self.use_count = 0;
+        }
+
+        self.use_count += 1;
+        self.encryption_count = self.encryption_count.wrapping_add(1);
+    }
+
+    /// Sign and encrypt one packet in place, returning its signature
+    ///
+    /// FIPS pads the data with zeros to the 3DES block size; the padding
+    /// length is the growth of `data`.
+    pub fn encrypt(&mut self, data: &mut Vec<u8>, salted: bool) -> [u8; 8] {
+        let signature = self.signature(data, salted, self.encryption_count);
+        self.next_packet();
+
+        match &mut self.state {
+            CipherState::Rc4(rc4) => rc4.process(data),
+            CipherState::Fips { encryptor, .. } => {
+                data.resize(data.len().next_multiple_of(FIPS_BLOCK_SIZE), 0);
+                for block in data.chunks_exact_mut(FIPS_BLOCK_SIZE) {
+                    encryptor.encrypt_block_mut(block.into());
+                }
+            }
+        }
+        signature
+    }
+
+    /// Decrypt one packet in place, strip FIPS padding and verify its signature
+    pub fn decrypt(
+        &mut self,
+        data: &mut Vec<u8>,
+        signature: &[u8; 8],
+        padding_length: u8,
+        salted: bool,
+    ) -> Result<()> {
+        let count = self.encryption_count;
+        self.next_packet();
+
+        match &mut self.state {
+            CipherState::Rc4(rc4) => rc4.process(data),
+            CipherState::Fips { decryptor, .. } => {
+                let padding_length = padding_length as usize;
+                if !data.len().is_multiple_of(FIPS_BLOCK_SIZE)
+                    || padding_length >= FIPS_BLOCK_SIZE
+                    || padding_length > data.len()
+                {
+                    return Err(PduError::ParseError(format!(
+                        "Invalid FIPS data length {} with padding {}",
+                        data.len(),
+                        padding_length
+                    )));
+                }
+                for block in data.chunks_exact_mut(FIPS_BLOCK_SIZE) {
+                    decryptor.decrypt_block_mut(block.into());
+                }
+                data.truncate(data.len() - padding_length);
+            }
+        }
+
+        if &self.signature(data, salted, count) != signature {
+            return Err(PduError::ParseError(
+                "MAC signature verification failed".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Standard RDP Security state for one endpoint
+///
+/// Wraps and unwraps the security header of slow-path PDUs, using the FIPS
+/// header when the method is FIPS. Fast-path callers use
+/// `encryptor`/`decryptor` directly since the signature lives in the
+/// fast-path header.
+#[derive(Debug, Clone)]
+pub struct StandardSecurity {
+    encryptor: RdpCipher,
+    decryptor: RdpCipher,
+}
+
+impl StandardSecurity {
+    /// Client side: encrypt with the client encrypt key
+    pub fn client(keys: &SessionKeys) -> Result<Self> {
+        Ok(Self {
+            encryptor: RdpCipher::new(
+                keys.method,
+                keys.mac_key.clone(),
+                keys.client_encrypt_key.clone(),
+            )?,
+            decryptor: RdpCipher::new(
+                keys.method,
+                keys.mac_key.clone(),
+                keys.client_decrypt_key.clone(),
+            )?,
+        })
+    }
+
+    /// Server side: encrypt with the client decrypt key
+    pub fn server(keys: &SessionKeys) -> Result<Self> {
+        let client = Self::client(keys)?;
+        Ok(Self {
+            encryptor: client.decryptor,
+            decryptor: client.encryptor,
+        })
+    }
+
+    /// Outgoing cipher
+    pub fn encryptor(&mut self) -> &mut RdpCipher {
+        &mut self.encryptor
+    }
+
+    /// Incoming cipher
+    pub fn decryptor(&mut self) -> &mut RdpCipher {
+        &mut self.decryptor
+    }
+
+    /// Build a signed and encrypted PDU (security header + data)
+    ///
+    /// `SEC_ENCRYPT` is added to `flags`; `SEC_SECURE_CHECKSUM` selects
+    /// the salted MAC (ignored for FIPS).
+    pub fn encrypt_pdu(&mut self, flags: SecurityFlags, data: &[u8]) -> Result<Vec<u8>> {
+        let flags = flags | SecurityFlags::ENCRYPT;
+        let salted = flags.contains(SecurityFlags::SECURE_CHECKSUM);
+
+        let mut payload = data.to_vec();
+        let signature = self.encryptor.encrypt(&mut payload, salted);
+
+        let mut buffer = Vec::with_capacity(FipsSecurityHeader::SIZE + payload.len());
+        if self.encryptor.method().is_fips() {
+            let padding_length = (payload.len() - data.len()) as u8;
+            FipsSecurityHeader::new(flags, padding_length, signature).encode(&mut buffer)?;
+        } else {
+            SignedSecurityHeader::new(flags, signature).encode(&mut buffer)?;
+        }
+        buffer.extend_from_slice(&payload);
+        Ok(buffer)
+    }
+
+    /// Parse a PDU with a security header, decrypting it when `SEC_ENCRYPT` is set
+    pub fn decrypt_pdu(&mut self, input: &[u8]) -> Result<(SecurityFlags, Vec<u8>)> {
+        let mut cursor = input;
+        let basic = BasicSecurityHeader::decode(&mut cursor)?;
+        if !basic.is_encrypted() {
+            return Ok((basic.flags, cursor.to_vec()));
+        }
+
+        // Re-read the full header now that the layout is known
+        let mut header = input;
+        let (signature, padding_length) = if self.decryptor.method().is_fips() {
+            let fips = FipsSecurityHeader::decode(&mut header)?;
+            (fips.data_signature, fips.padding_length)
+        } else {
+            (SignedSecurityHeader::decode(&mut header)?.data_signature, 0)
+        };
+
+        let mut payload = header.to_vec();
+        let salted = basic.flags.contains(SecurityFlags::SECURE_CHECKSUM);
+        self.decryptor
+            .decrypt(&mut payload, &signature, padding_length, salted)?;
+        Ok((basic.flags, payload))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session_keys(method: EncryptionMethod) -> SessionKeys {
+        SessionKeys::derive(&[0x11; 32], &[0x22; 32], method)
+    }
+
+    #[test]
+    fn test_standard_security_roundtrip() {
+        for method in [
+            EncryptionMethod::Bit40,
+            EncryptionMethod::Bit56,
+            EncryptionMethod::Bit128,
+        ] {
+            let keys = session_keys(method);
+            let mut client = StandardSecurity::client(&keys).unwrap();
+            let mut server = StandardSecurity::server(&keys).unwrap();
+
+            let pdu = client
+                .encrypt_pdu(SecurityFlags::INFO_PKT, b"client info")
+                .unwrap();
+            assert_eq!(&pdu[..4], &[0x48, 0x00, 0x00, 0x00]);
+            assert_ne!(&pdu[12..], b"client info");
+
+            let (flags, data) = server.decrypt_pdu(&pdu).unwrap();
+            assert_eq!(flags, SecurityFlags::INFO_PKT | SecurityFlags::ENCRYPT);
+            assert_eq!(data, b"client info");
+
+            let pdu = server
+                .encrypt_pdu(SecurityFlags::SECURE_CHECKSUM, b"update")
+                .unwrap();
+            assert_eq!(client.decrypt_pdu(&pdu).unwrap().1, b"update");
+        }
+    }
+
+    #[test]
+    fn test_standard_security_key_refresh() {
+        let keys = session_keys(EncryptionMethod::Bit128);
+        let mut client = StandardSecurity::client(&keys).unwrap();
+        let mut server = StandardSecurity::server(&keys).unwrap();
+
+        for i in 0..KEY_UPDATE_INTERVAL + 2 {
+            let data = i.to_le_bytes();
+            let pdu = client
+                .encrypt_pdu(SecurityFlags::SECURE_CHECKSUM, &data)
+                .unwrap();
+            assert_eq!(server.decrypt_pdu(&pdu).unwrap().1, data);
+        }
+
+        assert_eq!(
+            client.encryptor().encryption_count(),
+            KEY_UPDATE_INTERVAL + 2
+        );
+        assert_ne!(
+            client.encryptor().current_key(),
+            &keys.client_encrypt_key[..]
+        );
+        assert_eq!(
+            client.encryptor().current_key(),
+            server.decryptor().current_key()
+        );
+    }
+
+    #[test]
+    fn test_standard_security_tampered() {
+        let keys = session_keys(EncryptionMethod::Bit128);
+        let mut client = StandardSecurity::client(&keys).unwrap();
+        let mut server = StandardSecurity::server(&keys).unwrap();
+
+        let mut pdu = client.encrypt_pdu(SecurityFlags::empty(), b"data").unwrap();
+        pdu[13] ^= 0xFF;
+        assert!(server.decrypt_pdu(&pdu).is_err());
+        assert!(server.decrypt_pdu(&[0x08, 0x00, 0x00, 0x00, 0x01]).is_err());
+
+        let (flags, data) = server.decrypt_pdu(&[0x80, 0x00, 0x00, 0x00, 0xAA]).unwrap();
+        assert_eq!(flags, SecurityFlags::LICENSE_PKT);
+        assert_eq!(data, vec![0xAA]);
+    }
+
+    #[test]
+    fn test_fips_security_roundtrip() {
+        let mut client_random = [0u8; 32];
+        let mut server_random = [0u8; 32];
+        for i in 0..32 {
+            client_random[i] = i as u8;
+            server_random[i] = 32 + i as u8;
+        }
+        let keys = SessionKeys::derive(&client_random, &server_random, EncryptionMethod::Fips);
+        let mut client = StandardSecurity::client(&keys).unwrap();
+        let mut server = StandardSecurity::server(&keys).unwrap();
+
+        let pdu = client
+            .encrypt_pdu(SecurityFlags::empty(), b"hello")
+            .unwrap();
+        assert_eq!(
+            pdu,
+            [
+                &[0x08, 0x00, 0x00, 0x00, 0x10, 0x00, 0x01, 0x03][..],
+                &hex::decode("7d4226c2eea59d89").unwrap(),
+                &hex::decode("7b899d512ddf32d6").unwrap(),
+            ]
+            .concat()
+        );
+        assert_eq!(server.decrypt_pdu(&pdu).unwrap().1, b"hello");
+
+        // CBC state and the signature count carry over between packets
+        let pdu = client
+            .encrypt_pdu(SecurityFlags::empty(), b"second packet!")
+            .unwrap();
+        assert_eq!(pdu[7], 2);
+        assert_eq!(hex::encode(&pdu[8..16]), "9f8fe6a1a4ace8f4");
+        assert_eq!(hex::encode(&pdu[16..]), "28051b20b33c812900db2665c8983cf0");
+        assert_eq!(server.decrypt_pdu(&pdu).unwrap().1, b"second packet!");
+
+        let pdu = server
+            .encrypt_pdu(SecurityFlags::empty(), &[0x42; 16])
+            .unwrap();
+        assert_eq!(pdu[7], 0);
+        assert_eq!(client.decrypt_pdu(&pdu).unwrap().1, vec![0x42; 16]);
+
+        let mut pdu = client.encrypt_pdu(SecurityFlags::empty(), b"data").unwrap();
+        pdu[7] = 8;
+        assert!(server.decrypt_pdu(&pdu).is_err());
+    }
+
+    #[test]
+    fn test_fips_cipher_sp800_67() {
+        // NIST SP 800-67 TDEA example (ECB). Pre-XORing each plaintext block
+        // with the previous ciphertext block (the FIPS IV for the first)
+        // makes CBC produce the same ciphertext.
+        let key = hex::decode("0123456789abcdef23456789abcdef01456789abcdef0123").unwrap();
+        let plaintext = b"The qufck brown fox jump";
+        let ciphertext = hex::decode("a826fd8ce53b855fcce21c8112256fe668d5c05dd9b6b900").unwrap();
+
+        let mut data = Vec::new();
+        let mut chain = FIPS_IV;
+        for (block, expected) in plaintext.chunks(8).zip(ciphertext.chunks(8)) {
+            data.extend(block.iter().zip(chain).map(|(p, c)| p ^ c));
+            chain.copy_from_slice(expected);
+        }
+
+        let mut cipher = RdpCipher::new(EncryptionMethod::Fips, vec![0; 20], key).unwrap();
+        cipher.encrypt(&mut data, false);
+        assert_eq!(data, ciphertext);
+    }
+
+    #[test]
+    fn test_rdp_cipher_key_length() {
+        for (method, length) in [
+            (EncryptionMethod::Bit40, 8),
+            (EncryptionMethod::Bit128, 16),
+            (EncryptionMethod::Fips, 24),
+        ] {
+            assert!(RdpCipher::new(method, vec![0; 16], vec![0; length]).is_ok());
+            assert!(matches!(
+                RdpCipher::new(method, vec![0; 16], vec![0; length - 1]),
+                Err(PduError::InvalidLength { expected, actual })
+                    if expected == length && actual == length - 1
+            ));
+        }
+        assert!(RdpCipher::new(EncryptionMethod::Bit128, vec![0; 16], Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_rdp_cipher_debug_redacts_keys() {
+        let cipher =
+            RdpCipher::new(EncryptionMethod::Bit128, vec![0xA5; 16], vec![0x5A; 16]).unwrap();
+        let debug = format!("{:?}", cipher);
+        assert!(debug.contains("Bit128"));
+        assert!(debug.contains("encryption_count: 0"));
+        assert!(!debug.contains("165") && !debug.contains("90"));
+
+        let keys = SessionKeys::derive(&[0x11; 32], &[0x22; 32], EncryptionMethod::Bit128);
+        assert!(!format!("{:?}", keys).contains("mac_key"));
+    }
+}
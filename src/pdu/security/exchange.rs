@@ -0,0 +1,118 @@
+use super::certificate::RsaPublicKey;
+use super::header::{BasicSecurityHeader, SecurityFlags};
+use super::keys::RANDOM_LENGTH;
+use crate::pdu::{Pdu, PduError, Result};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Read, Write};
+
+/// Zero padding after the encrypted client random
+const PADDING_LENGTH: usize = 8;
+
+/// Security Exchange PDU (TS_SECURITY_PACKET, MS-RDPBCGR 2.2.1.10.1)
+///
+/// Carries the client random encrypted with the server public key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityExchangePdu {
+    /// Encrypted client random, little-endian, without the trailing padding
+    pub encrypted_client_random: Vec<u8>,
+}
+
+impl SecurityExchangePdu {
+    /// Encrypt the client random with the server public key (MS-RDPBCGR 5.3.4.1)
+    pub fn new(client_random: &[u8; RANDOM_LENGTH], public_key: &RsaPublicKey) -> Result<Self> {
+        Ok(Self {
+            encrypted_client_random: public_key.encrypt(client_random)?,
+        })
+    }
+}
+
+impl Pdu for SecurityExchangePdu {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        BasicSecurityHeader::new(SecurityFlags::EXCHANGE_PKT).encode(buffer)?;
+        buffer.write_u32::<LittleEndian>(
+            (self.encrypted_client_random.len() + PADDING_LENGTH) as u32,
+        )?;
+        buffer.write_all(&self.encrypted_client_random)?;
+        buffer.write_all(&[0u8; PADDING_LENGTH])?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let header = BasicSecurityHeader::decode(buffer)?;
+        if !header.flags.contains(SecurityFlags::EXCHANGE_PKT) {
+            return Err(PduError::InvalidHeader(format!(
+                "Expected SEC_EXCHANGE_PKT, got flags {:#06x}",
+                header.flags.bits()
+            )));
+        }
+
+        let length = buffer.read_u32::<LittleEndian>()? as usize;
+        if length <= PADDING_LENGTH {
+            return Err(PduError::InvalidLength {
+                expected: PADDING_LENGTH + 1,
+                actual: length,
+            });
+        }
+
+        let mut encrypted_client_random = Vec::new();
+        buffer
+            .take(length as u64)
+            .read_to_end(&mut encrypted_client_random)?;
+        if encrypted_client_random.len() != length {
+            return Err(PduError::InsufficientData {
+                needed: length,
+                available: encrypted_client_random.len(),
+            });
+        }
+        encrypted_client_random.truncate(length - PADDING_LENGTH);
+
+        Ok(Self {
+            encrypted_client_random,
+        })
+    }
+
+    fn size(&self) -> usize {
+        BasicSecurityHeader::SIZE + 4 + self.encrypted_client_random.len() + PADDING_LENGTH
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_security_exchange_pdu() {
+        // 512-bit key from the X.509 test certificate
+        let modulus = hex::decode(concat!(
+            "d04665436f4ef741502eedb6a7558c1397dab40818d33f288cc590761788399c",
+            "bc78594c60207abf3e8bc68c40f0998b3dbd6b073c8de7b263bfcf2677846931"
+        ))
+        .unwrap();
+        let public_key = RsaPublicKey::new(65537, &modulus).unwrap();
+
+        let client_random: [u8; RANDOM_LENGTH] = std::array::from_fn(|i| i as u8);
+        let pdu = SecurityExchangePdu::new(&client_random, &public_key).unwrap();
+
+        let mut buffer = Vec::new();
+        pdu.encode(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), pdu.size());
+        assert_eq!(
+            &buffer[..8],
+            &[0x01, 0x00, 0x00, 0x00, 0x48, 0x00, 0x00, 0x00]
+        );
+        assert_eq!(
+            hex::encode(&buffer[8..72]),
+            concat!(
+                "7d8ba9f48db83d9a21cc43c759b9cbd23611edeae09331e25f2ff148d2e52bb6",
+                "cc3b54633ef1a31623d7163862a01ed86a5f8f91abf1ff8cd3988a4c4d88eaba"
+            )
+        );
+        assert_eq!(&buffer[72..], &[0u8; 8]);
+
+        let decoded = SecurityExchangePdu::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded, pdu);
+
+        buffer[0] = 0x08;
+        assert!(SecurityExchangePdu::decode(&mut buffer.as_slice()).is_err());
+    }
+}
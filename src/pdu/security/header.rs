@@ -0,0 +1,250 @@
+use crate::pdu::{Pdu, PduError, Result};
+use bitflags::bitflags;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Read, Write};
+
+/// TSFIPS_VERSION1
+const TSFIPS_VERSION1: u8 = 0x01;
+
+bitflags! {
+    /// Security header flags (MS-RDPBCGR 2.2.8.1.1.2.1)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct SecurityFlags: u16 {
+        /// SEC_EXCHANGE_PKT - Security Exchange PDU
+        const EXCHANGE_PKT = 0x0001;
+        /// SEC_TRANSPORT_REQ - Initiate Multitransport Request PDU
+        const TRANSPORT_REQ = 0x0002;
+        /// RDP_SEC_TRANSPORT_RSP - Initiate Multitransport Response PDU
+        const TRANSPORT_RSP = 0x0004;
+        /// SEC_ENCRYPT - Data is encrypted and signed
+        const ENCRYPT = 0x0008;
+        /// SEC_RESET_SEQNO - Unused
+        const RESET_SEQNO = 0x0010;
+        /// SEC_IGNORE_SEQNO - Unused
+        const IGNORE_SEQNO = 0x0020;
+        /// SEC_INFO_PKT - Client Info PDU
+        const INFO_PKT = 0x0040;
+        /// SEC_LICENSE_PKT - Licensing PDU
+        const LICENSE_PKT = 0x0080;
+        /// SEC_LICENSE_ENCRYPT_CS - Client should encrypt licensing PDUs
+        const LICENSE_ENCRYPT_CS = 0x0200;
+        /// SEC_LICENSE_ENCRYPT_SC - Server licensing PDU is encrypted
+        const LICENSE_ENCRYPT_SC = 0x0200;
+        /// SEC_REDIRECTION_PKT - Standard Security Server Redirection PDU
+        const REDIRECTION_PKT = 0x0400;
+        /// SEC_SECURE_CHECKSUM - MAC is salted with the encryption count
+        const SECURE_CHECKSUM = 0x0800;
+        /// SEC_AUTODETECT_REQ - Auto-Detect Request PDU
+        const AUTODETECT_REQ = 0x1000;
+        /// SEC_AUTODETECT_RSP - Auto-Detect Response PDU
+        const AUTODETECT_RSP = 0x2000;
+        /// SEC_HEARTBEAT - Heartbeat PDU
+        const HEARTBEAT = 0x4000;
+        /// SEC_FLAGSHI_VALID - flagsHi field is valid
+        const FLAGSHI_VALID = 0x8000;
+    }
+}
+
+/// Basic Security Header (TS_SECURITY_HEADER, MS-RDPBCGR 2.2.8.1.1.2.1)
+///
+/// 4 bytes total
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BasicSecurityHeader {
+    /// Security flags
+    pub flags: SecurityFlags,
+    /// High flags (unused)
+    pub flags_hi: u16,
+}
+
+impl BasicSecurityHeader {
+    /// Header size (4 bytes)
+    pub const SIZE: usize = 4;
+
+    /// Create basic security header
+    pub fn new(flags: SecurityFlags) -> Self {
+        Self { flags, flags_hi: 0 }
+    }
+
+    /// Check whether the data is encrypted
+    pub fn is_encrypted(&self) -> bool {
+        self.flags.contains(SecurityFlags::ENCRYPT)
+    }
+}
+
+impl Pdu for BasicSecurityHeader {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_u16::<LittleEndian>(self.flags.bits())?;
+        buffer.write_u16::<LittleEndian>(self.flags_hi)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let flags = SecurityFlags::from_bits_retain(buffer.read_u16::<LittleEndian>()?);
+        let flags_hi = buffer.read_u16::<LittleEndian>()?;
+        Ok(Self { flags, flags_hi })
+    }
+
+    fn size(&self) -> usize {
+        Self::SIZE
+    }
+}
+
+/// Non-FIPS Security Header (TS_SECURITY_HEADER1, MS-RDPBCGR 2.2.8.1.1.2.2)
+///
+/// 12 bytes total
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignedSecurityHeader {
+    /// Basic security header
+    pub basic: BasicSecurityHeader,
+    /// MAC signature over the unencrypted data
+    pub data_signature: [u8; 8],
+}
+
+impl SignedSecurityHeader {
+    /// Header size (12 bytes)
+    pub const SIZE: usize = 12;
+
+    /// Create signed security header
+    pub fn new(flags: SecurityFlags, data_signature: [u8; 8]) -> Self {
+        Self {
+            basic: BasicSecurityHeader::new(flags),
+            data_signature,
+        }
+    }
+}
+
+impl Pdu for SignedSecurityHeader {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        self.basic.encode(buffer)?;
+        buffer.write_all(&self.data_signature)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let basic = BasicSecurityHeader::decode(buffer)?;
+        let mut data_signature = [0u8; 8];
+        buffer.read_exact(&mut data_signature)?;
+        Ok(Self {
+            basic,
+            data_signature,
+        })
+    }
+
+    fn size(&self) -> usize {
+        Self::SIZE
+    }
+}
+
+/// FIPS Security Header (TS_SECURITY_HEADER2, MS-RDPBCGR 2.2.8.1.1.2.3)
+///
+/// 16 bytes total
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FipsSecurityHeader {
+    /// Basic security header
+    pub basic: BasicSecurityHeader,
+    /// Number of padding bytes appended to the data before encryption
+    pub padding_length: u8,
+    /// HMAC-SHA1 signature over the unpadded data
+    pub data_signature: [u8; 8],
+}
+
+impl FipsSecurityHeader {
+    /// Header size (16 bytes)
+    pub const SIZE: usize = 16;
+
+    /// Value of the length field
+    const LENGTH: u16 = 0x0010;
+
+    /// Create FIPS security header
+    pub fn new(flags: SecurityFlags, padding_length: u8, data_signature: [u8; 8]) -> Self {
+        Self {
+            basic: BasicSecurityHeader::new(flags),
+            padding_length,
+            data_signature,
+        }
+    }
+}
+
+impl Pdu for FipsSecurityHeader {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        self.basic.encode(buffer)?;
+        buffer.write_u16::<LittleEndian>(Self::LENGTH)?;
+        buffer.write_u8(TSFIPS_VERSION1)?;
+        buffer.write_u8(self.padding_length)?;
+        buffer.write_all(&self.data_signature)?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let basic = BasicSecurityHeader::decode(buffer)?;
+
+        let length = buffer.read_u16::<LittleEndian>()?;
+        if length != Self::LENGTH {
+            return Err(PduError::InvalidLength {
+                expected: Self::LENGTH as usize,
+                actual: length as usize,
+            });
+        }
+        let version = buffer.read_u8()?;
+        if version != TSFIPS_VERSION1 {
+            return Err(PduError::UnsupportedVersion(version));
+        }
+        let padding_length = buffer.read_u8()?;
+
+        let mut data_signature = [0u8; 8];
+        buffer.read_exact(&mut data_signature)?;
+        Ok(Self {
+            basic,
+            padding_length,
+            data_signature,
+        })
+    }
+
+    fn size(&self) -> usize {
+        Self::SIZE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_security_headers() {
+        let header = SignedSecurityHeader::new(
+            SecurityFlags::ENCRYPT | SecurityFlags::INFO_PKT,
+            [1, 2, 3, 4, 5, 6, 7, 8],
+        );
+
+        let mut buffer = Vec::new();
+        header.encode(&mut buffer).unwrap();
+        assert_eq!(buffer, vec![0x48, 0x00, 0x00, 0x00, 1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let decoded = SignedSecurityHeader::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded, header);
+        assert!(decoded.basic.is_encrypted());
+
+        let basic = BasicSecurityHeader::decode(&mut [0x80, 0x00, 0x00, 0x00].as_slice()).unwrap();
+        assert_eq!(basic.flags, SecurityFlags::LICENSE_PKT);
+        assert!(!basic.is_encrypted());
+    }
+
+    #[test]
+    fn test_fips_security_header() {
+        let header = FipsSecurityHeader::new(SecurityFlags::ENCRYPT, 3, [0xAB; 8]);
+
+        let mut buffer = Vec::new();
+        header.encode(&mut buffer).unwrap();
+        assert_eq!(
+            &buffer[..8],
+            &[0x08, 0x00, 0x00, 0x00, 0x10, 0x00, 0x01, 0x03]
+        );
+        assert_eq!(buffer.len(), header.size());
+
+        let decoded = FipsSecurityHeader::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded, header);
+
+        buffer[6] = 0x02;
+        assert!(FipsSecurityHeader::decode(&mut buffer.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,436 @@
+use super::rc4::Rc4;
+use hmac::{Hmac, Mac};
+use md5::{Digest, Md5};
+use sha1::Sha1;
+
+/// Client and server random length (bytes)
+pub const RANDOM_LENGTH: usize = 32;
+
+/// Salt for 40-bit keys (MS-RDPBCGR 5.3.5.1)
+const SALT_40BIT: [u8; 3] = [0xD1, 0x26, 0x9E];
+
+/// Salt for 56-bit keys (MS-RDPBCGR 5.3.5.1)
+const SALT_56BIT: u8 = 0xD1;
+
+/// MAC and key update inner padding
+const PAD1: [u8; 40] = [0x36; 40];
+
+/// MAC and key update outer padding
+const PAD2: [u8; 48] = [0x5C; 48];
+
+/// 3DES key length (bytes)
+const FIPS_KEY_LENGTH: usize = 24;
+
+/// Standard RDP Security encryption method (MS-RDPBCGR 5.3.2)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum EncryptionMethod {
+    /// ENCRYPTION_METHOD_40BIT - 40-bit RC4
+    Bit40 = 0x0000_0001,
+    /// ENCRYPTION_METHOD_128BIT - 128-bit RC4
+    Bit128 = 0x0000_0002,
+    /// ENCRYPTION_METHOD_56BIT - 56-bit RC4
+    Bit56 = 0x0000_0008,
+    /// ENCRYPTION_METHOD_FIPS - 3DES-CBC with HMAC-SHA1 signatures
+    Fips = 0x0000_0010,
+}
+
+impl EncryptionMethod {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0x0000_0001 => Some(EncryptionMethod::Bit40),
+            0x0000_0002 => Some(EncryptionMethod::Bit128),
+            0x0000_0008 => Some(EncryptionMethod::Bit56),
+            0x0000_0010 => Some(EncryptionMethod::Fips),
+            _ => None,
+        }
+    }
+
+    pub fn as_u32(self) -> u32 {
+        self as u32
+    }
+
+    /// Check whether this is the FIPS method
+    pub fn is_fips(self) -> bool {
+        self == EncryptionMethod::Fips
+    }
+
+    /// Encryption key length in bytes
+    pub fn key_length(self) -> usize {
+        match self {
+            EncryptionMethod::Bit40 | EncryptionMethod::Bit56 => 8,
+            EncryptionMethod::Bit128 => 16,
+            EncryptionMethod::Fips => FIPS_KEY_LENGTH,
+        }
+    }
+
+    /// Reduce a 128-bit RC4 key to this method's strength
+    fn reduce_key(self, key: &[u8]) -> Vec<u8> {
+        let mut reduced = key[..self.key_length()].to_vec();
+        match self {
+            EncryptionMethod::Bit40 => reduced[..3].copy_from_slice(&SALT_40BIT),
+            EncryptionMethod::Bit56 => reduced[0] = SALT_56BIT,
+            EncryptionMethod::Bit128 | EncryptionMethod::Fips => {}
+        }
+        reduced
+    }
+}
+
+/// Standard RDP Security session keys (MS-RDPBCGR 5.3.5)
+///
+/// The client encrypts with `client_encrypt_key` and the server decrypts
+/// with it; `client_decrypt_key` is used the other way round. With FIPS
+/// the encryption keys are 3DES keys and `mac_key` is the HMAC-SHA1 key.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SessionKeys {
+    /// Encryption method the keys were derived for
+    pub method: EncryptionMethod,
+    /// MAC signing key
+    pub mac_key: Vec<u8>,
+    /// Initial client-to-server encryption key
+    pub client_encrypt_key: Vec<u8>,
+    /// Initial server-to-client encryption key
+    pub client_decrypt_key: Vec<u8>,
+}
+
+// Key material is secret, keep it out of debug output
+impl std::fmt::Debug for SessionKeys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SessionKeys")
+            .field("method", &self.method)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SessionKeys {
+    /// Derive session keys from the client and server randoms
+    pub fn derive(
+        client_random: &[u8; RANDOM_LENGTH],
+        server_random: &[u8; RANDOM_LENGTH],
+        method: EncryptionMethod,
+    ) -> Self {
+        if method.is_fips() {
+            return Self::derive_fips(client_random, server_random);
+        }
+
+        let mut pre_master_secret = [0u8; 48];
+        pre_master_secret[..24].copy_from_slice(&client_random[..24]);
+        pre_master_secret[24..].copy_from_slice(&server_random[..24]);
+
+        let master_secret = hash_blob(
+            &pre_master_secret,
+            [b"A", b"BB", b"CCC"],
+            client_random,
+            server_random,
+        );
+        let session_key_blob = hash_blob(
+            &master_secret,
+            [b"X", b"YY", b"ZZZ"],
+            client_random,
+            server_random,
+        );
+
+        let final_hash = |key: &[u8]| -> [u8; 16] {
+            Md5::new()
+                .chain_update(key)
+                .chain_update(client_random)
+                .chain_update(server_random)
+                .finalize()
+                .into()
+        };
+
+        Self {
+            method,
+            mac_key: method.reduce_key(&session_key_blob[..16]),
+            client_decrypt_key: method.reduce_key(&final_hash(&session_key_blob[16..32])),
+            client_encrypt_key: method.reduce_key(&final_hash(&session_key_blob[32..48])),
+        }
+    }
+
+    /// FIPS key generation (MS-RDPBCGR 5.3.5.2)
+    fn derive_fips(
+        client_random: &[u8; RANDOM_LENGTH],
+        server_random: &[u8; RANDOM_LENGTH],
+    ) -> Self {
+        let client_encrypt_key_t = Sha1::new()
+            .chain_update(&client_random[16..])
+            .chain_update(&server_random[16..])
+            .finalize();
+        let client_decrypt_key_t = Sha1::new()
+            .chain_update(&client_random[..16])
+            .chain_update(&server_random[..16])
+            .finalize();
+        let mac_key = Sha1::new()
+            .chain_update(client_decrypt_key_t)
+            .chain_update(client_encrypt_key_t)
+            .finalize();
+
+        Self {
+            method: EncryptionMethod::Fips,
+            mac_key: mac_key.to_vec(),
+            client_encrypt_key: fips_expand_key(&client_encrypt_key_t).to_vec(),
+            client_decrypt_key: fips_expand_key(&client_decrypt_key_t).to_vec(),
+        }
+    }
+}
+
+/// Expand a 160-bit SHA-1 digest into a 192-bit 3DES key
+///
+/// The first byte is appended to get 168 key bits, then a parity bit is
+/// inserted after every 7 bits.
+fn fips_expand_key(key_t: &[u8]) -> [u8; FIPS_KEY_LENGTH] {
+    // 21 key bytes, bit-reversed so bits can be read MSB first, plus one
+    // spare byte for the final window
+    let mut bits = [0u8; 22];
+    for (bit, byte) in bits.iter_mut().zip(key_t.iter().chain(&key_t[..1])) {
+        *bit = byte.reverse_bits();
+    }
+
+    let mut key = [0u8; FIPS_KEY_LENGTH];
+    for (i, out) in key.iter_mut().enumerate() {
+        let position = i * 7;
+        let (index, shift) = (position / 8, position % 8);
+        let window = u16::from_be_bytes([bits[index], bits[index + 1]]) << shift;
+        let seven_bits = (window >> 8) as u8 & 0xFE;
+
+        // Restore bit order and set odd parity in the low bit
+        let byte = seven_bits.reverse_bits() & 0xFE;
+        *out = byte | u8::from(byte.count_ones().is_multiple_of(2));
+    }
+    key
+}
+
+/// SaltedHash(S, I) = MD5(S + SHA(I + S + ClientRandom + ServerRandom))
+fn salted_hash(
+    secret: &[u8],
+    input: &[u8],
+    client_random: &[u8],
+    server_random: &[u8],
+) -> [u8; 16] {
+    let sha = Sha1::new()
+        .chain_update(input)
+        .chain_update(secret)
+        .chain_update(client_random)
+        .chain_update(server_random)
+        .finalize();
+    Md5::new()
+        .chain_update(secret)
+        .chain_update(sha)
+        .finalize()
+        .into()
+}
+
+/// Concatenate three salted hashes of `secret` (48 bytes)
+pub(crate) fn hash_blob(
+    secret: &[u8],
+    inputs: [&[u8]; 3],
+    client_random: &[u8],
+    server_random: &[u8],
+) -> [u8; 48] {
+    let mut blob = [0u8; 48];
+    for (chunk, input) in blob.chunks_mut(16).zip(inputs) {
+        chunk.copy_from_slice(&salted_hash(secret, input, client_random, server_random));
+    }
+    blob
+}
+
+/// MAC signature over unencrypted data (MS-RDPBCGR 5.3.6.1)
+pub fn compute_mac(mac_key: &[u8], data: &[u8]) -> [u8; 8] {
+    mac_signature(mac_key, data, None)
+}
+
+/// Salted MAC signature (MS-RDPBCGR 5.3.6.1.1)
+///
+/// `encryption_count` is the number of packets encrypted with this
+/// direction's keys before this one.
+pub fn compute_salted_mac(mac_key: &[u8], data: &[u8], encryption_count: u32) -> [u8; 8] {
+    mac_signature(mac_key, data, Some(encryption_count))
+}
+
+fn mac_signature(mac_key: &[u8], data: &[u8], encryption_count: Option<u32>) -> [u8; 8] {
+    let mut signature = [0u8; 8];
+    signature.copy_from_slice(&mac_digest(mac_key, data, encryption_count)[..8]);
+    signature
+}
+
+/// Full 16-byte MAC digest, also used for licensing MACData
+pub(crate) fn mac_digest(mac_key: &[u8], data: &[u8], encryption_count: Option<u32>) -> [u8; 16] {
+    let mut sha = Sha1::new()
+        .chain_update(mac_key)
+        .chain_update(PAD1)
+        .chain_update((data.len() as u32).to_le_bytes())
+        .chain_update(data);
+    if let Some(count) = encryption_count {
+        sha.update(count.to_le_bytes());
+    }
+
+    Md5::new()
+        .chain_update(mac_key)
+        .chain_update(PAD2)
+        .chain_update(sha.finalize())
+        .finalize()
+        .into()
+}
+
+/// FIPS signature: HMAC-SHA1 over the data and encryption count (MS-RDPBCGR 5.3.6.2)
+pub fn compute_fips_signature(mac_key: &[u8], data: &[u8], encryption_count: u32) -> [u8; 8] {
+    let mut hmac =
+        <Hmac<Sha1> as Mac>::new_from_slice(mac_key).expect("HMAC accepts keys of any length");
+    hmac.update(data);
+    hmac.update(&encryption_count.to_le_bytes());
+
+    let mut signature = [0u8; 8];
+    signature.copy_from_slice(&hmac.finalize().into_bytes()[..8]);
+    signature
+}
+
+/// Derive the next RC4 key after 4096 packets (MS-RDPBCGR 5.3.7.1)
+pub fn update_key(initial_key: &[u8], current_key: &[u8], method: EncryptionMethod) -> Vec<u8> {
+    let sha = Sha1::new()
+        .chain_update(initial_key)
+        .chain_update(PAD1)
+        .chain_update(current_key)
+        .finalize();
+    let temp_key = Md5::new()
+        .chain_update(initial_key)
+        .chain_update(PAD2)
+        .chain_update(sha)
+        .finalize();
+
+    let temp_key = &temp_key[..method.key_length()];
+    let mut new_key = temp_key.to_vec();
+    Rc4::new(temp_key).process(&mut new_key);
+    method.reduce_key(&new_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn randoms() -> ([u8; 32], [u8; 32]) {
+        let mut client_random = [0u8; 32];
+        let mut server_random = [0u8; 32];
+        for i in 0..32 {
+            client_random[i] = i as u8;
+            server_random[i] = 32 + i as u8;
+        }
+        (client_random, server_random)
+    }
+
+    #[test]
+    fn test_session_key_derivation() {
+        let (client_random, server_random) = randoms();
+
+        let keys = SessionKeys::derive(&client_random, &server_random, EncryptionMethod::Bit128);
+        assert_eq!(
+            hex::encode(&keys.mac_key),
+            "815370c6e31347c463ed25f1af48bbdf"
+        );
+        assert_eq!(
+            hex::encode(&keys.client_encrypt_key),
+            "702783c08474414a33a259c6faed480c"
+        );
+        assert_eq!(
+            hex::encode(&keys.client_decrypt_key),
+            "1cb207f61b7cd10dca9ec78871d0a142"
+        );
+
+        let keys = SessionKeys::derive(&client_random, &server_random, EncryptionMethod::Bit40);
+        assert_eq!(hex::encode(&keys.mac_key), "d1269ec6e31347c4");
+        assert_eq!(hex::encode(&keys.client_encrypt_key), "d1269ec08474414a");
+
+        let keys = SessionKeys::derive(&client_random, &server_random, EncryptionMethod::Bit56);
+        assert_eq!(hex::encode(&keys.mac_key), "d15370c6e31347c4");
+        assert_eq!(hex::encode(&keys.client_encrypt_key), "d12783c08474414a");
+    }
+
+    #[test]
+    fn test_mac_and_key_update() {
+        let (client_random, server_random) = randoms();
+        let keys = SessionKeys::derive(&client_random, &server_random, EncryptionMethod::Bit128);
+
+        assert_eq!(
+            hex::encode(compute_mac(&keys.mac_key, b"hello")),
+            "08e297801925be50"
+        );
+        assert_eq!(
+            hex::encode(compute_salted_mac(&keys.mac_key, b"hello", 5)),
+            "ea9af746ae19d2f3"
+        );
+
+        let key = &keys.client_encrypt_key;
+        assert_eq!(
+            hex::encode(update_key(key, key, EncryptionMethod::Bit128)),
+            "69d6cd7791712b7442a720f2d41b3e24"
+        );
+
+        let keys = SessionKeys::derive(&client_random, &server_random, EncryptionMethod::Bit40);
+        let key = &keys.client_encrypt_key;
+        assert_eq!(
+            hex::encode(update_key(key, key, EncryptionMethod::Bit40)),
+            "d1269e3467fb506e"
+        );
+    }
+
+    #[test]
+    fn test_fips_key_derivation() {
+        let (client_random, server_random) = randoms();
+
+        let keys = SessionKeys::derive(&client_random, &server_random, EncryptionMethod::Fips);
+        assert_eq!(
+            hex::encode(&keys.mac_key),
+            "bb45ea4383cd332a1f15df0981a4aaec95dc14eb"
+        );
+        assert_eq!(
+            hex::encode(&keys.client_encrypt_key),
+            "6708701a086d106b2a767f20017343203b16641a0d490132"
+        );
+        assert_eq!(
+            hex::encode(&keys.client_decrypt_key),
+            "4f75384f4f520b2561513261256e79490b61130b2a076e26"
+        );
+
+        assert_eq!(
+            hex::encode(compute_fips_signature(&keys.mac_key, b"hello", 0)),
+            "7d4226c2eea59d89"
+        );
+    }
+
+    #[test]
+    fn test_fips_expand_key_parity() {
+        // Each 7-bit group lands bit-reversed in the low 7 bits and the odd
+        // parity bit then replaces bit 0, as Windows and FreeRDP do, so the
+        // high bit is always clear
+        assert_eq!(fips_expand_key(&[0x00; 20]), [0x01; FIPS_KEY_LENGTH]);
+        assert_eq!(fips_expand_key(&[0xFF; 20]), [0x7F; FIPS_KEY_LENGTH]);
+
+        let (client_random, server_random) = randoms();
+        let keys = SessionKeys::derive(&client_random, &server_random, EncryptionMethod::Fips);
+        for key in [&keys.client_encrypt_key, &keys.client_decrypt_key] {
+            assert!(key.iter().all(|b| b.count_ones() % 2 == 1));
+        }
+    }
+
+    #[test]
+    fn test_fips_signature_rfc2202() {
+        // The signature is HMAC-SHA1(data + count) truncated to 8 bytes, so
+        // the RFC 2202 test cases apply with their last 4 data bytes as the
+        // little-endian count
+        assert_eq!(
+            hex::encode(compute_fips_signature(
+                &[0x0b; 20],
+                b"Hi T",
+                u32::from_le_bytes(*b"here")
+            )),
+            "b617318655057264"
+        );
+        assert_eq!(
+            hex::encode(compute_fips_signature(
+                b"Jefe",
+                b"what do ya want for noth",
+                u32::from_le_bytes(*b"ing?")
+            )),
+            "effcdf6ae5eb2fa2"
+        );
+    }
+}
@@ -0,0 +1,19 @@
+// Standard RDP Security
+pub mod certificate;
+pub mod cipher;
+pub mod exchange;
+pub mod header;
+pub mod keys;
+pub(crate) mod rc4;
+
+pub use certificate::{
+    CERT_CHAIN_VERSION_1, CERT_CHAIN_VERSION_2, ProprietaryCertificate, RsaPublicKey,
+    ServerCertificate, X509Certificate, X509CertificateChain,
+};
+pub use cipher::{KEY_UPDATE_INTERVAL, RdpCipher, StandardSecurity};
+pub use exchange::SecurityExchangePdu;
+pub use header::{BasicSecurityHeader, FipsSecurityHeader, SecurityFlags, SignedSecurityHeader};
+pub use keys::{
+    EncryptionMethod, RANDOM_LENGTH, SessionKeys, compute_fips_signature, compute_mac,
+    compute_salted_mac, update_key,
+};
@@ -0,0 +1,66 @@
+/// RC4 stream cipher
+///
+/// Encryption and decryption are the same operation. Crate-internal: callers
+/// validate key lengths before constructing it.
+#[derive(Clone)]
+pub(crate) struct Rc4 {
+    state: [u8; 256],
+    i: u8,
+    j: u8,
+}
+
+impl Rc4 {
+    /// Initialize the key schedule (key must not be empty)
+    pub(crate) fn new(key: &[u8]) -> Self {
+        assert!(!key.is_empty(), "RC4 key must not be empty");
+
+        let mut state = [0u8; 256];
+        for (i, s) in state.iter_mut().enumerate() {
+            *s = i as u8;
+        }
+
+        let mut j = 0u8;
+        for i in 0..256 {
+            j = j.wrapping_add(state[i]).wrapping_add(key[i % key.len()]);
+            state.swap(i, j as usize);
+        }
+
+        Self { state, i: 0, j: 0 }
+    }
+
+    /// Encrypt or decrypt data in place
+    pub(crate) fn process(&mut self, data: &mut [u8]) {
+        for byte in data {
+            self.i = self.i.wrapping_add(1);
+            self.j = self.j.wrapping_add(self.state[self.i as usize]);
+            self.state.swap(self.i as usize, self.j as usize);
+            let index = self.state[self.i as usize].wrapping_add(self.state[self.j as usize]);
+            *byte ^= self.state[index as usize];
+        }
+    }
+}
+
+// Key stream state is secret, keep it out of debug output
+impl std::fmt::Debug for Rc4 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Rc4").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rc4_vectors() {
+        let mut data = b"Plaintext".to_vec();
+        Rc4::new(b"Key").process(&mut data);
+        assert_eq!(hex::encode(&data), "bbf316e8d940af0ad3");
+
+        let mut rc4 = Rc4::new(b"Secret");
+        let mut data = b"Attack at dawn".to_vec();
+        rc4.process(&mut data[..4]);
+        rc4.process(&mut data[4..]);
+        assert_eq!(hex::encode(&data), "45a01f645fc35b383552544b9bf5");
+    }
+}
@@ -51,6 +51,42 @@ impl Protocol {
     }
 }
 
+/// RDP Negotiation Failure codes (MS-RDPBCGR 2.2.1.2.2)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum NegotiationFailureCode {
+    /// Server requires TLS
+    SslRequiredByServer = 0x01,
+    /// Server only allows Standard RDP Security
+    SslNotAllowedByServer = 0x02,
+    /// Server has no certificate for TLS
+    SslCertNotOnServer = 0x03,
+    /// Inconsistent flags in the request
+    InconsistentFlags = 0x04,
+    /// Server requires CredSSP
+    HybridRequiredByServer = 0x05,
+    /// Server requires TLS with user authentication
+    SslWithUserAuthRequiredByServer = 0x06,
+}
+
+impl NegotiationFailureCode {
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0x01 => Some(NegotiationFailureCode::SslRequiredByServer),
+            0x02 => Some(NegotiationFailureCode::SslNotAllowedByServer),
+            0x03 => Some(NegotiationFailureCode::SslCertNotOnServer),
+            0x04 => Some(NegotiationFailureCode::InconsistentFlags),
+            0x05 => Some(NegotiationFailureCode::HybridRequiredByServer),
+            0x06 => Some(NegotiationFailureCode::SslWithUserAuthRequiredByServer),
+            _ => None,
+        }
+    }
+
+    pub fn as_u32(self) -> u32 {
+        self as u32
+    }
+}
+
 /// RDP Negotiation Request/Response
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RdpNegotiation {
@@ -81,6 +117,23 @@ impl RdpNegotiation {
         }
     }
 
+    /// Create RDP Negotiation Failure (failure code in place of the protocol)
+    pub fn new_failure(failure_code: NegotiationFailureCode) -> Self {
+        Self {
+            neg_type: RDP_NEG_FAILURE,
+            flags: 0,
+            selected_protocol: failure_code.as_u32(),
+        }
+    }
+
+    /// Failure code of an RDP Negotiation Failure
+    pub fn failure_code(&self) -> Option<NegotiationFailureCode> {
+        if self.neg_type != RDP_NEG_FAILURE {
+            return None;
+        }
+        NegotiationFailureCode::from_u32(self.selected_protocol)
+    }
+
     /// Encode
     pub fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
         buffer.write_u8(self.neg_type)?;
@@ -288,20 +341,19 @@ impl Pdu for ConnectionRequest {
             let mut cursor = std::io::Cursor::new(&variable_data);
 
             // Parse Cookie
-            if let Ok(cookie_str) = std::str::from_utf8(&variable_data) {
-                if cookie_str.starts_with("Cookie: mstshash=") {
-                    if let Some(end) = cookie_str.find("\r\n") {
-                        cookie = Some(cookie_str[..end + 2].to_string());
-                        cursor.set_position((end + 2) as u64);
-                    }
-                }
+            if let Ok(cookie_str) = std::str::from_utf8(&variable_data)
+                && cookie_str.starts_with("Cookie: mstshash=")
+                && let Some(end) = cookie_str.find("\r\n")
+            {
+                cookie = Some(cookie_str[..end + 2].to_string());
+                cursor.set_position((end + 2) as u64);
             }
 
             // Parse RDP Negotiation
-            if cursor.position() < variable_data.len() as u64 {
-                if let Ok(negotiation) = RdpNegotiation::decode(&mut cursor) {
-                    rdp_negotiation = Some(negotiation);
-                }
+            if cursor.position() < variable_data.len() as u64
+                && let Ok(negotiation) = RdpNegotiation::decode(&mut cursor)
+            {
+                rdp_negotiation = Some(negotiation);
             }
         }
 
@@ -353,6 +405,14 @@ impl ConnectionConfirm {
         self
     }
 
+    /// Set RDP Negotiation Failure
+    pub fn with_negotiation_failure(mut self, failure_code: NegotiationFailureCode) -> Self {
+        self.rdp_negotiation = Some(RdpNegotiation::new_failure(failure_code));
+        self.header.length_indicator =
+            (X224_CONNECTION_HEADER_MIN_SIZE - 1 + RDP_NEG_DATA_SIZE) as u8;
+        self
+    }
+
     /// Return RDP Negotiation
     pub fn rdp_negotiation(&self) -> Option<&RdpNegotiation> {
         self.rdp_negotiation.as_ref()
@@ -549,6 +609,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_connection_confirm_with_negotiation_failure() {
+        let confirm = ConnectionConfirm::new(0x1234, 0)
+            .with_negotiation_failure(NegotiationFailureCode::HybridRequiredByServer);
+
+        let mut buffer = Vec::new();
+        confirm.encode(&mut buffer).unwrap();
+
+        assert_eq!(
+            &buffer[7..],
+            &[0x03, 0x00, 0x08, 0x00, 0x05, 0x00, 0x00, 0x00]
+        );
+
+        let mut cursor = Cursor::new(buffer);
+        let decoded = ConnectionConfirm::decode(&mut cursor).unwrap();
+
+        assert_eq!(
+            decoded.rdp_negotiation().unwrap().failure_code(),
+            Some(NegotiationFailureCode::HybridRequiredByServer)
+        );
+    }
+
     #[test]
     fn test_connection_roundtrip() {
         let request = ConnectionRequest::new(0xABCD)
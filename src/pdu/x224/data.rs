@@ -191,10 +191,10 @@ mod tests {
         let header = DataHeader::new(true);
         assert_eq!(header.length_indicator, 1);
         assert_eq!(header.pdu_type, X224_DATA_TYPE);
-        assert_eq!(header.eot, true);
+        assert!(header.eot);
 
         let header = DataHeader::new(false);
-        assert_eq!(header.eot, false);
+        assert!(!header.eot);
     }
 
     #[test]
@@ -244,7 +244,7 @@ mod tests {
         let pdu = DataPdu::new(payload.clone());
 
         assert_eq!(pdu.payload(), &payload[..]);
-        assert_eq!(pdu.eot(), true);
+        assert!(pdu.eot());
         assert_eq!(pdu.size(), 7); // 2 (header) + 5 (payload)
     }
 
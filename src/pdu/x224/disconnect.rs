@@ -0,0 +1,257 @@
+use crate::pdu::{Pdu, PduError, Result};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Read, Write};
+
+/// X.224 Disconnect Request PDU Type
+pub const X224_DR_TYPE: u8 = 0x80;
+
+/// X.224 Disconnect Request fixed header size (LI + Type + DST-REF + SRC-REF + Reason)
+pub const X224_DISCONNECT_HEADER_SIZE: usize = 7;
+
+/// X.224 Disconnect Reason (ISO 8073 13.5.3)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisconnectReason {
+    /// Reason not specified
+    NotSpecified,
+    /// Congestion at TSAP
+    Congestion,
+    /// Session entity not attached to TSAP
+    SessionNotAttached,
+    /// Address unknown
+    AddressUnknown,
+    /// Normal disconnect initiated by session entity
+    Normal,
+    /// Remote transport entity congestion at connect request time
+    RemoteCongestion,
+    /// Connection negotiation failed
+    NegotiationFailed,
+    /// Duplicate source reference detected for the same pair of NSAPs
+    DuplicateSourceReference,
+    /// Mismatched references
+    MismatchedReferences,
+    /// Protocol error
+    ProtocolError,
+    /// Reference overflow
+    ReferenceOverflow,
+    /// Connection request refused on this network connection
+    ConnectionRefused,
+    /// Header or parameter length invalid
+    InvalidLength,
+    /// Reason code not defined by ISO 8073
+    Other(u8),
+}
+
+impl DisconnectReason {
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            0x00 => DisconnectReason::NotSpecified,
+            0x01 => DisconnectReason::Congestion,
+            0x02 => DisconnectReason::SessionNotAttached,
+            0x03 => DisconnectReason::AddressUnknown,
+            0x80 => DisconnectReason::Normal,
+            0x81 => DisconnectReason::RemoteCongestion,
+            0x82 => DisconnectReason::NegotiationFailed,
+            0x83 => DisconnectReason::DuplicateSourceReference,
+            0x84 => DisconnectReason::MismatchedReferences,
+            0x85 => DisconnectReason::ProtocolError,
+            0x87 => DisconnectReason::ReferenceOverflow,
+            0x88 => DisconnectReason::ConnectionRefused,
+            0x8A => DisconnectReason::InvalidLength,
+            other => DisconnectReason::Other(other),
+        }
+    }
+
+    pub fn as_u8(self) -> u8 {
+        match self {
+            DisconnectReason::NotSpecified => 0x00,
+            DisconnectReason::Congestion => 0x01,
+            DisconnectReason::SessionNotAttached => 0x02,
+            DisconnectReason::AddressUnknown => 0x03,
+            DisconnectReason::Normal => 0x80,
+            DisconnectReason::RemoteCongestion => 0x81,
+            DisconnectReason::NegotiationFailed => 0x82,
+            DisconnectReason::DuplicateSourceReference => 0x83,
+            DisconnectReason::MismatchedReferences => 0x84,
+            DisconnectReason::ProtocolError => 0x85,
+            DisconnectReason::ReferenceOverflow => 0x87,
+            DisconnectReason::ConnectionRefused => 0x88,
+            DisconnectReason::InvalidLength => 0x8A,
+            DisconnectReason::Other(value) => value,
+        }
+    }
+
+    /// Human-readable description
+    pub fn description(self) -> &'static str {
+        match self {
+            DisconnectReason::NotSpecified => "Reason not specified",
+            DisconnectReason::Congestion => "Congestion at TSAP",
+            DisconnectReason::SessionNotAttached => "Session entity not attached to TSAP",
+            DisconnectReason::AddressUnknown => "Address unknown",
+            DisconnectReason::Normal => "Normal disconnect initiated by session entity",
+            DisconnectReason::RemoteCongestion => {
+                "Remote transport entity congestion at connect request time"
+            }
+            DisconnectReason::NegotiationFailed => "Connection negotiation failed",
+            DisconnectReason::DuplicateSourceReference => "Duplicate source reference",
+            DisconnectReason::MismatchedReferences => "Mismatched references",
+            DisconnectReason::ProtocolError => "Protocol error",
+            DisconnectReason::ReferenceOverflow => "Reference overflow",
+            DisconnectReason::ConnectionRefused => {
+                "Connection request refused on this network connection"
+            }
+            DisconnectReason::InvalidLength => "Header or parameter length invalid",
+            DisconnectReason::Other(_) => "Unknown reason",
+        }
+    }
+}
+
+/// X.224 Disconnect Request PDU
+///
+/// ```text
+/// +--------+--------+--------+--------+--------+--------+--------+
+/// |  LI    |  0x80  |     DST-REF     |     SRC-REF     | Reason |
+/// +--------+--------+--------+--------+--------+--------+--------+
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisconnectRequest {
+    /// Destination Reference
+    pub dst_ref: u16,
+    /// Source Reference
+    pub src_ref: u16,
+    /// Disconnect reason
+    pub reason: DisconnectReason,
+}
+
+impl DisconnectRequest {
+    /// Create new Disconnect Request
+    pub fn new(dst_ref: u16, src_ref: u16, reason: DisconnectReason) -> Self {
+        Self {
+            dst_ref,
+            src_ref,
+            reason,
+        }
+    }
+
+    /// Create normal disconnect request
+    pub fn normal() -> Self {
+        Self::new(0, 0, DisconnectReason::Normal)
+    }
+}
+
+impl Pdu for DisconnectRequest {
+    fn encode(&self, buffer: &mut dyn Write) -> Result<()> {
+        buffer.write_u8((X224_DISCONNECT_HEADER_SIZE - 1) as u8)?;
+        buffer.write_u8(X224_DR_TYPE)?;
+        buffer.write_u16::<LittleEndian>(self.dst_ref)?;
+        buffer.write_u16::<LittleEndian>(self.src_ref)?;
+        buffer.write_u8(self.reason.as_u8())?;
+        Ok(())
+    }
+
+    fn decode(buffer: &mut dyn Read) -> Result<Self> {
+        let length_indicator = buffer.read_u8()?;
+        let pdu_type = buffer.read_u8()?;
+
+        if pdu_type != X224_DR_TYPE {
+            return Err(PduError::InvalidPduType(pdu_type));
+        }
+
+        if (length_indicator as usize) < X224_DISCONNECT_HEADER_SIZE - 1 {
+            return Err(PduError::InvalidLength {
+                expected: X224_DISCONNECT_HEADER_SIZE - 1,
+                actual: length_indicator as usize,
+            });
+        }
+
+        let dst_ref = buffer.read_u16::<LittleEndian>()?;
+        let src_ref = buffer.read_u16::<LittleEndian>()?;
+        let reason = DisconnectReason::from_u8(buffer.read_u8()?);
+
+        // Skip variable part (additional information parameter), if any
+        let extra_bytes = length_indicator as usize + 1 - X224_DISCONNECT_HEADER_SIZE;
+        if extra_bytes > 0 {
+            let mut skip_buf = vec![0u8; extra_bytes];
+            buffer.read_exact(&mut skip_buf)?;
+        }
+
+        Ok(Self {
+            dst_ref,
+            src_ref,
+            reason,
+        })
+    }
+
+    fn size(&self) -> usize {
+        X224_DISCONNECT_HEADER_SIZE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdu::x224::PduType;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_disconnect_request_encode() {
+        let request = DisconnectRequest::normal();
+
+        let mut buffer = Vec::new();
+        request.encode(&mut buffer).unwrap();
+
+        assert_eq!(buffer, vec![0x06, 0x80, 0x00, 0x00, 0x00, 0x00, 0x80]);
+        assert_eq!(request.size(), buffer.len());
+        assert_eq!(
+            PduType::from_u8(buffer[1]),
+            Some(PduType::DisconnectRequest)
+        );
+    }
+
+    #[test]
+    fn test_disconnect_request_roundtrip() {
+        let request = DisconnectRequest::new(0x1234, 0x5678, DisconnectReason::ProtocolError);
+
+        let mut buffer = Vec::new();
+        request.encode(&mut buffer).unwrap();
+
+        let mut cursor = Cursor::new(buffer);
+        let decoded = DisconnectRequest::decode(&mut cursor).unwrap();
+
+        assert_eq!(request, decoded);
+    }
+
+    #[test]
+    fn test_disconnect_request_with_variable_part() {
+        // Additional information parameter (0xE0) appended to the fixed part
+        let buffer = vec![0x09, 0x80, 0x00, 0x00, 0x00, 0x00, 0x82, 0xE0, 0x01, 0xFF];
+
+        let mut cursor = Cursor::new(buffer);
+        let decoded = DisconnectRequest::decode(&mut cursor).unwrap();
+
+        assert_eq!(decoded.reason, DisconnectReason::NegotiationFailed);
+        assert_eq!(cursor.position(), 10);
+    }
+
+    #[test]
+    fn test_disconnect_request_unknown_reason() {
+        let buffer = vec![0x06, 0x80, 0x00, 0x00, 0x00, 0x00, 0x42];
+
+        let decoded = DisconnectRequest::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded.reason, DisconnectReason::Other(0x42));
+
+        let mut encoded = Vec::new();
+        decoded.encode(&mut encoded).unwrap();
+        assert_eq!(encoded, buffer);
+    }
+
+    #[test]
+    fn test_disconnect_request_invalid_type() {
+        let buffer = vec![0x06, 0xE0, 0x00, 0x00, 0x00, 0x00, 0x80];
+        let mut cursor = Cursor::new(buffer);
+
+        assert!(matches!(
+            DisconnectRequest::decode(&mut cursor),
+            Err(PduError::InvalidPduType(0xE0))
+        ));
+    }
+}
@@ -1,10 +1,13 @@
 pub mod connection;
 pub mod data;
+pub mod disconnect;
 
 pub use connection::{
-    ConnectionConfirm, ConnectionHeader, ConnectionRequest, Protocol, RdpNegotiation,
+    ConnectionConfirm, ConnectionHeader, ConnectionRequest, NegotiationFailureCode, Protocol,
+    RdpNegotiation,
 };
 pub use data::{DataHeader, DataPdu};
+pub use disconnect::{DisconnectReason, DisconnectRequest};
 
 /// X.224 PDU Type
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -0,0 +1,103 @@
+use pentardp_rs::acceptor::{AcceptorConfig, ServerAcceptor, ServerAcceptorState};
+use pentardp_rs::connector::{ClientConnector, ClientConnectorState, ConnectorConfig};
+use pentardp_rs::pdu::gcc::{ChannelDef, ChannelOptions};
+use pentardp_rs::pdu::x224::Protocol;
+use std::collections::VecDeque;
+
+/// Run a client connector against a server acceptor until both finish
+fn run_connection(client: &mut ClientConnector, server: &mut ServerAcceptor) {
+    let mut to_server: VecDeque<Vec<u8>> = VecDeque::new();
+    let mut to_client: VecDeque<Vec<u8>> = VecDeque::new();
+
+    for _ in 0..64 {
+        while !client.state().expects_input() && client.state() != ClientConnectorState::Connected {
+            if client.state() == ClientConnectorState::EnhancedSecurityUpgrade {
+                client.mark_security_upgrade_as_done().unwrap();
+            } else {
+                to_server.extend(client.step(&[]).unwrap());
+            }
+        }
+
+        while let Some(frame) = to_server.pop_front() {
+            to_client.extend(server.step(&frame).unwrap());
+        }
+        if server.state() == ServerAcceptorState::EnhancedSecurityUpgrade {
+            server.mark_security_upgrade_as_done().unwrap();
+        }
+
+        while let Some(frame) = to_client.pop_front() {
+            to_server.extend(client.step(&frame).unwrap());
+        }
+
+        if client.state() == ClientConnectorState::Connected
+            && server.state() == ServerAcceptorState::Accepted
+            && to_server.is_empty()
+        {
+            return;
+        }
+    }
+
+    panic!(
+        "connection did not complete: client {:?}, server {:?}",
+        client.state(),
+        server.state()
+    );
+}
+
+#[test]
+fn test_connector_against_acceptor() {
+    let channels = vec![
+        ChannelDef::new("rdpdr", ChannelOptions::INITIALIZED),
+        ChannelDef::new("cliprdr", ChannelOptions::INITIALIZED),
+    ];
+    let mut client = ClientConnector::new(
+        ConnectorConfig::new("alice", "secret")
+            .with_domain("CORP")
+            .with_static_channels(channels),
+    );
+    let mut server = ServerAcceptor::new(AcceptorConfig::new().with_desktop_size(1600, 900));
+
+    run_connection(&mut client, &mut server);
+
+    let connection = client.connection_result().unwrap();
+    let acceptance = server.acceptance_result().unwrap();
+
+    assert_eq!(connection.selected_protocol, Protocol::Hybrid as u32);
+    assert_eq!(acceptance.selected_protocol, Protocol::Hybrid as u32);
+    assert_eq!(connection.user_channel_id, acceptance.user_channel_id);
+    assert_eq!(connection.io_channel_id, acceptance.io_channel_id);
+    assert_eq!(connection.static_channels, acceptance.static_channels);
+    assert_eq!(
+        connection.static_channels,
+        vec![("rdpdr".to_string(), 1004), ("cliprdr".to_string(), 1005)]
+    );
+    assert_eq!(connection.user_channel_id, 1006);
+    assert_eq!(connection.share_id, acceptance.share_id);
+    assert_eq!(
+        (connection.desktop_width, connection.desktop_height),
+        (1600, 900)
+    );
+
+    assert_eq!(acceptance.client_info.user_name, "alice");
+    assert_eq!(acceptance.client_info.domain, "CORP");
+    assert_eq!(acceptance.client_capabilities.len(), 4);
+}
+
+#[test]
+fn test_connector_against_acceptor_rdp_security() {
+    let mut client = ClientConnector::new(
+        ConnectorConfig::new("bob", "").with_requested_protocols(Protocol::RdpSecurity as u32),
+    );
+    let mut server = ServerAcceptor::new(
+        AcceptorConfig::new().with_protocols(vec![Protocol::Ssl, Protocol::RdpSecurity]),
+    );
+
+    run_connection(&mut client, &mut server);
+
+    let connection = client.connection_result().unwrap();
+    assert_eq!(connection.selected_protocol, Protocol::RdpSecurity as u32);
+    assert_eq!(
+        server.acceptance_result().unwrap().client_info.user_name,
+        "bob"
+    );
+}
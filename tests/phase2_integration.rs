@@ -1,3 +1,4 @@
+use pentardp_rs::pdu::gcc::{ConferenceCreateRequest, ConferenceCreateResponse};
 use pentardp_rs::pdu::mcs::{
     AttachUserConfirm, AttachUserRequest, ChannelJoinConfirm, ChannelJoinRequest,
     ConnectInitial, ConnectResponse, DomainParameters, ErectDomainRequest, McsResult,
@@ -57,8 +58,8 @@ fn test_rdp_connection_sequence() {
     );
 
     // 3. MCS Connect-Initial (TPKT + X.224 Data)
-    let gcc_data = b"GCC Conference Create Request data".to_vec();
-    let mcs_ci = ConnectInitial::new(gcc_data.clone());
+    let gcc_request = ConferenceCreateRequest::new(b"client data blocks".to_vec());
+    let mcs_ci = ConnectInitial::new(&gcc_request).unwrap();
 
     let mut mcs_ci_buffer = Vec::new();
     mcs_ci.encode(&mut mcs_ci_buffer).unwrap();
@@ -80,11 +81,11 @@ fn test_rdp_connection_sequence() {
 
     let mut mcs_cursor = Cursor::new(decoded_x224_data.payload());
     let decoded_mcs_ci = ConnectInitial::decode(&mut mcs_cursor).unwrap();
-    assert_eq!(decoded_mcs_ci.user_data, gcc_data);
+    assert_eq!(decoded_mcs_ci.conference_request().unwrap(), gcc_request);
 
     // 4. MCS Connect-Response
-    let response_data = b"GCC Conference Create Response data".to_vec();
-    let mcs_cr = ConnectResponse::success(response_data.clone());
+    let gcc_response = ConferenceCreateResponse::new(b"server data blocks".to_vec());
+    let mcs_cr = ConnectResponse::success(&gcc_response).unwrap();
 
     let mut mcs_cr_buffer = Vec::new();
     mcs_cr.encode(&mut mcs_cr_buffer).unwrap();
@@ -92,7 +93,7 @@ fn test_rdp_connection_sequence() {
     let mut cursor = Cursor::new(&mcs_cr_buffer);
     let decoded_mcs_cr = ConnectResponse::decode(&mut cursor).unwrap();
     assert_eq!(decoded_mcs_cr.result, McsResult::RtSuccessful);
-    assert_eq!(decoded_mcs_cr.user_data, response_data);
+    assert_eq!(decoded_mcs_cr.conference_response().unwrap(), gcc_response);
 
     // 5. MCS Erect Domain Request
     let erect_domain = ErectDomainRequest::default_request();